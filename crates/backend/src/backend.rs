@@ -25,7 +25,7 @@ use ustr::Ustr;
 use uuid::Uuid;
 
 use crate::{
-    account::{BackendAccountInfo, MinecraftLoginInfo}, directories::LauncherDirectories, id_slab::IdSlab, instance::{Instance, ContentFolder}, launch::Launcher, metadata::{items::MinecraftVersionManifestMetadataItem, manager::MetadataManager}, mod_metadata::ModMetadataManager, persistent::Persistent
+    account::{BackendAccountInfo, MinecraftLoginInfo}, directories::LauncherDirectories, id_slab::IdSlab, instance::{Instance, ContentFolder}, launch::Launcher, metadata::{items::MinecraftVersionManifestMetadataItem, manager::MetadataManager}, mod_metadata::ModMetadataManager, persistent::Persistent, server_ping::ServerStatusCacheEntry
 };
 
 pub fn start(launcher_dir: PathBuf, send: FrontendHandle, self_handle: BackendHandle, recv: BackendReceiver) {
@@ -109,6 +109,7 @@ pub fn start(launcher_dir: PathBuf, send: FrontendHandle, self_handle: BackendHa
         config: Arc::new(RwLock::new(config)),
         secret_storage: Arc::new(OnceCell::new()),
         head_cache: Default::default(),
+        server_status_cache: Default::default(),
     };
 
     log::debug!("Doing initial backend load");
@@ -165,7 +166,8 @@ pub struct BackendState {
     pub account_info: Arc<RwLock<Persistent<BackendAccountInfo>>>,
     pub config: Arc<RwLock<Persistent<BackendConfig>>>,
     pub secret_storage: Arc<OnceCell<Result<PlatformSecretStorage, SecretStorageError>>>,
-    pub head_cache: Arc<RwLock<FxHashMap<Arc<str>, HeadCacheEntry>>>
+    pub head_cache: Arc<RwLock<FxHashMap<Arc<str>, HeadCacheEntry>>>,
+    pub server_status_cache: Arc<RwLock<FxHashMap<Arc<str>, ServerStatusCacheEntry>>>,
 }
 
 pub enum HeadCacheEntry {
@@ -308,10 +310,13 @@ impl BackendState {
                 name: instance.name,
                 dot_minecraft_folder: instance.dot_minecraft_path.clone(),
                 configuration: instance.configuration.get().clone(),
+                icon: instance.icon.clone(),
+                description: instance.description.clone(),
                 worlds_state: Arc::clone(&instance.worlds_state),
                 servers_state: Arc::clone(&instance.servers_state),
                 mods_state: Arc::clone(&instance.content_state[ContentFolder::Mods].load_state),
                 resource_packs_state: Arc::clone(&instance.content_state[ContentFolder::ResourcePacks].load_state),
+                shader_packs_state: Arc::clone(&instance.content_state[ContentFolder::ShaderPacks].load_state),
             };
             self.send.send(message);
 
@@ -968,6 +973,12 @@ impl BackendState {
                         resource_packs: Arc::clone(&content)
                     });
                 },
+                ContentFolder::ShaderPacks => {
+                    self.send.send(MessageToFrontend::InstanceShaderPacksUpdated {
+                        id,
+                        shader_packs: Arc::clone(&content)
+                    });
+                },
             }
         }
 
@@ -1005,11 +1016,22 @@ impl BackendState {
                     id,
                 });
             }
+            drop(file_watching);
+
+            if worlds.iter().any(|summary| summary.details.size.is_none()) {
+                tokio::task::spawn(self.clone().load_world_sizes(id, worlds));
+            }
         }
 
         result.map(|(worlds, _)| worlds)
     }
 
+    async fn load_world_sizes(self, id: InstanceID, worlds: Arc<[InstanceWorldSummary]>) {
+        if let Some(worlds) = Instance::load_world_sizes(self.instance_state.clone(), id, worlds).await {
+            self.send.send(MessageToFrontend::InstanceWorldsUpdated { id, worlds });
+        }
+    }
+
     pub async fn create_instance_sanitized(&self, name: &str, version: &str, loader: Loader) -> Option<PathBuf> {
         let mut name = sanitize_filename::sanitize_with_options(name, sanitize_filename::Options { windows: true, ..Default::default() });
 
@@ -1060,6 +1082,10 @@ impl BackendState {
             jvm_flags: None,
             jvm_binary: None,
             linux_wrapper: None,
+            env_vars: None,
+            resolution: None,
+            group: None,
+            tags: Vec::new(),
         };
 
         let info_path = instance_dir.join("info_v1.json");
@@ -1068,6 +1094,74 @@ impl BackendState {
         Some(instance_dir.clone())
     }
 
+    pub async fn apply_instance_template(&self, template: InstanceID, instance_dir: PathBuf, loader: Loader, minecraft_version: &str) {
+        let (template_configuration, template_dot_minecraft) = {
+            let mut instance_state = self.instance_state.write();
+            let Some(template) = instance_state.instances.get_mut(template) else {
+                self.send.send_warning("Unable to apply template, unknown instance");
+                return;
+            };
+            (template.configuration.get().clone(), template.dot_minecraft_path.clone())
+        };
+
+        // Mods are only copied when they were made for the same loader and Minecraft version
+        let copy_mods = template_configuration.loader == loader && template_configuration.minecraft_version.as_str() == minecraft_version;
+        if !copy_mods && loader != Loader::Vanilla {
+            self.send.send_warning(format!("Mods were not copied from the template because it uses {} {}",
+                template_configuration.loader.name(), template_configuration.minecraft_version));
+        }
+
+        let content_library_dir = self.directories.content_library_dir.clone();
+        let (settings_result, copy_result) = tokio::task::spawn_blocking(move || {
+            let info_path = instance_dir.join("info_v1.json");
+            let mut settings_result = Ok(());
+            if let Ok(mut instance_info) = crate::read_json::<InstanceConfiguration>(&info_path) {
+                instance_info.memory = template_configuration.memory;
+                instance_info.jvm_flags = template_configuration.jvm_flags.clone();
+                instance_info.jvm_binary = template_configuration.jvm_binary.clone();
+                instance_info.linux_wrapper = template_configuration.linux_wrapper;
+                instance_info.env_vars = template_configuration.env_vars.clone();
+                instance_info.resolution = template_configuration.resolution;
+                instance_info.group = template_configuration.group.clone();
+                instance_info.tags = template_configuration.tags.clone();
+                settings_result = crate::write_safe(&info_path, serde_json::to_string(&instance_info).unwrap().as_bytes());
+            }
+
+            let dot_minecraft = instance_dir.join(".minecraft");
+
+            let mut folders = vec!["config"];
+            if copy_mods {
+                folders.push("mods");
+            }
+
+            let mut copy_result = Ok(());
+
+            let options = template_dot_minecraft.join("options.txt");
+            if options.is_file() {
+                copy_result = std::fs::create_dir_all(&dot_minecraft).and_then(|_| std::fs::copy(&options, dot_minecraft.join("options.txt")).map(|_| ()));
+            }
+
+            for folder in folders {
+                let from = template_dot_minecraft.join(folder);
+                let to = dot_minecraft.join(folder);
+                // Synced folders are shared between instances and don't need to be copied
+                if copy_result.is_err() || !from.is_dir() || to.is_symlink() {
+                    continue;
+                }
+                copy_result = crate::snapshot::copy_dir_all(&from, &to, &content_library_dir).map(|_| ());
+            }
+
+            (settings_result, copy_result)
+        }).await.unwrap();
+
+        if let Err(err) = settings_result {
+            self.send.send_error(format!("Unable to apply template settings: {err}"));
+        }
+        if let Err(err) = copy_result {
+            self.send.send_error(format!("Unable to copy files from template: {err}"));
+        }
+    }
+
     pub async fn rename_instance(&self, id: InstanceID, name: &str) {
         if !crate::is_single_component_path(&name) {
             self.send.send_warning(format!("Unable to rename instance, name must not be a path: {}", name));
@@ -1086,9 +1180,47 @@ impl BackendState {
 
         if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
             let result = std::fs::rename(&instance.root_path, new_instance_dir);
-            if let Err(err) = result {
-                self.send.send_error(format!("Unable to rename instance folder: {}", err));
+            match result {
+                Ok(()) => {
+                    self.send.send(MessageToFrontend::InstanceRenamed {
+                        old_name: instance.name,
+                        new_name: name.into(),
+                    });
+                },
+                Err(err) => {
+                    self.send.send_error(format!("Unable to rename instance folder: {}", err));
+                },
+            }
+        }
+    }
+
+    pub async fn set_instance_icon(self, id: InstanceID, icon: Option<Arc<[u8]>>) {
+        let Some(icon_path) = self.instance_state.read().instances.get(id).map(|instance| instance.root_path.join("icon.png")) else {
+            return;
+        };
+
+        // Decoding and resizing large images is slow, so it shouldn't block the backend
+        let result = tokio::task::spawn_blocking(move || {
+            if let Some(icon) = icon {
+                let Some(png_bytes) = crate::instance::normalize_instance_icon(&icon) else {
+                    return Err("Unable to set icon, file is not a valid image".to_string());
+                };
+                crate::write_safe(&icon_path, &png_bytes).map_err(|err| format!("Unable to save icon: {err}"))
+            } else if icon_path.exists() {
+                std::fs::remove_file(&icon_path).map_err(|err| format!("Unable to remove icon: {err}"))
+            } else {
+                Ok(())
             }
+        }).await.unwrap();
+
+        if let Err(err) = result {
+            self.send.send_error(err);
+            return;
+        }
+
+        if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+            instance.reload_icon();
+            self.send.send(instance.create_modify_message());
         }
     }
 
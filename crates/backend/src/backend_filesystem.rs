@@ -333,6 +333,12 @@ impl BackendState {
                     } else {
                         self.load_instance_from_path(parent_path, true, true);
                     }
+                } else if file_name == "icon.png" || file_name == "description.md" {
+                    if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                        instance.reload_icon();
+                        instance.reload_description();
+                        self.send.send(instance.create_modify_message());
+                    }
                 } else if file_name == ".minecraft"
                     && let Some(instance) = self.instance_state.write().instances.get_mut(id)
                 {
@@ -436,6 +442,12 @@ impl BackendState {
                 if file_name == "info_v1.json" {
                     self.remove_instance(id);
                     self.file_watching.write().watch_filesystem(parent_path.into(), WatchTarget::InvalidInstanceDir);
+                } else if (file_name == "icon.png" || file_name == "description.md")
+                    && let Some(instance) = self.instance_state.write().instances.get_mut(id)
+                {
+                    instance.reload_icon();
+                    instance.reload_description();
+                    self.send.send(instance.create_modify_message());
                 }
             },
             WatchTarget::InstanceWorldDir { id } => {
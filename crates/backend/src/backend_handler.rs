@@ -1,19 +1,17 @@
-use std::{io::{BufRead, Read, Seek, SeekFrom, Write}, path::Path, sync::{atomic::Ordering, Arc}, time::{Duration, SystemTime}};
+use std::{io::{BufRead, Read, Seek, SeekFrom, Write}, path::Path, sync::Arc, time::{Duration, SystemTime}};
 
 use auth::{credentials::AccountCredentials, models::{MinecraftAccessToken, MinecraftProfileResponse}, secret::PlatformSecretStorage};
 use bridge::{
-    install::{ContentDownload, ContentInstall, ContentInstallFile, InstallTarget}, instance::{InstanceStatus, ContentType, ContentSummary}, message::{LogFiles, MessageToBackend, MessageToFrontend}, meta::MetadataResult, modal_action::{ModalAction, ModalActionVisitUrl, ProgressTracker, ProgressTrackerFinishType}, serial::AtomicOptionSerial
+    install::{ContentDownload, ContentInstall, ContentInstallFile, InstallTarget}, instance::InstanceStatus, message::{LogFiles, MessageToBackend, MessageToFrontend}, meta::MetadataResult, modal_action::{ModalAction, ModalActionVisitUrl, ProgressTracker, ProgressTrackerFinishType}, serial::AtomicOptionSerial
 };
-use futures::TryFutureExt;
 use rustc_hash::{FxHashMap, FxHashSet};
-use schema::{aux::AuxiliaryContentMeta, content::ContentSource, modrinth::ModrinthLoader, version::{LaunchArgument, LaunchArgumentValue}};
+use schema::{aux::AuxiliaryContentMeta, content::ContentSource, version::{LaunchArgument, LaunchArgumentValue}};
 use serde::Deserialize;
-use strum::IntoEnumIterator;
-use tokio::{io::AsyncBufReadExt, sync::Semaphore};
+use tokio::io::AsyncBufReadExt;
 use ustr::Ustr;
 
 use crate::{
-    BackendState, LoginError, account::{BackendAccount, MinecraftLoginInfo}, arcfactory::ArcStrFactory, instance::ContentFolder, launch::{ArgumentExpansionKey, LaunchError}, log_reader, metadata::{items::{AssetsIndexMetadataItem, FabricLoaderManifestMetadataItem, ForgeInstallerMavenMetadataItem, MinecraftVersionManifestMetadataItem, MinecraftVersionMetadataItem, ModrinthProjectVersionsMetadataItem, ModrinthSearchMetadataItem, ModrinthV3VersionUpdateMetadataItem, ModrinthVersionUpdateMetadataItem, MojangJavaRuntimeComponentMetadataItem, MojangJavaRuntimesMetadataItem, NeoforgeInstallerMavenMetadataItem, VersionUpdateParameters, VersionV3LoaderFields, VersionV3UpdateParameters}, manager::MetaLoadError}, mod_metadata::ModUpdateAction
+    BackendState, LoginError, account::{BackendAccount, MinecraftLoginInfo}, arcfactory::ArcStrFactory, instance::ContentFolder, launch::{ArgumentExpansionKey, LaunchError}, log_reader, metadata::{items::{AssetsIndexMetadataItem, CurseforgeModFilesMetadataItem, CurseforgeSearchMetadataItem, FabricLoaderManifestMetadataItem, ForgeInstallerMavenMetadataItem, MinecraftVersionManifestMetadataItem, MinecraftVersionMetadataItem, ModrinthProjectVersionsMetadataItem, ModrinthSearchMetadataItem, MojangJavaRuntimeComponentMetadataItem, MojangJavaRuntimesMetadataItem, NeoforgeInstallerMavenMetadataItem}, manager::MetaLoadError}, mod_metadata::ModUpdateAction
 };
 
 impl BackendState {
@@ -22,6 +20,7 @@ impl BackendState {
             MessageToBackend::RequestMetadata { request, force_reload } => {
                 let meta = self.meta.clone();
                 let send = self.send.clone();
+                let curseforge_api = self.curseforge_api();
                 tokio::task::spawn(async move {
                     let (result, keep_alive_handle) = match request {
                         bridge::meta::MetadataRequest::MinecraftVersionManifest => {
@@ -48,6 +47,17 @@ impl BackendState {
                             let (result, handle) = meta.fetch_with_keepalive(&ModrinthProjectVersionsMetadataItem(project_versions), force_reload).await;
                             (result.map(MetadataResult::ModrinthProjectVersionsResult), handle)
                         },
+                        bridge::meta::MetadataRequest::CurseforgeSearch(_) | bridge::meta::MetadataRequest::CurseforgeModFiles(_) if !curseforge_api.is_usable() => {
+                            (Err(MetaLoadError::Error("A CurseForge API key is needed, one can be set in the settings".into())), None)
+                        },
+                        bridge::meta::MetadataRequest::CurseforgeSearch(ref search) => {
+                            let (result, handle) = meta.fetch_with_keepalive(&CurseforgeSearchMetadataItem { api: &curseforge_api, request: search }, force_reload).await;
+                            (result.map(MetadataResult::CurseforgeSearchResult), handle)
+                        },
+                        bridge::meta::MetadataRequest::CurseforgeModFiles(ref mod_files) => {
+                            let (result, handle) = meta.fetch_with_keepalive(&CurseforgeModFilesMetadataItem { api: &curseforge_api, request: mod_files }, force_reload).await;
+                            (result.map(MetadataResult::CurseforgeModFilesResult), handle)
+                        },
                     };
                     let result = result.map_err(|err| format!("{}", err).into());
                     send.send(MessageToFrontend::MetadataResult {
@@ -63,20 +73,116 @@ impl BackendState {
             MessageToBackend::RequestLoadServers { id } => {
                 tokio::task::spawn(self.clone().load_instance_servers(id));
             },
+            MessageToBackend::EditServerList { id, edit } => {
+                tokio::task::spawn(self.clone().edit_server_list(id, edit));
+            },
+            MessageToBackend::CopyServer { id, index, target } => {
+                tokio::task::spawn(self.clone().copy_server(id, index, target));
+            },
+            MessageToBackend::PingServer { address, refresh, channel } => {
+                tokio::task::spawn(self.clone().ping_server(address, refresh, channel));
+            },
             MessageToBackend::RequestLoadMods { id } => {
                 tokio::task::spawn(self.clone().load_instance_content(id, ContentFolder::Mods));
             },
+            MessageToBackend::CheckModDependencies { id, channel } => {
+                tokio::task::spawn(self.clone().check_mod_dependencies(id, channel));
+            },
             MessageToBackend::RequestLoadResourcePacks { id } => {
                 tokio::task::spawn(self.clone().load_instance_content(id, ContentFolder::ResourcePacks));
             },
-            MessageToBackend::CreateInstance { name, version, loader } => {
-                self.create_instance(&name, &version, loader).await;
+            MessageToBackend::RequestLoadShaderPacks { id } => {
+                tokio::task::spawn(self.clone().load_instance_content(id, ContentFolder::ShaderPacks));
+            },
+            MessageToBackend::GetSelectedShaderPack { id, channel } => {
+                tokio::task::spawn(self.clone().get_selected_shader_pack(id, channel));
+            },
+            MessageToBackend::GetResourcePackOrder { id, channel } => {
+                tokio::task::spawn(self.clone().get_resource_pack_order(id, channel));
+            },
+            MessageToBackend::SetResourcePackOrder { id, enabled, incompatible, channel } => {
+                tokio::task::spawn(self.clone().set_resource_pack_order(id, enabled, incompatible, channel));
+            },
+            MessageToBackend::RequestLoadSnapshots { id } => {
+                self.load_instance_snapshots(id).await;
+            },
+            MessageToBackend::CreateInstanceSnapshot { id, note, include_worlds, modal_action } => {
+                tokio::task::spawn(self.clone().create_instance_snapshot_with_modal(id, note, include_worlds, modal_action));
+            },
+            MessageToBackend::RestoreInstanceSnapshot { id, snapshot, modal_action } => {
+                tokio::task::spawn(self.clone().restore_instance_snapshot(id, snapshot, modal_action));
+            },
+            MessageToBackend::DeleteInstanceSnapshot { id, snapshot } => {
+                self.delete_instance_snapshot(id, snapshot).await;
+            },
+            MessageToBackend::RequestLoadWorldBackups { id } => {
+                self.load_world_backups(id).await;
+            },
+            MessageToBackend::BackupWorld { id, world, modal_action } => {
+                tokio::task::spawn(self.clone().backup_world(id, world, modal_action));
+            },
+            MessageToBackend::RestoreWorldBackup { id, backup, modal_action } => {
+                tokio::task::spawn(self.clone().restore_world_backup(id, backup, modal_action));
+            },
+            MessageToBackend::DeleteWorldBackup { id, backup } => {
+                self.delete_world_backup(id, backup).await;
+            },
+            MessageToBackend::RenameWorld { id, world, name } => {
+                tokio::task::spawn(self.clone().rename_world(id, world, name));
+            },
+            MessageToBackend::DeleteWorld { id, world } => {
+                tokio::task::spawn(self.clone().delete_world(id, world));
+            },
+            MessageToBackend::CopyWorld { id, world, target, move_world, modal_action } => {
+                tokio::task::spawn(self.clone().copy_world(id, world, target, move_world, modal_action));
+            },
+            MessageToBackend::RequestLoadWorldDatapacks { id, world } => {
+                tokio::task::spawn(self.clone().load_world_datapacks(id, world));
+            },
+            MessageToBackend::GetDataVersion { minecraft_version, channel } => {
+                tokio::task::spawn(self.clone().get_data_version(minecraft_version, channel));
+            },
+            MessageToBackend::SetDatapackEnabled { id, world, datapack, enabled } => {
+                tokio::task::spawn(self.clone().set_datapack_enabled(id, world, datapack, enabled));
+            },
+            MessageToBackend::RequestLoadScreenshots { id } => {
+                tokio::task::spawn(self.clone().load_screenshots(id));
+            },
+            MessageToBackend::DeleteScreenshot { id, screenshot } => {
+                tokio::task::spawn(self.clone().delete_screenshot(id, screenshot));
+            },
+            MessageToBackend::ExportScreenshot { id, screenshot, target } => {
+                tokio::task::spawn(self.clone().export_screenshot(id, screenshot, target));
+            },
+            MessageToBackend::GetGameOptions { id, channel } => {
+                tokio::task::spawn(self.clone().get_game_options(id, channel));
+            },
+            MessageToBackend::SetGameOptions { id, options } => {
+                tokio::task::spawn(self.clone().set_game_options(id, options));
+            },
+            MessageToBackend::ApplyGameOptionsToAllInstances { options } => {
+                tokio::task::spawn(self.clone().apply_game_options_to_all_instances(options));
+            },
+            MessageToBackend::CreateInstance { name, version, loader, template } => {
+                if let Some(instance_dir) = self.create_instance(&name, &version, loader).await
+                    && let Some(template) = template
+                {
+                    self.apply_instance_template(template, instance_dir, loader, &version).await;
+                }
             },
             MessageToBackend::DeleteInstance { id } => {
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    // Synced screenshots folders are shared, so only the instance's own thumbnails are removed
+                    let screenshots_path = instance.dot_minecraft_path.join(crate::screenshot::SCREENSHOTS_FOLDER);
+                    let owns_screenshots = !screenshots_path.is_symlink();
+                    let thumbnails_dir = crate::screenshot::instance_thumbnails_dir(&self.directories.screenshot_thumbnails_dir,
+                        &crate::screenshot::canonical_path(&screenshots_path));
+
                     let result = std::fs::remove_dir_all(&instance.root_path);
                     if let Err(err) = result {
                         self.send.send_error(format!("Unable to delete instance folder: {}", err));
+                    } else if owns_screenshots {
+                        _ = std::fs::remove_dir_all(thumbnails_dir);
                     }
                 }
             },
@@ -98,6 +204,12 @@ impl BackendState {
                     });
                 }
             },
+            MessageToBackend::PlanInstanceMigration { id, minecraft_version, loader, modal_action, channel } => {
+                tokio::task::spawn(self.clone().plan_instance_migration(id, minecraft_version, loader, modal_action, channel));
+            },
+            MessageToBackend::ApplyInstanceMigration { id, plan, disable_incompatible, modal_action } => {
+                tokio::task::spawn(self.clone().apply_instance_migration(id, plan, disable_incompatible, modal_action));
+            },
             MessageToBackend::SetInstancePreferredLoaderVersion { id, loader_version } => {
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     instance.configuration.modify(|configuration| {
@@ -133,6 +245,56 @@ impl BackendState {
                     });
                 }
             },
+            MessageToBackend::SetInstanceEnvVars { id, env_vars } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.env_vars = Some(env_vars);
+                    });
+                }
+            },
+            MessageToBackend::SetInstanceResolution { id, resolution } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.resolution = Some(resolution);
+                    });
+                }
+            },
+            MessageToBackend::SetInstanceGroup { id, group } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.group = group;
+                    });
+                }
+            },
+            MessageToBackend::SetInstanceTags { id, tags } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    instance.configuration.modify(|configuration| {
+                        configuration.tags = tags;
+                    });
+                }
+            },
+            MessageToBackend::SetInstanceIcon { id, icon } => {
+                tokio::task::spawn(self.clone().set_instance_icon(id, icon));
+            },
+            MessageToBackend::SetInstanceDescription { id, description } => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+                    let description_path = instance.root_path.join("description.md");
+                    let result = if description.trim_ascii().is_empty() {
+                        if description_path.exists() {
+                            std::fs::remove_file(&description_path)
+                        } else {
+                            Ok(())
+                        }
+                    } else {
+                        crate::write_safe(&description_path, description.as_bytes())
+                    };
+                    if let Err(err) = result {
+                        self.send.send_error(format!("Unable to save description: {err}"));
+                    }
+                    instance.reload_description();
+                    self.send.send(instance.create_modify_message());
+                }
+            },
             MessageToBackend::KillInstance { id } => {
                 if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
                     if let Some(mut child) = instance.child.take() {
@@ -151,6 +313,9 @@ impl BackendState {
 
                 self.send.send_error("Can't kill instance, unknown id");
             },
+            MessageToBackend::CheckLaunchProblems { id, channel } => {
+                tokio::task::spawn(self.clone().check_launch_problems(id, channel));
+            },
             MessageToBackend::StartInstance {
                 id,
                 quick_play,
@@ -181,6 +346,12 @@ impl BackendState {
                         modal_action.set_finished();
                         return;
                     }
+                    if instance.busy {
+                        self.send.send_warning("Can't launch instance, a snapshot is being restored or deleted");
+                        modal_action.set_error_message("Can't launch instance, a snapshot is being restored or deleted".into());
+                        modal_action.set_finished();
+                        return;
+                    }
 
                     self.send.send(MessageToFrontend::MoveInstanceToTop {
                         id
@@ -195,6 +366,8 @@ impl BackendState {
                     return;
                 };
 
+                let configuration = configuration.with_defaults(&self.config.write().get().instance_defaults);
+
                 let launch_tracker = ProgressTracker::new(Arc::from("Launching"), self.send.clone());
                 modal_action.trackers.push(launch_tracker.clone());
 
@@ -319,6 +492,46 @@ impl BackendState {
                     }
                 }
             },
+            MessageToBackend::SetContentPinned { id, content_ids, pinned } => {
+                let mut instance_state = self.instance_state.write();
+                let Some(instance) = instance_state.instances.get_mut(id) else {
+                    self.send.send_error("Unable to find instance, unknown id");
+                    return;
+                };
+
+                let mut reload = FxHashSet::default();
+
+                for content_id in content_ids {
+                    let Some((instance_content, folder)) = instance.try_get_content(content_id) else {
+                        continue;
+                    };
+                    if instance_content.pinned == pinned {
+                        continue;
+                    }
+                    let Some(aux_path) = crate::pandora_aux_path_for_content(instance_content) else {
+                        continue;
+                    };
+
+                    let mut aux: AuxiliaryContentMeta = crate::read_json(&aux_path).unwrap_or_default();
+                    aux.pinned = pinned.clone();
+
+                    let bytes = match serde_json::to_vec(&aux) {
+                        Ok(bytes) => bytes,
+                        Err(err) => {
+                            log::error!("Unable to serialize AuxiliaryContentMeta: {err:?}");
+                            self.send.send_error("Unable to serialize AuxiliaryContentMeta");
+                            return;
+                        },
+                    };
+                    if let Err(err) = crate::write_safe(&aux_path, &bytes) {
+                        log::error!("Unable to save aux meta: {err:?}");
+                        self.send.send_error("Unable to save aux meta");
+                    }
+                    reload.insert((id, folder));
+                }
+
+                instance_state.reload_immediately.extend(reload);
+            },
             MessageToBackend::DownloadAllMetadata => {
                 self.download_all_metadata().await;
             },
@@ -354,202 +567,7 @@ impl BackendState {
                 instance_state.reload_immediately.extend(reload);
             },
             MessageToBackend::UpdateCheck { instance: id, modal_action } => {
-                let (loader, version) = if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
-                    let configuration = instance.configuration.get();
-                    (configuration.loader, configuration.minecraft_version)
-                } else {
-                    self.send.send_error("Can't update instance, unknown id");
-                    modal_action.set_error_message("Can't update instance, unknown id".into());
-                    modal_action.set_finished();
-                    return;
-                };
-
-                let mut content = Vec::new();
-                for folder in ContentFolder::iter() {
-                    let Some(summaries) = self.clone().load_instance_content(id, folder).await else {
-                        modal_action.set_finished();
-                        return;
-                    };
-                    content.extend_from_slice(&*summaries);
-                }
-
-                let modrinth_loader = loader.as_modrinth_loader();
-                if modrinth_loader == ModrinthLoader::Unknown {
-                    modal_action.set_error_message("Unable to update instance, unsupported loader".into());
-                    modal_action.set_finished();
-                    return;
-                }
-
-                let tracker = ProgressTracker::new("Checking content".into(), self.send.clone());
-                tracker.set_total(content.len());
-                modal_action.trackers.push(tracker.clone());
-
-                let semaphore = Semaphore::new(8);
-
-                let mod_params = &VersionUpdateParameters {
-                    loaders: [modrinth_loader].into(),
-                    game_versions: [version].into(),
-                };
-
-                let fabric_mod_params = &VersionUpdateParameters {
-                    loaders: [ModrinthLoader::Fabric].into(),
-                    game_versions: [version].into(),
-                };
-
-                let forge_mod_params = &VersionUpdateParameters {
-                    loaders: [ModrinthLoader::Forge].into(),
-                    game_versions: [version].into(),
-                };
-
-                let neoforge_mod_params = &VersionUpdateParameters {
-                    loaders: [ModrinthLoader::NeoForge].into(),
-                    game_versions: [version].into(),
-                };
-
-                let resourcepack_params = &VersionUpdateParameters {
-                    loaders: [ModrinthLoader::Minecraft].into(),
-                    game_versions: [version].into(),
-                };
-
-                let modrinth_modpack_params = &VersionV3UpdateParameters {
-                    loaders: ["mrpack".into()].into(),
-                    loader_fields: VersionV3LoaderFields {
-                        mrpack_loaders: [modrinth_loader].into(),
-                        game_versions: [version].into(),
-                    },
-                };
-
-                let meta = self.meta.clone();
-
-                let mut futures = Vec::new();
-
-                struct UpdateResult {
-                    mod_summary: Arc<ContentSummary>,
-                    action: ModUpdateAction,
-                }
-
-                { // Scope is needed so await doesn't complain about the non-send RwLockReadGuard
-                    let sources = self.mod_metadata_manager.read_content_sources();
-                    for summary in content.iter() {
-                        let source = sources.get(&summary.content_summary.hash).unwrap_or(ContentSource::Manual);
-                        let semaphore = &semaphore;
-                        let meta = &meta;
-                        let tracker = &tracker;
-                        futures.push(async move {
-                            match source {
-                                ContentSource::Manual => {
-                                    tracker.add_count(1);
-                                    tracker.notify();
-                                    Ok(ModUpdateAction::ManualInstall)
-                                },
-                                ContentSource::ModrinthUnknown | ContentSource::ModrinthProject { .. } => {
-                                    let permit = semaphore.acquire().await.unwrap();
-                                    let result = match summary.content_summary.extra {
-                                        ContentType::Fabric => {
-                                            meta.fetch(&ModrinthVersionUpdateMetadataItem {
-                                                sha1: hex::encode(summary.content_summary.hash).into(),
-                                                params: fabric_mod_params.clone()
-                                            }).await
-                                        },
-                                        ContentType::Forge => {
-                                            meta.fetch(&ModrinthVersionUpdateMetadataItem {
-                                                sha1: hex::encode(summary.content_summary.hash).into(),
-                                                params: forge_mod_params.clone()
-                                            }).await
-                                        },
-                                        ContentType::NeoForge => {
-                                            meta.fetch(&ModrinthVersionUpdateMetadataItem {
-                                                sha1: hex::encode(summary.content_summary.hash).into(),
-                                                params: neoforge_mod_params.clone()
-                                            }).await
-                                        },
-                                        ContentType::JavaModule => {
-                                            meta.fetch(&ModrinthVersionUpdateMetadataItem {
-                                                sha1: hex::encode(summary.content_summary.hash).into(),
-                                                params: mod_params.clone()
-                                            }).await
-                                        },
-                                        ContentType::ModrinthModpack { .. } => {
-                                            meta.fetch(&ModrinthV3VersionUpdateMetadataItem {
-                                                sha1: hex::encode(summary.content_summary.hash).into(),
-                                                params: modrinth_modpack_params.clone()
-                                            }).await
-                                        },
-                                        ContentType::ResourcePack => {
-                                            meta.fetch(&ModrinthVersionUpdateMetadataItem {
-                                                sha1: hex::encode(summary.content_summary.hash).into(),
-                                                params: resourcepack_params.clone()
-                                            }).await
-                                        },
-                                    };
-                                    drop(permit);
-
-                                    tracker.add_count(1);
-                                    tracker.notify();
-
-                                    if let Err(MetaLoadError::NonOK(404)) = result {
-                                        return Ok(ModUpdateAction::ErrorNotFound);
-                                    }
-
-                                    let result = result?;
-
-                                    if let ContentSource::ModrinthProject { ref project } = source {
-                                        if &result.0.project_id != project {
-                                            log::error!("Refusing to update {:?}, mismatched project ids: expected {}, got {}",
-                                                summary.content_summary.hash, &result.0.project_id, &project);
-                                            return Ok(ModUpdateAction::ErrorNotFound);
-                                        }
-                                    }
-
-                                    let install_file = result
-                                        .0
-                                        .files
-                                        .iter()
-                                        .find(|file| file.primary)
-                                        .unwrap_or(result.0.files.first().unwrap());
-
-                                    let mut latest_hash = [0u8; 20];
-                                    let Ok(_) = hex::decode_to_slice(&*install_file.hashes.sha1, &mut latest_hash) else {
-                                        return Ok(ModUpdateAction::ErrorInvalidHash);
-                                    };
-
-                                    if latest_hash == summary.content_summary.hash {
-                                        Ok(ModUpdateAction::AlreadyUpToDate)
-                                    } else {
-                                        Ok(ModUpdateAction::Modrinth {
-                                            file: install_file.clone(),
-                                            project_id: result.0.project_id.clone(),
-                                        })
-                                    }
-                                },
-                            }
-                        }.map_ok(|action| UpdateResult {
-                            mod_summary: summary.content_summary.clone(),
-                            action,
-                        }));
-                    }
-                }
-
-                let results: Result<Vec<UpdateResult>, MetaLoadError> = futures::future::try_join_all(futures).await;
-
-                match results {
-                    Ok(updates) => {
-                        let mut meta_updates = self.mod_metadata_manager.updates.write();
-
-                        for update in updates {
-                            update.mod_summary.update_status.store(update.action.to_status(), Ordering::Relaxed);
-                            meta_updates.insert(update.mod_summary.hash, update.action);
-                        }
-                    },
-                    Err(error) => {
-                        tracker.set_finished(ProgressTrackerFinishType::Error);
-                        modal_action.set_error_message(format!("Error checking for updates: {}", error).into());
-                        modal_action.set_finished();
-                        return;
-                    },
-                }
-
-                tracker.set_finished(ProgressTrackerFinishType::Normal);
+                self.check_content_updates(id, &modal_action).await;
                 modal_action.set_finished();
             },
             MessageToBackend::UpdateContent { instance: id, content_id: mod_id, modal_action } => {
@@ -562,6 +580,12 @@ impl BackendState {
                         return;
                     };
 
+                    if mod_summary.pinned.is_some() {
+                        self.send.send_error("Can't update mod in instance, mod is pinned");
+                        modal_action.set_finished();
+                        return;
+                    }
+
                     let Some(update_info) = self.mod_metadata_manager.updates.read().get(&mod_summary.content_summary.hash).cloned() else {
                         self.send.send_error("Can't update mod in instance, missing update action");
                         modal_action.set_finished();
@@ -611,6 +635,22 @@ impl BackendState {
                                 }].into(),
                             }
                         },
+                        ModUpdateAction::GithubRelease { asset, source, .. } => {
+                            ContentInstall {
+                                target: InstallTarget::Instance(id),
+                                loader_hint: loader,
+                                version_hint: Some(minecraft_version.into()),
+                                files: [crate::content_update::github_update_install_file(mod_summary, &asset, &source)].into(),
+                            }
+                        },
+                        ModUpdateAction::Curseforge { file } => {
+                            ContentInstall {
+                                target: InstallTarget::Instance(id),
+                                loader_hint: loader,
+                                version_hint: Some(minecraft_version.into()),
+                                files: [crate::content_update::curseforge_update_install_file(mod_summary, &file)].into(),
+                            }
+                        },
                     }
                 } else {
                     self.send.send_error("Can't update mod in instance, unknown instance id");
@@ -622,6 +662,21 @@ impl BackendState {
                 modal_action.set_finished();
                 self.send.send(MessageToFrontend::Refresh);
             },
+            MessageToBackend::PlanContentUpdates { id, modal_action, channel } => {
+                tokio::task::spawn(self.clone().plan_content_updates(id, modal_action, channel));
+            },
+            MessageToBackend::ApplyContentUpdates { id, entries, modal_action } => {
+                tokio::task::spawn(self.clone().apply_content_updates(id, entries, modal_action));
+            },
+            MessageToBackend::RollbackContentUpdates { id, modal_action } => {
+                tokio::task::spawn(self.clone().rollback_content_updates(id, modal_action));
+            },
+            MessageToBackend::GetContentLibrary { channel } => {
+                tokio::task::spawn(self.clone().get_content_library(channel));
+            },
+            MessageToBackend::PruneContentLibrary { modal_action, channel } => {
+                tokio::task::spawn(self.clone().prune_content_library(modal_action, channel));
+            },
             MessageToBackend::Sleep5s => {
                 tokio::time::sleep(Duration::from_secs(5)).await;
             },
@@ -834,6 +889,12 @@ impl BackendState {
                 let configuration = self.config.write().get().clone();
                 _ = channel.send(configuration);
             },
+            MessageToBackend::ReadNbtFile { path, modal_action, channel } => {
+                tokio::task::spawn(self.clone().read_nbt_file(path, modal_action, channel));
+            },
+            MessageToBackend::WriteNbtFile { path, file, backup, modal_action, channel } => {
+                tokio::task::spawn(self.clone().write_nbt_file(path, file, backup, modal_action, channel));
+            },
             MessageToBackend::CleanupOldLogFiles { instance: id } => {
                 let mut deleted = 0;
 
@@ -1035,6 +1096,21 @@ impl BackendState {
                     }
                 });
             },
+            MessageToBackend::SetInstanceDefaults { defaults } => {
+                self.config.write().modify(|config| {
+                    config.instance_defaults = defaults;
+                });
+            },
+            MessageToBackend::SetWorldBackupsToKeep { value } => {
+                self.config.write().modify(|config| {
+                    config.world_backups_to_keep = Some(value);
+                });
+            },
+            MessageToBackend::SetCurseforgeApiKey { key } => {
+                self.config.write().modify(|config| {
+                    config.curseforge_api_key = key;
+                });
+            },
             MessageToBackend::SetOpenGameOutputAfterLaunching { value } => {
                 self.config.write().modify(|config| {
                     config.dont_open_game_output_when_launching = !value;
@@ -1050,7 +1126,9 @@ impl BackendState {
                         "--run-instance",
                         instance.name.as_str()
                     ];
-                    crate::shortcut::create_shortcut(path, &format!("Launch {}", instance.name), &current_exe, args);
+                    let icon_path = instance.root_path.join("icon.png");
+                    let icon = instance.icon.is_some().then_some(icon_path.as_path());
+                    crate::shortcut::create_shortcut(path, &format!("Launch {}", instance.name), &current_exe, args, icon);
                 }
             },
             MessageToBackend::InstallUpdate { update, modal_action } => {
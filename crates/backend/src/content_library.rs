@@ -0,0 +1,434 @@
+use std::{path::{Path, PathBuf}, sync::Arc};
+
+use bridge::{
+    content_library::{ContentLibrary, ContentLibraryEntry, ContentLibraryLink},
+    instance::{ContentType, InstanceID},
+    modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType},
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use schema::update_batch::ContentUpdateBatchInfo;
+use sha1::{Digest, Sha1};
+
+use crate::{content_update::UPDATE_BATCH_INFO, lockfile::Lockfile, mod_metadata::ModMetadataManager, BackendState};
+
+#[derive(thiserror::Error, Debug)]
+enum ContentLibraryError {
+    #[error("Failed to perform I/O operation:\n{0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Failed to move to trash:\n{0}")]
+    TrashError(#[from] trash::Error),
+}
+
+struct ScanRoot {
+    instance: Option<InstanceID>,
+    name: Arc<str>,
+    path: Arc<Path>,
+}
+
+impl BackendState {
+    pub async fn get_content_library(self, channel: tokio::sync::oneshot::Sender<ContentLibrary>) {
+        let roots = self.content_library_scan_roots();
+        let content_library_dir = self.directories.content_library_dir.clone();
+        let mod_metadata_manager = self.mod_metadata_manager.clone();
+
+        let result = tokio::task::spawn_blocking(move || {
+            scan_content_library(&content_library_dir, &roots, &mod_metadata_manager)
+        }).await.unwrap();
+
+        match result {
+            Ok(library) => {
+                _ = channel.send(library);
+            },
+            Err(err) => {
+                self.send.send_error(format!("Error while scanning content library: {err}"));
+            },
+        }
+    }
+
+    pub async fn prune_content_library(self, modal_action: ModalAction, channel: tokio::sync::oneshot::Sender<ContentLibrary>) {
+        let tracker = ProgressTracker::new("Pruning content library".into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let result = self.prune_unused_entries().await;
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        tracker.notify();
+
+        match result {
+            Ok(library) => {
+                _ = channel.send(library);
+            },
+            Err(err) => {
+                modal_action.set_error_message(format!("Error while pruning content library:\n{err}").into());
+            },
+        }
+
+        modal_action.set_finished();
+    }
+
+    async fn prune_unused_entries(&self) -> Result<ContentLibrary, ContentLibraryError> {
+        let content_library_dir = self.directories.content_library_dir.clone();
+        let mod_metadata_manager = self.mod_metadata_manager.clone();
+
+        let library = {
+            let roots = self.content_library_scan_roots();
+            let content_library_dir = content_library_dir.clone();
+            let mod_metadata_manager = mod_metadata_manager.clone();
+            tokio::task::spawn_blocking(move || {
+                scan_content_library(&content_library_dir, &roots, &mod_metadata_manager)
+            }).await.unwrap()?
+        };
+
+        // Installs write library files under this lock, so holding it means nothing new can be linked
+        // between the scan below and moving the entry to the trash
+        let mut lockfiles = Vec::new();
+        for entry in library.entries.iter().filter(|entry| entry.is_prunable()) {
+            lockfiles.push(Lockfile::create(entry.path.with_added_extension("lock").into()).await?);
+        }
+
+        let roots = self.content_library_scan_roots();
+        let result = tokio::task::spawn_blocking(move || -> Result<ContentLibrary, ContentLibraryError> {
+            // Scan again now that the entries are locked, something may have been linked since the first scan
+            let library = scan_content_library(&content_library_dir, &roots, &mod_metadata_manager)?;
+            let removed = prune_entries(&library)?;
+            log::info!("Moved {removed} unused entries from the content library to the trash");
+
+            Ok(scan_content_library(&content_library_dir, &roots, &mod_metadata_manager)?)
+        }).await.unwrap();
+
+        drop(lockfiles);
+        result
+    }
+
+    fn content_library_scan_roots(&self) -> Vec<ScanRoot> {
+        let mut roots: Vec<ScanRoot> = self.instance_state.read().instances.iter().map(|instance| {
+            ScanRoot {
+                instance: Some(instance.id),
+                name: instance.name.as_str().into(),
+                path: instance.root_path.clone(),
+            }
+        }).collect();
+
+        roots.push(ScanRoot {
+            instance: None,
+            name: "Synced folders".into(),
+            path: self.directories.synced_dir.clone(),
+        });
+
+        roots
+    }
+}
+
+struct LibraryFile {
+    hash: [u8; 20],
+    path: PathBuf,
+    size: u64,
+    identity: Option<(u64, u64)>,
+}
+
+#[cfg(unix)]
+fn file_identity(metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+// Without inodes, files are matched by their hash instead, which also counts plain copies as links
+#[cfg(not(unix))]
+fn file_identity(_metadata: &std::fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(unix)]
+fn has_other_links(path: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path).map(|metadata| metadata.nlink() > 1).unwrap_or(true)
+}
+
+// Links can't be counted here, so this relies on the scan done while holding the install lock
+#[cfg(not(unix))]
+fn has_other_links(_path: &Path) -> bool {
+    false
+}
+
+fn read_library_files(content_library_dir: &Path) -> std::io::Result<Vec<LibraryFile>> {
+    let mut files = Vec::new();
+
+    let Ok(read_dir) = std::fs::read_dir(content_library_dir) else {
+        return Ok(files);
+    };
+
+    for hash_folder in read_dir {
+        let hash_folder = hash_folder?;
+        if !hash_folder.file_type()?.is_dir() {
+            continue;
+        }
+
+        for entry in std::fs::read_dir(hash_folder.path())? {
+            let entry = entry?;
+            let path = entry.path();
+
+            // Files are named after their sha1, temporary files from downloads in progress are skipped
+            let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let mut hash = [0u8; 20];
+            if hex::decode_to_slice(stem, &mut hash).is_err() {
+                continue;
+            }
+
+            let metadata = entry.metadata()?;
+            if !metadata.is_file() {
+                continue;
+            }
+
+            files.push(LibraryFile {
+                hash,
+                path,
+                size: metadata.len(),
+                identity: file_identity(&metadata),
+            });
+        }
+    }
+
+    Ok(files)
+}
+
+fn collect_links(
+    root: &ScanRoot,
+    folder: &Path,
+    library_files: &[LibraryFile],
+    by_identity: &FxHashMap<(u64, u64), usize>,
+    by_hash: &FxHashMap<[u8; 20], usize>,
+    sizes: &FxHashSet<u64>,
+    links: &mut [Vec<ContentLibraryLink>],
+) {
+    let Ok(read_dir) = std::fs::read_dir(folder) else {
+        return;
+    };
+
+    for entry in read_dir.flatten() {
+        // Symlinks aren't followed, synced folders are scanned separately
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        let path = entry.path();
+        if file_type.is_dir() {
+            collect_links(root, &path, library_files, by_identity, by_hash, sizes, links);
+            continue;
+        } else if !file_type.is_file() {
+            continue;
+        }
+
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if !sizes.contains(&metadata.len()) {
+            continue;
+        }
+
+        let index = if let Some(identity) = file_identity(&metadata) {
+            by_identity.get(&identity).copied()
+        } else {
+            std::fs::read(&path).ok().and_then(|bytes| {
+                let hash: [u8; 20] = Sha1::digest(&bytes).into();
+                by_hash.get(&hash).copied()
+            })
+        };
+        let Some(index) = index else {
+            continue;
+        };
+        if library_files[index].path == path {
+            continue;
+        }
+
+        let relative = path.strip_prefix(&root.path).unwrap_or(&path);
+        links[index].push(ContentLibraryLink {
+            instance: root.instance,
+            instance_name: root.name.clone(),
+            path: relative.to_string_lossy().into(),
+        });
+    }
+}
+
+fn scan_content_library(content_library_dir: &Path, roots: &[ScanRoot], mod_metadata_manager: &Arc<ModMetadataManager>) -> std::io::Result<ContentLibrary> {
+    let library_files = read_library_files(content_library_dir)?;
+
+    let mut by_identity = FxHashMap::default();
+    let mut by_hash = FxHashMap::default();
+    let mut sizes = FxHashSet::default();
+    for (index, file) in library_files.iter().enumerate() {
+        if let Some(identity) = file.identity {
+            by_identity.insert(identity, index);
+        }
+        by_hash.insert(file.hash, index);
+        sizes.insert(file.size);
+    }
+
+    let mut links = vec![Vec::new(); library_files.len()];
+    let mut retained_hashes = FxHashSet::default();
+
+    for root in roots {
+        collect_links(root, &root.path, &library_files, &by_identity, &by_hash, &sizes, &mut links);
+
+        // Replaced files are kept in the library so that the last update can be rolled back
+        if root.instance.is_some()
+            && let Ok(batch) = crate::read_json::<ContentUpdateBatchInfo>(&root.path.join(UPDATE_BATCH_INFO))
+        {
+            for entry in &batch.entries {
+                let mut hash = [0u8; 20];
+                if hex::decode_to_slice(&*entry.old_sha1, &mut hash).is_ok() {
+                    retained_hashes.insert(hash);
+                }
+            }
+        }
+    }
+
+    let summaries: Vec<_> = library_files.iter().map(|file| {
+        mod_metadata_manager.get_cached_by_sha1(&hex::encode(file.hash))
+            .or_else(|| mod_metadata_manager.get_path(&file.path))
+    }).collect();
+    mod_metadata_manager.save_path_hashes();
+
+    // Files of installed modpacks are only ever stored in the library
+    for (index, summary) in summaries.iter().enumerate() {
+        if links[index].is_empty() && !retained_hashes.contains(&library_files[index].hash) {
+            continue;
+        }
+        if let Some(summary) = summary
+            && let ContentType::ModrinthModpack { downloads, .. } = &summary.extra
+        {
+            for download in downloads.iter() {
+                let mut hash = [0u8; 20];
+                if hex::decode_to_slice(&*download.hashes.sha1, &mut hash).is_ok() {
+                    retained_hashes.insert(hash);
+                }
+            }
+        }
+    }
+
+    let mut library = ContentLibrary::default();
+    let mut entries = Vec::with_capacity(library_files.len());
+
+    for ((file, links), summary) in library_files.into_iter().zip(links).zip(summaries) {
+        let entry = ContentLibraryEntry {
+            hash: file.hash,
+            path: file.path.into(),
+            size: file.size,
+            summary,
+            links: links.into(),
+            retained: retained_hashes.contains(&file.hash),
+        };
+
+        library.total_size += entry.size;
+        if entry.is_prunable() {
+            library.prunable_size += entry.size;
+        } else {
+            library.saved_size += entry.size * entry.links.len().saturating_sub(1) as u64;
+        }
+
+        entries.push(entry);
+    }
+
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.size));
+    library.entries = entries.into();
+
+    Ok(library)
+}
+
+fn prune_entries(library: &ContentLibrary) -> Result<usize, ContentLibraryError> {
+    let mut removed = 0;
+
+    for entry in library.entries.iter() {
+        // Links from outside the scanned folders only show up in the link count
+        if !entry.is_prunable() || has_other_links(&entry.path) {
+            continue;
+        }
+
+        trash::delete(&entry.path)?;
+        removed += 1;
+
+        if let Some(parent) = entry.path.parent() {
+            _ = std::fs::remove_dir(parent);
+        }
+    }
+
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{path::{Path, PathBuf}, sync::Arc};
+
+    use bridge::instance::InstanceID;
+    use schema::update_batch::{ContentUpdateBatchEntry, ContentUpdateBatchInfo};
+    use sha1::{Digest, Sha1};
+
+    use crate::{content_update::UPDATE_BATCH_INFO, mod_metadata::ModMetadataManager};
+
+    use super::{ScanRoot, scan_content_library};
+
+    fn add_library_file(content_library_dir: &Path, data: &[u8]) -> ([u8; 20], PathBuf) {
+        let hash: [u8; 20] = Sha1::digest(data).into();
+        let path = crate::create_content_library_path(content_library_dir, hash, Some("jar"));
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, data).unwrap();
+        (hash, path)
+    }
+
+    #[test]
+    fn test_scan_content_library() {
+        let dir = tempfile::tempdir().unwrap();
+        let content_library_dir: Arc<Path> = dir.path().join("contentlibrary").into();
+        let content_meta_dir: Arc<Path> = dir.path().join("contentmeta").into();
+        let instance_dir: Arc<Path> = dir.path().join("instances").join("Instance").into();
+        std::fs::create_dir_all(&content_meta_dir).unwrap();
+        std::fs::create_dir_all(instance_dir.join(".minecraft").join("mods")).unwrap();
+
+        let (linked, linked_path) = add_library_file(&content_library_dir, b"linked mod");
+        let (retained, _) = add_library_file(&content_library_dir, b"replaced mod");
+        let (prunable, _) = add_library_file(&content_library_dir, b"unused mod");
+
+        std::fs::hard_link(&linked_path, instance_dir.join(".minecraft").join("mods").join("linked.jar")).unwrap();
+
+        let batch = ContentUpdateBatchInfo {
+            created: 0,
+            entries: vec![ContentUpdateBatchEntry {
+                old_path: "mods/replaced.jar".into(),
+                old_sha1: hex::encode(retained).into(),
+                new_path: "mods/linked.jar".into(),
+            }],
+        };
+        std::fs::write(instance_dir.join(UPDATE_BATCH_INFO), serde_json::to_vec(&batch).unwrap()).unwrap();
+
+        let roots = [ScanRoot {
+            instance: Some(InstanceID { index: 0, generation: 0 }),
+            name: "Instance".into(),
+            path: instance_dir.clone(),
+        }];
+        let mod_metadata_manager = Arc::new(ModMetadataManager::load(content_meta_dir, content_library_dir.clone()));
+
+        let library = scan_content_library(&content_library_dir, &roots, &mod_metadata_manager).unwrap();
+        assert_eq!(library.entries.len(), 3);
+
+        let entry = |hash: [u8; 20]| library.entries.iter().find(|entry| entry.hash == hash).unwrap();
+
+        let linked = entry(linked);
+        assert_eq!(linked.links.len(), 1);
+        assert_eq!(Path::new(&*linked.links[0].path), Path::new(".minecraft/mods/linked.jar"));
+        assert!(!linked.is_prunable());
+
+        let retained = entry(retained);
+        assert!(retained.links.is_empty());
+        assert!(retained.retained);
+        assert!(!retained.is_prunable());
+
+        let prunable = entry(prunable);
+        assert!(prunable.links.is_empty());
+        assert!(!prunable.retained);
+        assert!(prunable.is_prunable());
+
+        assert_eq!(library.prunable_size, b"unused mod".len() as u64);
+        assert_eq!(library.total_size, (b"linked mod".len() + b"replaced mod".len() + b"unused mod".len()) as u64);
+    }
+}
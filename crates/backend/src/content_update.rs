@@ -0,0 +1,827 @@
+use std::{ffi::OsStr, path::{Path, PathBuf}, sync::{atomic::Ordering, Arc}};
+
+use bridge::{
+    content_update::{ContentChangelog, ContentUpdateBatchSummary, ContentUpdateEntry, ContentUpdatePlan},
+    install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget},
+    instance::{ContentSummary, ContentType, InstanceContentSummary, InstanceID},
+    message::MessageToFrontend,
+    modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType},
+};
+use futures::TryFutureExt;
+use schema::{
+    content::ContentSource,
+    curseforge::{CurseforgeFile, CurseforgeModFilesRequest, CurseforgeModLoaderType},
+    github::GithubReleaseAsset,
+    loader::Loader,
+    modrinth::{ModrinthLoader, ModrinthProjectVersion, ModrinthProjectVersionsRequest},
+    update_batch::{ContentUpdateBatchEntry, ContentUpdateBatchInfo},
+};
+use strum::IntoEnumIterator;
+use tokio::sync::Semaphore;
+
+use crate::{
+    BackendState, IoOrSerializationError,
+    install_content::ContentInstallError,
+    instance::ContentFolder,
+    metadata::{
+        items::{
+            CurseforgeModFilesMetadataItem, ModrinthProjectVersionsMetadataItem, ModrinthV3VersionUpdateMetadataItem, ModrinthVersionUpdateMetadataItem, VersionUpdateParameters,
+            VersionV3LoaderFields, VersionV3UpdateParameters,
+        },
+        manager::MetaLoadError,
+    },
+    mod_metadata::ModUpdateAction,
+};
+
+pub(crate) const UPDATE_BATCH_INFO: &str = "update_batch.json";
+
+#[derive(thiserror::Error, Debug)]
+pub enum ContentUpdateError {
+    #[error("Unknown instance")]
+    UnknownInstance,
+    #[error("Instance is currently running")]
+    InstanceRunning,
+    #[error("Unable to check for updates")]
+    UpdateCheckFailed,
+    #[error("There are no updates to roll back")]
+    NothingToRollBack,
+    #[error("Failed to install updates:\n{0}")]
+    InstallFailed(ContentInstallError),
+    #[error("Failed to perform I/O operation:\n{0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Failed to serialize update batch info:\n{0}")]
+    SerializationError(#[from] serde_json::Error),
+}
+
+impl BackendState {
+    /// Checks every unpinned content item for updates, returns false if the check failed
+    pub async fn check_content_updates(&self, id: InstanceID, modal_action: &ModalAction) -> bool {
+        let (loader, version) = if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+            let configuration = instance.configuration.get();
+            (configuration.loader, configuration.minecraft_version)
+        } else {
+            self.send.send_error("Can't update instance, unknown id");
+            modal_action.set_error_message("Can't update instance, unknown id".into());
+            return false;
+        };
+
+        let mut content = Vec::new();
+        for folder in ContentFolder::iter() {
+            let Some(summaries) = self.clone().load_instance_content(id, folder).await else {
+                return false;
+            };
+            content.extend_from_slice(&*summaries);
+        }
+
+        // Pinned content is kept at its current version on purpose
+        content.retain(|summary| summary.pinned.is_none());
+
+        let modrinth_loader = loader.as_modrinth_loader();
+        if modrinth_loader == ModrinthLoader::Unknown {
+            modal_action.set_error_message("Unable to update instance, unsupported loader".into());
+            return false;
+        }
+
+        // Content that was installed by hand may still be known to CurseForge
+        self.identify_curseforge_content(&content).await;
+
+        let tracker = ProgressTracker::new("Checking content".into(), self.send.clone());
+        tracker.set_total(content.len());
+        modal_action.trackers.push(tracker.clone());
+
+        let semaphore = Semaphore::new(8);
+
+        let mod_params = &VersionUpdateParameters {
+            loaders: [modrinth_loader].into(),
+            game_versions: [version].into(),
+        };
+
+        let fabric_mod_params = &VersionUpdateParameters {
+            loaders: [ModrinthLoader::Fabric].into(),
+            game_versions: [version].into(),
+        };
+
+        let forge_mod_params = &VersionUpdateParameters {
+            loaders: [ModrinthLoader::Forge].into(),
+            game_versions: [version].into(),
+        };
+
+        let neoforge_mod_params = &VersionUpdateParameters {
+            loaders: [ModrinthLoader::NeoForge].into(),
+            game_versions: [version].into(),
+        };
+
+        let resourcepack_params = &VersionUpdateParameters {
+            loaders: [ModrinthLoader::Minecraft].into(),
+            game_versions: [version].into(),
+        };
+
+        let shaderpack_params = &VersionUpdateParameters {
+            loaders: [ModrinthLoader::Iris, ModrinthLoader::Optifine].into(),
+            game_versions: [version].into(),
+        };
+
+        let modrinth_modpack_params = &VersionV3UpdateParameters {
+            loaders: ["mrpack".into()].into(),
+            loader_fields: VersionV3LoaderFields {
+                mrpack_loaders: [modrinth_loader].into(),
+                game_versions: [version].into(),
+            },
+        };
+
+        let meta = self.meta.clone();
+        let curseforge_api = &self.curseforge_api();
+        let curseforge_version: Arc<str> = version.as_str().into();
+
+        let mut futures = Vec::new();
+
+        struct UpdateResult {
+            mod_summary: Arc<ContentSummary>,
+            action: ModUpdateAction,
+        }
+
+        { // Scope is needed so await doesn't complain about the non-send RwLockReadGuard
+            let sources = self.mod_metadata_manager.read_content_sources();
+            for summary in content.iter() {
+                let source = sources.get(&summary.content_summary.hash).unwrap_or(ContentSource::Manual);
+                let semaphore = &semaphore;
+                let meta = &meta;
+                let tracker = &tracker;
+                let curseforge_version = curseforge_version.clone();
+                futures.push(async move {
+                    match source {
+                        ContentSource::Manual | ContentSource::Url { .. } => {
+                            tracker.add_count(1);
+                            tracker.notify();
+                            Ok(ModUpdateAction::ManualInstall)
+                        },
+                        ContentSource::ModrinthUnknown | ContentSource::ModrinthProject { .. } => {
+                            let permit = semaphore.acquire().await.unwrap();
+                            let result = match summary.content_summary.extra {
+                                ContentType::Fabric => {
+                                    meta.fetch(&ModrinthVersionUpdateMetadataItem {
+                                        sha1: hex::encode(summary.content_summary.hash).into(),
+                                        params: fabric_mod_params.clone()
+                                    }).await
+                                },
+                                ContentType::Forge => {
+                                    meta.fetch(&ModrinthVersionUpdateMetadataItem {
+                                        sha1: hex::encode(summary.content_summary.hash).into(),
+                                        params: forge_mod_params.clone()
+                                    }).await
+                                },
+                                ContentType::NeoForge => {
+                                    meta.fetch(&ModrinthVersionUpdateMetadataItem {
+                                        sha1: hex::encode(summary.content_summary.hash).into(),
+                                        params: neoforge_mod_params.clone()
+                                    }).await
+                                },
+                                ContentType::JavaModule => {
+                                    meta.fetch(&ModrinthVersionUpdateMetadataItem {
+                                        sha1: hex::encode(summary.content_summary.hash).into(),
+                                        params: mod_params.clone()
+                                    }).await
+                                },
+                                ContentType::ModrinthModpack { .. } => {
+                                    meta.fetch(&ModrinthV3VersionUpdateMetadataItem {
+                                        sha1: hex::encode(summary.content_summary.hash).into(),
+                                        params: modrinth_modpack_params.clone()
+                                    }).await
+                                },
+                                ContentType::ResourcePack { .. } => {
+                                    meta.fetch(&ModrinthVersionUpdateMetadataItem {
+                                        sha1: hex::encode(summary.content_summary.hash).into(),
+                                        params: resourcepack_params.clone()
+                                    }).await
+                                },
+                                ContentType::ShaderPack { .. } => {
+                                    meta.fetch(&ModrinthVersionUpdateMetadataItem {
+                                        sha1: hex::encode(summary.content_summary.hash).into(),
+                                        params: shaderpack_params.clone()
+                                    }).await
+                                },
+                            };
+                            drop(permit);
+
+                            tracker.add_count(1);
+                            tracker.notify();
+
+                            if let Err(MetaLoadError::NonOK(404)) = result {
+                                return Ok(ModUpdateAction::ErrorNotFound);
+                            }
+
+                            let result = result?;
+
+                            if let ContentSource::ModrinthProject { ref project } = source {
+                                if &result.0.project_id != project {
+                                    log::error!("Refusing to update {:?}, mismatched project ids: expected {}, got {}",
+                                        summary.content_summary.hash, &result.0.project_id, &project);
+                                    return Ok(ModUpdateAction::ErrorNotFound);
+                                }
+                            }
+
+                            let install_file = result
+                                .0
+                                .files
+                                .iter()
+                                .find(|file| file.primary)
+                                .unwrap_or(result.0.files.first().unwrap());
+
+                            let mut latest_hash = [0u8; 20];
+                            let Ok(_) = hex::decode_to_slice(&*install_file.hashes.sha1, &mut latest_hash) else {
+                                return Ok(ModUpdateAction::ErrorInvalidHash);
+                            };
+
+                            if latest_hash == summary.content_summary.hash {
+                                Ok(ModUpdateAction::AlreadyUpToDate)
+                            } else {
+                                Ok(ModUpdateAction::Modrinth {
+                                    file: install_file.clone(),
+                                    project_id: result.0.project_id.clone(),
+                                })
+                            }
+                        },
+                        ContentSource::CurseforgeProject { project } => {
+                            let mod_loader_type = match summary.content_summary.extra {
+                                ContentType::Fabric => Some(CurseforgeModLoaderType::Fabric),
+                                ContentType::Forge => Some(CurseforgeModLoaderType::Forge),
+                                ContentType::NeoForge => Some(CurseforgeModLoaderType::NeoForge),
+                                ContentType::JavaModule => loader.as_curseforge_loader(),
+                                ContentType::ModrinthModpack { .. } | ContentType::ResourcePack { .. } | ContentType::ShaderPack { .. } => None,
+                            };
+
+                            let permit = semaphore.acquire().await.unwrap();
+                            let result = meta.fetch(&CurseforgeModFilesMetadataItem {
+                                api: curseforge_api,
+                                request: &CurseforgeModFilesRequest {
+                                    mod_id: project,
+                                    game_version: Some(curseforge_version),
+                                    mod_loader_type,
+                                },
+                            }).await;
+                            drop(permit);
+
+                            tracker.add_count(1);
+                            tracker.notify();
+
+                            let files = match result {
+                                Ok(files) => files,
+                                Err(err) => {
+                                    log::warn!("Unable to check CurseForge project {project} for updates: {err}");
+                                    return Ok(ModUpdateAction::ErrorNotFound);
+                                },
+                            };
+
+                            let Some(latest_file) = crate::curseforge::newest_file(&files.data, Loader::Unknown) else {
+                                return Ok(ModUpdateAction::ErrorNotFound);
+                            };
+
+                            let mut latest_hash = [0u8; 20];
+                            let Some(Ok(_)) = latest_file.sha1().map(|sha1| hex::decode_to_slice(&**sha1, &mut latest_hash)) else {
+                                return Ok(ModUpdateAction::ErrorInvalidHash);
+                            };
+
+                            if latest_hash == summary.content_summary.hash {
+                                Ok(ModUpdateAction::AlreadyUpToDate)
+                            } else if latest_file.download_url.is_none() {
+                                Ok(ModUpdateAction::ManualInstall)
+                            } else {
+                                Ok(ModUpdateAction::Curseforge {
+                                    file: latest_file.clone(),
+                                })
+                            }
+                        },
+                        ContentSource::GithubRelease { ref owner, ref repo, ref tag, ref asset } => {
+                            let permit = semaphore.acquire().await.unwrap();
+                            let result = self.fetch_github_release(owner, repo, None).await;
+                            drop(permit);
+
+                            tracker.add_count(1);
+                            tracker.notify();
+
+                            let release = match result {
+                                Ok(release) => release,
+                                Err(err) => {
+                                    log::warn!("Unable to check {owner}/{repo} for updates: {err}");
+                                    return Ok(ModUpdateAction::ErrorNotFound);
+                                },
+                            };
+
+                            if &release.tag_name == tag {
+                                return Ok(ModUpdateAction::AlreadyUpToDate);
+                            }
+
+                            // The shortest match skips variants such as -sources.jar that match the same pattern
+                            let latest_asset = release.assets.iter()
+                                .filter(|latest| crate::github_release::matches_asset_pattern(asset, &latest.name))
+                                .min_by_key(|latest| latest.name.len());
+                            let Some(latest_asset) = latest_asset else {
+                                return Ok(ModUpdateAction::ErrorNotFound);
+                            };
+
+                            Ok(ModUpdateAction::GithubRelease {
+                                asset: latest_asset.clone(),
+                                changelog: release.body.clone(),
+                                source: ContentSource::GithubRelease {
+                                    owner: owner.clone(),
+                                    repo: repo.clone(),
+                                    tag: release.tag_name.clone(),
+                                    asset: asset.clone(),
+                                },
+                            })
+                        },
+                    }
+                }.map_ok(|action| UpdateResult {
+                    mod_summary: summary.content_summary.clone(),
+                    action,
+                }));
+            }
+        }
+
+        let results: Result<Vec<UpdateResult>, MetaLoadError> = futures::future::try_join_all(futures).await;
+
+        match results {
+            Ok(updates) => {
+                let mut meta_updates = self.mod_metadata_manager.updates.write();
+
+                for update in updates {
+                    update.mod_summary.update_status.store(update.action.to_status(), Ordering::Relaxed);
+                    meta_updates.insert(update.mod_summary.hash, update.action);
+                }
+            },
+            Err(error) => {
+                tracker.set_finished(ProgressTrackerFinishType::Error);
+                modal_action.set_error_message(format!("Error checking for updates: {}", error).into());
+                return false;
+            },
+        }
+
+        tracker.set_finished(ProgressTrackerFinishType::Normal);
+        true
+    }
+
+    pub async fn plan_content_updates(self, id: InstanceID, modal_action: ModalAction, channel: tokio::sync::oneshot::Sender<ContentUpdatePlan>) {
+        match self.plan_content_updates_inner(id, &modal_action).await {
+            Ok(plan) => {
+                _ = channel.send(plan);
+            },
+            // The update check has already reported its error
+            Err(ContentUpdateError::UpdateCheckFailed) => {},
+            Err(err) => {
+                modal_action.set_error_message(format!("Unable to check for updates:\n{err}").into());
+            },
+        }
+        modal_action.set_finished();
+    }
+
+    async fn plan_content_updates_inner(&self, id: InstanceID, modal_action: &ModalAction) -> Result<ContentUpdatePlan, ContentUpdateError> {
+        let Some((root_path, minecraft_version)) = self.instance_state.write().instances.get_mut(id).map(|instance| {
+            (instance.root_path.clone(), instance.configuration.get().minecraft_version)
+        }) else {
+            return Err(ContentUpdateError::UnknownInstance);
+        };
+
+        if !self.check_content_updates(id, modal_action).await {
+            return Err(ContentUpdateError::UpdateCheckFailed);
+        }
+
+        let mut content = Vec::new();
+        for folder in ContentFolder::iter() {
+            let Some(summaries) = self.clone().load_instance_content(id, folder).await else {
+                return Err(ContentUpdateError::UpdateCheckFailed);
+            };
+            content.extend_from_slice(&*summaries);
+        }
+
+        let mut other_entries = Vec::new();
+        let pending: Vec<_> = {
+            let updates = self.mod_metadata_manager.updates.read();
+            content.iter().filter(|summary| summary.pinned.is_none()).filter_map(|summary| {
+                match updates.get(&summary.content_summary.hash) {
+                    Some(ModUpdateAction::Modrinth { file, project_id }) => Some((summary, file.clone(), project_id.clone())),
+                    Some(ModUpdateAction::GithubRelease { asset, changelog, source }) => {
+                        other_entries.push(github_update_entry(summary, asset, changelog, source));
+                        None
+                    },
+                    Some(ModUpdateAction::Curseforge { file }) => {
+                        other_entries.push(curseforge_update_entry(summary, file));
+                        None
+                    },
+                    _ => None,
+                }
+            }).collect()
+        };
+
+        let tracker = ProgressTracker::new("Fetching changelogs".into(), self.send.clone());
+        tracker.set_total(pending.len());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let semaphore = &Semaphore::new(8);
+        let meta = &self.meta;
+        let tracker_ref = &tracker;
+        let game_versions: Arc<[Arc<str>]> = [Arc::from(minecraft_version.as_str())].into();
+
+        let mut futures = Vec::new();
+        for (summary, file, project_id) in pending {
+            let game_versions = game_versions.clone();
+            futures.push(async move {
+                let loaders = match summary.content_summary.extra {
+                    ContentType::Fabric => Some([ModrinthLoader::Fabric].into()),
+                    ContentType::Forge => Some([ModrinthLoader::Forge].into()),
+                    ContentType::NeoForge => Some([ModrinthLoader::NeoForge].into()),
+                    _ => None,
+                };
+                let request = ModrinthProjectVersionsRequest {
+                    project_id: project_id.clone(),
+                    game_versions: Some(game_versions),
+                    loaders,
+                };
+
+                let permit = semaphore.acquire().await.unwrap();
+                let versions = meta.fetch(&ModrinthProjectVersionsMetadataItem(&request)).await;
+                drop(permit);
+
+                tracker_ref.add_count(1);
+                tracker_ref.notify();
+
+                // Changelogs are only informational, so the update can still be offered without them
+                let (old_version, new_version, changelogs) = match versions {
+                    Ok(versions) => find_changelogs(&versions.0, &hex::encode(summary.content_summary.hash), &file.hashes.sha1),
+                    Err(err) => {
+                        log::warn!("Unable to fetch versions of {project_id} for changelogs: {err}");
+                        (None, None, Vec::new())
+                    },
+                };
+
+                let mut path = summary.path.with_file_name(&*file.filename);
+                if !summary.enabled {
+                    path.add_extension("disabled");
+                }
+
+                ContentUpdateEntry {
+                    name: summary.content_summary.name.clone().unwrap_or_else(|| summary.filename.clone()),
+                    path: summary.path.clone(),
+                    hash: summary.content_summary.hash,
+                    old_version: old_version.unwrap_or_else(|| summary.content_summary.version_str.clone()),
+                    new_version: new_version.unwrap_or_else(|| file.filename.clone()),
+                    changelogs: changelogs.into(),
+                    install: ContentInstallFile {
+                        replace_old: Some(summary.path.clone()),
+                        path: ContentInstallPath::Raw(path.into()),
+                        download: ContentDownload::Url {
+                            url: file.url.clone(),
+                            sha1: file.hashes.sha1.clone(),
+                            size: file.size,
+                        },
+                        content_source: ContentSource::ModrinthProject { project: project_id },
+                    },
+                }
+            });
+        }
+
+        let mut entries = futures::future::join_all(futures).await;
+        entries.extend(other_entries);
+        entries.sort_by(|a, b| lexical_sort::natural_lexical_cmp(&a.name, &b.name));
+
+        tracker.set_finished(ProgressTrackerFinishType::Normal);
+        tracker.notify();
+
+        let last_batch = crate::read_json::<ContentUpdateBatchInfo>(&root_path.join(UPDATE_BATCH_INFO)).ok()
+            .filter(|info| !info.entries.is_empty())
+            .map(|info| ContentUpdateBatchSummary {
+                created: info.created,
+                count: info.entries.len(),
+            });
+
+        Ok(ContentUpdatePlan {
+            entries: entries.into(),
+            last_batch,
+        })
+    }
+
+    pub async fn apply_content_updates(self, id: InstanceID, entries: Arc<[ContentUpdateEntry]>, modal_action: ModalAction) {
+        match self.apply_content_updates_inner(id, &entries, &modal_action).await {
+            Ok(()) => {
+                self.send.send_success(format!("Updated {} items", entries.len()));
+            },
+            Err(err) => {
+                modal_action.set_error_message(format!("Unable to update content:\n{err}").into());
+            },
+        }
+        modal_action.set_finished();
+        self.send.send(MessageToFrontend::Refresh);
+    }
+
+    async fn apply_content_updates_inner(&self, id: InstanceID, entries: &Arc<[ContentUpdateEntry]>, modal_action: &ModalAction) -> Result<(), ContentUpdateError> {
+        let (root_path, dot_minecraft_path, loader, minecraft_version) = {
+            let mut instance_state = self.instance_state.write();
+            let Some(instance) = instance_state.instances.get_mut(id) else {
+                return Err(ContentUpdateError::UnknownInstance);
+            };
+            if instance.child.is_some() {
+                return Err(ContentUpdateError::InstanceRunning);
+            }
+            let configuration = instance.configuration.get();
+            (instance.root_path.clone(), instance.dot_minecraft_path.clone(), configuration.loader, configuration.minecraft_version)
+        };
+
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let tracker = ProgressTracker::new("Keeping replaced files".into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        // The replaced files are kept in the content library so that the batch can be rolled back
+        let batch_entries = {
+            let entries = entries.clone();
+            let content_library_dir = self.directories.content_library_dir.clone();
+            tokio::task::spawn_blocking(move || {
+                keep_replaced_files(&entries, &dot_minecraft_path, &content_library_dir)
+            }).await.unwrap()
+        };
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(batch_entries.is_err()));
+        tracker.notify();
+
+        let batch_entries = batch_entries?;
+
+        // Nothing is replaced unless every download succeeds, so the batch is applied as a whole
+        let content_install = ContentInstall {
+            target: InstallTarget::Instance(id),
+            loader_hint: loader,
+            version_hint: Some(minecraft_version.as_str().into()),
+            files: entries.iter().map(|entry| entry.install.clone()).collect(),
+        };
+        self.try_install_content(content_install, modal_action).await.map_err(ContentUpdateError::InstallFailed)?;
+
+        let info = ContentUpdateBatchInfo {
+            created: chrono::Local::now().timestamp_millis(),
+            entries: batch_entries,
+        };
+        let bytes = serde_json::to_vec(&info)?;
+        crate::write_safe(&root_path.join(UPDATE_BATCH_INFO), &bytes)?;
+
+        Ok(())
+    }
+
+    pub async fn rollback_content_updates(self, id: InstanceID, modal_action: ModalAction) {
+        let tracker = ProgressTracker::new("Rolling back updates".into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let result = self.rollback_content_updates_inner(id).await;
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        tracker.notify();
+
+        match result {
+            Ok((skipped, failed)) => {
+                for skipped in skipped {
+                    self.send.send_warning(format!("Skipped restoring {skipped}, the file is no longer available"));
+                }
+                if failed.is_empty() {
+                    self.send.send_success("Rolled back the last updates");
+                } else {
+                    let failed: Vec<String> = failed.iter().map(|(path, err)| format!("{path}: {err}")).collect();
+                    modal_action.set_error_message(format!("Unable to restore some files:\n{}", failed.join("\n")).into());
+                }
+            },
+            Err(err) => {
+                modal_action.set_error_message(format!("Unable to roll back updates:\n{err}").into());
+            },
+        }
+        modal_action.set_finished();
+        self.send.send(MessageToFrontend::Refresh);
+    }
+
+    /// Returns the entries that were skipped and the entries that failed to be restored
+    async fn rollback_content_updates_inner(&self, id: InstanceID) -> Result<(Vec<Arc<str>>, Vec<(Arc<str>, std::io::Error)>), ContentUpdateError> {
+        let (root_path, dot_minecraft_path) = {
+            let instance_state = self.instance_state.read();
+            let Some(instance) = instance_state.instances.get(id) else {
+                return Err(ContentUpdateError::UnknownInstance);
+            };
+            if instance.child.is_some() {
+                return Err(ContentUpdateError::InstanceRunning);
+            }
+            (instance.root_path.clone(), instance.dot_minecraft_path.clone())
+        };
+
+        let info_path = root_path.join(UPDATE_BATCH_INFO);
+        let info: ContentUpdateBatchInfo = match crate::read_json(&info_path) {
+            Ok(info) => info,
+            Err(IoOrSerializationError::Io(_)) => return Err(ContentUpdateError::NothingToRollBack),
+            Err(IoOrSerializationError::Serialization(err)) => return Err(err.into()),
+        };
+
+        let this = self.clone();
+        tokio::task::spawn_blocking(move || -> Result<_, ContentUpdateError> {
+            let mut skipped = Vec::new();
+            let mut failed = Vec::new();
+
+            for entry in &info.entries {
+                // The updated file may have been toggled since, the restored file keeps its current state
+                let new_path = without_disabled(&dot_minecraft_path.join(&*entry.new_path));
+                let disabled_new_path = new_path.with_added_extension("disabled");
+                let (current_path, enabled) = if new_path.is_file() {
+                    (new_path, true)
+                } else if disabled_new_path.is_file() {
+                    (disabled_new_path, false)
+                } else {
+                    skipped.push(entry.old_path.clone());
+                    continue;
+                };
+
+                let mut old_hash = [0u8; 20];
+                let Ok(_) = hex::decode_to_slice(&*entry.old_sha1, &mut old_hash) else {
+                    skipped.push(entry.old_path.clone());
+                    continue;
+                };
+
+                let old_path = without_disabled(&dot_minecraft_path.join(&*entry.old_path));
+                let library_path = content_library_path_for(&old_path, old_hash, &this.directories.content_library_dir);
+                if !library_path.is_file() {
+                    skipped.push(entry.old_path.clone());
+                    continue;
+                }
+
+                let target_path = if enabled {
+                    old_path
+                } else {
+                    old_path.with_added_extension("disabled")
+                };
+
+                let old_summary = this.mod_metadata_manager.get_path(&library_path);
+                this.replace_aux_path(&current_path, &old_summary, &target_path);
+
+                let result = std::fs::remove_file(&current_path).and_then(|_| {
+                    if std::fs::hard_link(&library_path, &target_path).is_err() {
+                        std::fs::copy(&library_path, &target_path)?;
+                    }
+                    Ok(())
+                });
+                if let Err(err) = result {
+                    failed.push((entry.old_path.clone(), err));
+                }
+            }
+
+            // A batch can only be rolled back once, unless some files failed so that they can be tried again
+            if failed.is_empty() {
+                std::fs::remove_file(&info_path)?;
+            }
+
+            Ok((skipped, failed))
+        }).await.unwrap()
+    }
+}
+
+// Finds the installed and new versions, along with the changelogs of every version in between
+fn find_changelogs(versions: &[ModrinthProjectVersion], old_sha1: &str, new_sha1: &str) -> (Option<Arc<str>>, Option<Arc<str>>, Vec<ContentChangelog>) {
+    let contains_file = |version: &ModrinthProjectVersion, sha1: &str| {
+        version.files.iter().any(|file| file.hashes.sha1.eq_ignore_ascii_case(sha1))
+    };
+
+    let old_index = versions.iter().position(|version| contains_file(version, old_sha1));
+    let old_version = old_index.map(|index| version_name(&versions[index]));
+
+    let Some(new_index) = versions.iter().position(|version| contains_file(version, new_sha1)) else {
+        return (old_version, None, Vec::new());
+    };
+
+    // Versions are listed newest first
+    let end = old_index.filter(|old_index| *old_index > new_index).unwrap_or(new_index + 1);
+    let changelogs = versions[new_index..end].iter().filter_map(|version| {
+        let changelog = version.changelog.as_deref()?.trim();
+        (!changelog.is_empty()).then(|| ContentChangelog {
+            version: version_name(version),
+            changelog: changelog.into(),
+        })
+    }).collect();
+
+    (old_version, Some(version_name(&versions[new_index])), changelogs)
+}
+
+pub(crate) fn github_update_install_file(summary: &InstanceContentSummary, asset: &GithubReleaseAsset, source: &ContentSource) -> ContentInstallFile {
+    let mut path = summary.path.with_file_name(&*asset.name);
+    if !summary.enabled {
+        path.add_extension("disabled");
+    }
+
+    ContentInstallFile {
+        replace_old: Some(summary.path.clone()),
+        path: ContentInstallPath::Raw(path.into()),
+        download: ContentDownload::UnverifiedUrl {
+            url: asset.browser_download_url.clone(),
+        },
+        content_source: source.clone(),
+    }
+}
+
+fn github_update_entry(summary: &InstanceContentSummary, asset: &GithubReleaseAsset, changelog: &Option<Arc<str>>, source: &ContentSource) -> ContentUpdateEntry {
+    let new_version = match source {
+        ContentSource::GithubRelease { tag, .. } => tag.clone(),
+        _ => asset.name.clone(),
+    };
+
+    let changelogs = changelog.as_deref().map(str::trim).filter(|changelog| !changelog.is_empty()).map(|changelog| ContentChangelog {
+        version: new_version.clone(),
+        changelog: changelog.into(),
+    });
+
+    ContentUpdateEntry {
+        name: summary.content_summary.name.clone().unwrap_or_else(|| summary.filename.clone()),
+        path: summary.path.clone(),
+        hash: summary.content_summary.hash,
+        old_version: summary.content_summary.version_str.clone(),
+        new_version,
+        changelogs: changelogs.into_iter().collect(),
+        install: github_update_install_file(summary, asset, source),
+    }
+}
+
+pub(crate) fn curseforge_update_install_file(summary: &InstanceContentSummary, file: &CurseforgeFile) -> ContentInstallFile {
+    let mut path = summary.path.with_file_name(&*file.file_name);
+    if !summary.enabled {
+        path.add_extension("disabled");
+    }
+
+    ContentInstallFile {
+        replace_old: Some(summary.path.clone()),
+        path: ContentInstallPath::Raw(path.into()),
+        download: ContentDownload::Curseforge {
+            project_id: file.mod_id,
+            file_id: Some(file.id),
+            install_dependencies: false,
+        },
+        content_source: ContentSource::CurseforgeProject { project: file.mod_id },
+    }
+}
+
+// CurseForge only has changelogs behind a separate request per file, so none are shown
+fn curseforge_update_entry(summary: &InstanceContentSummary, file: &CurseforgeFile) -> ContentUpdateEntry {
+    ContentUpdateEntry {
+        name: summary.content_summary.name.clone().unwrap_or_else(|| summary.filename.clone()),
+        path: summary.path.clone(),
+        hash: summary.content_summary.hash,
+        old_version: summary.content_summary.version_str.clone(),
+        new_version: file.display_name.clone(),
+        changelogs: Arc::from([]),
+        install: curseforge_update_install_file(summary, file),
+    }
+}
+
+fn version_name(version: &ModrinthProjectVersion) -> Arc<str> {
+    version.version_number.clone()
+        .or_else(|| version.name.clone())
+        .unwrap_or_else(|| version.id.clone())
+}
+
+fn keep_replaced_files(entries: &[ContentUpdateEntry], dot_minecraft_path: &Path, content_library_dir: &Path) -> std::io::Result<Vec<ContentUpdateBatchEntry>> {
+    let mut batch_entries = Vec::with_capacity(entries.len());
+
+    for entry in entries {
+        let ContentInstallPath::Raw(new_path) = &entry.install.path else {
+            continue;
+        };
+        let (Ok(old_path), Ok(new_path)) = (entry.path.strip_prefix(dot_minecraft_path), new_path.strip_prefix(dot_minecraft_path)) else {
+            continue;
+        };
+        let (Some(old_path), Some(new_path)) = (old_path.to_str(), new_path.to_str()) else {
+            continue;
+        };
+
+        let library_path = content_library_path_for(&entry.path, entry.hash, content_library_dir);
+        if !library_path.is_file() {
+            if let Some(parent) = library_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            if std::fs::hard_link(&entry.path, &library_path).is_err() {
+                std::fs::copy(&entry.path, &library_path)?;
+            }
+        }
+
+        batch_entries.push(ContentUpdateBatchEntry {
+            old_path: old_path.into(),
+            old_sha1: hex::encode(entry.hash).into(),
+            new_path: new_path.into(),
+        });
+    }
+
+    Ok(batch_entries)
+}
+
+fn content_library_path_for(path: &Path, hash: [u8; 20], content_library_dir: &Path) -> PathBuf {
+    let path = without_disabled(path);
+    crate::create_content_library_path(content_library_dir, hash, path.extension().and_then(OsStr::to_str))
+}
+
+fn without_disabled(path: &Path) -> PathBuf {
+    let mut path = path.to_path_buf();
+    if path.extension() == Some(OsStr::new("disabled")) {
+        path.set_extension("");
+    }
+    path
+}
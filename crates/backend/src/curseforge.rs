@@ -0,0 +1,234 @@
+use std::sync::Arc;
+
+use bridge::{
+    install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget},
+    instance::InstanceContentSummary,
+};
+use reqwest::StatusCode;
+use rustc_hash::{FxHashMap, FxHashSet};
+use schema::{
+    content::ContentSource,
+    curseforge::{CurseforgeFile, CurseforgeFingerprintMatch, CurseforgeFingerprintsRequest, CurseforgeFingerprintsResult, CurseforgeModFilesRequest, CURSEFORGE_MINECRAFT_GAME_ID},
+    loader::Loader,
+};
+
+use crate::{
+    instance::ContentFolder,
+    metadata::{items::{CurseforgeApi, CurseforgeFileMetadataItem, CurseforgeModFilesMetadataItem}, manager::MetaLoadError},
+    BackendState,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum CurseforgeError {
+    #[error("Failed to request from CurseForge")]
+    Reqwest(#[from] reqwest::Error),
+    #[error("CurseForge returned non-200 status code: {0}")]
+    NotOK(StatusCode),
+    #[error("Unable to parse response:\n{0}")]
+    SerdeJson(#[from] serde_json::Error),
+}
+
+/// CurseForge identifies files by a murmur2 hash (seed 1) of their contents with all whitespace removed
+pub(crate) fn fingerprint(bytes: &[u8]) -> u32 {
+    const M: u32 = 0x5bd1e995;
+
+    let normalized: Vec<u8> = bytes.iter().copied().filter(|byte| !matches!(byte, 9 | 10 | 13 | 32)).collect();
+
+    let mut hash = 1 ^ normalized.len() as u32;
+
+    let mut chunks = normalized.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes(chunk.try_into().unwrap());
+        k = k.wrapping_mul(M);
+        k ^= k >> 24;
+        k = k.wrapping_mul(M);
+        hash = hash.wrapping_mul(M) ^ k;
+    }
+
+    let remainder = chunks.remainder();
+    if !remainder.is_empty() {
+        for (index, byte) in remainder.iter().enumerate() {
+            hash ^= (*byte as u32) << (8 * index);
+        }
+        hash = hash.wrapping_mul(M);
+    }
+
+    hash ^= hash >> 13;
+    hash = hash.wrapping_mul(M);
+    hash ^= hash >> 15;
+    hash
+}
+
+/// Picks the newest file, preferring files that were uploaded for the given loader
+pub(crate) fn newest_file(files: &[CurseforgeFile], loader: Loader) -> Option<&CurseforgeFile> {
+    let for_loader = loader.as_curseforge_loader().and_then(|loader| {
+        files.iter()
+            .filter(|file| file.game_versions.iter().any(|version| &**version == loader.name()))
+            .max_by(|a, b| a.file_date.cmp(&b.file_date))
+    });
+
+    for_loader.or_else(|| files.iter().max_by(|a, b| a.file_date.cmp(&b.file_date)))
+}
+
+impl BackendState {
+    pub(crate) fn curseforge_api(&self) -> CurseforgeApi {
+        CurseforgeApi::from_config(self.config.write().get())
+    }
+
+    /// Fetches the given file, or the newest file of the project for the version and loader if none is given
+    pub(crate) async fn fetch_curseforge_file(
+        &self,
+        api: &CurseforgeApi,
+        project_id: u32,
+        file_id: Option<u32>,
+        version: Option<Arc<str>>,
+        loader: Loader,
+    ) -> Result<Option<CurseforgeFile>, MetaLoadError> {
+        if let Some(file_id) = file_id {
+            let result = self.meta.fetch(&CurseforgeFileMetadataItem { api, mod_id: project_id, file_id }).await?;
+            return Ok(Some(result.data.clone()));
+        }
+
+        let files = self.meta.fetch(&CurseforgeModFilesMetadataItem {
+            api,
+            request: &CurseforgeModFilesRequest {
+                mod_id: project_id,
+                game_version: version,
+                mod_loader_type: None,
+            },
+        }).await?;
+
+        Ok(newest_file(&files.data, loader).cloned())
+    }
+
+    async fn fetch_curseforge_fingerprint_matches(&self, api: &CurseforgeApi, fingerprints: Vec<u32>) -> Result<Vec<CurseforgeFingerprintMatch>, CurseforgeError> {
+        let response = api.post(&self.http_client, &format!("/v1/fingerprints/{CURSEFORGE_MINECRAFT_GAME_ID}"))
+            .json(&CurseforgeFingerprintsRequest { fingerprints })
+            .send().await?;
+
+        if response.status() != StatusCode::OK {
+            return Err(CurseforgeError::NotOK(response.status()));
+        }
+
+        let bytes = response.bytes().await?;
+        let result: CurseforgeFingerprintsResult = serde_json::from_slice(&bytes)?;
+        Ok(result.data.exact_matches)
+    }
+
+    /// Looks up manually installed content on CurseForge, so that it can be updated from there
+    pub(crate) async fn identify_curseforge_content(&self, content: &[InstanceContentSummary]) {
+        let api = self.curseforge_api();
+        if !api.is_usable() {
+            return;
+        }
+
+        let unknown: Vec<_> = {
+            let sources = self.mod_metadata_manager.read_content_sources();
+            content.iter()
+                .filter(|summary| matches!(sources.get(&summary.content_summary.hash), None | Some(ContentSource::Manual)))
+                .map(|summary| (summary.path.clone(), summary.content_summary.hash))
+                .collect()
+        };
+
+        if unknown.is_empty() {
+            return;
+        }
+
+        let by_fingerprint = tokio::task::spawn_blocking(move || {
+            unknown.into_iter().filter_map(|(path, hash)| {
+                let bytes = std::fs::read(&path).ok()?;
+                Some((fingerprint(&bytes), hash))
+            }).collect::<FxHashMap<u32, [u8; 20]>>()
+        }).await.unwrap();
+
+        let matches = match self.fetch_curseforge_fingerprint_matches(&api, by_fingerprint.keys().copied().collect()).await {
+            Ok(matches) => matches,
+            Err(err) => {
+                log::warn!("Unable to identify content on CurseForge: {err}");
+                return;
+            },
+        };
+
+        let sources = matches.into_iter().filter_map(|fingerprint_match| {
+            let hash = by_fingerprint.get(&fingerprint_match.file.file_fingerprint)?;
+            Some((*hash, ContentSource::CurseforgeProject { project: fingerprint_match.file.mod_id }))
+        });
+        self.mod_metadata_manager.set_content_sources(sources);
+    }
+
+    /// Adds the required dependencies of CurseForge files, skipping projects that are already installed
+    pub(crate) async fn add_curseforge_dependencies(&self, content: &ContentInstall) -> Result<Arc<[ContentInstallFile]>, MetaLoadError> {
+        let wants_dependencies = content.files.iter()
+            .any(|file| matches!(file.download, ContentDownload::Curseforge { install_dependencies: true, .. }));
+        if !wants_dependencies {
+            return Ok(content.files.clone());
+        }
+
+        let mut seen = FxHashSet::default();
+        for file in content.files.iter() {
+            if let ContentDownload::Curseforge { project_id, .. } = file.download {
+                seen.insert(project_id);
+            }
+        }
+
+        if let InstallTarget::Instance(id) = content.target
+            && let Some(installed) = self.clone().load_instance_content(id, ContentFolder::Mods).await
+        {
+            let sources = self.mod_metadata_manager.read_content_sources();
+            for summary in installed.iter() {
+                if let Some(ContentSource::CurseforgeProject { project }) = sources.get(&summary.content_summary.hash) {
+                    seen.insert(project);
+                }
+            }
+        }
+
+        let api = self.curseforge_api();
+        let mut files = content.files.to_vec();
+
+        // Dependencies are appended, so they have their own dependencies added as well
+        let mut index = 0;
+        while index < files.len() {
+            if let ContentDownload::Curseforge { project_id, file_id, install_dependencies: true } = files[index].download {
+                let file = self.fetch_curseforge_file(&api, project_id, file_id, content.version_hint.clone(), content.loader_hint).await?;
+
+                for dependency in file.iter().flat_map(|file| file.dependencies.iter()) {
+                    if dependency.is_required() && seen.insert(dependency.mod_id) {
+                        files.push(ContentInstallFile {
+                            replace_old: None,
+                            path: ContentInstallPath::Automatic,
+                            download: ContentDownload::Curseforge {
+                                project_id: dependency.mod_id,
+                                file_id: None,
+                                install_dependencies: true,
+                            },
+                            content_source: ContentSource::CurseforgeProject { project: dependency.mod_id },
+                        });
+                    }
+                }
+            }
+            index += 1;
+        }
+
+        Ok(files.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(fingerprint(b""), 1540447798);
+        assert_eq!(fingerprint(b"a"), 626045324);
+        assert_eq!(fingerprint(b"abc"), 1621425345);
+        assert_eq!(fingerprint(b"abcd"), 3376380438);
+        assert_eq!(fingerprint(b"Hello,world!"), 2173663876);
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_whitespace() {
+        assert_eq!(fingerprint(b"Hello, world!"), fingerprint(b"Hello,world!"));
+        assert_eq!(fingerprint(b" a\tb\r\nc "), fingerprint(b"abc"));
+    }
+}
@@ -0,0 +1,174 @@
+use std::{path::Path, sync::Arc};
+
+use bridge::{
+    instance::{ContentType, InstanceDatapackSummary, InstanceID, WorldDatapacks},
+    message::MessageToFrontend,
+};
+use nbt::TAG_STRING_ID;
+use rustc_hash::FxHashSet;
+
+use crate::{
+    BackendState,
+    mod_metadata::ModMetadataManager,
+    resource_pack::read_client_pack_version,
+    world::{WorldError, read_level_dat, world_path, write_level_dat},
+};
+
+const DATAPACKS_FOLDER: &str = "datapacks";
+
+impl BackendState {
+    pub async fn load_world_datapacks(self, id: InstanceID, world: Arc<str>) {
+        let Some((saves_path, minecraft_version)) = self.instance_state.write().instances.get_mut(id).map(|instance| {
+            (instance.saves_path.clone(), instance.configuration.get().minecraft_version)
+        }) else {
+            return;
+        };
+
+        let client_jar = self.client_jar_path(minecraft_version);
+        let mod_metadata_manager = self.mod_metadata_manager.clone();
+
+        let world2 = world.clone();
+        let result = tokio::task::spawn_blocking(move || -> Result<WorldDatapacks, WorldError> {
+            let world_path = world_path(&saves_path, &world2)?;
+            let disabled = read_disabled_datapacks(&world_path)?;
+            Ok(WorldDatapacks {
+                pack_format: read_client_pack_version(&client_jar).and_then(|pack_version| pack_version.data()),
+                datapacks: list_datapacks(&world_path.join(DATAPACKS_FOLDER), &disabled, &mod_metadata_manager).into(),
+            })
+        }).await.unwrap();
+
+        match result {
+            Ok(datapacks) => self.send.send(MessageToFrontend::InstanceWorldDatapacksUpdated { id, world, datapacks }),
+            Err(err) => self.send.send_error(format!("Unable to load datapacks: {err}")),
+        }
+    }
+
+    pub async fn set_datapack_enabled(self, id: InstanceID, world: Arc<str>, datapack: Arc<str>, enabled: bool) {
+        let result = match self.world_paths(id) {
+            Ok(paths) => {
+                let world = world.clone();
+                tokio::task::spawn_blocking(move || {
+                    if !crate::is_single_component_path(&datapack) {
+                        return Err(WorldError::InvalidDatapack(datapack));
+                    }
+                    let world_path = world_path(&paths.saves_path, &world)?;
+                    set_datapack_enabled(&world_path, &datapack, enabled)
+                }).await.unwrap()
+            },
+            Err(err) => Err(err),
+        };
+
+        if let Err(err) = result {
+            self.send.send_error(format!("Unable to update datapack: {err}"));
+        }
+
+        self.load_world_datapacks(id, world).await;
+    }
+}
+
+fn read_disabled_datapacks(world_path: &Path) -> Result<FxHashSet<String>, WorldError> {
+    let nbt = read_level_dat(world_path)?;
+
+    let mut disabled = FxHashSet::default();
+    if let Some(root) = nbt.as_compound()
+        && let Some(data) = root.find_compound("Data")
+        && let Some(data_packs) = data.find_compound("DataPacks")
+        && let Some(list) = data_packs.find_list("Disabled", TAG_STRING_ID)
+    {
+        for index in 0..list.len() {
+            if let Some(name) = list.get_string(index) {
+                disabled.insert(name.clone());
+            }
+        }
+    }
+    Ok(disabled)
+}
+
+fn set_datapack_enabled(world_path: &Path, datapack: &str, enabled: bool) -> Result<(), WorldError> {
+    let mut nbt = read_level_dat(world_path)?;
+
+    let Some(mut root) = nbt.as_compound_mut() else {
+        return Err(WorldError::InvalidLevelDat(anyhow::anyhow!("Unable to get root compound")));
+    };
+    let Some(mut data) = root.find_compound_mut("Data") else {
+        return Err(WorldError::InvalidLevelDat(anyhow::anyhow!("Unable to get Data")));
+    };
+    if data.find_compound("DataPacks").is_none() {
+        data.create_compound("DataPacks");
+    }
+    let Some(mut data_packs) = data.find_compound_mut("DataPacks") else {
+        return Err(WorldError::InvalidLevelDat(anyhow::anyhow!("Unable to get DataPacks")));
+    };
+
+    let name = format!("file/{datapack}");
+    let read_list = |key: &str| -> Vec<String> {
+        let Some(list) = data_packs.find_list(key, TAG_STRING_ID) else {
+            return Vec::new();
+        };
+        (0..list.len())
+            .filter_map(|index| list.get_string(index))
+            .filter(|entry| **entry != name)
+            .cloned()
+            .collect()
+    };
+    let mut enabled_list = read_list("Enabled");
+    let mut disabled_list = read_list("Disabled");
+
+    if enabled {
+        enabled_list.push(name);
+    } else {
+        disabled_list.push(name);
+    }
+
+    for (key, entries) in [("Enabled", enabled_list), ("Disabled", disabled_list)] {
+        let mut list = data_packs.create_list(key, TAG_STRING_ID);
+        for entry in entries {
+            list.insert_string(entry);
+        }
+    }
+
+    write_level_dat(world_path, &nbt)
+}
+
+fn list_datapacks(datapacks_path: &Path, disabled: &FxHashSet<String>, mod_metadata_manager: &Arc<ModMetadataManager>) -> Vec<InstanceDatapackSummary> {
+    let Ok(read_dir) = std::fs::read_dir(datapacks_path) else {
+        return Vec::new();
+    };
+
+    let mut datapacks = Vec::new();
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        let Some(filename) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if filename.starts_with('.') {
+            continue;
+        }
+
+        let summary = if path.is_dir() {
+            mod_metadata_manager.get_pack_directory(&path)
+        } else if filename.ends_with(".zip") {
+            mod_metadata_manager.get_path(&path)
+        } else {
+            continue;
+        };
+        let Some(summary) = summary else {
+            continue;
+        };
+        let ContentType::ResourcePack { pack_format } = summary.extra else {
+            continue;
+        };
+
+        datapacks.push(InstanceDatapackSummary {
+            enabled: !disabled.contains(&format!("file/{filename}")),
+            filename: filename.into(),
+            path: path.as_path().into(),
+            description: summary.version_str.clone(),
+            png_icon: summary.png_icon.clone(),
+            pack_format,
+        });
+    }
+
+    datapacks.sort_by(|a, b| lexical_sort::natural_lexical_cmp(&a.filename, &b.filename));
+    datapacks
+}
@@ -19,6 +19,8 @@ pub struct LauncherDirectories {
     pub content_library_dir: Arc<Path>,
     pub content_meta_dir: Arc<Path>,
 
+    pub screenshot_thumbnails_dir: Arc<Path>,
+
     pub temp_dir: Arc<Path>,
     pub temp_natives_base_dir: Arc<Path>,
 
@@ -49,6 +51,8 @@ impl LauncherDirectories {
         let content_library_dir = launcher_dir.join("contentlibrary");
         let content_meta_dir = launcher_dir.join("contentmeta");
 
+        let screenshot_thumbnails_dir = launcher_dir.join("thumbnails").join("screenshots");
+
         let temp_dir = launcher_dir.join("temp");
         let temp_natives_base_dir = temp_dir.join("natives");
 
@@ -74,6 +78,8 @@ impl LauncherDirectories {
             content_library_dir: content_library_dir.into(),
             content_meta_dir: content_meta_dir.into(),
 
+            screenshot_thumbnails_dir: screenshot_thumbnails_dir.into(),
+
             temp_dir: temp_dir.into(),
             temp_natives_base_dir: temp_natives_base_dir.into(),
 
@@ -0,0 +1,217 @@
+use std::sync::Arc;
+
+use reqwest::StatusCode;
+use schema::{content::ContentSource, github::{GithubRelease, GithubReleaseAsset}, loader::Loader};
+
+use crate::BackendState;
+
+#[derive(thiserror::Error, Debug)]
+pub enum GithubReleaseError {
+    #[error("Failed to request release from GitHub")]
+    Reqwest(#[from] reqwest::Error),
+    #[error("GitHub returned non-200 status code: {0}")]
+    NotOK(StatusCode),
+    #[error("Unable to parse release:\n{0}")]
+    SerdeJson(#[from] serde_json::Error),
+    #[error("Release {0} doesn't have a downloadable asset")]
+    NoMatchingAsset(Arc<str>),
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum GithubUrl {
+    /// Direct download of a release asset
+    Asset {
+        owner: Arc<str>,
+        repo: Arc<str>,
+        tag: Arc<str>,
+        url: Arc<str>,
+    },
+    /// Repository or release page, None means the latest release
+    Release {
+        owner: Arc<str>,
+        repo: Arc<str>,
+        tag: Option<Arc<str>>,
+    },
+}
+
+pub(crate) fn parse_github_url(url: &str) -> Option<GithubUrl> {
+    let without_scheme = url.strip_prefix("https://").or_else(|| url.strip_prefix("http://"))?;
+    let path = without_scheme.strip_prefix("github.com/").or_else(|| without_scheme.strip_prefix("www.github.com/"))?;
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    let mut segments = path.split('/').filter(|segment| !segment.is_empty());
+    let owner: Arc<str> = segments.next()?.into();
+    let repo = segments.next()?;
+    let repo: Arc<str> = repo.strip_suffix(".git").unwrap_or(repo).into();
+
+    match (segments.next(), segments.next(), segments.next(), segments.next(), segments.next()) {
+        (None, ..) | (Some("releases"), None, ..) | (Some("releases"), Some("latest"), None, ..) => {
+            Some(GithubUrl::Release { owner, repo, tag: None })
+        },
+        (Some("releases"), Some("tag"), Some(tag), None, ..) => {
+            Some(GithubUrl::Release { owner, repo, tag: Some(tag.into()) })
+        },
+        (Some("releases"), Some("download"), Some(tag), Some(_), None) => {
+            Some(GithubUrl::Asset { owner, repo, tag: tag.into(), url: url.into() })
+        },
+        _ => None,
+    }
+}
+
+/// Replaces the version in the asset name with `*` so that the same asset can be found in later releases
+pub(crate) fn asset_pattern(asset: &str, tag: &str) -> Arc<str> {
+    let version = tag.strip_prefix('v').unwrap_or(tag);
+    if !version.is_empty() && asset.contains(version) {
+        asset.replacen(version, "*", 1).into()
+    } else {
+        asset.into()
+    }
+}
+
+pub(crate) fn matches_asset_pattern(pattern: &str, asset: &str) -> bool {
+    if let Some((prefix, suffix)) = pattern.split_once('*') {
+        asset.len() >= prefix.len() + suffix.len() && asset.starts_with(prefix) && asset.ends_with(suffix)
+    } else {
+        asset == pattern
+    }
+}
+
+fn pick_asset(release: &GithubRelease, loader: Loader) -> Option<&GithubReleaseAsset> {
+    let candidates: Vec<&GithubReleaseAsset> = release.assets.iter().filter(|asset| {
+        let name = asset.name.to_ascii_lowercase();
+        let installable = name.ends_with(".jar") || name.ends_with(".zip") || name.ends_with(".mrpack");
+        installable && !name.contains("-sources") && !name.contains("-javadoc")
+    }).collect();
+
+    // Releases often contain a jar per loader, "forge" is also part of "neoforge"
+    let loader_name = loader.name().to_ascii_lowercase();
+    let for_loader = candidates.iter().find(|asset| {
+        let name = asset.name.to_ascii_lowercase();
+        name.contains(&loader_name) && !(loader == Loader::Forge && name.contains("neoforge"))
+    });
+
+    for_loader.or(candidates.first()).copied()
+}
+
+impl BackendState {
+    pub(crate) async fn fetch_github_release(&self, owner: &str, repo: &str, tag: Option<&str>) -> Result<GithubRelease, GithubReleaseError> {
+        let base = self.config.write().get().github_api_url().to_string();
+        let url = if let Some(tag) = tag {
+            format!("{base}/repos/{owner}/{repo}/releases/tags/{tag}")
+        } else {
+            format!("{base}/repos/{owner}/{repo}/releases/latest")
+        };
+
+        let response = self.redirecting_http_client.get(url)
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .send().await?;
+
+        if response.status() != StatusCode::OK {
+            return Err(GithubReleaseError::NotOK(response.status()));
+        }
+
+        let bytes = response.bytes().await?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Finds the asset that a GitHub url refers to, along with the source that later updates are checked against
+    pub(crate) async fn resolve_github_url(&self, github_url: &GithubUrl, loader: Loader) -> Result<(GithubReleaseAsset, ContentSource), GithubReleaseError> {
+        let (owner, repo, release, asset) = match github_url {
+            GithubUrl::Asset { owner, repo, tag, url } => {
+                let release = self.fetch_github_release(owner, repo, Some(&**tag)).await?;
+                let filename = url.rsplit('/').next().unwrap_or_default();
+                let asset = release.assets.iter()
+                    .find(|asset| &asset.browser_download_url == url)
+                    .or_else(|| release.assets.iter().find(|asset| &*asset.name == filename))
+                    .cloned();
+                (owner, repo, release, asset)
+            },
+            GithubUrl::Release { owner, repo, tag } => {
+                let release = self.fetch_github_release(owner, repo, tag.as_deref()).await?;
+                let asset = pick_asset(&release, loader).cloned();
+                (owner, repo, release, asset)
+            },
+        };
+
+        let Some(asset) = asset else {
+            return Err(GithubReleaseError::NoMatchingAsset(release.tag_name.clone()));
+        };
+
+        let source = ContentSource::GithubRelease {
+            owner: owner.clone(),
+            repo: repo.clone(),
+            asset: asset_pattern(&asset.name, &release.tag_name),
+            tag: release.tag_name,
+        };
+        Ok((asset, source))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release(tag: Option<&str>) -> Option<GithubUrl> {
+        Some(GithubUrl::Release { owner: "owner".into(), repo: "repo".into(), tag: tag.map(Arc::from) })
+    }
+
+    #[test]
+    fn test_parse_release_urls() {
+        assert_eq!(parse_github_url("https://github.com/owner/repo"), release(None));
+        assert_eq!(parse_github_url("https://github.com/owner/repo/"), release(None));
+        assert_eq!(parse_github_url("http://www.github.com/owner/repo"), release(None));
+        assert_eq!(parse_github_url("https://github.com/owner/repo/releases"), release(None));
+        assert_eq!(parse_github_url("https://github.com/owner/repo/releases/latest"), release(None));
+        assert_eq!(parse_github_url("https://github.com/owner/repo/releases/tag/v1.2.0"), release(Some("v1.2.0")));
+    }
+
+    #[test]
+    fn test_parse_git_suffix_and_query() {
+        assert_eq!(parse_github_url("https://github.com/owner/repo.git"), release(None));
+        assert_eq!(parse_github_url("https://github.com/owner/repo?tab=readme"), release(None));
+        assert_eq!(parse_github_url("https://github.com/owner/repo/releases/tag/v1.2.0?expanded=true#assets"), release(Some("v1.2.0")));
+    }
+
+    #[test]
+    fn test_parse_download_url() {
+        let url = "https://github.com/owner/repo/releases/download/v1.2.0/mod-1.2.0.jar";
+        assert_eq!(parse_github_url(url), Some(GithubUrl::Asset {
+            owner: "owner".into(),
+            repo: "repo".into(),
+            tag: "v1.2.0".into(),
+            url: url.into(),
+        }));
+    }
+
+    #[test]
+    fn test_parse_invalid_urls() {
+        assert_eq!(parse_github_url("https://gitlab.com/owner/repo"), None);
+        assert_eq!(parse_github_url("github.com/owner/repo"), None);
+        assert_eq!(parse_github_url("https://github.com/owner"), None);
+        assert_eq!(parse_github_url("https://github.com/owner/repo/issues/1"), None);
+        assert_eq!(parse_github_url("https://github.com/owner/repo/releases/download/v1.2.0"), None);
+    }
+
+    #[test]
+    fn test_asset_pattern() {
+        assert_eq!(&*asset_pattern("mod-1.2.0.jar", "v1.2.0"), "mod-*.jar");
+        assert_eq!(&*asset_pattern("mod-1.2.0.jar", "1.2.0"), "mod-*.jar");
+        assert_eq!(&*asset_pattern("mod-fabric-1.2.0+1.21.jar", "v1.2.0"), "mod-fabric-*+1.21.jar");
+        assert_eq!(&*asset_pattern("mod.jar", "v1.2.0"), "mod.jar");
+        assert_eq!(&*asset_pattern("mod.jar", "v"), "mod.jar");
+    }
+
+    #[test]
+    fn test_matches_asset_pattern() {
+        assert!(matches_asset_pattern("mod-*.jar", "mod-1.3.0.jar"));
+        assert!(matches_asset_pattern("mod-*.jar", "mod-.jar"));
+        assert!(!matches_asset_pattern("mod-*.jar", "mod-1.3.0-sources.zip"));
+        assert!(!matches_asset_pattern("mod-*-mod.jar", "mod-mod.jar"));
+        assert!(matches_asset_pattern("mod.jar", "mod.jar"));
+        assert!(!matches_asset_pattern("mod.jar", "mod-1.3.0.jar"));
+
+        let pattern = asset_pattern("mod-fabric-1.2.0.jar", "v1.2.0");
+        assert!(matches_asset_pattern(&pattern, "mod-fabric-1.3.0.jar"));
+        assert!(!matches_asset_pattern(&pattern, "mod-forge-1.3.0.jar"));
+    }
+}
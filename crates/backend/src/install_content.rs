@@ -3,12 +3,13 @@ use std::{ffi::{OsStr, OsString}, io::Write, path::{Path, PathBuf}, sync::Arc};
 use bridge::{
     install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath}, instance::{ContentType, ContentSummary}, modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType}, safe_path::SafePath
 };
+use rand::RngCore;
 use reqwest::StatusCode;
 use schema::{content::ContentSource, loader::Loader, modrinth::{ModrinthLoader, ModrinthProjectVersionsRequest}};
 use sha1::{Digest, Sha1};
 use tokio::io::AsyncWriteExt;
 
-use crate::{lockfile::Lockfile, metadata::{items::{MinecraftVersionManifestMetadataItem, ModrinthProjectVersionsMetadataItem, ModrinthVersionMetadataItem}, manager::MetaLoadError}, BackendState};
+use crate::{github_release::GithubReleaseError, lockfile::Lockfile, metadata::{items::{MinecraftVersionManifestMetadataItem, ModrinthProjectVersionsMetadataItem, ModrinthVersionMetadataItem}, manager::MetaLoadError}, BackendState};
 
 #[derive(thiserror::Error, Debug)]
 pub enum ContentInstallError {
@@ -26,6 +27,8 @@ pub enum ContentInstallError {
     WrongFilesize,
     #[error("Downloaded file had the wrong hash")]
     WrongHash,
+    #[error("Downloaded file is larger than {} MiB", MAX_UNVERIFIED_DOWNLOAD_SIZE / 1024 / 1024)]
+    FileTooLarge,
     #[error("Hash isn't a valid sha1 hash:\n{0}")]
     InvalidHash(Arc<str>),
     #[error("Failed to perform I/O operation:\n{0}")]
@@ -34,8 +37,17 @@ pub enum ContentInstallError {
     MetaLoadError(#[from] MetaLoadError),
     #[error("Mismatched project id for version {0}, expected {1} got {2}")]
     MismatchedProjectIdForVersion(Arc<str>, Arc<str>, Arc<str>),
+    #[error("Failed to find GitHub release:\n{0}")]
+    GithubRelease(#[from] GithubReleaseError),
+    #[error("{0} can't be downloaded, the author has disabled downloads from other launchers")]
+    CurseforgeDownloadDisabled(Arc<str>),
+    #[error("{0} was removed from the content library while installing")]
+    RemovedFromLibrary(Arc<str>),
 }
 
+// Files installed from arbitrary urls have no known size, so they're limited to avoid filling up the disk
+const MAX_UNVERIFIED_DOWNLOAD_SIZE: usize = 1024 * 1024 * 1024;
+
 struct InstallFromContentLibrary {
     from: PathBuf,
     replace: Option<Arc<Path>>,
@@ -71,11 +83,19 @@ impl From<&Path> for FilenameAndExtension {
 
 impl BackendState {
     pub async fn install_content(&self, content: ContentInstall, modal_action: ModalAction) {
+        if let Err(error) = self.try_install_content(content, &modal_action).await {
+            modal_action.set_error_message(Arc::from(format!("{}", error).as_str()));
+        }
+    }
+
+    pub(crate) async fn try_install_content(&self, content: ContentInstall, modal_action: &ModalAction) -> Result<(), ContentInstallError> {
         let semaphore = tokio::sync::Semaphore::new(8);
 
+        let files = self.add_curseforge_dependencies(&content).await?;
+
         let mut tasks = Vec::new();
 
-        for content_file in content.files.iter() {
+        for content_file in files.iter() {
             tasks.push(async {
                 match content_file.download {
                     bridge::install::ContentDownload::Modrinth { ref project_id, ref version_id } => {
@@ -128,7 +148,7 @@ impl BackendState {
                                 return Err(ContentInstallError::InvalidFilename(install_file.filename.clone()));
                             };
 
-                            let (path, hash, mod_summary) = self.download_file_into_library(&modal_action,
+                            let (path, hash, mod_summary) = self.download_file_into_library(modal_action,
                                 (&safe_filename).into(), url, sha1, size, &semaphore).await?;
 
                             let install_path = match &content_file.path {
@@ -136,14 +156,7 @@ impl BackendState {
                                 ContentInstallPath::Safe(safe_path) => safe_path.to_path(Path::new("")).into(),
                                 ContentInstallPath::Automatic => {
                                     let base = if let Some(mod_summary) = &mod_summary {
-                                        match mod_summary.extra {
-                                            ContentType::Fabric | ContentType::Forge | ContentType::NeoForge | ContentType::JavaModule | ContentType::ModrinthModpack { .. } => {
-                                                Path::new("mods")
-                                            },
-                                            ContentType::ResourcePack => {
-                                                Path::new("resourcepacks")
-                                            }
-                                        }
+                                        Path::new(install_directory(&mod_summary.extra))
                                     } else if let Some(loaders) = &version.loaders {
                                         let mut base = None;
                                         for loader in loaders.iter() {
@@ -184,7 +197,7 @@ impl BackendState {
                             ContentInstallPath::Automatic => unimplemented!(),
                         };
 
-                        let (path, hash, mod_summary) = self.download_file_into_library(&modal_action,
+                        let (path, hash, mod_summary) = self.download_file_into_library(modal_action,
                             name, url, sha1, size, &semaphore).await?;
 
                         let install_path = match &content_file.path {
@@ -202,6 +215,94 @@ impl BackendState {
                             mod_summary
                         });
                     },
+                    bridge::install::ContentDownload::Curseforge { project_id, file_id, .. } => {
+                        let api = self.curseforge_api();
+                        let Some(file) = self.fetch_curseforge_file(&api, project_id, file_id,
+                            content.version_hint.clone(), content.loader_hint).await?
+                        else {
+                            return Err(ContentInstallError::UnableToFindDependencyVersion);
+                        };
+
+                        let Some(url) = &file.download_url else {
+                            return Err(ContentInstallError::CurseforgeDownloadDisabled(file.display_name.clone()));
+                        };
+                        let Some(safe_filename) = SafePath::new(&file.file_name) else {
+                            return Err(ContentInstallError::InvalidFilename(file.file_name.clone()));
+                        };
+
+                        let (path, hash, mod_summary) = if let Some(sha1) = file.sha1() {
+                            self.download_file_into_library(modal_action,
+                                (&safe_filename).into(), url, sha1, file.file_length, &semaphore).await?
+                        } else {
+                            let (path, hash, mod_summary, _) = self.download_unverified_file_into_library(modal_action,
+                                url, Some(file.file_name.clone()), &semaphore).await?;
+                            (path, hash, mod_summary)
+                        };
+
+                        let install_path = match &content_file.path {
+                            ContentInstallPath::Raw(path) => path.clone(),
+                            ContentInstallPath::Safe(safe_path) => safe_path.to_path(Path::new("")).into(),
+                            ContentInstallPath::Automatic => {
+                                let base = if let Some(mod_summary) = &mod_summary {
+                                    install_directory(&mod_summary.extra)
+                                } else if file.game_versions.iter().any(|version| {
+                                    matches!(&**version, "Fabric" | "Forge" | "NeoForge" | "Quilt")
+                                }) {
+                                    "mods"
+                                } else {
+                                    return Err(ContentInstallError::UnableToDetermineContentType(file.file_name.clone()));
+                                };
+                                safe_filename.to_path(Path::new(base)).into()
+                            },
+                        };
+
+                        Ok(InstallFromContentLibrary {
+                            from: path,
+                            replace: content_file.replace_old.clone(),
+                            hash,
+                            install_path,
+                            content_file: content_file.clone(),
+                            mod_summary
+                        })
+                    },
+                    bridge::install::ContentDownload::UnverifiedUrl { ref url } => {
+                        let mut content_file = content_file.clone();
+
+                        // GitHub release pages are resolved to an asset, which is also remembered for updates
+                        let (url, filename) = if let Some(github_url) = crate::github_release::parse_github_url(url) {
+                            let (asset, source) = self.resolve_github_url(&github_url, content.loader_hint).await?;
+                            content_file.content_source = source;
+                            (asset.browser_download_url, Some(asset.name))
+                        } else {
+                            (url.clone(), None)
+                        };
+
+                        let (path, hash, mod_summary, filename) = self.download_unverified_file_into_library(modal_action,
+                            &url, filename, &semaphore).await?;
+
+                        let install_path = match &content_file.path {
+                            ContentInstallPath::Raw(path) => path.clone(),
+                            ContentInstallPath::Safe(safe_path) => safe_path.to_path(Path::new("")).into(),
+                            ContentInstallPath::Automatic => {
+                                let Some(mod_summary) = &mod_summary else {
+                                    return Err(ContentInstallError::UnableToDetermineContentType(filename));
+                                };
+                                let Some(safe_filename) = SafePath::new(&filename) else {
+                                    return Err(ContentInstallError::InvalidFilename(filename));
+                                };
+                                safe_filename.to_path(Path::new(install_directory(&mod_summary.extra))).into()
+                            },
+                        };
+
+                        return Ok(InstallFromContentLibrary {
+                            from: path,
+                            replace: content_file.replace_old.clone(),
+                            hash,
+                            install_path,
+                            content_file,
+                            mod_summary
+                        });
+                    },
                     bridge::install::ContentDownload::File { path: ref copy_path } => {
                         let title = format!("Copying {}", copy_path.file_name().unwrap().to_string_lossy());
                         let tracker = ProgressTracker::new(title.into(), self.send.clone());
@@ -275,77 +376,108 @@ impl BackendState {
             });
         }
 
-        let result: Result<Vec<InstallFromContentLibrary>, ContentInstallError> = futures::future::try_join_all(tasks).await;
-        match result {
-            Ok(files) => {
-                let mut instance_dir = None;
-
-                match content.target {
-                    bridge::install::InstallTarget::Instance(instance_id) => {
-                        if let Some(instance) = self.instance_state.write().instances.get_mut(instance_id) {
-                            if instance.configuration.get().loader == Loader::Vanilla && content.loader_hint != Loader::Unknown {
-                                instance.configuration.modify(|config| {
-                                    config.loader = content.loader_hint;
-                                });
-                            }
+        let files: Vec<InstallFromContentLibrary> = futures::future::try_join_all(tasks).await?;
 
-                            instance_dir = Some(instance.dot_minecraft_path.clone());
-                        }
-                    },
-                    bridge::install::InstallTarget::Library => {},
-                    bridge::install::InstallTarget::NewInstance { name } => {
-                        let mut minecraft_version = content.version_hint;
-                        if minecraft_version.is_none() {
-                            if let Ok(meta) = self.meta.fetch(&MinecraftVersionManifestMetadataItem).await {
-                                minecraft_version = Some(meta.latest.release.into());
-                            }
-                        }
+        let mut instance_dir = None;
+        let mut target_instance = None;
 
-                        if let Some(minecraft_version) = minecraft_version {
-                            instance_dir = self.create_instance_sanitized(&name, &minecraft_version, content.loader_hint).await
-                                .map(|v| v.join(".minecraft").into());
-                        }
-                    },
+        match content.target {
+            bridge::install::InstallTarget::Instance(instance_id) => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(instance_id) {
+                    if instance.configuration.get().loader == Loader::Vanilla && content.loader_hint != Loader::Unknown {
+                        instance.configuration.modify(|config| {
+                            config.loader = content.loader_hint;
+                        });
+                    }
+
+                    instance_dir = Some(instance.dot_minecraft_path.clone());
+                    target_instance = Some(instance_id);
+                }
+            },
+            bridge::install::InstallTarget::Library => {},
+            bridge::install::InstallTarget::NewInstance { name } => {
+                let mut minecraft_version = content.version_hint;
+                if minecraft_version.is_none() {
+                    if let Ok(meta) = self.meta.fetch(&MinecraftVersionManifestMetadataItem).await {
+                        minecraft_version = Some(meta.latest.release.into());
+                    }
                 }
 
-                let sources = files.iter()
-                    .filter_map(|install| {
-                        if install.content_file.content_source != ContentSource::Manual {
-                            Some((install.hash.clone(), install.content_file.content_source.clone()))
-                        } else {
-                            None
+                if let Some(minecraft_version) = minecraft_version {
+                    let instance_root = self.create_instance_sanitized(&name, &minecraft_version, content.loader_hint).await;
+
+                    if let Some(instance_root) = &instance_root {
+                        let modpack_icon = files.iter()
+                            .filter_map(|install| install.mod_summary.as_ref())
+                            .find(|summary| matches!(summary.extra, ContentType::ModrinthModpack { .. }))
+                            .and_then(|summary| summary.png_icon.as_ref());
+                        if let Some(modpack_icon) = modpack_icon
+                            && let Some(png_bytes) = crate::instance::normalize_instance_icon(modpack_icon)
+                        {
+                            _ = crate::write_safe(&instance_root.join("icon.png"), &png_bytes);
                         }
-                    });
-                self.mod_metadata_manager.set_content_sources(sources);
+                    }
+
+                    instance_dir = instance_root.map(|v| v.join(".minecraft").into());
+                }
+            },
+        }
 
-                if let Some(instance_dir) = instance_dir {
-                    for install in files {
-                        let target_path = instance_dir.join(&install.install_path);
+        let sources = files.iter()
+            .filter_map(|install| {
+                if install.content_file.content_source != ContentSource::Manual {
+                    Some((install.hash.clone(), install.content_file.content_source.clone()))
+                } else {
+                    None
+                }
+            });
+        self.mod_metadata_manager.set_content_sources(sources);
 
-                        let _ = std::fs::create_dir_all(target_path.parent().unwrap());
+        let mut datapack_worlds: Vec<Arc<str>> = Vec::new();
+        if let Some(instance_dir) = instance_dir {
+            for install in files {
+                let target_path = instance_dir.join(&install.install_path);
 
-                        if let Some(replace) = install.replace {
-                            self.replace_aux_path(&replace, &install.mod_summary, &target_path);
-                            let _ = std::fs::remove_file(replace);
-                        }
-                        let _ = std::fs::hard_link(install.from, target_path);
-                    }
+                if let Some(world) = datapack_world(&install.install_path) && !datapack_worlds.contains(&world) {
+                    datapack_worlds.push(world);
                 }
-            },
-            Err(error) => {
-                modal_action.set_error_message(Arc::from(format!("{}", error).as_str()));
-            },
+
+                let _ = std::fs::create_dir_all(target_path.parent().unwrap());
+
+                // Pruning the content library holds the same lock, so the file can't be trashed while it's linked
+                let _lockfile = Lockfile::create(install.from.with_added_extension("lock").into()).await;
+                if !install.from.exists() {
+                    return Err(ContentInstallError::RemovedFromLibrary(install.install_path.to_string_lossy().into()));
+                }
+
+                if let Some(replace) = install.replace {
+                    self.replace_aux_path(&replace, &install.mod_summary, &target_path);
+                    let _ = std::fs::remove_file(replace);
+                }
+                let _ = std::fs::hard_link(install.from, target_path);
+            }
         }
+
+        // The datapacks folder isn't watched, so reload any worlds that received datapacks
+        if let Some(instance_id) = target_instance {
+            for world in datapack_worlds {
+                self.clone().load_world_datapacks(instance_id, world).await;
+            }
+        }
+
+        Ok(())
     }
 
-    fn replace_aux_path(&self, replace: &Path, new_summary: &Option<Arc<ContentSummary>>, new_path: &Path) {
+    pub(crate) fn replace_aux_path(&self, replace: &Path, new_summary: &Option<Arc<ContentSummary>>, new_path: &Path) {
         let Some(old_summary) = self.mod_metadata_manager.get_path(&replace) else {
             return;
         };
+        let old_summary = crate::mod_metadata::name_shader_pack(old_summary, replace);
 
         let Some(new_summary) = new_summary else {
             return;
         };
+        let new_summary = crate::mod_metadata::name_shader_pack(new_summary.clone(), new_path);
 
         let Some(old_aux_path) = crate::pandora_aux_path(&old_summary.id, &old_summary.name, &replace) else {
             return;
@@ -393,6 +525,87 @@ impl BackendState {
         Ok(result)
     }
 
+    async fn download_unverified_file_into_library(&self, modal_action: &ModalAction, url: &Arc<str>, filename: Option<Arc<str>>, semaphore: &tokio::sync::Semaphore) -> Result<(PathBuf, [u8; 20], Option<Arc<ContentSummary>>, Arc<str>), ContentInstallError> {
+        let _permit = semaphore.acquire().await.unwrap();
+
+        let response = self.redirecting_http_client.get(&**url).send().await?;
+
+        if response.status() != StatusCode::OK {
+            return Err(ContentInstallError::NotOK(response.status()));
+        }
+
+        // Use the name after redirects, the original url may just be a download link
+        let filename = filename.unwrap_or_else(|| {
+            let last_segment = response.url().path_segments().and_then(|mut segments| segments.next_back()).unwrap_or_default();
+            percent_decode(last_segment).into()
+        });
+
+        let title = format!("Downloading {}", filename);
+        let tracker = ProgressTracker::new(title.into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+
+        let content_length = response.content_length().unwrap_or(0) as usize;
+        if content_length > MAX_UNVERIFIED_DOWNLOAD_SIZE {
+            tracker.set_finished(ProgressTrackerFinishType::Error);
+            tracker.notify();
+            return Err(ContentInstallError::FileTooLarge);
+        }
+
+        tracker.set_total(content_length);
+        tracker.notify();
+
+        // The hash is only known once the download finishes, so the file is moved into the library afterwards
+        let _ = tokio::fs::create_dir_all(&self.directories.temp_dir).await;
+        let temp_path = self.directories.temp_dir.join(format!("download_{}", rand::thread_rng().next_u64()));
+
+        let result = download_unverified_file_into_temp(response, &temp_path, &tracker).await;
+        let hash = match result {
+            Ok(hash) => hash,
+            Err(err) => {
+                let _ = tokio::fs::remove_file(&temp_path).await;
+                tracker.set_finished(ProgressTrackerFinishType::Error);
+                tracker.notify();
+                return Err(err);
+            },
+        };
+
+        let extension = Path::new(&*filename).extension().and_then(OsStr::to_str);
+        let path = crate::create_content_library_path(&self.directories.content_library_dir, hash, extension);
+        if let Some(parent) = path.parent() {
+            let _ = tokio::fs::create_dir_all(parent).await;
+        }
+
+        // Same lock as verified downloads, so other installs never link a partially written file
+        let lockfile = Lockfile::create(path.with_added_extension("lock").into()).await;
+
+        let result = {
+            let path = path.clone();
+            let temp_path = temp_path.clone();
+            tokio::task::spawn_blocking(move || {
+                if crate::check_sha1_hash(&path, hash).unwrap_or(false) {
+                    std::fs::remove_file(&temp_path)
+                } else {
+                    std::fs::rename(&temp_path, &path)
+                }
+            }).await.unwrap()
+        };
+
+        drop(lockfile);
+
+        if let Err(err) = result {
+            let _ = tokio::fs::remove_file(&temp_path).await;
+            tracker.set_finished(ProgressTrackerFinishType::Error);
+            tracker.notify();
+            return Err(err.into());
+        }
+
+        tracker.set_finished(ProgressTrackerFinishType::Fast);
+        tracker.notify();
+
+        let mod_summary = self.mod_metadata_manager.get_path(&path);
+        Ok((path, hash, mod_summary, filename))
+    }
+
     async fn download_file_into_library_inner(&self, modal_action: &ModalAction, name: FilenameAndExtension, url: &Arc<str>, sha1: &Arc<str>, size: usize, semaphore: &tokio::sync::Semaphore) -> Result<(PathBuf, [u8; 20], Option<Arc<ContentSummary>>), ContentInstallError> {
         let mut expected_hash = [0u8; 20];
         let Ok(_) = hex::decode_to_slice(&**sha1, &mut expected_hash) else {
@@ -495,3 +708,72 @@ impl BackendState {
         Ok((path, expected_hash, summary))
     }
 }
+
+async fn download_unverified_file_into_temp(response: reqwest::Response, temp_path: &Path, tracker: &ProgressTracker) -> Result<[u8; 20], ContentInstallError> {
+    let mut file = tokio::fs::File::create(temp_path).await?;
+
+    use futures::StreamExt;
+    let mut stream = response.bytes_stream();
+
+    let mut total_bytes = 0;
+
+    let mut hasher = Sha1::new();
+    while let Some(item) = stream.next().await {
+        let item = item?;
+
+        total_bytes += item.len();
+        if total_bytes > MAX_UNVERIFIED_DOWNLOAD_SIZE {
+            return Err(ContentInstallError::FileTooLarge);
+        }
+
+        tracker.add_count(item.len());
+        tracker.notify();
+
+        hasher.write_all(&item)?;
+        file.write_all(&item).await?;
+    }
+
+    file.flush().await?;
+
+    Ok(hasher.finalize().into())
+}
+
+fn install_directory(content_type: &ContentType) -> &'static str {
+    match content_type {
+        ContentType::Fabric | ContentType::Forge | ContentType::NeoForge | ContentType::JavaModule | ContentType::ModrinthModpack { .. } => "mods",
+        ContentType::ResourcePack { .. } => "resourcepacks",
+        ContentType::ShaderPack { .. } => "shaderpacks",
+    }
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        if bytes[index] == b'%'
+            && let Some(hex) = bytes.get(index + 1..index + 3)
+            && let Ok(hex) = str::from_utf8(hex)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn datapack_world(install_path: &Path) -> Option<Arc<str>> {
+    let mut components = install_path.components();
+    if components.next()?.as_os_str() != "saves" {
+        return None;
+    }
+    let world = components.next()?.as_os_str().to_str()?;
+    if components.next()?.as_os_str() != "datapacks" {
+        return None;
+    }
+    Some(world.into())
+}
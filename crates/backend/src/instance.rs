@@ -8,12 +8,13 @@ use anyhow::Context;
 use base64::Engine;
 use bridge::{
     instance::{
-        ContentSummary, InstanceContentID, InstanceContentSummary, InstanceID, InstanceServerSummary, InstanceStatus, InstanceWorldSummary
+        ContentSummary, InstanceContentID, InstanceContentSummary, InstanceID, InstanceServerSummary, InstanceStatus, InstanceWorldSummary,
+        WorldDetails, WorldDifficulty, WorldGameMode,
     }, message::{AtomicBridgeDataLoadState, BridgeDataLoadState, MessageToFrontend}, notify_signal::{KeepAliveNotifySignal, KeepAliveNotifySignalHandle}
 };
 use parking_lot::RwLock;
 use relative_path::RelativePath;
-use schema::{aux::{AuxDisabledChildren, AuxiliaryContentMeta}, instance::InstanceConfiguration};
+use schema::{aux::AuxiliaryContentMeta, instance::InstanceConfiguration};
 use strum::IntoEnumIterator;
 use thiserror::Error;
 
@@ -30,8 +31,12 @@ pub struct Instance {
     pub saves_path: Arc<Path>,
     pub name: Ustr,
     pub configuration: Persistent<InstanceConfiguration>,
+    pub icon: Option<Arc<[u8]>>,
+    pub description: Arc<str>,
 
     pub child: Option<Child>,
+    // Set while files of the instance are being swapped out (e.g. restoring a snapshot), the instance can't be launched meanwhile
+    pub busy: bool,
 
     pub watching_dot_minecraft: bool,
     pub watching_server_dat: bool,
@@ -69,6 +74,7 @@ pub struct ContentFolderState {
 pub enum ContentFolder {
     Mods,
     ResourcePacks,
+    ShaderPacks,
 }
 
 impl ContentFolder {
@@ -76,6 +82,7 @@ impl ContentFolder {
         match self {
             ContentFolder::Mods => RelativePath::new("mods"),
             ContentFolder::ResourcePacks => RelativePath::new("resourcepacks"),
+            ContentFolder::ShaderPacks => RelativePath::new("shaderpacks"),
         }
     }
 }
@@ -269,6 +276,38 @@ impl Instance {
         Some((result, true))
     }
 
+    /// Fills in the sizes of the worlds, returns None if the worlds were reloaded in the meantime
+    pub async fn load_world_sizes(
+        instances: Arc<RwLock<BackendStateInstances>>,
+        id: InstanceID,
+        worlds: Arc<[InstanceWorldSummary]>,
+    ) -> Option<Arc<[InstanceWorldSummary]>> {
+        let sized: Arc<[InstanceWorldSummary]> = tokio::task::spawn_blocking({
+            let worlds = worlds.clone();
+            move || {
+                worlds.iter().map(|summary| {
+                    if summary.details.size.is_some() {
+                        return summary.clone();
+                    }
+                    let mut details = (*summary.details).clone();
+                    details.size = Some(directory_size(&summary.level_path));
+                    InstanceWorldSummary {
+                        details: Arc::new(details),
+                        ..summary.clone()
+                    }
+                }).collect()
+            }
+        }).await.unwrap();
+
+        let mut guard = instances.write();
+        let this = guard.instances.get_mut(id)?;
+        if !this.worlds.as_ref().is_some_and(|current| Arc::ptr_eq(current, &worlds)) {
+            return None;
+        }
+        this.worlds = Some(sized.clone());
+        Some(sized)
+    }
+
     fn load_worlds_all(saves_path: &Path) -> Arc<[InstanceWorldSummary]> {
         log::info!("Loading all worlds in {:?}", saves_path);
 
@@ -530,6 +569,8 @@ impl Instance {
             }
         }
 
+        mod_metadata_manager.save_path_hashes();
+
         summaries.sort_by(|a, b| {
             a.content_summary.id.cmp(&b.content_summary.id)
                 .then_with(|| lexical_sort::natural_lexical_cmp(&a.filename, &b.filename).reverse())
@@ -615,6 +656,7 @@ impl Instance {
                             enabled,
                             content_source: old_summary.content_source.clone(),
                             disabled_children: old_summary.disabled_children.clone(),
+                            pinned: old_summary.pinned.clone(),
                         });
                     }
 
@@ -622,6 +664,8 @@ impl Instance {
             }
         }
 
+        mod_metadata_manager.save_path_hashes();
+
         summaries.sort_by(|a, b| {
             a.content_summary.id.cmp(&b.content_summary.id)
                 .then_with(|| a.filename.cmp(&b.filename).reverse())
@@ -660,8 +704,11 @@ impl Instance {
             saves_path: saves_path.into(),
             name: path.file_name().unwrap().to_string_lossy().into_owned().into(),
             configuration: instance_info,
+            icon: load_icon(path),
+            description: load_description(path),
 
             child: None,
+            busy: false,
 
             watching_dot_minecraft: false,
             watching_server_dat: false,
@@ -723,6 +770,16 @@ impl Instance {
         self.root_path = new.root_path;
         self.name = new.name;
         self.configuration = new.configuration;
+        self.icon = new.icon;
+        self.description = new.description;
+    }
+
+    pub fn reload_icon(&mut self) {
+        self.icon = load_icon(&self.root_path);
+    }
+
+    pub fn reload_description(&mut self) {
+        self.description = load_description(&self.root_path);
     }
 
     pub fn status(&self) -> InstanceStatus {
@@ -743,11 +800,36 @@ impl Instance {
             name: self.name,
             dot_minecraft_folder: self.dot_minecraft_path.clone(),
             configuration: self.configuration.get().clone(),
+            icon: self.icon.clone(),
+            description: self.description.clone(),
             status,
         }
     }
 }
 
+fn load_icon(root_path: &Path) -> Option<Arc<[u8]>> {
+    std::fs::read(root_path.join("icon.png")).ok().map(Arc::from)
+}
+
+fn load_description(root_path: &Path) -> Arc<str> {
+    std::fs::read_to_string(root_path.join("description.md")).map(Arc::from).unwrap_or_default()
+}
+
+// Icons are stored as a png with at most 256x256 pixels so they can be used directly for shortcuts
+pub fn normalize_instance_icon(bytes: &[u8]) -> Option<Vec<u8>> {
+    let image = image::load_from_memory(bytes).ok()?;
+
+    let image = if image.width() > 256 || image.height() > 256 {
+        image.resize(256, 256, image::imageops::FilterType::Lanczos3)
+    } else {
+        image
+    };
+
+    let mut png_bytes = Vec::new();
+    image.write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageFormat::Png).ok()?;
+    Some(png_bytes)
+}
+
 fn create_instance_content_summary(path: &Path, mod_metadata_manager: &Arc<ModMetadataManager>) -> Option<InstanceContentSummary> {
     if !path.is_file() {
         return None;
@@ -766,13 +848,10 @@ fn create_instance_content_summary(path: &Path, mod_metadata_manager: &Arc<ModMe
         log::trace!("Skipping content file {}, unknown extension", filename);
         return None;
     };
-    let Ok(mut file) = std::fs::File::open(&path) else {
-        return None;
-    };
-
-    let Some(summary) = mod_metadata_manager.get_file(&mut file) else {
+    let Some(summary) = mod_metadata_manager.get_path(path) else {
         return None;
     };
+    let summary = crate::mod_metadata::name_shader_pack(summary, path);
 
     let filename_without_disabled = if !enabled {
         &filename[..filename.len()-".disabled".len()]
@@ -799,7 +878,7 @@ fn create_instance_content_summary(path: &Path, mod_metadata_manager: &Arc<ModMe
         .chain(std::iter::once(lowercase_filename))
         .collect();
 
-    let disabled_children = read_disabled_children_for(&summary, path).unwrap_or_default();
+    let aux = read_aux_meta_for(&summary, path).unwrap_or_default();
 
     Some(InstanceContentSummary {
         content_summary: summary,
@@ -810,17 +889,17 @@ fn create_instance_content_summary(path: &Path, mod_metadata_manager: &Arc<ModMe
         path: path.into(),
         enabled,
         content_source,
-        disabled_children: Arc::new(disabled_children),
+        disabled_children: Arc::new(aux.disabled_children),
+        pinned: aux.pinned,
     })
 }
 
-fn read_disabled_children_for(
+fn read_aux_meta_for(
     summary: &ContentSummary,
     path: &Path,
-) -> Option<AuxDisabledChildren> {
+) -> Option<AuxiliaryContentMeta> {
     let aux_path = crate::pandora_aux_path(&summary.id, &summary.name, path)?;
-    let aux: AuxiliaryContentMeta = crate::read_json(&aux_path).ok()?;
-    Some(aux.disabled_children)
+    crate::read_json(&aux_path).ok()
 }
 
 fn load_world_summary(path: &Path) -> anyhow::Result<InstanceWorldSummary> {
@@ -844,6 +923,28 @@ fn load_world_summary(path: &Path) -> anyhow::Result<InstanceWorldSummary> {
     let last_played: i64 = data.find_numeric("LastPlayed").context("Unable to get LastPlayed")?;
     let level_name = data.find_string("LevelName").cloned().unwrap_or_default();
 
+    // Worlds created before 1.16 store the seed directly in Data
+    let seed = data.find_compound("WorldGenSettings")
+        .and_then(|settings| settings.find_numeric("seed"))
+        .or_else(|| data.find_numeric("RandomSeed"));
+
+    let enabled_data_packs = data.find_compound("DataPacks")
+        .and_then(|data_packs| data_packs.find_list("Enabled", nbt::TAG_STRING_ID))
+        .map(|enabled| enabled.iter().filter_map(|pack| pack.as_string().map(|pack| Arc::from(pack.as_str()))).collect())
+        .unwrap_or_default();
+
+    let details = WorldDetails {
+        game_mode: data.find_numeric("GameType").and_then(WorldGameMode::from_id),
+        hardcore: data.find_numeric::<i8>("hardcore").unwrap_or(0) != 0,
+        difficulty: data.find_numeric("Difficulty").and_then(WorldDifficulty::from_id),
+        seed,
+        game_version: data.find_compound("Version").and_then(|version| version.find_string("Name")).map(|name| name.as_str().into()),
+        data_version: data.find_numeric("DataVersion"),
+        enabled_data_packs,
+        allow_commands: data.find_numeric::<i8>("allowCommands").unwrap_or(0) != 0,
+        size: None,
+    };
+
     let folder = path.file_name().context("Unable to get filename")?.to_string_lossy();
 
     let subtitle = if let Some(date_time) = chrono::DateTime::from_timestamp_millis(last_played) && last_played > 0 {
@@ -872,9 +973,29 @@ fn load_world_summary(path: &Path) -> anyhow::Result<InstanceWorldSummary> {
         level_path: path.into(),
         last_played,
         png_icon: icon,
+        details: Arc::new(details),
     })
 }
 
+fn directory_size(path: &Path) -> u64 {
+    let Ok(read_dir) = std::fs::read_dir(path) else {
+        return 0;
+    };
+
+    let mut size = 0;
+    for entry in read_dir.flatten() {
+        let Ok(file_type) = entry.file_type() else {
+            continue;
+        };
+        if file_type.is_dir() {
+            size += directory_size(&entry.path());
+        } else if let Ok(metadata) = entry.metadata() {
+            size += metadata.len();
+        }
+    }
+    size
+}
+
 fn load_servers_summary(server_dat_path: &Path) -> anyhow::Result<Vec<InstanceServerSummary>> {
     let raw = std::fs::read(server_dat_path)?;
 
@@ -886,7 +1007,7 @@ fn load_servers_summary(server_dat_path: &Path) -> anyhow::Result<Vec<InstanceSe
 
     let mut summaries = Vec::with_capacity(servers.len());
 
-    for server in servers.iter() {
+    for (index, server) in servers.iter().enumerate() {
         let server = server.as_compound().unwrap();
 
         if let Some(hidden) = server.find_byte("hidden")
@@ -909,6 +1030,7 @@ fn load_servers_summary(server_dat_path: &Path) -> anyhow::Result<Vec<InstanceSe
             .and_then(|v| base64::engine::general_purpose::STANDARD.decode(v).map(Arc::from).ok());
 
         summaries.push(InstanceServerSummary {
+            index,
             name,
             ip: Arc::from(ip.as_str()),
             png_icon: icon,
@@ -110,9 +110,13 @@ impl Launcher {
 
         let _ = std::fs::create_dir_all(&dot_minecraft_path);
 
+        let custom_resolution = instance_info.resolution
+            .filter(|resolution| resolution.enabled)
+            .map(|resolution| (resolution.width.max(1), resolution.height.max(1)));
+
         let launch_rule_context = LaunchRuleContext {
             is_demo_user: false,
-            custom_resolution: None,
+            custom_resolution,
             quick_play,
         };
 
@@ -2108,6 +2112,10 @@ impl LaunchContext {
         #[cfg(not(target_os = "linux"))]
         let mut command = std::process::Command::new(&*self.java_path);
 
+        if let Some(env_vars) = &self.configuration.env_vars && env_vars.enabled {
+            command.envs(env_vars.iter());
+        }
+
         command.current_dir(&self.game_dir);
         command.stdin(Stdio::piped());
         command.stdout(Stdio::piped());
@@ -0,0 +1,122 @@
+use std::{cmp::Ordering, sync::Arc};
+
+use bridge::{
+    instance::{ContentType, InstanceContentSummary, InstanceID, LaunchProblem, LaunchProblemKind, ModDependencyKind, ModpackChildRef},
+    mod_version::{compare_versions, version_matches},
+};
+use rustc_hash::FxHashMap;
+use schema::loader::Loader;
+
+use crate::{
+    BackendState,
+    instance::ContentFolder,
+    mod_dependencies::{EnabledMod, describe_versions, enabled_mod_summaries, is_release_version},
+};
+
+const NEOFORGE_DROPPED_FORGE_MODS: &str = "1.20.5";
+
+impl BackendState {
+    pub async fn check_launch_problems(self, id: InstanceID, channel: tokio::sync::oneshot::Sender<Vec<LaunchProblem>>) {
+        let Some((loader, minecraft_version)) = self.instance_state.write().instances.get_mut(id).map(|instance| {
+            let configuration = instance.configuration.get();
+            (configuration.loader, configuration.minecraft_version)
+        }) else {
+            return;
+        };
+
+        // Mods aren't loaded at all without a loader, so there is nothing that could fail
+        if matches!(loader, Loader::Vanilla | Loader::Unknown) {
+            _ = channel.send(Vec::new());
+            return;
+        }
+
+        let Some(mods) = self.clone().load_instance_content(id, ContentFolder::Mods).await else {
+            return;
+        };
+
+        _ = channel.send(find_launch_problems(&mods, loader, &minecraft_version));
+    }
+}
+
+pub(crate) fn find_launch_problems(mods: &[InstanceContentSummary], loader: Loader, minecraft_version: &str) -> Vec<LaunchProblem> {
+    let is_release = is_release_version(minecraft_version);
+
+    let mut problems = Vec::new();
+    let mut by_id: FxHashMap<&str, Vec<&EnabledMod>> = FxHashMap::default();
+
+    let enabled = enabled_mod_summaries(mods);
+    for enabled_mod in &enabled {
+        let summary = enabled_mod.summary;
+
+        let mod_loader = match summary.extra {
+            ContentType::Fabric => Loader::Fabric,
+            ContentType::Forge => Loader::Forge,
+            ContentType::NeoForge => Loader::NeoForge,
+            _ => continue,
+        };
+        // NeoForge kept reading Forge's mods.toml until 1.20.5, so those mods may still be compatible on older versions
+        let forge_on_neoforge = mod_loader == Loader::Forge && loader == Loader::NeoForge
+            && (!is_release || compare_versions(minecraft_version, NEOFORGE_DROPPED_FORGE_MODS) == Ordering::Less);
+        let supported = mod_loader == loader || forge_on_neoforge;
+        if !supported {
+            problems.push(create_problem(enabled_mod, LaunchProblemKind::WrongLoader { loader: mod_loader }));
+            continue;
+        }
+
+        if is_release && let Some(mod_info) = &summary.mod_info {
+            let minecraft = mod_info.dependencies.iter()
+                .find(|dependency| &*dependency.id == "minecraft" && dependency.kind == ModDependencyKind::Required);
+            if let Some(minecraft) = minecraft
+                && !version_matches(minecraft_version, &minecraft.versions, mod_loader != Loader::Fabric)
+            {
+                problems.push(create_problem(enabled_mod, LaunchProblemKind::WrongMinecraftVersion {
+                    versions: describe_versions(&minecraft.versions),
+                }));
+            }
+        }
+
+        if let Some(id) = &summary.id {
+            by_id.entry(id).or_default().push(enabled_mod);
+        }
+    }
+
+    for (id, duplicates) in by_id {
+        if duplicates.len() < 2 {
+            continue;
+        }
+
+        let newest = duplicates.iter().copied().max_by(|a, b| compare_mod_versions(a, b)).unwrap();
+        let kept: Arc<str> = format!("{} {}", newest.name(), newest.summary.version_str).into();
+
+        for duplicate in duplicates {
+            if !std::ptr::eq(duplicate, newest) {
+                problems.push(create_problem(duplicate, LaunchProblemKind::Duplicate {
+                    id: id.into(),
+                    kept: kept.clone(),
+                }));
+            }
+        }
+    }
+
+    problems
+}
+
+pub(crate) fn compare_mod_versions(a: &EnabledMod, b: &EnabledMod) -> Ordering {
+    match (&a.summary.mod_info, &b.summary.mod_info) {
+        (Some(a), Some(b)) => compare_versions(&a.version, &b.version),
+        _ => compare_versions(&a.summary.version_str, &b.summary.version_str),
+    }
+}
+
+fn create_problem(enabled_mod: &EnabledMod, kind: LaunchProblemKind) -> LaunchProblem {
+    LaunchProblem {
+        content_id: enabled_mod.content.id,
+        modpack_child: enabled_mod.modpack_path.map(|path| ModpackChildRef {
+            id: enabled_mod.summary.id.clone(),
+            name: enabled_mod.summary.name.clone(),
+            filename: path.clone(),
+        }),
+        mod_name: enabled_mod.name(),
+        kind,
+    }
+}
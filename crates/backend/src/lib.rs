@@ -14,21 +14,39 @@ mod backend_handler;
 
 mod account;
 mod arcfactory;
+mod content_library;
+mod content_update;
+mod curseforge;
+mod datapack;
 mod directories;
+mod github_release;
 mod install_content;
 mod instance;
 mod java_manifest;
 mod launch;
+mod launch_check;
 mod launch_wrapper;
 mod lockfile;
 mod log_reader;
 mod metadata;
+mod migration;
+mod mod_dependencies;
 mod mod_metadata;
+mod mod_summary_cache;
+mod nbt_file;
+mod options;
 mod id_slab;
 mod persistent;
+mod resource_pack;
+mod screenshot;
+mod server_ping;
+mod servers;
+mod shader_pack;
 mod shortcut;
+mod snapshot;
 mod syncing;
 mod update;
+mod world;
 
 pub(crate) fn is_single_component_path(path: &str) -> bool {
     let path = std::path::Path::new(path);
@@ -4,7 +4,7 @@ use std::{
 
 use reqwest::RequestBuilder;
 use schema::{
-    assets_index::AssetsIndex, fabric_launch::FabricLaunch, fabric_loader_manifest::{FABRIC_LOADER_MANIFEST_URL, FabricLoaderManifest}, forge::{ForgeMavenManifest, NeoforgeMavenManifest, VersionFragment}, java_runtime_component::JavaRuntimeComponentManifest, java_runtimes::{JAVA_RUNTIMES_URL, JavaRuntimes}, maven::MavenMetadataXml, modrinth::{MODRINTH_SEARCH_URL, ModrinthLoader, ModrinthProjectVersion, ModrinthProjectVersionsRequest, ModrinthProjectVersionsResult, ModrinthSearchRequest, ModrinthSearchResult, ModrinthVersionFileUpdateResult}, version::MinecraftVersion, version_manifest::{MOJANG_VERSION_MANIFEST_URL, MinecraftVersionLink, MinecraftVersionManifest}
+    assets_index::AssetsIndex, backend_config::BackendConfig, curseforge::{CurseforgeFileResult, CurseforgeModFilesRequest, CurseforgeModFilesResult, CurseforgeSearchRequest, CurseforgeSearchResult, CURSEFORGE_API_URL, CURSEFORGE_MINECRAFT_GAME_ID}, fabric_launch::FabricLaunch, fabric_loader_manifest::{FABRIC_LOADER_MANIFEST_URL, FabricLoaderManifest}, forge::{ForgeMavenManifest, NeoforgeMavenManifest, VersionFragment}, java_runtime_component::JavaRuntimeComponentManifest, java_runtimes::{JAVA_RUNTIMES_URL, JavaRuntimes}, maven::MavenMetadataXml, modrinth::{MODRINTH_SEARCH_URL, ModrinthLoader, ModrinthProjectVersion, ModrinthProjectVersionsRequest, ModrinthProjectVersionsResult, ModrinthSearchRequest, ModrinthSearchResult, ModrinthVersionFileUpdateResult}, version::MinecraftVersion, version_manifest::{MOJANG_VERSION_MANIFEST_URL, MinecraftVersionLink, MinecraftVersionManifest}
 };
 use serde::Serialize;
 use ustr::Ustr;
@@ -328,7 +328,7 @@ impl MetadataItem for ModrinthVersionMetadataItem {
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, Hash)]
 pub struct VersionUpdateParameters {
     pub loaders: Arc<[ModrinthLoader]>,
     pub game_versions: Arc<[Ustr]>,
@@ -353,7 +353,7 @@ impl MetadataItem for ModrinthVersionUpdateMetadataItem {
     }
 
     fn state(&self, states: &mut MetadataManagerStates) -> MetaLoadStateWrapper<Self::T> {
-        states.modrinth_version_updates.entry(self.sha1.clone()).or_default().clone()
+        states.modrinth_version_updates.entry((self.sha1.clone(), self.params.clone())).or_default().clone()
     }
 
     fn deserialize(bytes: &[u8]) -> Result<Self::T, MetaLoadError> {
@@ -361,13 +361,13 @@ impl MetadataItem for ModrinthVersionUpdateMetadataItem {
     }
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, Hash)]
 pub struct VersionV3UpdateParameters {
     pub loaders: Arc<[Arc<str>]>,
     pub loader_fields: VersionV3LoaderFields,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, PartialEq, Eq, Hash)]
 pub struct VersionV3LoaderFields {
     pub mrpack_loaders: Arc<[ModrinthLoader]>,
     pub game_versions: Arc<[Ustr]>,
@@ -392,7 +392,129 @@ impl MetadataItem for ModrinthV3VersionUpdateMetadataItem {
     }
 
     fn state(&self, states: &mut MetadataManagerStates) -> MetaLoadStateWrapper<Self::T> {
-        states.modrinth_version_updates.entry(self.sha1.clone()).or_default().clone()
+        states.modrinth_v3_version_updates.entry((self.sha1.clone(), self.params.clone())).or_default().clone()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self::T, MetaLoadError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CurseforgeApi {
+    pub url: Arc<str>,
+    pub key: Option<Arc<str>>,
+}
+
+impl CurseforgeApi {
+    pub fn from_config(config: &BackendConfig) -> Self {
+        Self {
+            url: config.curseforge_api_url().into(),
+            key: config.curseforge_api_key.clone().filter(|key| !key.is_empty()),
+        }
+    }
+
+    /// The official API rejects requests without a key, a custom url might not need one
+    pub fn is_usable(&self) -> bool {
+        self.key.is_some() || &*self.url != CURSEFORGE_API_URL
+    }
+
+    pub fn get(&self, client: &reqwest::Client, path: &str) -> RequestBuilder {
+        self.with_key(client.get(format!("{}{}", self.url, path)))
+    }
+
+    pub fn post(&self, client: &reqwest::Client, path: &str) -> RequestBuilder {
+        self.with_key(client.post(format!("{}{}", self.url, path)))
+    }
+
+    fn with_key(&self, request: RequestBuilder) -> RequestBuilder {
+        if let Some(key) = &self.key {
+            request.header("x-api-key", &**key)
+        } else {
+            request
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CurseforgeSearchMetadataItem<'a> {
+    pub api: &'a CurseforgeApi,
+    pub request: &'a CurseforgeSearchRequest,
+}
+
+impl<'a> MetadataItem for CurseforgeSearchMetadataItem<'a> {
+    type T = CurseforgeSearchResult;
+
+    fn request(&self, client: &reqwest::Client) -> RequestBuilder {
+        // Sort field 2 is popularity
+        self.api.get(client, "/v1/mods/search")
+            .query(&[("gameId", CURSEFORGE_MINECRAFT_GAME_ID), ("sortField", 2)])
+            .query(&[("sortOrder", "desc")])
+            .query(self.request)
+    }
+
+    fn expires(&self) -> bool {
+        true
+    }
+
+    fn state(&self, states: &mut MetadataManagerStates) -> MetaLoadStateWrapper<Self::T> {
+        states.curseforge_search.entry(self.request.clone()).or_default().clone()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self::T, MetaLoadError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct CurseforgeModFilesMetadataItem<'a> {
+    pub api: &'a CurseforgeApi,
+    pub request: &'a CurseforgeModFilesRequest,
+}
+
+impl<'a> MetadataItem for CurseforgeModFilesMetadataItem<'a> {
+    type T = CurseforgeModFilesResult;
+
+    fn request(&self, client: &reqwest::Client) -> RequestBuilder {
+        // Files are listed newest first, 50 is the largest page CurseForge allows
+        self.api.get(client, &format!("/v1/mods/{}/files", self.request.mod_id))
+            .query(&[("pageSize", 50)])
+            .query(self.request)
+    }
+
+    fn expires(&self) -> bool {
+        true
+    }
+
+    fn state(&self, states: &mut MetadataManagerStates) -> MetaLoadStateWrapper<Self::T> {
+        states.curseforge_mod_files.entry(self.request.clone()).or_default().clone()
+    }
+
+    fn deserialize(bytes: &[u8]) -> Result<Self::T, MetaLoadError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+}
+
+#[derive(Debug)]
+pub struct CurseforgeFileMetadataItem<'a> {
+    pub api: &'a CurseforgeApi,
+    pub mod_id: u32,
+    pub file_id: u32,
+}
+
+impl<'a> MetadataItem for CurseforgeFileMetadataItem<'a> {
+    type T = CurseforgeFileResult;
+
+    fn request(&self, client: &reqwest::Client) -> RequestBuilder {
+        self.api.get(client, &format!("/v1/mods/{}/files/{}", self.mod_id, self.file_id))
+    }
+
+    fn expires(&self) -> bool {
+        true
+    }
+
+    fn state(&self, states: &mut MetadataManagerStates) -> MetaLoadStateWrapper<Self::T> {
+        states.curseforge_files.entry((self.mod_id, self.file_id)).or_default().clone()
     }
 
     fn deserialize(bytes: &[u8]) -> Result<Self::T, MetaLoadError> {
@@ -0,0 +1,304 @@
+use std::sync::Arc;
+
+use bridge::{
+    install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget},
+    instance::{ContentType, InstanceContentSummary, InstanceID},
+    message::MessageToFrontend,
+    migration::{InstanceMigrationEntry, InstanceMigrationPlan, InstanceMigrationStatus},
+    modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType},
+};
+use schema::{content::ContentSource, loader::Loader, modrinth::ModrinthLoader};
+use tokio::sync::Semaphore;
+use ustr::Ustr;
+
+use crate::{
+    BackendState,
+    install_content::ContentInstallError,
+    instance::ContentFolder,
+    metadata::{items::{ModrinthVersionUpdateMetadataItem, VersionUpdateParameters}, manager::MetaLoadError},
+    snapshot::SnapshotError,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum MigrationError {
+    #[error("Unknown instance")]
+    UnknownInstance,
+    #[error("Instance is currently running")]
+    InstanceRunning,
+    #[error("Unable to load mods")]
+    UnableToLoadMods,
+    #[error("Failed to create snapshot before migrating:\n{0}")]
+    Snapshot(#[from] SnapshotError),
+    #[error("Failed to check for compatible versions:\n{0}")]
+    MetaLoadError(#[from] MetaLoadError),
+    #[error("Failed to install updates, the instance has been restored from the snapshot:\n{0}")]
+    InstallFailed(ContentInstallError),
+    #[error("Failed to disable incompatible mods, the instance has been restored from the snapshot:\n{0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl BackendState {
+    pub async fn plan_instance_migration(
+        self,
+        id: InstanceID,
+        minecraft_version: Ustr,
+        loader: Loader,
+        modal_action: ModalAction,
+        channel: tokio::sync::oneshot::Sender<InstanceMigrationPlan>,
+    ) {
+        match self.plan_instance_migration_inner(id, minecraft_version, loader, &modal_action).await {
+            Ok(plan) => {
+                _ = channel.send(plan);
+            },
+            Err(err) => {
+                modal_action.set_error_message(format!("Unable to check mod compatibility:\n{err}").into());
+            },
+        }
+        modal_action.set_finished();
+    }
+
+    async fn plan_instance_migration_inner(
+        &self,
+        id: InstanceID,
+        minecraft_version: Ustr,
+        loader: Loader,
+        modal_action: &ModalAction,
+    ) -> Result<InstanceMigrationPlan, MigrationError> {
+        if self.instance_state.read().instances.get(id).is_none() {
+            return Err(MigrationError::UnknownInstance);
+        }
+
+        // Vanilla doesn't load mods at all, so they're left as they are instead of all being marked incompatible
+        if loader == Loader::Vanilla {
+            return Ok(InstanceMigrationPlan {
+                minecraft_version,
+                loader,
+                entries: [].into(),
+            });
+        }
+
+        let Some(mods) = self.clone().load_instance_content(id, ContentFolder::Mods).await else {
+            return Err(MigrationError::UnableToLoadMods);
+        };
+
+        let tracker = ProgressTracker::new("Checking mod compatibility".into(), self.send.clone());
+        tracker.set_total(mods.len());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let modrinth_loader = loader.as_modrinth_loader();
+        let params = &VersionUpdateParameters {
+            loaders: [modrinth_loader].into(),
+            game_versions: [minecraft_version].into(),
+        };
+
+        let semaphore = &Semaphore::new(8);
+        let meta = &self.meta;
+        let tracker_ref = &tracker;
+
+        let mut futures = Vec::new();
+
+        { // Scope is needed so await doesn't complain about the non-send RwLockReadGuard
+            let sources = self.mod_metadata_manager.read_content_sources();
+            for summary in mods.iter() {
+                let source = sources.get(&summary.content_summary.hash).unwrap_or(ContentSource::Manual);
+                futures.push(async move {
+                    let status = check_migration_status(summary, source, modrinth_loader, params, meta, semaphore).await;
+                    tracker_ref.add_count(1);
+                    tracker_ref.notify();
+                    status.map(|status| InstanceMigrationEntry {
+                        name: summary.content_summary.name.clone().unwrap_or_else(|| summary.filename.clone()),
+                        path: summary.path.clone(),
+                        enabled: summary.enabled,
+                        status,
+                    })
+                });
+            }
+        }
+
+        let result: Result<Vec<InstanceMigrationEntry>, MetaLoadError> = futures::future::try_join_all(futures).await;
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        tracker.notify();
+
+        let mut entries = result?;
+        entries.sort_by(|a, b| lexical_sort::natural_lexical_cmp(&a.name, &b.name));
+
+        Ok(InstanceMigrationPlan {
+            minecraft_version,
+            loader,
+            entries: entries.into(),
+        })
+    }
+
+    pub async fn apply_instance_migration(self, id: InstanceID, plan: InstanceMigrationPlan, disable_incompatible: bool, modal_action: ModalAction) {
+        match self.apply_instance_migration_inner(id, &plan, disable_incompatible, &modal_action).await {
+            Ok(()) => {
+                self.send.send_success(format!("Migrated instance to {} {}", plan.loader.name(), plan.minecraft_version));
+            },
+            Err(err) => {
+                modal_action.set_error_message(format!("Unable to migrate instance:\n{err}").into());
+            },
+        }
+        modal_action.set_finished();
+        self.send.send(MessageToFrontend::Refresh);
+    }
+
+    async fn apply_instance_migration_inner(
+        &self,
+        id: InstanceID,
+        plan: &InstanceMigrationPlan,
+        disable_incompatible: bool,
+        modal_action: &ModalAction,
+    ) -> Result<(), MigrationError> {
+        {
+            let instance_state = self.instance_state.read();
+            let Some(instance) = instance_state.instances.get(id) else {
+                return Err(MigrationError::UnknownInstance);
+            };
+            if instance.child.is_some() {
+                return Err(MigrationError::InstanceRunning);
+            }
+        }
+
+        let tracker = ProgressTracker::new("Creating snapshot".into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let note = format!("Before migrating to {} {}", plan.loader.name(), plan.minecraft_version);
+        let snapshot = self.create_instance_snapshot(id, note.into(), false).await;
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(snapshot.is_err()));
+        tracker.notify();
+
+        let snapshot = snapshot?;
+
+        let files: Vec<ContentInstallFile> = plan.entries.iter().filter_map(|entry| match &entry.status {
+            InstanceMigrationStatus::Update { install, .. } => Some(install.clone()),
+            _ => None,
+        }).collect();
+
+        if !files.is_empty() {
+            let content_install = ContentInstall {
+                target: InstallTarget::Instance(id),
+                loader_hint: plan.loader,
+                version_hint: Some(plan.minecraft_version.as_str().into()),
+                files: files.into(),
+            };
+            if let Err(install_error) = self.try_install_content(content_install, modal_action).await {
+                self.rollback_migration(id, snapshot.id.clone()).await;
+                return Err(MigrationError::InstallFailed(install_error));
+            }
+        }
+
+        if disable_incompatible {
+            for entry in plan.entries.iter() {
+                if !entry.enabled || !matches!(entry.status, InstanceMigrationStatus::Incompatible) {
+                    continue;
+                }
+                if let Err(err) = std::fs::rename(&entry.path, entry.path.with_added_extension("disabled")) {
+                    self.rollback_migration(id, snapshot.id.clone()).await;
+                    return Err(err.into());
+                }
+            }
+        }
+
+        if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+            instance.configuration.modify(|configuration| {
+                if configuration.loader != plan.loader {
+                    configuration.loader = plan.loader;
+                    configuration.preferred_loader_version = None;
+                }
+                configuration.minecraft_version = plan.minecraft_version;
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn rollback_migration(&self, id: InstanceID, snapshot: Arc<str>) {
+        if let Err(err) = self.restore_instance_snapshot_inner(id, snapshot.clone()).await {
+            log::error!("Unable to restore snapshot {snapshot} after failed migration: {err}");
+            self.send.send_error(format!("Unable to restore snapshot {snapshot} after failed migration: {err}"));
+        }
+    }
+}
+
+async fn check_migration_status(
+    summary: &InstanceContentSummary,
+    source: ContentSource,
+    modrinth_loader: ModrinthLoader,
+    params: &VersionUpdateParameters,
+    meta: &crate::metadata::manager::MetadataManager,
+    semaphore: &Semaphore,
+) -> Result<InstanceMigrationStatus, MetaLoadError> {
+    if modrinth_loader == ModrinthLoader::Unknown {
+        return Ok(InstanceMigrationStatus::Incompatible);
+    }
+
+    let project = match source {
+        ContentSource::Manual | ContentSource::Url { .. } | ContentSource::CurseforgeProject { .. } | ContentSource::GithubRelease { .. } => return Ok(InstanceMigrationStatus::Unknown),
+        ContentSource::ModrinthUnknown => None,
+        ContentSource::ModrinthProject { project } => Some(project),
+    };
+
+    match summary.content_summary.extra {
+        ContentType::Fabric | ContentType::Forge | ContentType::NeoForge | ContentType::JavaModule => {},
+        ContentType::ModrinthModpack { .. } | ContentType::ResourcePack { .. } | ContentType::ShaderPack { .. } => return Ok(InstanceMigrationStatus::Unknown),
+    }
+
+    let permit = semaphore.acquire().await.unwrap();
+    let result = meta.fetch(&ModrinthVersionUpdateMetadataItem {
+        sha1: hex::encode(summary.content_summary.hash).into(),
+        params: params.clone(),
+    }).await;
+    drop(permit);
+
+    if let Err(MetaLoadError::NonOK(404)) = result {
+        return Ok(InstanceMigrationStatus::Incompatible);
+    }
+
+    let result = result?;
+
+    if let Some(project) = project && result.0.project_id != project {
+        log::error!("Refusing to migrate {:?}, mismatched project ids: expected {}, got {}",
+            summary.content_summary.hash, &project, &result.0.project_id);
+        return Ok(InstanceMigrationStatus::Unknown);
+    }
+
+    let Some(install_file) = result.0.files.iter().find(|file| file.primary).or(result.0.files.first()) else {
+        return Ok(InstanceMigrationStatus::Incompatible);
+    };
+
+    let mut latest_hash = [0u8; 20];
+    let Ok(_) = hex::decode_to_slice(&*install_file.hashes.sha1, &mut latest_hash) else {
+        return Ok(InstanceMigrationStatus::Unknown);
+    };
+
+    if latest_hash == summary.content_summary.hash {
+        return Ok(InstanceMigrationStatus::Compatible);
+    }
+
+    let mut path = summary.path.with_file_name(&*install_file.filename);
+    if !summary.enabled {
+        path.add_extension("disabled");
+    }
+
+    let version = result.0.version_number.clone()
+        .or_else(|| result.0.name.clone())
+        .unwrap_or_else(|| install_file.filename.clone());
+
+    Ok(InstanceMigrationStatus::Update {
+        version,
+        install: ContentInstallFile {
+            replace_old: Some(summary.path.clone()),
+            path: ContentInstallPath::Raw(path.into()),
+            download: ContentDownload::Url {
+                url: install_file.url.clone(),
+                sha1: install_file.hashes.sha1.clone(),
+                size: install_file.size,
+            },
+            content_source: ContentSource::ModrinthProject { project: result.0.project_id.clone() },
+        },
+    })
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use bridge::{
+    instance::{ContentSummary, ContentType, InstanceContentSummary, InstanceID, ModDependencyIssue, ModDependencyIssueKind, ModDependencyKind},
+    mod_version::version_matches,
+};
+use rustc_hash::FxHashMap;
+
+use crate::{BackendState, instance::ContentFolder};
+
+// Provided by the game or the loader itself instead of a jar in the mods folder
+const BUILTIN_IDS: &[&str] = &["java", "fabricloader", "fabric-loader", "quilt_loader", "mixinextras", "forge", "neoforge", "fml", "javafml"];
+
+impl BackendState {
+    pub async fn check_mod_dependencies(self, id: InstanceID, channel: tokio::sync::oneshot::Sender<Vec<ModDependencyIssue>>) {
+        let Some(minecraft_version) = self.instance_state.write().instances.get_mut(id).map(|instance| instance.configuration.get().minecraft_version) else {
+            return;
+        };
+
+        let Some(mods) = self.clone().load_instance_content(id, ContentFolder::Mods).await else {
+            return;
+        };
+
+        _ = channel.send(find_dependency_issues(&mods, &minecraft_version));
+    }
+}
+
+pub(crate) struct EnabledMod<'a> {
+    pub content: &'a InstanceContentSummary,
+    pub summary: &'a Arc<ContentSummary>,
+    /// Path of the file inside the modpack, if the mod is a child of an installed modpack
+    pub modpack_path: Option<&'a Arc<str>>,
+}
+
+impl EnabledMod<'_> {
+    pub fn name(&self) -> Arc<str> {
+        self.summary.name.clone()
+            .or_else(|| self.modpack_path.cloned())
+            .unwrap_or_else(|| self.content.filename.clone())
+    }
+}
+
+/// Enabled mods of an instance, including the enabled children of installed modpacks
+pub(crate) fn enabled_mod_summaries(mods: &[InstanceContentSummary]) -> Vec<EnabledMod<'_>> {
+    let mut enabled = Vec::new();
+    for content in mods {
+        if !content.enabled {
+            continue;
+        }
+
+        if let ContentType::ModrinthModpack { downloads, summaries, .. } = &content.content_summary.extra {
+            let disabled = &content.disabled_children;
+            for (download, summary) in downloads.iter().zip(summaries.iter()) {
+                let Some(summary) = summary else {
+                    continue;
+                };
+                if disabled.disabled_filenames.contains(&download.path)
+                    || summary.id.as_ref().is_some_and(|id| disabled.disabled_ids.contains(id))
+                    || summary.name.as_ref().is_some_and(|name| disabled.disabled_names.contains(name))
+                {
+                    continue;
+                }
+                enabled.push(EnabledMod {
+                    content,
+                    summary,
+                    modpack_path: Some(&download.path),
+                });
+            }
+        } else {
+            enabled.push(EnabledMod {
+                content,
+                summary: &content.content_summary,
+                modpack_path: None,
+            });
+        }
+    }
+    enabled
+}
+
+pub(crate) fn find_dependency_issues(mods: &[InstanceContentSummary], minecraft_version: &str) -> Vec<ModDependencyIssue> {
+    let enabled = enabled_mod_summaries(mods);
+
+    let mut installed: FxHashMap<&str, &str> = FxHashMap::default();
+    for EnabledMod { summary, .. } in &enabled {
+        let Some(mod_info) = &summary.mod_info else {
+            continue;
+        };
+        if let Some(id) = &summary.id {
+            installed.insert(id, &mod_info.version);
+        }
+        for provided in mod_info.provides.iter() {
+            installed.entry(&provided.id).or_insert(&provided.version);
+        }
+    }
+
+    // Snapshot names can't be compared against version ranges, so only the presence of the game is checked
+    let is_release = is_release_version(minecraft_version);
+
+    let mut issues = Vec::new();
+    for enabled_mod in &enabled {
+        let summary = enabled_mod.summary;
+        let Some(mod_info) = &summary.mod_info else {
+            continue;
+        };
+        let maven = !matches!(summary.extra, ContentType::Fabric);
+
+        for dependency in mod_info.dependencies.iter() {
+            if summary.id.as_ref() == Some(&dependency.id) {
+                continue;
+            }
+
+            // None if the dependency isn't installed, Some(None) if it is but its version can't be checked
+            let found: Option<Option<&str>> = if &*dependency.id == "minecraft" {
+                Some(is_release.then_some(minecraft_version))
+            } else if BUILTIN_IDS.contains(&&*dependency.id) {
+                Some(None)
+            } else {
+                installed.get(&*dependency.id).map(|version| Some(*version))
+            };
+
+            let matches = |version: Option<&str>| {
+                version.is_none_or(|version| version_matches(version, &dependency.versions, maven))
+            };
+            let found_str = |version: Option<&str>| -> Arc<str> {
+                version.unwrap_or("unknown version").into()
+            };
+
+            let kind = match (dependency.kind, found) {
+                (ModDependencyKind::Required, None) => ModDependencyIssueKind::Missing,
+                (ModDependencyKind::Recommended, None) => ModDependencyIssueKind::MissingRecommended,
+                (ModDependencyKind::Required | ModDependencyKind::Optional, Some(version)) if !matches(version) => {
+                    ModDependencyIssueKind::WrongVersion { found: found_str(version) }
+                },
+                (ModDependencyKind::Recommended, Some(version)) if !matches(version) => {
+                    ModDependencyIssueKind::WrongRecommendedVersion { found: found_str(version) }
+                },
+                (ModDependencyKind::Breaks, Some(version)) if matches(version) => {
+                    ModDependencyIssueKind::Breaks { found: found_str(version) }
+                },
+                (ModDependencyKind::Conflicts, Some(version)) if matches(version) => {
+                    ModDependencyIssueKind::Conflicts { found: found_str(version) }
+                },
+                _ => continue,
+            };
+
+            issues.push(ModDependencyIssue {
+                mod_name: enabled_mod.name(),
+                filename: enabled_mod.content.filename.clone(),
+                dependency: dependency.id.clone(),
+                versions: describe_versions(&dependency.versions),
+                kind,
+            });
+        }
+    }
+
+    issues.sort_by_key(|issue| !issue.kind.is_error());
+    issues
+}
+
+pub(crate) fn is_release_version(minecraft_version: &str) -> bool {
+    minecraft_version.bytes().all(|byte| byte.is_ascii_digit() || byte == b'.')
+}
+
+pub(crate) fn describe_versions(versions: &[Arc<str>]) -> Arc<str> {
+    if versions.is_empty() {
+        "any version".into()
+    } else {
+        versions.join(" or ").into()
+    }
+}
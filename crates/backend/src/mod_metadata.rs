@@ -2,18 +2,20 @@ use std::{
     io::{BufRead, Cursor, Read, Write}, path::{Path, PathBuf}, sync::Arc
 };
 
-use bridge::{instance::{AtomicContentUpdateStatus, ContentUpdateStatus, ContentType, ContentSummary}, safe_path::SafePath};
+use bridge::{instance::{AtomicContentUpdateStatus, ContentUpdateStatus, ContentType, ContentSummary, ModDependency, ModDependencyKind, ModInfo, ProvidedMod}, safe_path::SafePath};
 use image::imageops::FilterType;
 use indexmap::IndexMap;
 use parking_lot::{RwLock, RwLockReadGuard};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use rc_zip_sync::EntryHandle;
 use rustc_hash::{FxHashMap, FxHashSet};
-use schema::{content::ContentSource, fabric_mod::{FabricModJson, Icon, Person}, forge_mod::{JarJarMetadata, ModsToml}, modrinth::{ModrinthFile, ModrinthSideRequirement}, mrpack::ModrinthIndexJson, resourcepack::PackMcmeta};
+use schema::{content::ContentSource, curseforge::CurseforgeFile, fabric_mod::{FabricModJson, Icon, Person}, forge_mod::{JarJarMetadata, McmodInfo, ModsToml}, github::GithubReleaseAsset, legacy_mod::{LiteModJson, RiftModJson}, modrinth::{ModrinthFile, ModrinthSideRequirement}, mrpack::ModrinthIndexJson, resourcepack::PackMcmeta};
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, DeserializeAs};
 use sha1::{Digest, Sha1};
 
+use crate::mod_summary_cache::ModSummaryCache;
+
 #[derive(Clone)]
 pub enum ModUpdateAction {
     ErrorNotFound,
@@ -24,6 +26,14 @@ pub enum ModUpdateAction {
         file: ModrinthFile,
         project_id: Arc<str>,
     },
+    GithubRelease {
+        asset: GithubReleaseAsset,
+        changelog: Option<Arc<str>>,
+        source: ContentSource,
+    },
+    Curseforge {
+        file: CurseforgeFile,
+    },
 }
 
 impl ModUpdateAction {
@@ -34,6 +44,8 @@ impl ModUpdateAction {
             ModUpdateAction::AlreadyUpToDate => ContentUpdateStatus::AlreadyUpToDate,
             ModUpdateAction::ManualInstall => ContentUpdateStatus::ManualInstall,
             ModUpdateAction::Modrinth { .. } => ContentUpdateStatus::Modrinth,
+            ModUpdateAction::GithubRelease { .. } => ContentUpdateStatus::GithubRelease,
+            ModUpdateAction::Curseforge { .. } => ContentUpdateStatus::Curseforge,
         }
     }
 }
@@ -42,6 +54,7 @@ pub struct ModMetadataManager {
     content_library_dir: Arc<Path>,
     sources_dir: PathBuf,
     by_hash: RwLock<FxHashMap<[u8; 20], Option<Arc<ContentSummary>>>>,
+    summary_cache: ModSummaryCache,
     content_sources: RwLock<ContentSources>,
     parents_by_missing_child: RwLock<FxHashMap<[u8; 20], Vec<[u8; 20]>>>,
     pub updates: RwLock<FxHashMap<[u8; 20], ModUpdateAction>>,
@@ -69,10 +82,13 @@ impl ModMetadataManager {
             Default::default()
         };
 
+        let summary_cache = ModSummaryCache::load(&content_meta_dir);
+
         Self {
             content_library_dir,
             sources_dir,
             by_hash: Default::default(),
+            summary_cache,
             content_sources: RwLock::new(content_sources),
             parents_by_missing_child: Default::default(),
             updates: Default::default(),
@@ -100,19 +116,26 @@ impl ModMetadataManager {
 
     pub fn get_path(self: &Arc<Self>, path: &Path) -> Option<Arc<ContentSummary>> {
         let mut file = std::fs::File::open(path).ok()?;
-        self.get_file(&mut file)
-    }
 
-    pub fn get_file(self: &Arc<Self>, file: &mut std::fs::File) -> Option<Arc<ContentSummary>> {
-        let mut hasher = Sha1::new();
-        let _ = std::io::copy(file, &mut hasher).ok()?;
-        let actual_hash: [u8; 20] = hasher.finalize().into();
+        // Unchanged files don't need to be hashed again
+        let metadata = file.metadata().ok();
+        if let Some(metadata) = &metadata
+            && let Some(hash) = self.summary_cache.get_hash_for_path(path, metadata)
+            && let Some(summary) = self.get_cached(hash)
+        {
+            return summary;
+        }
+
+        let actual_hash = hash_file(&mut file)?;
+        if let Some(metadata) = &metadata {
+            self.summary_cache.set_hash_for_path(path, metadata, actual_hash);
+        }
 
-        if let Some(summary) = self.by_hash.read().get(&actual_hash) {
-            return summary.clone();
+        if let Some(summary) = self.get_cached(actual_hash) {
+            return summary;
         }
 
-        let summary = self.load_mod_summary(actual_hash, file, true);
+        let summary = self.load_mod_summary(actual_hash, &file, true);
 
         self.put(actual_hash, summary.clone());
 
@@ -122,7 +145,22 @@ impl ModMetadataManager {
     pub fn get_cached_by_sha1(self: &Arc<Self>, sha1: &str) -> Option<Arc<ContentSummary>> {
         let mut hash = [0u8; 20];
         hex::decode_to_slice(sha1, &mut hash).ok()?;
-        self.by_hash.read().get(&hash).cloned().flatten()
+        self.get_cached(hash).flatten()
+    }
+
+    /// Writes the hashes of files loaded by path, should be called after loading a batch of files
+    pub fn save_path_hashes(&self) {
+        self.summary_cache.save_path_hashes();
+    }
+
+    fn get_cached(&self, hash: [u8; 20]) -> Option<Option<Arc<ContentSummary>>> {
+        if let Some(summary) = self.by_hash.read().get(&hash) {
+            return Some(summary.clone());
+        }
+
+        let summary = self.summary_cache.get(hash)?;
+        self.by_hash.write().insert(hash, summary.clone());
+        Some(summary)
     }
 
     pub fn get_bytes(self: &Arc<Self>, bytes: &[u8]) -> Option<Arc<ContentSummary>> {
@@ -130,8 +168,8 @@ impl ModMetadataManager {
         hasher.write_all(bytes).ok()?;
         let actual_hash: [u8; 20] = hasher.finalize().into();
 
-        if let Some(summary) = self.by_hash.read().get(&actual_hash) {
-            return summary.clone();
+        if let Some(summary) = self.get_cached(actual_hash) {
+            return summary;
         }
 
         let summary = self.load_mod_summary(actual_hash, &bytes, true);
@@ -141,7 +179,17 @@ impl ModMetadataManager {
         summary
     }
 
+    /// Unpacked packs aren't cached, they are identified by the hash of their pack.mcmeta
+    pub fn get_pack_directory(&self, path: &Path) -> Option<Arc<ContentSummary>> {
+        let bytes = std::fs::read(path.join("pack.mcmeta")).ok()?;
+        let hash: [u8; 20] = Sha1::digest(&bytes).into();
+
+        let png_icon = std::fs::read(path.join("pack.png")).ok().and_then(load_icon_bytes);
+        load_pack_summary(hash, &bytes, png_icon)
+    }
+
     fn put(self: &Arc<Self>, hash: [u8; 20], summary: Option<Arc<ContentSummary>>) {
+        self.summary_cache.put(hash, &summary);
         self.by_hash.write().insert(hash, summary.clone());
 
         if let Some(parents) = self.parents_by_missing_child.write().remove(&hash) {
@@ -164,10 +212,22 @@ impl ModMetadataManager {
             self.load_forge_mod(hash, &archive, file, ContentType::NeoForge)
         } else if let Some(file) = archive.by_name("META-INF/jarjar/metadata.json") {
             self.load_jarjar(hash, &archive, file)
+        } else if let Some(file) = archive.by_name("mcmod.info") {
+            // Broken or empty legacy metadata is common, those jars are still listed through their manifest
+            self.load_mcmod_info(hash, &archive, file)
+                .or_else(|| self.load_from_java_manifest(hash, &archive, archive.by_name("META-INF/MANIFEST.MF")?))
+        } else if let Some(file) = archive.by_name("litemod.json") {
+            self.load_litemod(hash, &archive, file)
+                .or_else(|| self.load_from_java_manifest(hash, &archive, archive.by_name("META-INF/MANIFEST.MF")?))
+        } else if let Some(file) = archive.by_name("riftmod.json") {
+            self.load_riftmod(hash, file)
+                .or_else(|| self.load_from_java_manifest(hash, &archive, archive.by_name("META-INF/MANIFEST.MF")?))
         } else if let Some(file) = archive.by_name("META-INF/MANIFEST.MF") {
             self.load_from_java_manifest(hash, &archive, file)
         } else if let Some(file) = archive.by_name("pack.mcmeta") {
             self.load_from_pack_mcmeta(hash, &archive, file)
+        } else if archive.entries().any(|entry| entry.name.starts_with("shaders/")) {
+            self.load_shader_pack(hash, &archive)
         } else if allow_children && let Some(file) = archive.by_name("modrinth.index.json") {
             self.load_modrinth_modpack(hash, &archive, file)
         } else {
@@ -215,6 +275,27 @@ impl ModMetadataManager {
             "".into()
         };
 
+        let mut provides: Vec<ProvidedMod> = fabric_mod_json.provides.iter().map(|id| ProvidedMod {
+            id: id.clone(),
+            version: fabric_mod_json.version.clone(),
+        }).collect();
+        provides.extend(self.load_nested_provides(archive, fabric_mod_json.jars.iter().map(|jar| &*jar.file)));
+
+        let mut dependencies = Vec::new();
+        let declarations = [
+            (&fabric_mod_json.depends, ModDependencyKind::Required),
+            (&fabric_mod_json.recommends, ModDependencyKind::Recommended),
+            (&fabric_mod_json.breaks, ModDependencyKind::Breaks),
+            (&fabric_mod_json.conflicts, ModDependencyKind::Conflicts),
+        ];
+        for (declared, kind) in declarations {
+            dependencies.extend(declared.iter().map(|(id, dependency)| ModDependency {
+                id: id.clone(),
+                kind,
+                versions: dependency.predicates().into(),
+            }));
+        }
+
         Some(Arc::new(ContentSummary {
             id: Some(fabric_mod_json.id),
             hash,
@@ -223,7 +304,12 @@ impl ModMetadataManager {
             version_str: format!("v{}", fabric_mod_json.version).into(),
             png_icon,
             update_status: Arc::new(AtomicContentUpdateStatus::new(ContentUpdateStatus::Unknown)),
-            extra: ContentType::Fabric
+            extra: ContentType::Fabric,
+            mod_info: Some(Arc::new(ModInfo {
+                version: fabric_mod_json.version,
+                provides: provides.into(),
+                dependencies: dependencies.into(),
+            })),
         }))
     }
 
@@ -253,32 +339,84 @@ impl ModMetadataManager {
             "".into()
         };
 
-        let mut version = format!("v{}", first.version.as_deref().unwrap_or("1"));
-        if version.contains("${file.jarVersion}") {
-            if let Some(manifest) = archive.by_name("META-INF/MANIFEST.MF") {
-                if let Ok(manifest_bytes) = manifest.bytes() {
-                    if let Ok(manifest_str) = str::from_utf8(&manifest_bytes) {
-                        let manifest_map = crate::java_manifest::parse_java_manifest(manifest_str);
-                        if let Some(impl_version) = manifest_map.get("Implementation-Version") {
-                            version = version.replace("${file.jarVersion}", impl_version);
-                        }
-                    }
-                }
-            }
+        let mut version = first.version.as_deref().unwrap_or("1").to_string();
+        if version.contains("${file.jarVersion}") && let Some(impl_version) = read_manifest_attribute(archive, "Implementation-Version") {
+            version = version.replace("${file.jarVersion}", &impl_version);
+        }
+
+        let version: Arc<str> = version.into();
+
+        let mut provides: Vec<ProvidedMod> = mods_toml.mods.iter().skip(1).map(|other| ProvidedMod {
+            id: other.mod_id.clone(),
+            version: other.version.clone().filter(|version| !version.contains("${")).unwrap_or_else(|| version.clone()),
+        }).collect();
+        if let Some(jarjar) = archive.by_name("META-INF/jarjar/metadata.json")
+            && let Ok(bytes) = jarjar.bytes()
+            && let Ok(metadata_json) = serde_json::from_slice::<JarJarMetadata>(&bytes)
+        {
+            provides.extend(self.load_nested_provides(archive, metadata_json.jars.iter().map(|jar| &*jar.path)));
         }
 
+        let dependencies: Vec<ModDependency> = mods_toml.dependencies.get(&first.mod_id).into_iter().flatten()
+            .filter(|dependency| !dependency.side.as_deref().is_some_and(|side| side.eq_ignore_ascii_case("server")))
+            .map(|dependency| {
+                let kind = match dependency.r#type.as_deref().map(str::to_ascii_lowercase).as_deref() {
+                    Some("required") => ModDependencyKind::Required,
+                    Some("optional") => ModDependencyKind::Optional,
+                    Some("incompatible") => ModDependencyKind::Breaks,
+                    Some("discouraged") => ModDependencyKind::Conflicts,
+                    _ if dependency.mandatory == Some(false) => ModDependencyKind::Optional,
+                    _ => ModDependencyKind::Required,
+                };
+                ModDependency {
+                    id: dependency.mod_id.clone(),
+                    kind,
+                    versions: dependency.version_range.iter().cloned().collect(),
+                }
+            })
+            .collect();
+
         Some(Arc::new(ContentSummary {
             id: Some(first.mod_id.clone()),
             hash,
             name: Some(name),
             authors,
-            version_str: version.into(),
+            version_str: format!("v{version}").into(),
             png_icon,
             update_status: Arc::new(AtomicContentUpdateStatus::new(ContentUpdateStatus::Unknown)),
             extra,
+            mod_info: Some(Arc::new(ModInfo {
+                version,
+                provides: provides.into(),
+                dependencies: dependencies.into(),
+            })),
         }))
     }
 
+    // Nested jars are loaded like any other mod file, so their summaries are cached by hash as well
+    fn load_nested_provides<'a, R: rc_zip_sync::HasCursor>(self: &Arc<Self>, archive: &rc_zip_sync::ArchiveHandle<R>, paths: impl Iterator<Item = &'a str>) -> Vec<ProvidedMod> {
+        let mut provides = Vec::new();
+        for path in paths {
+            let Some(child) = archive.by_name(path) else {
+                continue;
+            };
+            let Ok(child_bytes) = child.bytes() else {
+                continue;
+            };
+            let Some(child) = self.get_bytes(&child_bytes) else {
+                continue;
+            };
+            if let Some(id) = &child.id && let Some(mod_info) = &child.mod_info {
+                provides.push(ProvidedMod {
+                    id: id.clone(),
+                    version: mod_info.version.clone(),
+                });
+                provides.extend(mod_info.provides.iter().cloned());
+            }
+        }
+        provides
+    }
+
     fn load_modrinth_modpack<R: rc_zip_sync::HasCursor>(self: &Arc<Self>, hash: [u8; 20], archive: &rc_zip_sync::ArchiveHandle<R>, file: EntryHandle<'_, R>) -> Option<Arc<ContentSummary>> {
         let modrinth_index_json: ModrinthIndexJson = serde_json::from_slice(&file.bytes().ok()?).inspect_err(|e| {
             log::error!("Error parsing modrinth.index.json: {e}");
@@ -324,7 +462,7 @@ impl ModMetadataManager {
                 return None;
             };
 
-            if let Some(cached) = self.by_hash.read().get(&file_hash).cloned() {
+            if let Some(cached) = self.get_cached(file_hash) {
                 return cached;
             }
 
@@ -377,7 +515,113 @@ impl ModMetadataManager {
                 downloads: modrinth_index_json.files,
                 summaries: summaries.into(),
                 overrides: overrides.into_iter().collect(),
+            },
+            mod_info: None,
+        }))
+    }
+
+    fn load_mcmod_info<R: rc_zip_sync::HasCursor>(self: &Arc<Self>, hash: [u8; 20], archive: &rc_zip_sync::ArchiveHandle<R>, file: EntryHandle<'_, R>) -> Option<Arc<ContentSummary>> {
+        let mut bytes = file.bytes().ok()?;
+
+        // Like fabric.mod.json, raw newlines inside strings are common
+        for byte in bytes.iter_mut() {
+            if *byte == '\n' as u8 {
+                *byte = ' ' as u8;
             }
+        }
+
+        let mcmod_info: McmodInfo = serde_json::from_slice(&bytes).inspect_err(|e| {
+            log::error!("Error parsing mcmod.info: {e}");
+        }).ok()?;
+
+        drop(file);
+
+        let mut mods = mcmod_info.into_mods().into_iter();
+        let first = mods.next()?;
+
+        let name = first.name.clone().filter(|name| !name.is_empty()).unwrap_or_else(|| Arc::clone(&first.modid));
+
+        let mut png_icon: Option<Arc<[u8]>> = None;
+        if let Some(logo_file) = first.logo_file.as_deref().map(|logo_file| logo_file.trim_start_matches('/'))
+            && !logo_file.is_empty()
+            && let Some(icon_file) = archive.by_name(logo_file)
+        {
+            png_icon = load_icon(icon_file);
+        }
+
+        let authors = create_authors_string(&first.author_list).map(Arc::from).unwrap_or_default();
+
+        // Versions are often left as an unreplaced build placeholder
+        let version: Arc<str> = first.version.clone()
+            .filter(|version| is_substituted_version(version))
+            .or_else(|| read_manifest_attribute(archive, "Implementation-Version").map(Arc::from))
+            .unwrap_or_default();
+
+        let provides: Vec<ProvidedMod> = mods.map(|other| ProvidedMod {
+            id: other.modid,
+            version: other.version.filter(|version| is_substituted_version(version)).unwrap_or_else(|| version.clone()),
+        }).collect();
+
+        // Dependencies declared here are informational only, Forge reads them from the @Mod annotation
+        Some(Arc::new(ContentSummary {
+            id: Some(first.modid),
+            hash,
+            name: Some(name),
+            authors,
+            version_str: if version.is_empty() { "".into() } else { format!("v{version}").into() },
+            png_icon,
+            update_status: Arc::new(AtomicContentUpdateStatus::new(ContentUpdateStatus::Unknown)),
+            extra: ContentType::Forge,
+            mod_info: Some(Arc::new(ModInfo {
+                version,
+                provides: provides.into(),
+                dependencies: [].into(),
+            })),
+        }))
+    }
+
+    fn load_litemod<R: rc_zip_sync::HasCursor>(self: &Arc<Self>, hash: [u8; 20], archive: &rc_zip_sync::ArchiveHandle<R>, file: EntryHandle<'_, R>) -> Option<Arc<ContentSummary>> {
+        let bytes = file.bytes().ok()?;
+
+        let litemod_json: LiteModJson = serde_json::from_slice(&bytes).inspect_err(|e| {
+            log::error!("Error parsing litemod.json: {e}");
+        }).ok()?;
+
+        drop(file);
+
+        let version = litemod_json.version.filter(|version| is_substituted_version(version))
+            .or_else(|| read_manifest_attribute(archive, "Implementation-Version").map(Arc::from));
+
+        Some(Arc::new(ContentSummary {
+            id: Some(litemod_json.name.clone()),
+            hash,
+            name: Some(litemod_json.display_name.unwrap_or(litemod_json.name)),
+            authors: litemod_json.author.map(|author| Arc::from(format!("By {author}"))).unwrap_or_default(),
+            version_str: version.map(|version| Arc::from(format!("v{version}"))).unwrap_or_default(),
+            png_icon: None,
+            update_status: Arc::new(AtomicContentUpdateStatus::new(ContentUpdateStatus::Unknown)),
+            extra: ContentType::JavaModule,
+            mod_info: None,
+        }))
+    }
+
+    fn load_riftmod<R: rc_zip_sync::HasCursor>(self: &Arc<Self>, hash: [u8; 20], file: EntryHandle<'_, R>) -> Option<Arc<ContentSummary>> {
+        let bytes = file.bytes().ok()?;
+
+        let riftmod_json: RiftModJson = serde_json::from_slice(&bytes).inspect_err(|e| {
+            log::error!("Error parsing riftmod.json: {e}");
+        }).ok()?;
+
+        Some(Arc::new(ContentSummary {
+            id: Some(riftmod_json.id.clone()),
+            hash,
+            name: Some(riftmod_json.name.unwrap_or(riftmod_json.id)),
+            authors: create_authors_string(&riftmod_json.authors).map(Arc::from).unwrap_or_default(),
+            version_str: "".into(),
+            png_icon: None,
+            update_status: Arc::new(AtomicContentUpdateStatus::new(ContentUpdateStatus::Unknown)),
+            extra: ContentType::JavaModule,
+            mod_info: None,
         }))
     }
 
@@ -446,42 +690,110 @@ impl ModMetadataManager {
             version_str: version.unwrap_or_default(),
             png_icon: None,
             update_status: Arc::new(AtomicContentUpdateStatus::new(ContentUpdateStatus::Unknown)),
-            extra: ContentType::JavaModule
+            extra: ContentType::JavaModule,
+            mod_info: None,
         }))
     }
 
     fn load_from_pack_mcmeta<R: rc_zip_sync::HasCursor>(self: &Arc<Self>, hash: [u8; 20], archive: &rc_zip_sync::ArchiveHandle<R>, file: EntryHandle<'_, R>) -> Option<Arc<ContentSummary>> {
         let bytes = file.bytes().ok()?;
+        drop(file);
 
-        let pack_mcmeta: PackMcmeta = serde_json::from_slice(&bytes).inspect_err(|e| {
-            log::error!("Error parsing jarjar/metadata.json: {e}");
-        }).ok()?;
+        let png_icon = archive.by_name("pack.png").and_then(load_icon);
+        load_pack_summary(hash, &bytes, png_icon)
+    }
 
-        drop(file);
+    fn load_shader_pack<R: rc_zip_sync::HasCursor>(self: &Arc<Self>, hash: [u8; 20], archive: &rc_zip_sync::ArchiveHandle<R>) -> Option<Arc<ContentSummary>> {
+        // shaders.properties is used by both Iris and OptiFine, packs without it still load but only with defaults
+        let has_properties = archive.by_name("shaders/shaders.properties").is_some();
 
-        let mut png_icon = None;
-        if let Some(icon) = archive.by_name("pack.png") {
-            png_icon = load_icon(icon);
-        }
+        let version_str = if has_properties {
+            "Iris/OptiFine shader pack"
+        } else {
+            "Shader pack"
+        };
 
+        // Named after the file once it's known, see name_shader_pack
         Some(Arc::new(ContentSummary {
             id: None,
             hash,
             name: None,
             authors: "".into(),
-            version_str: pack_mcmeta.pack.description,
-            png_icon,
+            version_str: version_str.into(),
+            png_icon: None,
             update_status: Arc::new(AtomicContentUpdateStatus::new(ContentUpdateStatus::Unknown)),
-            extra: ContentType::ResourcePack
+            extra: ContentType::ShaderPack { has_properties },
+            mod_info: None,
         }))
     }
 }
 
+/// Shader packs contain no metadata besides their shaders, so they're named after their file instead
+pub(crate) fn name_shader_pack(summary: Arc<ContentSummary>, path: &Path) -> Arc<ContentSummary> {
+    if summary.name.is_some() || !matches!(summary.extra, ContentType::ShaderPack { .. }) {
+        return summary;
+    }
+
+    let mut path = path.to_path_buf();
+    if path.extension().is_some_and(|extension| extension == "disabled") {
+        path.set_extension("");
+    }
+    let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) else {
+        return summary;
+    };
+
+    Arc::new(ContentSummary {
+        name: Some(name.into()),
+        ..(*summary).clone()
+    })
+}
+
+fn read_manifest_attribute<R: rc_zip_sync::HasCursor>(archive: &rc_zip_sync::ArchiveHandle<R>, key: &str) -> Option<String> {
+    let manifest = archive.by_name("META-INF/MANIFEST.MF")?;
+    let bytes = manifest.bytes().ok()?;
+    let manifest_str = str::from_utf8(&bytes).ok()?;
+    crate::java_manifest::parse_java_manifest(manifest_str).remove(key)
+}
+
+fn is_substituted_version(version: &str) -> bool {
+    !version.is_empty() && !version.contains("${") && !(version.starts_with('@') && version.ends_with('@'))
+}
+
+fn hash_file(file: &mut std::fs::File) -> Option<[u8; 20]> {
+    let mut hasher = Sha1::new();
+    let _ = std::io::copy(file, &mut hasher).ok()?;
+    Some(hasher.finalize().into())
+}
+
+fn load_pack_summary(hash: [u8; 20], pack_mcmeta: &[u8], png_icon: Option<Arc<[u8]>>) -> Option<Arc<ContentSummary>> {
+    let pack_mcmeta: PackMcmeta = serde_json::from_slice(pack_mcmeta).inspect_err(|e| {
+        log::error!("Error parsing pack.mcmeta: {e}");
+    }).ok()?;
+
+    Some(Arc::new(ContentSummary {
+        id: None,
+        hash,
+        name: None,
+        authors: "".into(),
+        version_str: pack_mcmeta.pack.description.clone(),
+        png_icon,
+        update_status: Arc::new(AtomicContentUpdateStatus::new(ContentUpdateStatus::Unknown)),
+        extra: ContentType::ResourcePack {
+            pack_format: pack_mcmeta.pack.format_range(),
+        },
+        mod_info: None,
+    }))
+}
+
 fn load_icon<R: rc_zip_sync::HasCursor>(icon_file: rc_zip_sync::EntryHandle<R>) -> Option<Arc<[u8]>> {
-    let Ok(mut icon_bytes) = icon_file.bytes() else {
+    let Ok(icon_bytes) = icon_file.bytes() else {
         return None;
     };
 
+    load_icon_bytes(icon_bytes)
+}
+
+fn load_icon_bytes(mut icon_bytes: Vec<u8>) -> Option<Arc<[u8]>> {
     let Ok(image) = image::load_from_memory(&icon_bytes) else {
         return None;
     };
@@ -557,6 +869,9 @@ impl ContentSources {
                     ContentSource::ModrinthProject { project: _ } => {
                         old_source == &value || value == ContentSource::ModrinthUnknown
                     },
+                    ContentSource::Url { .. } | ContentSource::CurseforgeProject { .. } | ContentSource::GithubRelease { .. } => {
+                        old_source == &value || matches!(value, ContentSource::Manual | ContentSource::ModrinthUnknown)
+                    },
                 };
                 if skip {
                     return false;
@@ -619,6 +934,66 @@ impl ContentSources {
                 data.push(project.len() as u8);
                 data.extend_from_slice(project.as_bytes());
             },
+            ContentSource::Url { url } => {
+                Self::write_variable(data, key, 3, url.as_bytes());
+            },
+            ContentSource::CurseforgeProject { project } => {
+                data.push(5_u8);
+                data.push(4_u8);
+                data.extend_from_slice(&project.to_le_bytes());
+            },
+            ContentSource::GithubRelease { owner, repo, tag, asset } => {
+                let joined = [&**owner, &**repo, &**tag, &**asset].join("\n");
+                Self::write_variable(data, key, 4, joined.as_bytes());
+            },
+        }
+    }
+
+    // The size of a record has to fit in a single byte so older launchers can still skip it. Longer origins are split
+    // into chunk records (type 6) followed by an end record (type 7) holding the original type, which older launchers
+    // skip entirely and then treat the content as installed manually
+    fn write_variable(data: &mut Vec<u8>, key: &[u8], source_type: u8, value: &[u8]) {
+        if let Ok(len) = u8::try_from(value.len()) {
+            data.push(source_type);
+            data.push(len);
+            data.extend_from_slice(value);
+            return;
+        }
+
+        let mut chunks = value.chunks(u8::MAX as usize - 1).peekable();
+        let mut first = true;
+        while let Some(chunk) = chunks.next() {
+            // The key of the first record has already been written by the caller
+            if !first {
+                data.extend_from_slice(key);
+            }
+            first = false;
+
+            if chunks.peek().is_some() {
+                data.push(6_u8);
+                data.push(chunk.len() as u8);
+            } else {
+                data.push(7_u8);
+                data.push(chunk.len() as u8 + 1);
+                data.push(source_type);
+            }
+            data.extend_from_slice(chunk);
+        }
+    }
+
+    fn read_variable(source_type: u8, value: &[u8]) -> Option<ContentSource> {
+        let value = str::from_utf8(value).ok()?;
+
+        match source_type {
+            3 => Some(ContentSource::Url { url: value.into() }),
+            4 => {
+                let mut parts = value.split('\n');
+                let (Some(owner), Some(repo), Some(tag), Some(asset)) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+                    return None;
+                };
+                Some(ContentSource::GithubRelease { owner: owner.into(), repo: repo.into(), tag: tag.into(), asset: asset.into() })
+            },
+            _ => None,
         }
     }
 
@@ -678,6 +1053,7 @@ impl ContentSources {
 
             let mut key_buf = [0_u8; 19];
             let mut type_and_size_buf = [0_u8; 2];
+            let mut long_value: Option<([u8; 19], Vec<u8>)> = None;
             loop {
                 if cursor.read_exact(&mut key_buf).is_err() {
                     break;
@@ -708,6 +1084,59 @@ impl ContentSources {
 
                         ContentSource::ModrinthProject { project: project_id.into() }
                     },
+                    3 | 4 => {
+                        let mut value_buf = vec![0_u8; type_and_size_buf[1] as usize];
+
+                        if cursor.read_exact(&mut value_buf).is_err() {
+                            break;
+                        }
+
+                        let Some(source) = Self::read_variable(type_and_size_buf[0], &value_buf) else {
+                            continue;
+                        };
+                        source
+                    },
+                    6 | 7 => {
+                        let mut chunk_buf = vec![0_u8; type_and_size_buf[1] as usize];
+
+                        if cursor.read_exact(&mut chunk_buf).is_err() {
+                            break;
+                        }
+
+                        let mut value = match long_value.take() {
+                            Some((key, value)) if key == key_buf => value,
+                            _ => Vec::new(),
+                        };
+
+                        if type_and_size_buf[0] == 6 {
+                            value.extend_from_slice(&chunk_buf);
+                            long_value = Some((key_buf, value));
+                            continue;
+                        }
+
+                        let Some((&source_type, chunk)) = chunk_buf.split_first() else {
+                            continue;
+                        };
+                        value.extend_from_slice(chunk);
+
+                        let Some(source) = Self::read_variable(source_type, &value) else {
+                            continue;
+                        };
+                        source
+                    },
+                    5 => {
+                        let mut project_buf = [0_u8; 4];
+
+                        if type_and_size_buf[1] != 4 {
+                            cursor.consume(type_and_size_buf[1] as usize);
+                            continue;
+                        }
+                        if cursor.read_exact(&mut project_buf).is_err() {
+                            break;
+                        }
+
+                        ContentSource::CurseforgeProject { project: u32::from_le_bytes(project_buf) }
+                    },
                     _ => {
                         cursor.consume(type_and_size_buf[1] as usize);
                         continue;
@@ -753,3 +1182,75 @@ pub enum LegacyContentSource {
     Manual,
     Modrinth,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufRead, Cursor, Read};
+
+    use schema::content::ContentSource;
+
+    use super::ContentSources;
+
+    fn hash(first_byte: u8, last_byte: u8) -> [u8; 20] {
+        let mut hash = [0_u8; 20];
+        hash[0] = first_byte;
+        hash[19] = last_byte;
+        hash
+    }
+
+    #[test]
+    fn test_content_sources_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let long_url = ContentSource::Url { url: format!("https://example.com/{}.jar", "a".repeat(600)).into() };
+        let long_release = ContentSource::GithubRelease {
+            owner: "owner".into(),
+            repo: "repo".repeat(100).into(),
+            tag: "v1.0".into(),
+            asset: "mod-*.jar".into(),
+        };
+
+        let mut sources = ContentSources::default();
+        sources.set(&hash(7, 1), ContentSource::ModrinthProject { project: "AANobbMI".into() });
+        sources.set(&hash(7, 2), long_url.clone());
+        sources.set(&hash(7, 3), ContentSource::Url { url: "https://example.com/mod.jar".into() });
+        sources.set(&hash(7, 4), long_release.clone());
+        sources.set(&hash(7, 5), ContentSource::CurseforgeProject { project: 238222 });
+        sources.write_all_to_file(dir.path());
+
+        let loaded = ContentSources::load_all(dir.path()).unwrap();
+        assert_eq!(loaded.get(&hash(7, 1)), Some(ContentSource::ModrinthProject { project: "AANobbMI".into() }));
+        assert_eq!(loaded.get(&hash(7, 2)), Some(long_url));
+        assert_eq!(loaded.get(&hash(7, 3)), Some(ContentSource::Url { url: "https://example.com/mod.jar".into() }));
+        assert_eq!(loaded.get(&hash(7, 4)), Some(long_release));
+        assert_eq!(loaded.get(&hash(7, 5)), Some(ContentSource::CurseforgeProject { project: 238222 }));
+    }
+
+    #[test]
+    fn test_content_sources_skippable_by_old_readers() {
+        let mut sources = ContentSources::default();
+        sources.set(&hash(0, 1), ContentSource::Url { url: "a".repeat(1000).into() });
+        sources.set(&hash(0, 2), ContentSource::ModrinthUnknown);
+
+        let mut data = Vec::new();
+        for (key, source) in &sources.by_first_byte[0] {
+            ContentSources::write(&mut data, key, source);
+        }
+
+        // Older launchers only know the key, type and size framing and skip records of unknown types
+        let mut cursor = Cursor::new(data);
+        let mut key_buf = [0_u8; 19];
+        let mut type_and_size_buf = [0_u8; 2];
+        let mut known = Vec::new();
+        while cursor.read_exact(&mut key_buf).is_ok() {
+            cursor.read_exact(&mut type_and_size_buf).unwrap();
+            if type_and_size_buf[0] <= 5 {
+                known.push((key_buf[18], type_and_size_buf[0]));
+            }
+            cursor.consume(type_and_size_buf[1] as usize);
+        }
+
+        assert_eq!(cursor.position() as usize, cursor.get_ref().len());
+        assert_eq!(known, vec![(2, 1)]);
+    }
+}
@@ -0,0 +1,314 @@
+use std::{path::{Path, PathBuf}, sync::Arc, time::SystemTime};
+
+use base64::Engine;
+use bridge::instance::{AtomicContentUpdateStatus, ContentSummary, ContentType, ContentUpdateStatus, ModDependency, ModDependencyKind, ModInfo, ProvidedMod};
+use parking_lot::RwLock;
+use rustc_hash::FxHashMap;
+use schema::resourcepack::PackFormatRange;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+// Increment whenever parsing changes, so that summaries from older versions get parsed again
+const SUMMARY_CACHE_VERSION: u32 = 2;
+
+/// Parsed summaries stored on disk by hash, so content doesn't need to be read again after a restart
+pub struct ModSummaryCache {
+    summaries_dir: PathBuf,
+    path_hashes_json: PathBuf,
+    path_hashes: RwLock<PathHashes>,
+}
+
+#[derive(Default)]
+struct PathHashes {
+    by_path: FxHashMap<PathBuf, PathHashEntry>,
+    dirty: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct PathHashEntry {
+    size: u64,
+    modified: u64,
+    #[serde(with = "hex::serde")]
+    hash: [u8; 20],
+}
+
+impl ModSummaryCache {
+    pub fn load(content_meta_dir: &Path) -> Self {
+        let summaries_dir = content_meta_dir.join("summaries");
+        let path_hashes_json = content_meta_dir.join("path_hashes.json");
+
+        // Entries for files that were changed or removed are dropped
+        let mut path_hashes = PathHashes::default();
+        if let Ok(by_path) = crate::read_json::<FxHashMap<PathBuf, PathHashEntry>>(&path_hashes_json) {
+            let len = by_path.len();
+            path_hashes.by_path = by_path.into_iter().filter(|(path, entry)| {
+                std::fs::metadata(path).ok()
+                    .and_then(|metadata| PathHashEntry::new(&metadata, entry.hash))
+                    .is_some_and(|current| current == *entry)
+            }).collect();
+            path_hashes.dirty = path_hashes.by_path.len() != len;
+        }
+
+        Self {
+            summaries_dir,
+            path_hashes_json,
+            path_hashes: RwLock::new(path_hashes),
+        }
+    }
+
+    pub fn get_hash_for_path(&self, path: &Path, metadata: &std::fs::Metadata) -> Option<[u8; 20]> {
+        let path_hashes = self.path_hashes.read();
+        let entry = path_hashes.by_path.get(path)?;
+        let current = PathHashEntry::new(metadata, entry.hash)?;
+        (current == *entry).then_some(entry.hash)
+    }
+
+    pub fn set_hash_for_path(&self, path: &Path, metadata: &std::fs::Metadata, hash: [u8; 20]) {
+        // Paths are stored as json keys, which need to be valid utf-8
+        if path.to_str().is_none() {
+            return;
+        }
+        let Some(entry) = PathHashEntry::new(metadata, hash) else {
+            return;
+        };
+
+        let mut path_hashes = self.path_hashes.write();
+        if path_hashes.by_path.insert(path.to_path_buf(), entry) != Some(entry) {
+            path_hashes.dirty = true;
+        }
+    }
+
+    pub fn save_path_hashes(&self) {
+        let bytes = {
+            let mut path_hashes = self.path_hashes.write();
+            if !path_hashes.dirty {
+                return;
+            }
+            path_hashes.dirty = false;
+
+            match serde_json::to_vec(&path_hashes.by_path) {
+                Ok(bytes) => bytes,
+                Err(err) => {
+                    log::error!("Unable to serialize path hashes: {err}");
+                    return;
+                },
+            }
+        };
+
+        if let Err(err) = crate::write_safe(&self.path_hashes_json, &bytes) {
+            log::error!("Unable to save path hashes: {err}");
+        }
+    }
+
+    /// Returns None if nothing was cached, Some(None) if the file was cached as not being valid content
+    pub fn get(&self, hash: [u8; 20]) -> Option<Option<Arc<ContentSummary>>> {
+        let bytes = std::fs::read(self.summary_path(hash)).ok()?;
+        let cached: CachedSummaryFile = serde_json::from_slice(&bytes).ok()?;
+        if cached.version != SUMMARY_CACHE_VERSION {
+            return None;
+        }
+        Some(cached.summary.map(|summary| Arc::new(summary.into_summary())))
+    }
+
+    pub fn put(&self, hash: [u8; 20], summary: &Option<Arc<ContentSummary>>) {
+        let summary = match summary {
+            Some(summary) => {
+                let Some(cached) = CachedSummary::from_summary(summary) else {
+                    return;
+                };
+                Some(cached)
+            },
+            None => None,
+        };
+
+        let file = CachedSummaryFile {
+            version: SUMMARY_CACHE_VERSION,
+            summary,
+        };
+
+        let bytes = match serde_json::to_vec(&file) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                log::error!("Unable to serialize content summary: {err}");
+                return;
+            },
+        };
+
+        if let Err(err) = crate::write_safe(&self.summary_path(hash), &bytes) {
+            log::error!("Unable to save content summary: {err}");
+        }
+    }
+
+    fn summary_path(&self, hash: [u8; 20]) -> PathBuf {
+        crate::create_content_library_path(&self.summaries_dir, hash, Some("json"))
+    }
+}
+
+impl PathHashEntry {
+    fn new(metadata: &std::fs::Metadata, hash: [u8; 20]) -> Option<Self> {
+        let modified = metadata.modified().ok()?.duration_since(SystemTime::UNIX_EPOCH).ok()?;
+        Some(Self {
+            size: metadata.len(),
+            modified: modified.as_nanos() as u64,
+            hash,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedSummaryFile {
+    version: u32,
+    summary: Option<CachedSummary>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedSummary {
+    id: Option<Arc<str>>,
+    #[serde(with = "hex::serde")]
+    hash: [u8; 20],
+    name: Option<Arc<str>>,
+    version_str: Arc<str>,
+    authors: Arc<str>,
+    #[serde(serialize_with = "serialize_icon", deserialize_with = "deserialize_icon")]
+    png_icon: Option<Arc<[u8]>>,
+    extra: CachedContentType,
+    mod_info: Option<CachedModInfo>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum CachedContentType {
+    Fabric,
+    Forge,
+    NeoForge,
+    JavaModule,
+    ResourcePack {
+        pack_format: Option<(u32, u32)>,
+    },
+    ShaderPack {
+        has_properties: bool,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedModInfo {
+    version: Arc<str>,
+    provides: Vec<(Arc<str>, Arc<str>)>,
+    dependencies: Vec<CachedModDependency>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CachedModDependency {
+    id: Arc<str>,
+    kind: CachedModDependencyKind,
+    versions: Arc<[Arc<str>]>,
+}
+
+#[derive(Serialize, Deserialize)]
+enum CachedModDependencyKind {
+    Required,
+    Optional,
+    Recommended,
+    Breaks,
+    Conflicts,
+}
+
+impl CachedSummary {
+    // Modpacks aren't cached, their summary depends on which of their files have been downloaded
+    fn from_summary(summary: &ContentSummary) -> Option<Self> {
+        let extra = match &summary.extra {
+            ContentType::Fabric => CachedContentType::Fabric,
+            ContentType::Forge => CachedContentType::Forge,
+            ContentType::NeoForge => CachedContentType::NeoForge,
+            ContentType::JavaModule => CachedContentType::JavaModule,
+            ContentType::ModrinthModpack { .. } => return None,
+            ContentType::ResourcePack { pack_format } => CachedContentType::ResourcePack {
+                pack_format: pack_format.map(|range| (range.min, range.max)),
+            },
+            ContentType::ShaderPack { has_properties } => CachedContentType::ShaderPack {
+                has_properties: *has_properties,
+            },
+        };
+
+        let mod_info = summary.mod_info.as_ref().map(|mod_info| CachedModInfo {
+            version: mod_info.version.clone(),
+            provides: mod_info.provides.iter().map(|provided| (provided.id.clone(), provided.version.clone())).collect(),
+            dependencies: mod_info.dependencies.iter().map(|dependency| CachedModDependency {
+                id: dependency.id.clone(),
+                kind: match dependency.kind {
+                    ModDependencyKind::Required => CachedModDependencyKind::Required,
+                    ModDependencyKind::Optional => CachedModDependencyKind::Optional,
+                    ModDependencyKind::Recommended => CachedModDependencyKind::Recommended,
+                    ModDependencyKind::Breaks => CachedModDependencyKind::Breaks,
+                    ModDependencyKind::Conflicts => CachedModDependencyKind::Conflicts,
+                },
+                versions: dependency.versions.clone(),
+            }).collect(),
+        });
+
+        Some(Self {
+            id: summary.id.clone(),
+            hash: summary.hash,
+            name: summary.name.clone(),
+            version_str: summary.version_str.clone(),
+            authors: summary.authors.clone(),
+            png_icon: summary.png_icon.clone(),
+            extra,
+            mod_info,
+        })
+    }
+
+    fn into_summary(self) -> ContentSummary {
+        let extra = match self.extra {
+            CachedContentType::Fabric => ContentType::Fabric,
+            CachedContentType::Forge => ContentType::Forge,
+            CachedContentType::NeoForge => ContentType::NeoForge,
+            CachedContentType::JavaModule => ContentType::JavaModule,
+            CachedContentType::ResourcePack { pack_format } => ContentType::ResourcePack {
+                pack_format: pack_format.map(|(min, max)| PackFormatRange { min, max }),
+            },
+            CachedContentType::ShaderPack { has_properties } => ContentType::ShaderPack { has_properties },
+        };
+
+        let mod_info = self.mod_info.map(|mod_info| Arc::new(ModInfo {
+            version: mod_info.version,
+            provides: mod_info.provides.into_iter().map(|(id, version)| ProvidedMod { id, version }).collect(),
+            dependencies: mod_info.dependencies.into_iter().map(|dependency| ModDependency {
+                id: dependency.id,
+                kind: match dependency.kind {
+                    CachedModDependencyKind::Required => ModDependencyKind::Required,
+                    CachedModDependencyKind::Optional => ModDependencyKind::Optional,
+                    CachedModDependencyKind::Recommended => ModDependencyKind::Recommended,
+                    CachedModDependencyKind::Breaks => ModDependencyKind::Breaks,
+                    CachedModDependencyKind::Conflicts => ModDependencyKind::Conflicts,
+                },
+                versions: dependency.versions,
+            }).collect(),
+        }));
+
+        ContentSummary {
+            id: self.id,
+            hash: self.hash,
+            name: self.name,
+            version_str: self.version_str,
+            authors: self.authors,
+            png_icon: self.png_icon,
+            update_status: Arc::new(AtomicContentUpdateStatus::new(ContentUpdateStatus::Unknown)),
+            extra,
+            mod_info,
+        }
+    }
+}
+
+fn serialize_icon<S: Serializer>(icon: &Option<Arc<[u8]>>, serializer: S) -> Result<S::Ok, S::Error> {
+    icon.as_ref()
+        .map(|icon| base64::engine::general_purpose::STANDARD.encode(icon))
+        .serialize(serializer)
+}
+
+fn deserialize_icon<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Arc<[u8]>>, D::Error> {
+    let Some(encoded) = Option::<String>::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+    base64::engine::general_purpose::STANDARD.decode(encoded)
+        .map(|icon| Some(icon.into()))
+        .map_err(serde::de::Error::custom)
+}
@@ -0,0 +1,166 @@
+use std::{io::{Read, Write}, path::{Path, PathBuf}, sync::Arc};
+
+use bridge::{
+    modal_action::ModalAction,
+    nbt_file::{NbtCompression, NbtFile},
+};
+
+use crate::BackendState;
+
+#[derive(thiserror::Error, Debug)]
+pub enum NbtFileError {
+    #[error("Invalid NBT data:\n{0}")]
+    InvalidNbt(anyhow::Error),
+    #[error("Failed to perform I/O operation:\n{0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl BackendState {
+    pub async fn read_nbt_file(self, path: Arc<Path>, modal_action: ModalAction, channel: tokio::sync::oneshot::Sender<NbtFile>) {
+        let result = tokio::task::spawn_blocking(move || read_nbt_file(&path)).await.unwrap();
+
+        match result {
+            Ok(file) => {
+                _ = channel.send(file);
+            },
+            Err(err) => {
+                modal_action.set_error_message(format!("Unable to open NBT file:\n{err}").into());
+            },
+        }
+        modal_action.set_finished();
+    }
+
+    pub async fn write_nbt_file(self, path: Arc<Path>, file: NbtFile, backup: bool, modal_action: ModalAction, channel: tokio::sync::oneshot::Sender<()>) {
+        let result = tokio::task::spawn_blocking(move || write_nbt_file(&path, &file, backup)).await.unwrap();
+
+        match result {
+            Ok(()) => {
+                _ = channel.send(());
+                self.send.send_success("NBT file saved");
+            },
+            Err(err) => modal_action.set_error_message(format!("Unable to save NBT file:\n{err}").into()),
+        }
+        modal_action.set_finished();
+    }
+}
+
+fn detect_compression(bytes: &[u8]) -> NbtCompression {
+    match bytes {
+        [0x1f, 0x8b, ..] => NbtCompression::Gzip,
+        // Zlib header: CM = 8 (deflate) and the header checksum must be a multiple of 31
+        [cmf, flg, ..] if (cmf & 0x0F) == 8 && ((u16::from(*cmf) << 8) | u16::from(*flg)) % 31 == 0 => NbtCompression::Zlib,
+        _ => NbtCompression::Uncompressed,
+    }
+}
+
+fn read_nbt_file(path: &Path) -> Result<NbtFile, NbtFileError> {
+    let bytes = std::fs::read(path)?;
+    let compression = detect_compression(&bytes);
+
+    let mut decompressed = Vec::new();
+    let mut data = match compression {
+        NbtCompression::Uncompressed => bytes.as_slice(),
+        NbtCompression::Gzip => {
+            flate2::bufread::GzDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+            decompressed.as_slice()
+        },
+        NbtCompression::Zlib => {
+            flate2::bufread::ZlibDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+            decompressed.as_slice()
+        },
+    };
+
+    let nbt = nbt::decode::read_named(&mut data).map_err(NbtFileError::InvalidNbt)?;
+
+    Ok(NbtFile { nbt, compression })
+}
+
+fn write_nbt_file(path: &Path, file: &NbtFile, backup: bool) -> Result<(), NbtFileError> {
+    let encoded = nbt::encode::write_named(&file.nbt);
+
+    let bytes = match file.compression {
+        NbtCompression::Uncompressed => encoded,
+        NbtCompression::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&encoded)?;
+            encoder.finish()?
+        },
+        NbtCompression::Zlib => {
+            let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&encoded)?;
+            encoder.finish()?
+        },
+    };
+
+    // Only the first save keeps a backup, so that it's still the original file after saving again
+    if backup && path.exists() {
+        let mut backup = PathBuf::from(path);
+        backup.add_extension("bak");
+        std::fs::copy(path, &backup)?;
+    }
+
+    crate::write_safe(path, &bytes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bridge::nbt_file::{NbtCompression, NbtFile};
+    use nbt::{NBT, TAG_INT_ID};
+
+    use super::{read_nbt_file, write_nbt_file};
+
+    fn create_nbt() -> NBT {
+        let mut nbt = NBT::new_named("Data".to_string());
+        let mut root = nbt.as_compound_mut().unwrap();
+        root.insert_string("LevelName", "World".to_string());
+        let mut list = root.create_list("Values", TAG_INT_ID);
+        for value in 0..3 {
+            list.insert_int(value);
+        }
+        nbt
+    }
+
+    fn list_values(nbt: &NBT) -> Vec<i32> {
+        let list = nbt.as_compound().unwrap().find_list("Values", TAG_INT_ID).unwrap();
+        (0..list.len()).map(|index| *list.get_int(index).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_nbt_file_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+
+        for compression in [NbtCompression::Uncompressed, NbtCompression::Gzip, NbtCompression::Zlib] {
+            let path = dir.path().join(format!("{}.dat", compression.name()));
+            let file = NbtFile { nbt: create_nbt(), compression };
+            write_nbt_file(&path, &file, false).unwrap();
+
+            let read = read_nbt_file(&path).unwrap();
+            assert_eq!(read.compression, compression);
+            assert_eq!(read.nbt, file.nbt);
+        }
+    }
+
+    #[test]
+    fn test_nbt_file_list_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("level.dat");
+        write_nbt_file(&path, &NbtFile { nbt: create_nbt(), compression: NbtCompression::Gzip }, false).unwrap();
+
+        let mut file = read_nbt_file(&path).unwrap();
+        let mut root = file.nbt.as_compound_mut().unwrap();
+        let mut list = root.find_list_mut("Values", TAG_INT_ID).unwrap();
+        assert!(list.move_element(2, 0));
+        assert!(list.remove(1));
+        list.insert_int(7);
+        write_nbt_file(&path, &file, true).unwrap();
+
+        let edited = read_nbt_file(&path).unwrap();
+        assert_eq!(edited.compression, NbtCompression::Gzip);
+        assert_eq!(list_values(&edited.nbt), [2, 1, 7]);
+
+        // The backup is the file as it was before saving
+        let backup = read_nbt_file(&path.with_added_extension("bak")).unwrap();
+        assert_eq!(list_values(&backup.nbt), [0, 1, 2]);
+    }
+}
@@ -0,0 +1,70 @@
+use std::sync::Arc;
+
+use bridge::instance::InstanceID;
+
+use crate::{BackendState, syncing::{read_options_txt, write_options_txt}};
+
+pub(crate) const OPTIONS_TXT: &str = "options.txt";
+
+impl BackendState {
+    pub async fn get_game_options(self, id: InstanceID, channel: tokio::sync::oneshot::Sender<Vec<(Arc<str>, Arc<str>)>>) {
+        let Some(dot_minecraft_path) = self.instance_state.read().instances.get(id).map(|instance| instance.dot_minecraft_path.clone()) else {
+            return;
+        };
+
+        let options = tokio::task::spawn_blocking(move || read_options_txt(&dot_minecraft_path.join(OPTIONS_TXT))).await.unwrap();
+        _ = channel.send(options);
+    }
+
+    pub async fn set_game_options(self, id: InstanceID, options: Vec<(Arc<str>, Arc<str>)>) {
+        let dot_minecraft_path = {
+            let instance_state = self.instance_state.read();
+            let Some(instance) = instance_state.instances.get(id) else {
+                return;
+            };
+            if instance.child.is_some() {
+                self.send.send_error("Unable to save options: instance is currently running");
+                return;
+            }
+            instance.dot_minecraft_path.clone()
+        };
+
+        let result = tokio::task::spawn_blocking(move || {
+            std::fs::create_dir_all(&dot_minecraft_path)?;
+            write_options_txt(&dot_minecraft_path.join(OPTIONS_TXT), &options)
+        }).await.unwrap();
+
+        match result {
+            Ok(()) => self.send.send_success("Options saved"),
+            Err(err) => self.send.send_error(format!("Unable to save options: {err}")),
+        }
+    }
+
+    pub async fn apply_game_options_to_all_instances(self, options: Vec<(Arc<str>, Arc<str>)>) {
+        let mut skipped = 0;
+        let mut paths = Vec::new();
+        for instance in self.instance_state.read().instances.iter() {
+            if instance.child.is_some() {
+                skipped += 1;
+            } else {
+                paths.push(instance.dot_minecraft_path.clone());
+            }
+        }
+
+        let result = tokio::task::spawn_blocking(move || -> std::io::Result<usize> {
+            for dot_minecraft_path in &paths {
+                std::fs::create_dir_all(dot_minecraft_path)?;
+                write_options_txt(&dot_minecraft_path.join(OPTIONS_TXT), &options)?;
+            }
+            Ok(paths.len())
+        }).await.unwrap();
+
+        match result {
+            Ok(count) if skipped > 0 => {
+                self.send.send_warning(format!("Options applied to {count} instances, skipped {skipped} running instances"));
+            },
+            Ok(count) => self.send.send_success(format!("Options applied to {count} instances")),
+            Err(err) => self.send.send_error(format!("Unable to apply options: {err}")),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use std::{path::{Path, PathBuf}, sync::Arc};
+
+use bridge::instance::{InstanceID, ResourcePackOrder};
+use rc_zip_sync::ReadZip;
+use schema::resourcepack::{ClientPackVersion, ClientVersionJson};
+use ustr::Ustr;
+
+use crate::{
+    BackendState,
+    options::OPTIONS_TXT,
+    syncing::{read_options_txt, write_options_txt},
+};
+
+impl BackendState {
+    pub(crate) fn client_jar_path(&self, minecraft_version: Ustr) -> PathBuf {
+        self.directories.libraries_dir.join(format!("net/minecraft/{0}/minecraft-client-{0}.jar", minecraft_version))
+    }
+
+    pub async fn get_resource_pack_order(self, id: InstanceID, channel: tokio::sync::oneshot::Sender<ResourcePackOrder>) {
+        if let Some(order) = self.read_resource_pack_order(id).await {
+            _ = channel.send(order);
+        }
+    }
+
+    async fn read_resource_pack_order(&self, id: InstanceID) -> Option<ResourcePackOrder> {
+        let (dot_minecraft_path, minecraft_version) = self.instance_state.write().instances.get_mut(id).map(|instance| {
+            (instance.dot_minecraft_path.clone(), instance.configuration.get().minecraft_version)
+        })?;
+        let client_jar = self.client_jar_path(minecraft_version);
+
+        let order = tokio::task::spawn_blocking(move || {
+            let options = read_options_txt(&dot_minecraft_path.join(OPTIONS_TXT));
+            let read_list = |key: &str| -> Vec<Arc<str>> {
+                options.iter()
+                    .find(|(option, _)| &**option == key)
+                    .and_then(|(_, value)| serde_json::from_str(value).ok())
+                    .unwrap_or_default()
+            };
+
+            ResourcePackOrder {
+                pack_format: read_client_pack_version(&client_jar).and_then(|pack_version| pack_version.resource()),
+                enabled: read_list("resourcePacks"),
+                incompatible: read_list("incompatibleResourcePacks"),
+            }
+        }).await.unwrap();
+
+        Some(order)
+    }
+
+    pub async fn set_resource_pack_order(
+        self,
+        id: InstanceID,
+        enabled: Vec<Arc<str>>,
+        incompatible: Vec<Arc<str>>,
+        channel: tokio::sync::oneshot::Sender<ResourcePackOrder>,
+    ) {
+        self.write_resource_pack_order(id, enabled, incompatible).await;
+
+        // Reply with what is actually on disk, so the frontend doesn't show an order that wasn't saved
+        if let Some(order) = self.read_resource_pack_order(id).await {
+            _ = channel.send(order);
+        }
+    }
+
+    async fn write_resource_pack_order(&self, id: InstanceID, enabled: Vec<Arc<str>>, incompatible: Vec<Arc<str>>) {
+        let dot_minecraft_path = {
+            let instance_state = self.instance_state.read();
+            let Some(instance) = instance_state.instances.get(id) else {
+                return;
+            };
+            // The game rewrites options.txt when it closes, so changes made now would be lost
+            if instance.child.is_some() {
+                self.send.send_error("Unable to change resource pack order: instance is currently running");
+                return;
+            }
+            instance.dot_minecraft_path.clone()
+        };
+
+        let options: Vec<(Arc<str>, Arc<str>)> = vec![
+            ("resourcePacks".into(), serde_json::to_string(&enabled).unwrap().into()),
+            ("incompatibleResourcePacks".into(), serde_json::to_string(&incompatible).unwrap().into()),
+        ];
+
+        let result = tokio::task::spawn_blocking(move || {
+            std::fs::create_dir_all(&dot_minecraft_path)?;
+            write_options_txt(&dot_minecraft_path.join(OPTIONS_TXT), &options)
+        }).await.unwrap();
+
+        if let Err(err) = result {
+            self.send.send_error(format!("Unable to change resource pack order: {err}"));
+        }
+    }
+}
+
+// version.json inside the client jar contains the pack formats and data version of the version, since 1.14
+fn read_client_version_json(client_jar: &Path) -> Option<ClientVersionJson> {
+    let file = std::fs::File::open(client_jar).ok()?;
+    let archive = file.read_zip().ok()?;
+    let bytes = archive.by_name("version.json")?.bytes().ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+pub(crate) fn read_client_pack_version(client_jar: &Path) -> Option<ClientPackVersion> {
+    read_client_version_json(client_jar)?.pack_version
+}
+
+pub(crate) fn read_client_data_version(client_jar: &Path) -> Option<i32> {
+    read_client_version_json(client_jar)?.world_version
+}
@@ -0,0 +1,222 @@
+use std::{path::{Path, PathBuf}, sync::Arc, time::UNIX_EPOCH};
+
+use bridge::{
+    instance::{InstanceID, InstanceScreenshotSummary},
+    message::MessageToFrontend,
+};
+use image::imageops::FilterType;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+use rustc_hash::FxHashSet;
+use sha1::{Digest, Sha1};
+
+use crate::BackendState;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ScreenshotError {
+    #[error("Unknown instance")]
+    UnknownInstance,
+    #[error("Invalid screenshot: {0}")]
+    InvalidScreenshot(Arc<str>),
+    #[error("Failed to perform I/O operation:\n{0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Failed to move to trash:\n{0}")]
+    TrashError(#[from] trash::Error),
+}
+
+pub(crate) const SCREENSHOTS_FOLDER: &str = "screenshots";
+const THUMBNAIL_SIZE: u32 = 256;
+
+impl BackendState {
+    fn screenshot_path(&self, id: InstanceID, screenshot: &Arc<str>) -> Result<PathBuf, ScreenshotError> {
+        let Some(dot_minecraft_path) = self.instance_state.read().instances.get(id).map(|instance| instance.dot_minecraft_path.clone()) else {
+            return Err(ScreenshotError::UnknownInstance);
+        };
+        if !crate::is_single_component_path(screenshot) {
+            return Err(ScreenshotError::InvalidScreenshot(screenshot.clone()));
+        }
+        Ok(dot_minecraft_path.join(SCREENSHOTS_FOLDER).join(&**screenshot))
+    }
+
+    pub async fn load_screenshots(self, id: InstanceID) {
+        let thumbnails_dir = self.directories.screenshot_thumbnails_dir.clone();
+        let (dot_minecraft_path, all_screenshots_paths) = {
+            let instance_state = self.instance_state.read();
+            let Some(instance) = instance_state.instances.get(id) else {
+                return;
+            };
+            let all_screenshots_paths: Vec<PathBuf> = instance_state.instances.iter()
+                .map(|instance| instance.dot_minecraft_path.join(SCREENSHOTS_FOLDER))
+                .collect();
+            (instance.dot_minecraft_path.clone(), all_screenshots_paths)
+        };
+
+        let screenshots = tokio::task::spawn_blocking(move || {
+            let live_thumbnail_dirs: FxHashSet<PathBuf> = all_screenshots_paths.iter()
+                .map(|screenshots_path| instance_thumbnails_dir(&thumbnails_dir, &canonical_path(screenshots_path)))
+                .collect();
+            prune_thumbnail_dirs(&thumbnails_dir, &live_thumbnail_dirs);
+            list_screenshots(&dot_minecraft_path.join(SCREENSHOTS_FOLDER), &thumbnails_dir)
+        }).await.unwrap_or_default();
+
+        self.send.send(MessageToFrontend::InstanceScreenshotsUpdated {
+            id,
+            screenshots: screenshots.into(),
+        });
+    }
+
+    pub async fn delete_screenshot(self, id: InstanceID, screenshot: Arc<str>) {
+        let thumbnails_dir = self.directories.screenshot_thumbnails_dir.clone();
+        let result = match self.screenshot_path(id, &screenshot) {
+            Ok(path) => tokio::task::spawn_blocking(move || -> Result<(), ScreenshotError> {
+                let thumbnail_path = std::fs::metadata(&path).ok().map(|metadata| thumbnail_path(&thumbnails_dir, &canonical_path(&path), &metadata));
+                trash::delete(&path)?;
+                if let Some(thumbnail_path) = thumbnail_path {
+                    _ = std::fs::remove_file(thumbnail_path);
+                }
+                Ok(())
+            }).await.unwrap(),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(()) => self.send.send_success("Screenshot moved to trash"),
+            Err(err) => self.send.send_error(format!("Unable to delete screenshot: {err}")),
+        }
+
+        self.load_screenshots(id).await;
+    }
+
+    pub async fn export_screenshot(self, id: InstanceID, screenshot: Arc<str>, target: PathBuf) {
+        let result = match self.screenshot_path(id, &screenshot) {
+            Ok(path) => tokio::task::spawn_blocking(move || -> Result<(), ScreenshotError> {
+                std::fs::copy(&path, &target)?;
+                Ok(())
+            }).await.unwrap(),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(()) => self.send.send_success("Screenshot exported"),
+            Err(err) => self.send.send_error(format!("Unable to export screenshot: {err}")),
+        }
+    }
+}
+
+fn list_screenshots(screenshots_path: &Path, thumbnails_dir: &Path) -> Vec<InstanceScreenshotSummary> {
+    let Ok(read_dir) = std::fs::read_dir(screenshots_path) else {
+        return Vec::new();
+    };
+
+    // Synced instances share the same screenshots folder, so the canonical path identifies a screenshot
+    let canonical_screenshots_path = canonical_path(screenshots_path);
+
+    let entries: Vec<_> = read_dir.flatten().filter_map(|entry| {
+        let path = entry.path();
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        if extension != "png" && extension != "jpg" && extension != "jpeg" {
+            return None;
+        }
+        let metadata = entry.metadata().ok().filter(std::fs::Metadata::is_file)?;
+        Some((path, metadata))
+    }).collect();
+
+    // Decoding full size screenshots is slow, so thumbnails are generated in parallel and cached on disk
+    let mut screenshots: Vec<(InstanceScreenshotSummary, PathBuf)> = entries.into_par_iter().filter_map(|(path, metadata)| {
+        let filename: Arc<str> = path.file_name()?.to_str()?.into();
+        let created = metadata.modified().ok()
+            .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or(0);
+        let canonical_path = canonical_screenshots_path.join(&*filename);
+        let thumbnail_path = thumbnail_path(thumbnails_dir, &canonical_path, &metadata);
+        let thumbnail = load_thumbnail(&thumbnail_path, &path);
+
+        Some((InstanceScreenshotSummary {
+            filename,
+            path: path.as_path().into(),
+            canonical_path: canonical_path.into(),
+            created,
+            size: metadata.len(),
+            thumbnail,
+        }, thumbnail_path))
+    }).collect();
+
+    screenshots.sort_by_key(|(screenshot, _)| -screenshot.created);
+
+    // Thumbnails of screenshots that were deleted or changed outside the launcher are no longer needed
+    let live_thumbnails: FxHashSet<PathBuf> = screenshots.iter().map(|(_, thumbnail_path)| thumbnail_path.clone()).collect();
+    if let Ok(read_dir) = std::fs::read_dir(instance_thumbnails_dir(thumbnails_dir, &canonical_screenshots_path)) {
+        for entry in read_dir.flatten() {
+            let path = entry.path();
+            if !live_thumbnails.contains(&path) {
+                _ = std::fs::remove_file(path);
+            }
+        }
+    }
+
+    screenshots.into_iter().map(|(screenshot, _)| screenshot).collect()
+}
+
+pub(crate) fn canonical_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+// Thumbnails are grouped by canonical screenshots folder so the cache of a deleted or moved instance can be removed
+// as a whole, while synced instances share one cache
+pub(crate) fn instance_thumbnails_dir(thumbnails_dir: &Path, screenshots_path: &Path) -> PathBuf {
+    let mut hasher = Sha1::new();
+    hasher.update(screenshots_path.as_os_str().as_encoded_bytes());
+    thumbnails_dir.join(hex::encode(hasher.finalize()))
+}
+
+fn prune_thumbnail_dirs(thumbnails_dir: &Path, live_thumbnail_dirs: &FxHashSet<PathBuf>) {
+    let Ok(read_dir) = std::fs::read_dir(thumbnails_dir) else {
+        return;
+    };
+
+    for entry in read_dir.flatten() {
+        let path = entry.path();
+        if live_thumbnail_dirs.contains(&path) {
+            continue;
+        }
+        if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            _ = std::fs::remove_dir_all(path);
+        } else {
+            _ = std::fs::remove_file(path);
+        }
+    }
+}
+
+fn thumbnail_path(thumbnails_dir: &Path, canonical_path: &Path, metadata: &std::fs::Metadata) -> PathBuf {
+    let modified = metadata.modified().ok()
+        .and_then(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_nanos())
+        .unwrap_or(0);
+
+    let mut hasher = Sha1::new();
+    hasher.update(canonical_path.as_os_str().as_encoded_bytes());
+    hasher.update(modified.to_le_bytes());
+    hasher.update(metadata.len().to_le_bytes());
+    let screenshots_path = canonical_path.parent().unwrap_or(canonical_path);
+    instance_thumbnails_dir(thumbnails_dir, screenshots_path).join(format!("{}.png", hex::encode(hasher.finalize())))
+}
+
+fn load_thumbnail(thumbnail_path: &Path, path: &Path) -> Option<Arc<[u8]>> {
+    if let Ok(bytes) = std::fs::read(thumbnail_path) {
+        return Some(bytes.into());
+    }
+
+    let image = image::open(path).ok()?;
+    let thumbnail = image.resize(THUMBNAIL_SIZE, THUMBNAIL_SIZE, FilterType::Triangle);
+
+    let mut bytes = Vec::new();
+    thumbnail.write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png).ok()?;
+
+    if let Some(parent) = thumbnail_path.parent()
+        && std::fs::create_dir_all(parent).is_ok()
+    {
+        _ = crate::write_safe(thumbnail_path, &bytes);
+    }
+
+    Some(bytes.into())
+}
@@ -0,0 +1,491 @@
+use std::{
+    net::Ipv6Addr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use base64::Engine;
+use bridge::instance::ServerStatus;
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+use crate::BackendState;
+
+const DEFAULT_PORT: u16 = 25565;
+const CACHE_TTL: Duration = Duration::from_secs(60);
+const TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_PACKET_LENGTH: usize = 2 * 1024 * 1024;
+
+// Latest servers answer regardless of the protocol version, older ones ignore it
+const STATUS_PROTOCOL_VERSION: i32 = -1;
+const LEGACY_PROTOCOL_VERSION: u8 = 74;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ServerPingError {
+    #[error("Invalid server address")]
+    InvalidAddress,
+    #[error("Timed out")]
+    Timeout,
+    #[error("Invalid response: {0}")]
+    InvalidResponse(&'static str),
+    #[error("Invalid status json: {0}")]
+    InvalidJson(#[from] serde_json::Error),
+    #[error("Failed to perform I/O operation: {0}")]
+    IoError(#[from] std::io::Error),
+}
+
+pub struct ServerStatusCacheEntry {
+    time: Instant,
+    status: Option<ServerStatus>,
+}
+
+impl BackendState {
+    pub async fn ping_server(self, address: Arc<str>, refresh: bool, channel: tokio::sync::oneshot::Sender<Option<ServerStatus>>) {
+        if !refresh && let Some(entry) = self.server_status_cache.read().get(&address) && entry.time.elapsed() < CACHE_TTL {
+            _ = channel.send(entry.status.clone());
+            return;
+        }
+
+        let status = match ping(&address).await {
+            Ok(status) => Some(status),
+            Err(error) => {
+                log::debug!("Unable to ping server {address}: {error}");
+                None
+            },
+        };
+
+        // Expired entries are dropped here, otherwise servers that are no longer listed stay cached forever
+        let mut server_status_cache = self.server_status_cache.write();
+        server_status_cache.retain(|_, entry| entry.time.elapsed() < CACHE_TTL);
+        server_status_cache.insert(address, ServerStatusCacheEntry {
+            time: Instant::now(),
+            status: status.clone(),
+        });
+        drop(server_status_cache);
+        _ = channel.send(status);
+    }
+}
+
+async fn ping(address: &str) -> Result<ServerStatus, ServerPingError> {
+    ping_with_timeout(address, TIMEOUT).await
+}
+
+async fn ping_with_timeout(address: &str, timeout: Duration) -> Result<ServerStatus, ServerPingError> {
+    let (host, port) = parse_address(address).ok_or(ServerPingError::InvalidAddress)?;
+
+    let (connect_host, connect_port) = match port {
+        Some(port) => (host.clone(), port),
+        None => resolve_srv(&host, timeout).await.unwrap_or_else(|| (host.clone(), DEFAULT_PORT)),
+    };
+    let port = port.unwrap_or(DEFAULT_PORT);
+
+    // Old servers may not answer the modern handshake at all, so a timeout also falls back to the legacy ping
+    let stream = connect(&connect_host, connect_port, timeout).await?;
+    match tokio::time::timeout(timeout, ping_modern(stream, &host, port)).await {
+        Ok(Ok(status)) => return Ok(status),
+        Ok(Err(error)) => log::debug!("Modern ping of {address} failed, trying legacy ping: {error}"),
+        Err(_) => log::debug!("Modern ping of {address} timed out, trying legacy ping"),
+    }
+
+    let stream = connect(&connect_host, connect_port, timeout).await?;
+    match tokio::time::timeout(timeout, ping_legacy(stream, &host, port)).await {
+        Ok(result) => result,
+        Err(_) => Err(ServerPingError::Timeout),
+    }
+}
+
+async fn connect(host: &str, port: u16, timeout: Duration) -> Result<TcpStream, ServerPingError> {
+    match tokio::time::timeout(timeout, TcpStream::connect((host, port))).await {
+        Ok(stream) => Ok(stream?),
+        Err(_) => Err(ServerPingError::Timeout),
+    }
+}
+
+fn parse_address(address: &str) -> Option<(String, Option<u16>)> {
+    let address = address.trim();
+    if address.is_empty() {
+        return None;
+    }
+
+    if let Some(rest) = address.strip_prefix('[') {
+        let (host, rest) = rest.split_once(']')?;
+        let port = if rest.is_empty() {
+            None
+        } else {
+            Some(rest.strip_prefix(':')?.parse().ok()?)
+        };
+        return Some((host.to_owned(), port));
+    }
+
+    if address.parse::<Ipv6Addr>().is_ok() {
+        return Some((address.to_owned(), None));
+    }
+
+    match address.rsplit_once(':') {
+        Some((host, port)) if !host.is_empty() => Some((host.to_owned(), Some(port.parse().ok()?))),
+        Some(_) => None,
+        None => Some((address.to_owned(), None)),
+    }
+}
+
+async fn resolve_srv(host: &str, timeout: Duration) -> Option<(String, u16)> {
+    if host.parse::<std::net::IpAddr>().is_ok() {
+        return None;
+    }
+
+    let resolver = hickory_resolver::TokioAsyncResolver::tokio_from_system_conf().ok()?;
+    let lookup = tokio::time::timeout(timeout, resolver.srv_lookup(format!("_minecraft._tcp.{host}."))).await.ok()?.ok()?;
+
+    let record = lookup.iter().min_by_key(|record| (record.priority(), std::cmp::Reverse(record.weight())))?;
+    let target = record.target().to_utf8();
+    let target = target.trim_end_matches('.');
+    if target.is_empty() {
+        return None;
+    }
+
+    Some((target.to_owned(), record.port()))
+}
+
+#[derive(Deserialize)]
+struct StatusResponse {
+    version: Option<StatusVersion>,
+    players: Option<StatusPlayers>,
+    #[serde(default)]
+    description: serde_json::Value,
+    favicon: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StatusVersion {
+    name: String,
+    protocol: i32,
+}
+
+#[derive(Deserialize)]
+struct StatusPlayers {
+    max: i32,
+    online: i32,
+}
+
+async fn ping_modern(mut stream: TcpStream, host: &str, port: u16) -> Result<ServerStatus, ServerPingError> {
+    let mut handshake = Vec::new();
+    write_varint(&mut handshake, STATUS_PROTOCOL_VERSION);
+    write_string(&mut handshake, host);
+    handshake.extend_from_slice(&port.to_be_bytes());
+    write_varint(&mut handshake, 1);
+
+    let mut request = Vec::new();
+    write_packet(&mut request, 0x00, &handshake);
+    write_packet(&mut request, 0x00, &[]);
+
+    let start = Instant::now();
+    stream.write_all(&request).await?;
+
+    let (packet_id, data) = read_packet(&mut stream).await?;
+    let mut latency = start.elapsed();
+    if packet_id != 0x00 {
+        return Err(ServerPingError::InvalidResponse("expected status response"));
+    }
+
+    let mut data = data.as_slice();
+    let json_length = read_varint_from_slice(&mut data)?;
+    let json = usize::try_from(json_length).ok().and_then(|length| data.get(..length))
+        .ok_or(ServerPingError::InvalidResponse("status json length out of bounds"))?;
+    let response: StatusResponse = serde_json::from_slice(json)?;
+
+    // Prefer the ping round trip over the status response, which can include a large favicon
+    let payload = rand::random::<i64>();
+    let mut ping = Vec::new();
+    write_packet(&mut ping, 0x01, &payload.to_be_bytes());
+    let start = Instant::now();
+    if stream.write_all(&ping).await.is_ok()
+        && let Ok((0x01, pong)) = read_packet(&mut stream).await
+        && pong == payload.to_be_bytes()
+    {
+        latency = start.elapsed();
+    }
+
+    let mut motd = String::new();
+    append_chat_component(&response.description, &mut motd);
+
+    let favicon = response.favicon.as_deref().and_then(|favicon| {
+        let favicon = favicon.strip_prefix("data:image/png;base64,")?;
+        let favicon: String = favicon.chars().filter(|c| !c.is_whitespace()).collect();
+        base64::engine::general_purpose::STANDARD.decode(favicon).ok()
+    });
+
+    let (version, protocol) = response.version.map(|version| (version.name, version.protocol)).unwrap_or_default();
+    let (players_online, players_max) = response.players.map(|players| (players.online, players.max)).unwrap_or_default();
+
+    Ok(ServerStatus {
+        latency_ms: latency.as_millis().min(u32::MAX as u128) as u32,
+        motd: strip_formatting(&motd).into(),
+        version: strip_formatting(&version).into(),
+        protocol,
+        players_online,
+        players_max,
+        favicon: favicon.map(Arc::from),
+    })
+}
+
+async fn ping_legacy(mut stream: TcpStream, host: &str, port: u16) -> Result<ServerStatus, ServerPingError> {
+    let host_utf16: Vec<u16> = host.encode_utf16().collect();
+
+    let mut request = vec![0xFE, 0x01, 0xFA];
+    write_utf16_string(&mut request, &"MC|PingHost".encode_utf16().collect::<Vec<_>>());
+    request.extend_from_slice(&(7 + host_utf16.len() as u16 * 2).to_be_bytes());
+    request.push(LEGACY_PROTOCOL_VERSION);
+    write_utf16_string(&mut request, &host_utf16);
+    request.extend_from_slice(&(port as i32).to_be_bytes());
+
+    let start = Instant::now();
+    stream.write_all(&request).await?;
+
+    if stream.read_u8().await? != 0xFF {
+        return Err(ServerPingError::InvalidResponse("expected kick packet"));
+    }
+    let latency = start.elapsed();
+
+    let length = stream.read_u16().await? as usize;
+    let mut data = vec![0_u8; length * 2];
+    stream.read_exact(&mut data).await?;
+    let response = String::from_utf16_lossy(&data.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect::<Vec<_>>());
+
+    let (protocol, version, motd, online, max) = if let Some(response) = response.strip_prefix("\u{a7}1\0") {
+        // 1.4 - 1.6: §1\0protocol\0version\0motd\0online\0max
+        let mut parts = response.split('\0');
+        let protocol = parts.next().and_then(|part| part.parse().ok()).unwrap_or(0);
+        let version = parts.next().unwrap_or_default();
+        let motd = parts.next().unwrap_or_default();
+        (protocol, version, motd, parts.next(), parts.next())
+    } else {
+        // Beta 1.8 - 1.3: motd§online§max
+        let mut parts = response.rsplitn(3, '\u{a7}');
+        let max = parts.next();
+        let online = parts.next();
+        let motd = parts.next().unwrap_or_default();
+        (0, "", motd, online, max)
+    };
+
+    Ok(ServerStatus {
+        latency_ms: latency.as_millis().min(u32::MAX as u128) as u32,
+        motd: strip_formatting(motd).into(),
+        version: strip_formatting(version).into(),
+        protocol,
+        players_online: online.and_then(|online| online.parse().ok()).unwrap_or(0),
+        players_max: max.and_then(|max| max.parse().ok()).unwrap_or(0),
+        favicon: None,
+    })
+}
+
+fn append_chat_component(component: &serde_json::Value, output: &mut String) {
+    match component {
+        serde_json::Value::String(text) => output.push_str(text),
+        serde_json::Value::Array(components) => {
+            for component in components {
+                append_chat_component(component, output);
+            }
+        },
+        serde_json::Value::Object(object) => {
+            if let Some(serde_json::Value::String(text)) = object.get("text") {
+                output.push_str(text);
+            } else if let Some(serde_json::Value::String(translate)) = object.get("translate") {
+                output.push_str(translate);
+            }
+            if let Some(extra) = object.get("extra") {
+                append_chat_component(extra, output);
+            }
+        },
+        _ => {},
+    }
+}
+
+fn strip_formatting(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\u{a7}' {
+            chars.next();
+        } else {
+            stripped.push(c);
+        }
+    }
+    stripped
+}
+
+fn write_varint(buf: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7F == 0 {
+            buf.push(value as u8);
+            return;
+        }
+        buf.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+}
+
+fn write_string(buf: &mut Vec<u8>, value: &str) {
+    write_varint(buf, value.len() as i32);
+    buf.extend_from_slice(value.as_bytes());
+}
+
+fn write_utf16_string(buf: &mut Vec<u8>, value: &[u16]) {
+    buf.extend_from_slice(&(value.len() as u16).to_be_bytes());
+    for c in value {
+        buf.extend_from_slice(&c.to_be_bytes());
+    }
+}
+
+fn write_packet(buf: &mut Vec<u8>, packet_id: i32, data: &[u8]) {
+    let mut body = Vec::with_capacity(data.len() + 1);
+    write_varint(&mut body, packet_id);
+    body.extend_from_slice(data);
+
+    write_varint(buf, body.len() as i32);
+    buf.extend_from_slice(&body);
+}
+
+async fn read_packet(stream: &mut (impl AsyncRead + Unpin)) -> Result<(i32, Vec<u8>), ServerPingError> {
+    let length = read_varint(stream).await?;
+    let length = usize::try_from(length).ok().filter(|length| *length > 0 && *length <= MAX_PACKET_LENGTH)
+        .ok_or(ServerPingError::InvalidResponse("packet length out of bounds"))?;
+
+    let mut data = vec![0_u8; length];
+    stream.read_exact(&mut data).await?;
+
+    let mut slice = data.as_slice();
+    let packet_id = read_varint_from_slice(&mut slice)?;
+    let header_length = length - slice.len();
+    data.drain(..header_length);
+
+    Ok((packet_id, data))
+}
+
+async fn read_varint(stream: &mut (impl AsyncRead + Unpin)) -> Result<i32, ServerPingError> {
+    let mut value = 0_u32;
+    for i in 0..5 {
+        let byte = stream.read_u8().await?;
+        value |= ((byte & 0x7F) as u32) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(ServerPingError::InvalidResponse("VarInt too long"))
+}
+
+fn read_varint_from_slice(data: &mut &[u8]) -> Result<i32, ServerPingError> {
+    let mut value = 0_u32;
+    for i in 0..5 {
+        let Some((&byte, rest)) = data.split_first() else {
+            return Err(ServerPingError::InvalidResponse("unexpected end of packet"));
+        };
+        *data = rest;
+        value |= ((byte & 0x7F) as u32) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(ServerPingError::InvalidResponse("VarInt too long"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::{ping, ping_with_timeout, read_packet, write_packet, write_string};
+
+    #[tokio::test]
+    async fn test_modern_ping() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+
+            let (packet_id, mut handshake) = read_packet(&mut stream).await.unwrap();
+            assert_eq!(packet_id, 0x00);
+            assert_eq!(handshake.pop(), Some(1), "handshake should request the status state");
+
+            let (packet_id, request) = read_packet(&mut stream).await.unwrap();
+            assert_eq!(packet_id, 0x00);
+            assert!(request.is_empty());
+
+            let mut response = Vec::new();
+            write_string(&mut response, r#"{
+                "version": {"name": "1.21.4", "protocol": 769},
+                "players": {"max": 20, "online": 3},
+                "description": {"text": "A ", "extra": [{"text": "§aMinecraft"}, " Server"]}
+            }"#);
+            let mut packet = Vec::new();
+            write_packet(&mut packet, 0x00, &response);
+            stream.write_all(&packet).await.unwrap();
+
+            let (packet_id, payload) = read_packet(&mut stream).await.unwrap();
+            assert_eq!(packet_id, 0x01);
+            let mut packet = Vec::new();
+            write_packet(&mut packet, 0x01, &payload);
+            stream.write_all(&packet).await.unwrap();
+        });
+
+        let status = ping(&format!("127.0.0.1:{port}")).await.unwrap();
+        server.await.unwrap();
+
+        assert_eq!(&*status.motd, "A Minecraft Server");
+        assert_eq!(&*status.version, "1.21.4");
+        assert_eq!(status.protocol, 769);
+        assert_eq!(status.players_online, 3);
+        assert_eq!(status.players_max, 20);
+        assert!(status.favicon.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_legacy_ping() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let server = tokio::spawn(async move {
+            // Like an old server, the modern handshake is never answered
+            let (unanswered, _) = listener.accept().await.unwrap();
+
+            let (mut stream, _) = listener.accept().await.unwrap();
+            assert_eq!(stream.read_u8().await.unwrap(), 0xFE);
+            assert_eq!(stream.read_u8().await.unwrap(), 0x01);
+            assert_eq!(stream.read_u8().await.unwrap(), 0xFA);
+            let channel_length = stream.read_u16().await.unwrap() as usize;
+            let mut channel = vec![0_u8; channel_length * 2];
+            stream.read_exact(&mut channel).await.unwrap();
+            let data_length = stream.read_u16().await.unwrap() as usize;
+            let mut data = vec![0_u8; data_length];
+            stream.read_exact(&mut data).await.unwrap();
+
+            let response: Vec<u16> = "\u{a7}1\u{0}78\u{0}1.6.4\u{0}A \u{a7}cLegacy\u{a7}r Server\u{0}5\u{0}20".encode_utf16().collect();
+            let mut packet = vec![0xFF];
+            packet.extend_from_slice(&(response.len() as u16).to_be_bytes());
+            for c in response {
+                packet.extend_from_slice(&c.to_be_bytes());
+            }
+            stream.write_all(&packet).await.unwrap();
+
+            drop(unanswered);
+        });
+
+        // A short timeout so the test doesn't wait for the unanswered modern handshake as long as the launcher would
+        let status = ping_with_timeout(&format!("127.0.0.1:{port}"), Duration::from_millis(200)).await.unwrap();
+        server.await.unwrap();
+
+        assert_eq!(&*status.motd, "A Legacy Server");
+        assert_eq!(&*status.version, "1.6.4");
+        assert_eq!(status.protocol, 78);
+        assert_eq!(status.players_online, 5);
+        assert_eq!(status.players_max, 20);
+    }
+}
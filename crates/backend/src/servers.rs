@@ -0,0 +1,198 @@
+use std::{path::Path, sync::Arc};
+
+use bridge::instance::{InstanceID, ServerListEdit};
+use nbt::{ListRefMut, NBT, NBTRefMut};
+use schema::backend_config::SyncTarget;
+
+use crate::BackendState;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ServerListError {
+    #[error("Unknown instance")]
+    UnknownInstance,
+    #[error("Unknown server")]
+    UnknownServer,
+    #[error("Instance is currently running")]
+    InstanceRunning,
+    #[error("Unable to read servers.dat:\n{0}")]
+    InvalidServersDat(anyhow::Error),
+    #[error("Failed to perform I/O operation:\n{0}")]
+    IoError(#[from] std::io::Error),
+}
+
+impl BackendState {
+    pub async fn edit_server_list(self, id: InstanceID, edit: ServerListEdit) {
+        let synced_servers = self.config.write().get().sync_targets.contains(SyncTarget::Servers);
+
+        let (path, synced) = {
+            let instance_state = self.instance_state.read();
+            let Some(instance) = instance_state.instances.get(id) else {
+                self.send.send_error(format!("Unable to edit server list:\n{}", ServerListError::UnknownInstance));
+                return;
+            };
+
+            // The game rewrites servers.dat when it closes, so changes made now would be lost. When servers
+            // are synced, any running instance would overwrite the edit for every instance
+            let running = if synced_servers {
+                instance_state.instances.iter().any(|instance| instance.child.is_some())
+            } else {
+                instance.child.is_some()
+            };
+            if running {
+                self.send.send_error(format!("Unable to edit server list:\n{}", ServerListError::InstanceRunning));
+                return;
+            }
+
+            // When servers are synced, every instance gets the edited servers.dat so it stays the latest one
+            let synced: Vec<(InstanceID, Arc<Path>)> = if synced_servers {
+                instance_state.instances.iter()
+                    .filter(|instance| instance.id != id)
+                    .map(|instance| (instance.id, instance.server_dat_path.clone()))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            (instance.server_dat_path.clone(), synced)
+        };
+        let synced_paths: Vec<Arc<Path>> = synced.iter().map(|(_, path)| path.clone()).collect();
+
+        let result = tokio::task::spawn_blocking(move || {
+            let mut nbt = read_servers_dat(&path)?;
+            with_servers_list(&mut nbt, |servers| apply_edit(servers, edit))?;
+
+            let bytes = nbt::encode::write_named(&nbt);
+            crate::write_safe(&path, &bytes)?;
+            for synced_path in synced_paths {
+                if let Err(err) = crate::write_safe(&synced_path, &bytes) {
+                    log::error!("Unable to sync servers.dat to {:?}: {:?}", synced_path, err);
+                }
+            }
+            Ok::<_, ServerListError>(())
+        }).await.unwrap();
+
+        if let Err(err) = result {
+            self.send.send_error(format!("Unable to edit server list:\n{err}"));
+        }
+
+        let mut instance_state = self.instance_state.write();
+        for affected in std::iter::once(id).chain(synced.iter().map(|(id, _)| *id)) {
+            if let Some(instance) = instance_state.instances.get_mut(affected) {
+                instance.mark_servers_dirty();
+            }
+        }
+    }
+
+    pub async fn copy_server(self, id: InstanceID, index: usize, target: InstanceID) {
+        if self.config.write().get().sync_targets.contains(SyncTarget::Servers) {
+            self.send.send_warning("Servers are synced between instances, this server is already available everywhere");
+            return;
+        }
+
+        let paths = {
+            let instance_state = self.instance_state.read();
+            instance_state.instances.get(id).map(|instance| instance.server_dat_path.clone())
+                .zip(instance_state.instances.get(target).map(|instance| (instance.server_dat_path.clone(), instance.child.is_some())))
+        };
+        let Some((source_path, (target_path, target_running))) = paths else {
+            self.send.send_error(format!("Unable to copy server:\n{}", ServerListError::UnknownInstance));
+            return;
+        };
+        if target_running {
+            self.send.send_error(format!("Unable to copy server:\n{}", ServerListError::InstanceRunning));
+            return;
+        }
+
+        let result = tokio::task::spawn_blocking(move || {
+            let source = read_servers_dat(&source_path)?;
+            let Some(root) = source.as_compound() else {
+                return Err(ServerListError::UnknownServer);
+            };
+            let Some(servers) = root.find_list("servers", nbt::TAG_COMPOUND_ID) else {
+                return Err(ServerListError::UnknownServer);
+            };
+            let Some(server) = servers.get(index).and_then(|server| server.as_compound()) else {
+                return Err(ServerListError::UnknownServer);
+            };
+
+            let mut nbt = read_servers_dat(&target_path)?;
+            with_servers_list(&mut nbt, |mut servers| {
+                server.clone_into(servers.create_compound());
+                Ok(())
+            })?;
+            crate::write_safe(&target_path, &nbt::encode::write_named(&nbt))?;
+            Ok(())
+        }).await.unwrap();
+
+        match result {
+            Ok(()) => {
+                if let Some(instance) = self.instance_state.write().instances.get_mut(target) {
+                    instance.mark_servers_dirty();
+                }
+                self.send.send_success("Server copied");
+            },
+            Err(err) => self.send.send_error(format!("Unable to copy server:\n{err}")),
+        }
+    }
+}
+
+fn read_servers_dat(path: &Path) -> Result<NBT, ServerListError> {
+    if !path.exists() {
+        return Ok(NBT::new());
+    }
+
+    let raw = std::fs::read(path)?;
+    let mut nbt_data = raw.as_slice();
+    nbt::decode::read_named(&mut nbt_data).map_err(ServerListError::InvalidServersDat)
+}
+
+fn with_servers_list(
+    nbt: &mut NBT,
+    modify: impl FnOnce(ListRefMut<'_>) -> Result<(), ServerListError>,
+) -> Result<(), ServerListError> {
+    let Some(mut root) = nbt.as_compound_mut() else {
+        return Err(ServerListError::InvalidServersDat(anyhow::anyhow!("Unable to get root compound")));
+    };
+
+    if root.find_list("servers", nbt::TAG_COMPOUND_ID).is_none() {
+        // Empty lists may have been written without a type, anything else is unexpected
+        if !root.find_list_of_any("servers").is_none_or(|servers| servers.is_empty()) {
+            return Err(ServerListError::InvalidServersDat(anyhow::anyhow!("Unable to get servers")));
+        }
+        root.create_list("servers", nbt::TAG_COMPOUND_ID);
+    }
+
+    let Some(servers) = root.find_list_mut("servers", nbt::TAG_COMPOUND_ID) else {
+        return Err(ServerListError::InvalidServersDat(anyhow::anyhow!("Unable to get servers")));
+    };
+    modify(servers)
+}
+
+fn apply_edit(mut servers: ListRefMut<'_>, edit: ServerListEdit) -> Result<(), ServerListError> {
+    match edit {
+        ServerListEdit::Add { name, ip } => {
+            let mut server = servers.create_compound();
+            server.insert_string("name", name.to_string());
+            server.insert_string("ip", ip.to_string());
+        },
+        ServerListEdit::Edit { index, name, ip } => {
+            // Only the name and address are replaced, the icon and other settings are kept
+            let Some(NBTRefMut::Compound(mut server)) = servers.get_mut(index) else {
+                return Err(ServerListError::UnknownServer);
+            };
+            server.insert_string("name", name.to_string());
+            server.insert_string("ip", ip.to_string());
+        },
+        ServerListEdit::Remove { index } => {
+            if !servers.remove(index) {
+                return Err(ServerListError::UnknownServer);
+            }
+        },
+        ServerListEdit::Move { from, to } => {
+            if !servers.move_element(from, to) {
+                return Err(ServerListError::UnknownServer);
+            }
+        },
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::{path::Path, sync::Arc};
+
+use bridge::instance::InstanceID;
+
+use crate::BackendState;
+
+impl BackendState {
+    pub async fn get_selected_shader_pack(self, id: InstanceID, channel: tokio::sync::oneshot::Sender<Option<Arc<str>>>) {
+        let Some(dot_minecraft_path) = self.instance_state.read().instances.get(id).map(|instance| instance.dot_minecraft_path.clone()) else {
+            return;
+        };
+
+        let selected = tokio::task::spawn_blocking(move || read_selected_shader_pack(&dot_minecraft_path)).await.unwrap();
+        _ = channel.send(selected);
+    }
+}
+
+fn read_selected_shader_pack(dot_minecraft_path: &Path) -> Option<Arc<str>> {
+    // Iris and its Forge port Oculus
+    for config in ["config/iris.properties", "config/oculus.properties"] {
+        let Ok(contents) = std::fs::read_to_string(dot_minecraft_path.join(config)) else {
+            continue;
+        };
+        if read_property(&contents, "enableShaders").as_deref() == Some("false") {
+            return None;
+        }
+        return read_property(&contents, "shaderPack").filter(|pack| !pack.is_empty()).map(Arc::from);
+    }
+
+    let contents = std::fs::read_to_string(dot_minecraft_path.join("optionsshaders.txt")).ok()?;
+    read_property(&contents, "shaderPack")
+        .filter(|pack| !pack.is_empty() && pack != "OFF" && pack != "(internal)")
+        .map(Arc::from)
+}
+
+fn read_property(contents: &str, key: &str) -> Option<String> {
+    for line in contents.lines() {
+        let line = line.trim_start();
+        if line.starts_with('#') || line.starts_with('!') {
+            continue;
+        }
+        let Some((line_key, value)) = line.split_once('=') else {
+            continue;
+        };
+        if line_key.trim_end() != key {
+            continue;
+        }
+
+        // Java properties escape some characters (e.g. ':') with a backslash
+        let mut unescaped = String::with_capacity(value.len());
+        let mut chars = value.trim_start().chars();
+        while let Some(char) = chars.next() {
+            if char == '\\' {
+                if let Some(escaped) = chars.next() {
+                    unescaped.push(escaped);
+                }
+            } else {
+                unescaped.push(char);
+            }
+        }
+        return Some(unescaped);
+    }
+    None
+}
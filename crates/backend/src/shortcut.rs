@@ -3,7 +3,7 @@ use std::path::Path;
 use std::path::PathBuf;
 
 #[cfg(target_os = "linux")]
-pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str]) {
+pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str], icon: Option<&Path>) {
     log::info!("Creating linux shortcut at {:?}", path);
 
     if !has_extension(&path, "desktop") {
@@ -15,12 +15,14 @@ pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str])
     };
     let exec = shell_words::join(std::iter::once(bin).chain(args.iter().map(|s| *s)));
 
+    let icon = icon.and_then(Path::to_str).map(|icon| format!("Icon={icon}\n")).unwrap_or_default();
+
     _ = std::fs::write(&path, format!(r#"[Desktop Entry]
 Type=Application
 Version=1.0
 Name={name}
 Exec=sh -c "{exec}"
-Categories=Games;Minecraft;Launcher;
+{icon}Categories=Games;Minecraft;Launcher;
 "#).as_bytes());
 
     use std::os::unix::fs::PermissionsExt;
@@ -28,7 +30,7 @@ Categories=Games;Minecraft;Launcher;
 }
 
 #[cfg(target_os = "windows")]
-pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str]) {
+pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str], icon: Option<&Path>) {
     log::info!("Creating windows shortcut at {:?}", path);
 
     if !has_extension(&path, "lnk") {
@@ -40,12 +42,23 @@ pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str])
     let args_str = shell_words::join(args);
     sl.set_arguments(Some(args_str.into()));
     sl.set_name(Some(name.into()));
+
+    // Windows shortcuts can't reference pngs, so convert the icon into an ico next to it
+    if let Some(icon) = icon && let Ok(image) = image::open(icon) {
+        let ico_path = icon.with_extension("ico");
+        if image.save_with_format(&ico_path, image::ImageFormat::Ico).is_ok()
+            && let Some(ico_path) = ico_path.to_str()
+        {
+            sl.set_icon_location(Some(ico_path.into()));
+        }
+    }
+
     _ = sl.create_lnk(path);
 
 }
 
 #[cfg(target_os = "macos")]
-pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str]) {
+pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str], icon: Option<&Path>) {
     log::info!("Creating macos shortcut at {:?}", path);
 
     if !has_extension(&path, "app") {
@@ -56,6 +69,16 @@ pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str])
 
     _ = std::fs::create_dir_all(&path);
 
+    // Finder only shows bundle icons in the icns format, so the png is converted
+    let mut icon_entry = String::new();
+    if let Some(icon) = icon && let Some(icns) = create_icns(icon) {
+        let resources = path.join("Resources");
+        _ = std::fs::create_dir_all(&resources);
+        if std::fs::write(resources.join("icon.icns"), icns).is_ok() {
+            icon_entry.push_str("<key>CFBundleIconFile</key>\n   \t<string>icon.icns</string>\n   \t");
+        }
+    }
+
     let info_plist = path.join("Info.plist");
     _ = std::fs::write(&info_plist, format!(r#"<?xml version="1.0" encoding="UTF-8"?>
 <!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
@@ -65,7 +88,7 @@ pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str])
    	<string>run.sh</string>
    	<key>CFBundleIdentifier</key>
    	<string>com.moulberry.pandoralauncher.Shortcut</string>
-   	<key>CFBundleName</key>
+   	{icon_entry}<key>CFBundleName</key>
    	<string>{name}</string>
    	<key>CFBundleDisplayName</key>
    	<string>{name}</string>
@@ -98,6 +121,35 @@ pub fn create_shortcut(mut path: PathBuf, name: &str, bin: &Path, args: &[&str])
     let _ = std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755));
 }
 
+// Modern icns entries (ic07 for 128x128, ic08 for 256x256) can contain png data directly
+#[cfg(target_os = "macos")]
+fn create_icns(icon: &Path) -> Option<Vec<u8>> {
+    let image = image::open(icon).ok()?;
+
+    let mut entries = Vec::new();
+    for (icon_type, size) in [(b"ic07", 128), (b"ic08", 256)] {
+        // Icons have to be square, so the image is centered on a transparent background
+        let resized = image.resize(size, size, image::imageops::FilterType::Lanczos3);
+        let mut square = image::RgbaImage::new(size, size);
+        let x = (size - resized.width()) / 2;
+        let y = (size - resized.height()) / 2;
+        image::imageops::overlay(&mut square, &resized.to_rgba8(), x as i64, y as i64);
+
+        let mut png_bytes = Vec::new();
+        square.write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageFormat::Png).ok()?;
+
+        entries.extend_from_slice(icon_type);
+        entries.extend_from_slice(&(png_bytes.len() as u32 + 8).to_be_bytes());
+        entries.extend_from_slice(&png_bytes);
+    }
+
+    let mut icns = Vec::with_capacity(entries.len() + 8);
+    icns.extend_from_slice(b"icns");
+    icns.extend_from_slice(&(entries.len() as u32 + 8).to_be_bytes());
+    icns.extend_from_slice(&entries);
+    Some(icns)
+}
+
 fn has_extension(path: &Path, extension: &str) -> bool {
     let Some(path_extension) = path.extension() else {
         return false;
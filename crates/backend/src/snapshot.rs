@@ -0,0 +1,506 @@
+use std::{ffi::OsStr, path::{Path, PathBuf}, sync::Arc};
+
+use bridge::{instance::{InstanceID, InstanceSnapshotSummary}, message::MessageToFrontend, modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType}};
+use rand::RngCore;
+use schema::snapshot::InstanceSnapshotInfo;
+use sha1::{Digest, Sha1};
+use strum::IntoEnumIterator;
+
+use crate::{instance::ContentFolder, BackendState, IoOrSerializationError, WatchTarget};
+
+#[derive(thiserror::Error, Debug)]
+pub enum SnapshotError {
+    #[error("Unknown instance")]
+    UnknownInstance,
+    #[error("Instance is currently running")]
+    InstanceRunning,
+    #[error("Another snapshot operation is in progress")]
+    InstanceBusy,
+    #[error("Snapshot not found")]
+    NotFound,
+    #[error("Invalid snapshot id: {0}")]
+    InvalidId(Arc<str>),
+    #[error("Snapshot is incomplete, missing info_v1.json")]
+    Incomplete,
+    #[error("Failed to perform I/O operation:\n{0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Failed to serialize snapshot info:\n{0}")]
+    SerializationError(#[from] serde_json::Error),
+}
+
+const SNAPSHOTS_FOLDER: &str = "snapshots";
+const SNAPSHOT_INFO: &str = "snapshot.json";
+
+// Paths are relative to the instance root, snapshots mirror the same layout
+const SNAPSHOT_FILES: &[&str] = &["info_v1.json", ".minecraft/options.txt"];
+const SNAPSHOT_FOLDERS: &[&str] = &[".minecraft/mods", ".minecraft/config"];
+const SNAPSHOT_AUX_ONLY_FOLDERS: &[&str] = &[".minecraft/resourcepacks"];
+const SNAPSHOT_WORLDS_FOLDER: &str = ".minecraft/saves";
+
+impl BackendState {
+    pub async fn load_instance_snapshots(&self, id: InstanceID) {
+        let Some(root_path) = self.instance_state.read().instances.get(id).map(|instance| instance.root_path.clone()) else {
+            return;
+        };
+
+        let snapshots = tokio::task::spawn_blocking(move || list_snapshots(&root_path)).await.unwrap_or_default();
+
+        self.send.send(MessageToFrontend::InstanceSnapshotsUpdated {
+            id,
+            snapshots: snapshots.into(),
+        });
+    }
+
+    pub async fn create_instance_snapshot(&self, id: InstanceID, note: Arc<str>, include_worlds: bool) -> Result<InstanceSnapshotSummary, SnapshotError> {
+        let Some(root_path) = self.instance_state.read().instances.get(id).map(|instance| instance.root_path.clone()) else {
+            return Err(SnapshotError::UnknownInstance);
+        };
+
+        let content_library_dir = self.directories.content_library_dir.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            create_snapshot(&root_path, &content_library_dir, note, include_worlds)
+        }).await.unwrap();
+
+        self.load_instance_snapshots(id).await;
+
+        result
+    }
+
+    pub async fn create_instance_snapshot_with_modal(self, id: InstanceID, note: Arc<str>, include_worlds: bool, modal_action: ModalAction) {
+        let tracker = ProgressTracker::new("Creating snapshot".into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let result = self.create_instance_snapshot(id, note, include_worlds).await;
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        tracker.notify();
+
+        match result {
+            Ok(_) => self.send.send_success("Snapshot created"),
+            Err(err) => modal_action.set_error_message(format!("Unable to create snapshot:\n{err}").into()),
+        }
+        modal_action.set_finished();
+    }
+
+    pub async fn restore_instance_snapshot(self, id: InstanceID, snapshot: Arc<str>, modal_action: ModalAction) {
+        let tracker = ProgressTracker::new("Restoring snapshot".into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let result = self.restore_instance_snapshot_inner(id, snapshot).await;
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        tracker.notify();
+
+        match result {
+            Ok(skipped) => {
+                for skipped in skipped {
+                    self.send.send_warning(format!("Skipped restoring {skipped} because it is synced between instances"));
+                }
+                self.send.send_success("Snapshot restored");
+            },
+            Err(err) => modal_action.set_error_message(format!("Unable to restore snapshot:\n{err}").into()),
+        }
+        modal_action.set_finished();
+    }
+
+    pub(crate) async fn restore_instance_snapshot_inner(&self, id: InstanceID, snapshot: Arc<str>) -> Result<Vec<&'static str>, SnapshotError> {
+        let root_path = self.mark_instance_busy(id)?;
+
+        let content_library_dir = self.directories.content_library_dir.clone();
+        let result = tokio::task::spawn_blocking(move || {
+            restore_snapshot(&root_path, &content_library_dir, &snapshot)
+        }).await.unwrap();
+
+        self.clear_instance_busy(id);
+
+        if result.is_ok() && let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+            instance.configuration.mark_changed(&instance.root_path.join("info_v1.json"));
+            instance.mark_world_dirty(None);
+            for folder in ContentFolder::iter() {
+                instance.content_state[folder].mark_dirty(None);
+            }
+
+            // The restored folders are new inodes, so any existing watches need to be recreated
+            let mut file_watching = self.file_watching.write();
+            if instance.watching_saves_dir {
+                file_watching.watch_filesystem(instance.saves_path.clone(), WatchTarget::InstanceSavesDir { id });
+            }
+            for folder in ContentFolder::iter() {
+                if instance.content_state[folder].watching_path {
+                    file_watching.watch_filesystem(instance.content_state[folder].path.clone(), WatchTarget::InstanceContentDir { id, folder });
+                }
+            }
+            drop(file_watching);
+
+            self.send.send(instance.create_modify_message());
+        }
+
+        result
+    }
+
+    pub async fn delete_instance_snapshot(&self, id: InstanceID, snapshot: Arc<str>) {
+        let result = match self.mark_instance_busy(id) {
+            Ok(root_path) => {
+                let result = tokio::task::spawn_blocking(move || delete_snapshot(&root_path, &snapshot)).await.unwrap();
+                self.clear_instance_busy(id);
+                result
+            },
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            self.send.send_error(format!("Unable to delete snapshot: {err}"));
+        }
+
+        self.load_instance_snapshots(id).await;
+    }
+
+    // The check and the flag are set under the same lock, so a launch can't start in between
+    fn mark_instance_busy(&self, id: InstanceID) -> Result<Arc<Path>, SnapshotError> {
+        let mut instance_state = self.instance_state.write();
+        let Some(instance) = instance_state.instances.get_mut(id) else {
+            return Err(SnapshotError::UnknownInstance);
+        };
+        if instance.child.is_some() {
+            return Err(SnapshotError::InstanceRunning);
+        }
+        if instance.busy {
+            return Err(SnapshotError::InstanceBusy);
+        }
+        instance.busy = true;
+        Ok(instance.root_path.clone())
+    }
+
+    fn clear_instance_busy(&self, id: InstanceID) {
+        if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+            instance.busy = false;
+        }
+    }
+}
+
+fn list_snapshots(root_path: &Path) -> Vec<InstanceSnapshotSummary> {
+    let Ok(read_dir) = std::fs::read_dir(root_path.join(SNAPSHOTS_FOLDER)) else {
+        return Vec::new();
+    };
+
+    let mut snapshots = Vec::new();
+
+    for entry in read_dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Some(id) = entry.file_name().to_str().map(Arc::<str>::from) else {
+            continue;
+        };
+        if id.starts_with('.') {
+            continue;
+        }
+
+        // Snapshots without an info file were never completed
+        let Ok(info) = crate::read_json::<InstanceSnapshotInfo>(&entry.path().join(SNAPSHOT_INFO)) else {
+            continue;
+        };
+
+        snapshots.push(InstanceSnapshotSummary {
+            id,
+            created: info.created,
+            note: info.note,
+            size: info.size,
+            includes_worlds: info.includes_worlds,
+        });
+    }
+
+    snapshots.sort_by_key(|snapshot| -snapshot.created);
+    snapshots
+}
+
+fn create_snapshot(root_path: &Path, content_library_dir: &Path, note: Arc<str>, include_worlds: bool) -> Result<InstanceSnapshotSummary, SnapshotError> {
+    let snapshots_dir = root_path.join(SNAPSHOTS_FOLDER);
+    std::fs::create_dir_all(&snapshots_dir)?;
+
+    let now = chrono::Local::now();
+    let base_id = now.format("%Y-%m-%d_%H-%M-%S").to_string();
+    let mut id = base_id.clone();
+    let mut counter = 1;
+    while snapshots_dir.join(&id).exists() {
+        counter += 1;
+        id = format!("{base_id}_{counter}");
+    }
+
+    // Copy into a temporary folder first so that a partial snapshot never shows up in the list
+    let temp_dir = snapshots_dir.join(format!(".{}.new", rand::thread_rng().next_u32()));
+
+    let result = copy_snapshot_entries(root_path, &temp_dir, content_library_dir, include_worlds).and_then(|size| {
+        let info = InstanceSnapshotInfo {
+            created: now.timestamp_millis(),
+            note: note.clone(),
+            includes_worlds: include_worlds,
+            size,
+        };
+        let bytes = serde_json::to_vec(&info)?;
+        crate::write_safe(&temp_dir.join(SNAPSHOT_INFO), &bytes)?;
+        std::fs::rename(&temp_dir, snapshots_dir.join(&id))?;
+        Ok(info)
+    });
+
+    let info = match result {
+        Ok(info) => info,
+        Err(err) => {
+            _ = std::fs::remove_dir_all(&temp_dir);
+            return Err(err.into());
+        },
+    };
+
+    Ok(InstanceSnapshotSummary {
+        id: id.into(),
+        created: info.created,
+        note,
+        size: info.size,
+        includes_worlds,
+    })
+}
+
+fn delete_snapshot(root_path: &Path, id: &str) -> Result<(), SnapshotError> {
+    let snapshot_dir = get_snapshot_dir(root_path, id)?;
+    std::fs::remove_dir_all(snapshot_dir)?;
+    Ok(())
+}
+
+fn get_snapshot_dir(root_path: &Path, id: &str) -> Result<PathBuf, SnapshotError> {
+    if !crate::is_single_component_path(id) || id.starts_with('.') {
+        return Err(SnapshotError::InvalidId(id.into()));
+    }
+
+    let snapshot_dir = root_path.join(SNAPSHOTS_FOLDER).join(id);
+    if !snapshot_dir.is_dir() {
+        return Err(SnapshotError::NotFound);
+    }
+    Ok(snapshot_dir)
+}
+
+struct RestoredEntry {
+    live: PathBuf,
+    backup: Option<PathBuf>,
+}
+
+fn restore_snapshot(root_path: &Path, content_library_dir: &Path, id: &str) -> Result<Vec<&'static str>, SnapshotError> {
+    let snapshot_dir = get_snapshot_dir(root_path, id)?;
+    let info: InstanceSnapshotInfo = match crate::read_json(&snapshot_dir.join(SNAPSHOT_INFO)) {
+        Ok(info) => info,
+        Err(IoOrSerializationError::Io(_)) => return Err(SnapshotError::NotFound),
+        Err(IoOrSerializationError::Serialization(err)) => return Err(err.into()),
+    };
+
+    let snapshots_dir = root_path.join(SNAPSHOTS_FOLDER);
+    let staging_dir = snapshots_dir.join(format!(".restore.{}.new", rand::thread_rng().next_u32()));
+    let backup_dir = snapshots_dir.join(format!(".backup.{}.new", rand::thread_rng().next_u32()));
+
+    // Stage a fresh copy so that the snapshot itself is never modified by the game afterwards
+    if let Err(err) = copy_snapshot_entries(&snapshot_dir, &staging_dir, content_library_dir, info.includes_worlds) {
+        _ = std::fs::remove_dir_all(&staging_dir);
+        return Err(err.into());
+    }
+    if !staging_dir.join("info_v1.json").is_file() {
+        _ = std::fs::remove_dir_all(&staging_dir);
+        return Err(SnapshotError::Incomplete);
+    }
+
+    let mut entries: Vec<PathBuf> = SNAPSHOT_FILES.iter().chain(SNAPSHOT_FOLDERS.iter()).map(PathBuf::from).collect();
+    if info.includes_worlds {
+        entries.push(PathBuf::from(SNAPSHOT_WORLDS_FOLDER));
+    }
+    for folder in SNAPSHOT_AUX_ONLY_FOLDERS {
+        for base in [root_path, staging_dir.as_path()] {
+            for aux_file in read_aux_files(&base.join(folder)) {
+                let relative = Path::new(folder).join(aux_file);
+                if !entries.contains(&relative) {
+                    entries.push(relative);
+                }
+            }
+        }
+    }
+
+    let mut skipped = Vec::new();
+    let mut restored = Vec::new();
+
+    let result = (|| -> std::io::Result<()> {
+        for relative in &entries {
+            let live = root_path.join(relative);
+            let staged = staging_dir.join(relative);
+
+            if live.is_symlink() {
+                if let Some(synced) = SNAPSHOT_FOLDERS.iter().chain(std::iter::once(&SNAPSHOT_WORLDS_FOLDER)).find(|folder| Path::new(folder) == relative) {
+                    skipped.push(*synced);
+                }
+                continue;
+            }
+
+            let backup = if live.exists() {
+                let backup = backup_dir.join(relative);
+                if let Some(parent) = backup.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                if live.is_dir() {
+                    std::fs::rename(&live, &backup)?;
+                } else {
+                    // Files are replaced atomically below, so only keep a link to the old contents
+                    std::fs::hard_link(&live, &backup)?;
+                }
+                Some(backup)
+            } else {
+                None
+            };
+
+            let has_backup = backup.is_some();
+            restored.push(RestoredEntry { live: live.clone(), backup });
+
+            if staged.exists() {
+                if let Some(parent) = live.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::rename(&staged, &live)?;
+            } else if has_backup && live.is_file() {
+                std::fs::remove_file(&live)?;
+            }
+        }
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        for entry in restored.into_iter().rev() {
+            rollback_entry(entry);
+        }
+        _ = std::fs::remove_dir_all(&staging_dir);
+        _ = std::fs::remove_dir_all(&backup_dir);
+        return Err(err.into());
+    }
+
+    _ = std::fs::remove_dir_all(&staging_dir);
+    _ = std::fs::remove_dir_all(&backup_dir);
+
+    Ok(skipped)
+}
+
+fn rollback_entry(entry: RestoredEntry) {
+    let Some(backup) = entry.backup else {
+        if entry.live.is_dir() {
+            _ = std::fs::remove_dir_all(&entry.live);
+        } else {
+            _ = std::fs::remove_file(&entry.live);
+        }
+        return;
+    };
+
+    if backup.is_dir() {
+        _ = std::fs::remove_dir_all(&entry.live);
+    }
+    if let Err(err) = std::fs::rename(&backup, &entry.live) {
+        log::error!("Unable to roll back {:?} after failed snapshot restore: {err}", entry.live);
+    }
+}
+
+fn copy_snapshot_entries(from_root: &Path, to_root: &Path, content_library_dir: &Path, include_worlds: bool) -> std::io::Result<u64> {
+    std::fs::create_dir_all(to_root)?;
+
+    let mut size = 0;
+
+    for file in SNAPSHOT_FILES {
+        let from = from_root.join(file);
+        if from.is_file() {
+            let to = to_root.join(file);
+            if let Some(parent) = to.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            size += copy_file(&from, &to, content_library_dir)?;
+        }
+    }
+
+    let worlds = include_worlds.then_some(&SNAPSHOT_WORLDS_FOLDER);
+    for folder in SNAPSHOT_FOLDERS.iter().chain(worlds) {
+        let from = from_root.join(folder);
+        if from.is_dir() {
+            size += copy_dir_all(&from, &to_root.join(folder), content_library_dir)?;
+        }
+    }
+
+    for folder in SNAPSHOT_AUX_ONLY_FOLDERS {
+        let from = from_root.join(folder);
+        let to = to_root.join(folder);
+        for aux_file in read_aux_files(&from) {
+            std::fs::create_dir_all(&to)?;
+            size += std::fs::copy(from.join(&aux_file), to.join(&aux_file))?;
+        }
+    }
+
+    Ok(size)
+}
+
+fn read_aux_files(folder: &Path) -> Vec<PathBuf> {
+    let Ok(read_dir) = std::fs::read_dir(folder) else {
+        return Vec::new();
+    };
+
+    read_dir.filter_map(|entry| {
+        let file_name = entry.ok()?.file_name();
+        let bytes = file_name.as_encoded_bytes();
+        (bytes.starts_with(b".") && bytes.ends_with(b".aux.json")).then(|| PathBuf::from(file_name))
+    }).collect()
+}
+
+pub(crate) fn copy_dir_all(from: &Path, to: &Path, content_library_dir: &Path) -> std::io::Result<u64> {
+    std::fs::create_dir_all(to)?;
+
+    let mut size = 0;
+
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        if file_name.as_encoded_bytes().starts_with(b".pandora.") {
+            continue;
+        }
+
+        let from = entry.path();
+        let to = to.join(&file_name);
+
+        let metadata = std::fs::metadata(&from)?;
+        if metadata.is_dir() {
+            size += copy_dir_all(&from, &to, content_library_dir)?;
+        } else if metadata.is_file() {
+            size += copy_file(&from, &to, content_library_dir)?;
+        }
+    }
+
+    Ok(size)
+}
+
+// Returns the amount of bytes actually copied, files linked from the content library take up no extra space
+fn copy_file(from: &Path, to: &Path, content_library_dir: &Path) -> std::io::Result<u64> {
+    if let Some(library_path) = find_in_content_library(from, content_library_dir)
+        && std::fs::hard_link(&library_path, to).is_ok()
+    {
+        return Ok(0);
+    }
+
+    std::fs::copy(from, to)
+}
+
+fn find_in_content_library(path: &Path, content_library_dir: &Path) -> Option<PathBuf> {
+    let mut real_path = path.to_path_buf();
+    if real_path.extension() == Some(OsStr::new("disabled")) {
+        real_path.set_extension("");
+    }
+
+    let extension = real_path.extension()?.to_str()?;
+    if !matches!(extension, "jar" | "zip" | "mrpack") {
+        return None;
+    }
+
+    let mut file = std::fs::File::open(path).ok()?;
+    let mut hasher = Sha1::new();
+    std::io::copy(&mut file, &mut hasher).ok()?;
+    let hash: [u8; 20] = hasher.finalize().into();
+
+    let library_path = crate::create_content_library_path(content_library_dir, hash, Some(extension));
+    library_path.is_file().then_some(library_path)
+}
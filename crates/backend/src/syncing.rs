@@ -110,10 +110,11 @@ fn find_latest(filename: &'static str, directories: &LauncherDirectories) -> Opt
 }
 
 fn create_combined_options_txt(fallback: &Path, current: &Path, directories: &LauncherDirectories) -> String {
-    let mut values = read_options_txt(fallback);
+    let fallback_content = std::fs::read_to_string(fallback).unwrap_or_default();
+    let mut values = parse_options_txt(&fallback_content);
 
     let Ok(read_dir) = std::fs::read_dir(&directories.instances_dir) else {
-        return create_options_txt(values);
+        return create_options_txt(&fallback_content, &values);
     };
 
     let mut paths = Vec::new();
@@ -143,48 +144,81 @@ fn create_combined_options_txt(fallback: &Path, current: &Path, directories: &La
 
     paths.sort_by_key(|(time, _)| *time);
 
-    for (_, path) in paths {
-        let mut new_values = read_options_txt(&path);
+    let mut indices: FxHashMap<Arc<str>, usize> = values.iter().enumerate().map(|(index, (key, _))| (key.clone(), index)).collect();
 
-        if path != current {
-            new_values.remove("resourcePacks");
-            new_values.remove("incompatibleResourcePacks");
-        }
+    for (_, path) in paths {
+        for (key, value) in read_options_txt(&path) {
+            if path != current && (&*key == "resourcePacks" || &*key == "incompatibleResourcePacks") {
+                continue;
+            }
 
-        for (key, value) in new_values {
-            values.insert(key, value);
+            if let Some(&index) = indices.get(&key) {
+                values[index].1 = value;
+            } else {
+                indices.insert(key.clone(), values.len());
+                values.push((key, value));
+            }
         }
     }
 
-    create_options_txt(values)
+    create_options_txt(&fallback_content, &values)
 }
 
-fn create_options_txt(values: FxHashMap<String, String>) -> String {
-    let mut options = String::new();
+// Rewrites the values of existing keys in place so that unknown keys and the original ordering are kept,
+// keys that aren't present yet are appended at the end
+pub(crate) fn create_options_txt(existing: &str, options: &[(Arc<str>, Arc<str>)]) -> String {
+    let mut remaining: FxHashMap<&str, &str> = options.iter().map(|(key, value)| (&**key, &**value)).collect();
+
+    let mut new_content = String::with_capacity(existing.len());
+    for line in existing.lines() {
+        let trimmed = line.trim_ascii();
+        if let Some((key, _)) = trimmed.split_once(':')
+            && let Some(value) = remaining.remove(key)
+        {
+            new_content.push_str(key);
+            new_content.push(':');
+            new_content.push_str(value);
+        } else {
+            new_content.push_str(line);
+        }
+        new_content.push('\n');
+    }
 
-    for (key, value) in values {
-        options.push_str(&key);
-        options.push(':');
-        options.push_str(&value);
-        options.push('\n');
+    for (key, value) in options {
+        if remaining.remove(&**key).is_some() {
+            new_content.push_str(key);
+            new_content.push(':');
+            new_content.push_str(value);
+            new_content.push('\n');
+        }
     }
 
-    options
+    new_content
+}
+
+pub(crate) fn write_options_txt(path: &Path, options: &[(Arc<str>, Arc<str>)]) -> std::io::Result<()> {
+    let existing = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => return Err(err),
+    };
+
+    crate::write_safe(path, create_options_txt(&existing, options).as_bytes())
 }
 
-fn read_options_txt(path: &Path) -> FxHashMap<String, String> {
+pub(crate) fn read_options_txt(path: &Path) -> Vec<(Arc<str>, Arc<str>)> {
     let Ok(content) = std::fs::read_to_string(path) else {
-        return FxHashMap::default();
+        return Vec::new();
     };
 
-    let mut values = FxHashMap::default();
-    for line in content.split('\n') {
-        let line = line.trim_ascii();
-        if let Some((key, value)) = line.split_once(':') {
-            values.insert(key.to_string(), value.to_string());
-        }
-    }
-    values
+    parse_options_txt(&content)
+}
+
+fn parse_options_txt(content: &str) -> Vec<(Arc<str>, Arc<str>)> {
+    content.lines()
+        .filter_map(|line| line.trim_ascii().split_once(':'))
+        .map(|(key, value)| (key.into(), value.into()))
+        .collect()
 }
 
 pub fn get_sync_state(want_sync: EnumSet<SyncTarget>, directories: &LauncherDirectories) -> std::io::Result<SyncState> {
@@ -0,0 +1,558 @@
+use std::{io::{Read, Write}, path::{Path, PathBuf}, sync::Arc};
+
+use bridge::{
+    instance::{InstanceID, InstanceWorldBackupSummary},
+    message::MessageToFrontend,
+    modal_action::{ModalAction, ProgressTracker, ProgressTrackerFinishType},
+    safe_path::SafePath,
+};
+use rc_zip_sync::ReadZip;
+use ustr::Ustr;
+
+use crate::{resource_pack::read_client_data_version, BackendState};
+
+#[derive(thiserror::Error, Debug)]
+pub enum WorldError {
+    #[error("Unknown instance")]
+    UnknownInstance,
+    #[error("Instance is currently running")]
+    InstanceRunning,
+    #[error("Invalid world: {0}")]
+    InvalidWorld(Arc<str>),
+    #[error("Invalid backup: {0}")]
+    InvalidBackup(Arc<str>),
+    #[error("Invalid datapack: {0}")]
+    InvalidDatapack(Arc<str>),
+    #[error("Both instances share the same saves folder")]
+    SameSavesFolder,
+    #[error("Unable to read level.dat:\n{0}")]
+    InvalidLevelDat(anyhow::Error),
+    #[error("Failed to perform I/O operation:\n{0}")]
+    IoError(#[from] std::io::Error),
+    #[error("Failed to write backup:\n{0}")]
+    ZipWriteError(#[from] zip::result::ZipError),
+    #[error("Failed to read backup:\n{0}")]
+    ZipReadError(#[from] rc_zip_sync::rc_zip::Error),
+    #[error("Failed to move to trash:\n{0}")]
+    TrashError(#[from] trash::Error),
+}
+
+const WORLD_BACKUPS_FOLDER: &str = "world_backups";
+
+pub(crate) struct WorldPaths {
+    pub root_path: Arc<Path>,
+    pub saves_path: Arc<Path>,
+}
+
+impl BackendState {
+    pub(crate) fn world_paths(&self, id: InstanceID) -> Result<WorldPaths, WorldError> {
+        let instance_state = self.instance_state.read();
+        let Some(instance) = instance_state.instances.get(id) else {
+            return Err(WorldError::UnknownInstance);
+        };
+        if instance.child.is_some() {
+            return Err(WorldError::InstanceRunning);
+        }
+        Ok(WorldPaths {
+            root_path: instance.root_path.clone(),
+            saves_path: instance.saves_path.clone(),
+        })
+    }
+
+    fn mark_worlds_changed(&self, id: InstanceID, world_path: &Path, removed: bool) {
+        if removed {
+            self.file_watching.write().remove(world_path);
+        }
+        if let Some(instance) = self.instance_state.write().instances.get_mut(id) {
+            instance.mark_world_dirty(Some(world_path.into()));
+        }
+    }
+
+    pub async fn load_world_backups(&self, id: InstanceID) {
+        let Some(root_path) = self.instance_state.read().instances.get(id).map(|instance| instance.root_path.clone()) else {
+            return;
+        };
+
+        let backups = tokio::task::spawn_blocking(move || list_world_backups(&root_path)).await.unwrap_or_default();
+
+        self.send.send(MessageToFrontend::InstanceWorldBackupsUpdated {
+            id,
+            backups: backups.into(),
+        });
+    }
+
+    pub async fn backup_world(self, id: InstanceID, world: Arc<str>, modal_action: ModalAction) {
+        let tracker = ProgressTracker::new(format!("Backing up {world}").into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let keep = self.config.write().get().world_backups_to_keep();
+        let result = match self.world_paths(id) {
+            Ok(paths) => tokio::task::spawn_blocking(move || {
+                let backup = create_world_backup(&paths.root_path, &paths.saves_path, &world)?;
+                rotate_world_backups(&paths.root_path, &world, keep)?;
+                Ok(backup)
+            }).await.unwrap(),
+            Err(err) => Err(err),
+        };
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        tracker.notify();
+
+        match result {
+            Ok(_) => self.send.send_success("World backup created"),
+            Err(err) => modal_action.set_error_message(format!("Unable to back up world:\n{err}").into()),
+        }
+        modal_action.set_finished();
+
+        self.load_world_backups(id).await;
+    }
+
+    pub async fn restore_world_backup(self, id: InstanceID, backup: Arc<str>, modal_action: ModalAction) {
+        let tracker = ProgressTracker::new("Restoring world backup".into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let result = match self.world_paths(id) {
+            Ok(paths) => tokio::task::spawn_blocking(move || {
+                restore_world_backup(&paths.root_path, &paths.saves_path, &backup)
+            }).await.unwrap(),
+            Err(err) => Err(err),
+        };
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        tracker.notify();
+
+        match result {
+            Ok(world_path) => {
+                self.mark_worlds_changed(id, &world_path, false);
+                self.send.send_success("World backup restored");
+            },
+            Err(err) => modal_action.set_error_message(format!("Unable to restore world backup:\n{err}").into()),
+        }
+        modal_action.set_finished();
+
+        self.load_instance_worlds(id).await;
+    }
+
+    pub async fn delete_world_backup(&self, id: InstanceID, backup: Arc<str>) {
+        let Some(root_path) = self.instance_state.read().instances.get(id).map(|instance| instance.root_path.clone()) else {
+            return;
+        };
+
+        let result = tokio::task::spawn_blocking(move || -> Result<(), WorldError> {
+            let path = world_backup_path(&root_path, &backup)?;
+            std::fs::remove_file(path)?;
+            Ok(())
+        }).await.unwrap();
+        if let Err(err) = result {
+            self.send.send_error(format!("Unable to delete world backup: {err}"));
+        }
+
+        self.load_world_backups(id).await;
+    }
+
+    pub async fn rename_world(self, id: InstanceID, world: Arc<str>, name: Arc<str>) {
+        let result = match self.world_paths(id) {
+            Ok(paths) => tokio::task::spawn_blocking(move || {
+                let world_path = world_path(&paths.saves_path, &world)?;
+                set_level_name(&world_path, &name)?;
+                Ok(world_path)
+            }).await.unwrap(),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(world_path) => self.mark_worlds_changed(id, &world_path, false),
+            Err(err) => self.send.send_error(format!("Unable to rename world: {err}")),
+        }
+
+        self.load_instance_worlds(id).await;
+    }
+
+    pub async fn delete_world(self, id: InstanceID, world: Arc<str>) {
+        let result = match self.world_paths(id) {
+            Ok(paths) => tokio::task::spawn_blocking(move || {
+                let world_path = world_path(&paths.saves_path, &world)?;
+                trash::delete(&world_path)?;
+                Ok(world_path)
+            }).await.unwrap(),
+            Err(err) => Err(err),
+        };
+
+        match result {
+            Ok(world_path) => {
+                self.mark_worlds_changed(id, &world_path, true);
+                self.send.send_success("World moved to trash");
+            },
+            Err(err) => self.send.send_error(format!("Unable to delete world: {err}")),
+        }
+
+        self.load_instance_worlds(id).await;
+    }
+
+    pub async fn copy_world(self, id: InstanceID, world: Arc<str>, target: InstanceID, move_world: bool, modal_action: ModalAction) {
+        let title = if move_world { "Moving world" } else { "Copying world" };
+        let tracker = ProgressTracker::new(title.into(), self.send.clone());
+        modal_action.trackers.push(tracker.clone());
+        tracker.notify();
+
+        let paths = self.world_paths(id).and_then(|from| Ok((from, self.world_paths(target)?)));
+        let content_library_dir = self.directories.content_library_dir.clone();
+        let result = match paths {
+            Ok((from, to)) => tokio::task::spawn_blocking(move || {
+                copy_world(&from.saves_path, &to.saves_path, &world, move_world, &content_library_dir)
+            }).await.unwrap(),
+            Err(err) => Err(err),
+        };
+
+        tracker.set_finished(ProgressTrackerFinishType::from_err(result.is_err()));
+        tracker.notify();
+
+        match result {
+            Ok((from, to)) => {
+                self.mark_worlds_changed(id, &from, move_world);
+                self.mark_worlds_changed(target, &to, false);
+                self.send.send_success(if move_world { "World moved" } else { "World copied" });
+            },
+            Err(err) => modal_action.set_error_message(format!("Unable to copy world:\n{err}").into()),
+        }
+        modal_action.set_finished();
+
+        self.clone().load_instance_worlds(id).await;
+        self.load_instance_worlds(target).await;
+    }
+
+    /// The data version of the client, only known once the version has been downloaded
+    pub async fn get_data_version(self, minecraft_version: Ustr, channel: tokio::sync::oneshot::Sender<Option<i32>>) {
+        let client_jar = self.client_jar_path(minecraft_version);
+        let data_version = tokio::task::spawn_blocking(move || read_client_data_version(&client_jar)).await.unwrap();
+        _ = channel.send(data_version);
+    }
+}
+
+pub(crate) fn world_path(saves_path: &Path, world: &str) -> Result<PathBuf, WorldError> {
+    if !crate::is_single_component_path(world) || world.starts_with('.') {
+        return Err(WorldError::InvalidWorld(world.into()));
+    }
+    let path = saves_path.join(world);
+    if !path.join("level.dat").is_file() {
+        return Err(WorldError::InvalidWorld(world.into()));
+    }
+    Ok(path)
+}
+
+fn world_backup_path(root_path: &Path, backup: &str) -> Result<PathBuf, WorldError> {
+    if !crate::is_single_component_path(backup) || parse_backup_name(backup).is_none() {
+        return Err(WorldError::InvalidBackup(backup.into()));
+    }
+    Ok(root_path.join(WORLD_BACKUPS_FOLDER).join(backup))
+}
+
+// Backups are named <created millis>_<world folder>.zip
+fn parse_backup_name(backup: &str) -> Option<(i64, &str)> {
+    let (created, world) = backup.strip_suffix(".zip")?.split_once('_')?;
+    Some((created.parse().ok()?, world))
+}
+
+fn list_world_backups(root_path: &Path) -> Vec<InstanceWorldBackupSummary> {
+    let Ok(read_dir) = std::fs::read_dir(root_path.join(WORLD_BACKUPS_FOLDER)) else {
+        return Vec::new();
+    };
+
+    let mut backups = Vec::new();
+
+    for entry in read_dir {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Some(id) = entry.file_name().to_str().map(Arc::<str>::from) else {
+            continue;
+        };
+        let Some((created, world)) = parse_backup_name(&id) else {
+            continue;
+        };
+        let size = entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+
+        backups.push(InstanceWorldBackupSummary {
+            world: world.into(),
+            id,
+            created,
+            size,
+        });
+    }
+
+    backups.sort_by_key(|backup| -backup.created);
+    backups
+}
+
+fn create_world_backup(root_path: &Path, saves_path: &Path, world: &str) -> Result<PathBuf, WorldError> {
+    let world_path = world_path(saves_path, world)?;
+
+    let backups_dir = root_path.join(WORLD_BACKUPS_FOLDER);
+    std::fs::create_dir_all(&backups_dir)?;
+
+    let backup_path = backups_dir.join(format!("{}_{}.zip", chrono::Utc::now().timestamp_millis(), world));
+
+    // Write to a temporary file first so an interrupted backup never shows up as a valid one
+    let mut temp_path = backup_path.clone();
+    temp_path.add_extension("new");
+
+    let result = (|| -> Result<(), WorldError> {
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&temp_path)?);
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .large_file(true);
+        add_dir_to_zip(&mut writer, &world_path, world, options)?;
+        writer.finish()?;
+        Ok(())
+    })();
+
+    if let Err(err) = result {
+        _ = std::fs::remove_file(&temp_path);
+        return Err(err);
+    }
+
+    std::fs::rename(&temp_path, &backup_path)?;
+    Ok(backup_path)
+}
+
+fn add_dir_to_zip(
+    writer: &mut zip::ZipWriter<std::fs::File>,
+    dir: &Path,
+    prefix: &str,
+    options: zip::write::SimpleFileOptions,
+) -> Result<(), WorldError> {
+    writer.add_directory(prefix, options)?;
+
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let Some(file_name) = entry.file_name().to_str().map(str::to_owned) else {
+            log::warn!("Skipping non-UTF-8 file {:?} in world backup", entry.path());
+            continue;
+        };
+        // The lock is held by the game and can't be read on some platforms
+        if file_name == "session.lock" {
+            continue;
+        }
+
+        let path = entry.path();
+        let name = format!("{prefix}/{file_name}");
+
+        let metadata = std::fs::metadata(&path)?;
+        if metadata.is_dir() {
+            add_dir_to_zip(writer, &path, &name, options)?;
+        } else if metadata.is_file() {
+            writer.start_file(name, options)?;
+            std::io::copy(&mut std::fs::File::open(&path)?, writer)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn rotate_world_backups(root_path: &Path, world: &str, keep: u32) -> Result<(), WorldError> {
+    if keep == 0 {
+        return Ok(());
+    }
+
+    let backups_dir = root_path.join(WORLD_BACKUPS_FOLDER);
+    let backups = list_world_backups(root_path);
+    for backup in backups.iter().filter(|backup| &*backup.world == world).skip(keep as usize) {
+        std::fs::remove_file(backups_dir.join(&*backup.id))?;
+    }
+
+    Ok(())
+}
+
+fn restore_world_backup(root_path: &Path, saves_path: &Path, backup: &str) -> Result<PathBuf, WorldError> {
+    let backup_path = world_backup_path(root_path, backup)?;
+    let Some((_, world)) = parse_backup_name(backup) else {
+        return Err(WorldError::InvalidBackup(backup.into()));
+    };
+    if !crate::is_single_component_path(world) {
+        return Err(WorldError::InvalidBackup(backup.into()));
+    }
+
+    std::fs::create_dir_all(saves_path)?;
+
+    // Extract next to the world first, the existing world is only replaced once extraction succeeded
+    let extract_dir = saves_path.join(format!(".{world}.restoring"));
+    if extract_dir.exists() {
+        std::fs::remove_dir_all(&extract_dir)?;
+    }
+    std::fs::create_dir_all(&extract_dir)?;
+
+    let result = (|| -> Result<(), WorldError> {
+        let file = std::fs::File::open(&backup_path)?;
+        let archive = file.read_zip()?;
+        for entry in archive.entries() {
+            let Some(path) = SafePath::new(&entry.name) else {
+                continue;
+            };
+            let output_path = path.to_path(&extract_dir);
+            match entry.kind() {
+                rc_zip_sync::rc_zip::EntryKind::Directory => {
+                    std::fs::create_dir_all(output_path)?;
+                },
+                rc_zip_sync::rc_zip::EntryKind::File => {
+                    if let Some(parent) = output_path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    let mut outfile = std::fs::File::create(&output_path)?;
+                    std::io::copy(&mut entry.reader(), &mut outfile)?;
+                },
+                rc_zip_sync::rc_zip::EntryKind::Symlink => {},
+            }
+        }
+        Ok(())
+    })();
+
+    let extracted_world = extract_dir.join(world);
+    if result.is_err() || !extracted_world.join("level.dat").is_file() {
+        _ = std::fs::remove_dir_all(&extract_dir);
+        result?;
+        return Err(WorldError::InvalidBackup(backup.into()));
+    }
+
+    let world_path = saves_path.join(world);
+    if world_path.exists() {
+        trash::delete(&world_path)?;
+    }
+    std::fs::rename(&extracted_world, &world_path)?;
+    _ = std::fs::remove_dir_all(&extract_dir);
+
+    Ok(world_path)
+}
+
+pub(crate) fn read_level_dat(world_path: &Path) -> Result<nbt::NBT, WorldError> {
+    let compressed = std::fs::read(world_path.join("level.dat"))?;
+    let mut decompressed = Vec::new();
+    flate2::bufread::GzDecoder::new(compressed.as_slice()).read_to_end(&mut decompressed)?;
+
+    let mut nbt_data = decompressed.as_slice();
+    nbt::decode::read_named(&mut nbt_data).map_err(WorldError::InvalidLevelDat)
+}
+
+pub(crate) fn write_level_dat(world_path: &Path, nbt: &nbt::NBT) -> Result<(), WorldError> {
+    let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&nbt::encode::write_named(nbt))?;
+    crate::write_safe(&world_path.join("level.dat"), &encoder.finish()?)?;
+    Ok(())
+}
+
+fn set_level_name(world_path: &Path, name: &str) -> Result<(), WorldError> {
+    let mut nbt = read_level_dat(world_path)?;
+
+    let Some(mut root) = nbt.as_compound_mut() else {
+        return Err(WorldError::InvalidLevelDat(anyhow::anyhow!("Unable to get root compound")));
+    };
+    let Some(mut data) = root.find_compound_mut("Data") else {
+        return Err(WorldError::InvalidLevelDat(anyhow::anyhow!("Unable to get Data")));
+    };
+    data.insert_string("LevelName", name.to_string());
+
+    write_level_dat(world_path, &nbt)
+}
+
+fn copy_world(
+    from_saves: &Path,
+    to_saves: &Path,
+    world: &str,
+    move_world: bool,
+    content_library_dir: &Path,
+) -> Result<(PathBuf, PathBuf), WorldError> {
+    let from = world_path(from_saves, world)?;
+
+    std::fs::create_dir_all(to_saves)?;
+    if from_saves.canonicalize()? == to_saves.canonicalize()? {
+        return Err(WorldError::SameSavesFolder);
+    }
+
+    let mut to = to_saves.join(world);
+    let mut suffix = 2;
+    while to.exists() {
+        to = to_saves.join(format!("{world} ({suffix})"));
+        suffix += 1;
+    }
+
+    if move_world && std::fs::rename(&from, &to).is_ok() {
+        return Ok((from, to));
+    }
+
+    if let Err(err) = crate::snapshot::copy_dir_all(&from, &to, content_library_dir) {
+        _ = std::fs::remove_dir_all(&to);
+        return Err(err.into());
+    }
+
+    if move_world {
+        std::fs::remove_dir_all(&from)?;
+    }
+
+    Ok((from, to))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::{WORLD_BACKUPS_FOLDER, create_world_backup, list_world_backups, restore_world_backup, rotate_world_backups};
+
+    fn create_world(saves_path: &Path, world: &str) {
+        let world_path = saves_path.join(world);
+        std::fs::create_dir_all(world_path.join("region")).unwrap();
+        std::fs::write(world_path.join("level.dat"), b"level").unwrap();
+        std::fs::write(world_path.join("region").join("r.0.0.mca"), b"region").unwrap();
+    }
+
+    #[test]
+    fn test_rotate_world_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let backups_dir = dir.path().join(WORLD_BACKUPS_FOLDER);
+        std::fs::create_dir_all(&backups_dir).unwrap();
+        for created in 1..=5 {
+            std::fs::write(backups_dir.join(format!("{created}_World.zip")), b"").unwrap();
+        }
+        std::fs::write(backups_dir.join("1_Other.zip"), b"").unwrap();
+
+        // Zero keeps every backup
+        rotate_world_backups(dir.path(), "World", 0).unwrap();
+        assert_eq!(list_world_backups(dir.path()).len(), 6);
+
+        rotate_world_backups(dir.path(), "World", 2).unwrap();
+        let backups: Vec<String> = list_world_backups(dir.path()).into_iter().map(|backup| backup.id.to_string()).collect();
+        assert_eq!(backups, ["5_World.zip", "4_World.zip", "1_Other.zip"]);
+    }
+
+    #[test]
+    fn test_backup_and_restore_world() {
+        let dir = tempfile::tempdir().unwrap();
+        let saves_path = dir.path().join("saves");
+        create_world(&saves_path, "World");
+
+        let backup_path = create_world_backup(dir.path(), &saves_path, "World").unwrap();
+        let backup = backup_path.file_name().unwrap().to_str().unwrap();
+        let backups = list_world_backups(dir.path());
+        assert_eq!(backups.len(), 1);
+        assert_eq!(&*backups[0].world, "World");
+        assert_eq!(&*backups[0].id, backup);
+
+        std::fs::remove_dir_all(saves_path.join("World")).unwrap();
+
+        let world_path = restore_world_backup(dir.path(), &saves_path, backup).unwrap();
+        assert_eq!(world_path, saves_path.join("World"));
+        assert_eq!(std::fs::read(world_path.join("level.dat")).unwrap(), b"level");
+        assert_eq!(std::fs::read(world_path.join("region").join("r.0.0.mca")).unwrap(), b"region");
+        assert!(!saves_path.join(".World.restoring").exists());
+    }
+
+    #[test]
+    fn test_restore_invalid_backup() {
+        let dir = tempfile::tempdir().unwrap();
+        let saves_path = dir.path().join("saves");
+
+        assert!(restore_world_backup(dir.path(), &saves_path, "../World.zip").is_err());
+        assert!(restore_world_backup(dir.path(), &saves_path, "1_Missing.zip").is_err());
+        assert!(!saves_path.join("Missing").exists());
+    }
+}
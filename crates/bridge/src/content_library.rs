@@ -0,0 +1,39 @@
+use std::{path::Path, sync::Arc};
+
+use crate::instance::{ContentSummary, InstanceID};
+
+#[derive(Debug, Clone, Default)]
+pub struct ContentLibrary {
+    pub entries: Arc<[ContentLibraryEntry]>,
+    pub total_size: u64,
+    /// Space saved by linking files instead of keeping a separate copy in every instance
+    pub saved_size: u64,
+    /// Space that can be freed by pruning entries that aren't needed anymore
+    pub prunable_size: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentLibraryEntry {
+    pub hash: [u8; 20],
+    pub path: Arc<Path>,
+    pub size: u64,
+    pub summary: Option<Arc<ContentSummary>>,
+    pub links: Arc<[ContentLibraryLink]>,
+    /// Needed despite not being linked anywhere, e.g. as part of an installed modpack or to roll back an update
+    pub retained: bool,
+}
+
+impl ContentLibraryEntry {
+    pub fn is_prunable(&self) -> bool {
+        self.links.is_empty() && !self.retained
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentLibraryLink {
+    /// None for files in the synced folders
+    pub instance: Option<InstanceID>,
+    pub instance_name: Arc<str>,
+    /// Relative to the instance folder
+    pub path: Arc<str>,
+}
@@ -0,0 +1,34 @@
+use std::{path::Path, sync::Arc};
+
+use crate::install::ContentInstallFile;
+
+#[derive(Debug, Clone)]
+pub struct ContentUpdatePlan {
+    pub entries: Arc<[ContentUpdateEntry]>,
+    // The last applied batch, if it can still be rolled back
+    pub last_batch: Option<ContentUpdateBatchSummary>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentUpdateBatchSummary {
+    pub created: i64,
+    pub count: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentUpdateEntry {
+    pub name: Arc<str>,
+    pub path: Arc<Path>,
+    pub hash: [u8; 20],
+    pub old_version: Arc<str>,
+    pub new_version: Arc<str>,
+    /// Changelogs of the new version and every version released since the installed one, newest first
+    pub changelogs: Arc<[ContentChangelog]>,
+    pub install: ContentInstallFile,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContentChangelog {
+    pub version: Arc<str>,
+    pub changelog: Arc<str>,
+}
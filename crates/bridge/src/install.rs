@@ -47,6 +47,15 @@ pub enum ContentDownload {
         sha1: Arc<str>,
         size: usize,
     },
+    Curseforge {
+        project_id: u32,
+        file_id: Option<u32>,
+        install_dependencies: bool,
+    },
+    /// Url without a known hash, the hash is computed once the file is downloaded
+    UnverifiedUrl {
+        url: Arc<str>,
+    },
     File {
         path: PathBuf,
     }
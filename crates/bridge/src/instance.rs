@@ -1,6 +1,6 @@
 use std::{collections::HashSet, path::Path, sync::Arc};
 
-use schema::{aux::AuxDisabledChildren, content::ContentSource, modification::ModrinthModpackFileDownload};
+use schema::{aux::{AuxDisabledChildren, AuxPinned}, content::ContentSource, loader::Loader, modification::ModrinthModpackFileDownload, resourcepack::PackFormatRange};
 
 use crate::safe_path::SafePath;
 
@@ -48,15 +48,171 @@ pub struct InstanceWorldSummary {
     pub level_path: Arc<Path>,
     pub last_played: i64,
     pub png_icon: Option<Arc<[u8]>>,
+    pub details: Arc<WorldDetails>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorldDetails {
+    pub game_mode: Option<WorldGameMode>,
+    pub hardcore: bool,
+    pub difficulty: Option<WorldDifficulty>,
+    pub seed: Option<i64>,
+    pub game_version: Option<Arc<str>>,
+    pub data_version: Option<i32>,
+    pub enabled_data_packs: Arc<[Arc<str>]>,
+    pub allow_commands: bool,
+    // Calculated after the summary has been loaded, walking every file of a large world is slow
+    pub size: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldGameMode {
+    Survival,
+    Creative,
+    Adventure,
+    Spectator,
+}
+
+impl WorldGameMode {
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Survival),
+            1 => Some(Self::Creative),
+            2 => Some(Self::Adventure),
+            3 => Some(Self::Spectator),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Survival => "Survival",
+            Self::Creative => "Creative",
+            Self::Adventure => "Adventure",
+            Self::Spectator => "Spectator",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorldDifficulty {
+    Peaceful,
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl WorldDifficulty {
+    pub fn from_id(id: i32) -> Option<Self> {
+        match id {
+            0 => Some(Self::Peaceful),
+            1 => Some(Self::Easy),
+            2 => Some(Self::Normal),
+            3 => Some(Self::Hard),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Peaceful => "Peaceful",
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct InstanceServerSummary {
+    pub index: usize,
     pub name: Arc<str>,
     pub ip: Arc<str>,
     pub png_icon: Option<Arc<[u8]>>,
 }
 
+#[derive(Debug, Clone)]
+pub enum ServerListEdit {
+    Add {
+        name: Arc<str>,
+        ip: Arc<str>,
+    },
+    Edit {
+        index: usize,
+        name: Arc<str>,
+        ip: Arc<str>,
+    },
+    Remove {
+        index: usize,
+    },
+    Move {
+        from: usize,
+        to: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerStatus {
+    pub latency_ms: u32,
+    pub motd: Arc<str>,
+    pub version: Arc<str>,
+    pub protocol: i32,
+    pub players_online: i32,
+    pub players_max: i32,
+    pub favicon: Option<Arc<[u8]>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstanceSnapshotSummary {
+    pub id: Arc<str>,
+    pub created: i64,
+    pub note: Arc<str>,
+    pub size: u64,
+    pub includes_worlds: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstanceWorldBackupSummary {
+    pub id: Arc<str>,
+    pub world: Arc<str>,
+    pub created: i64,
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ResourcePackOrder {
+    pub pack_format: Option<u32>,
+    pub enabled: Vec<Arc<str>>,
+    pub incompatible: Vec<Arc<str>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstanceScreenshotSummary {
+    pub filename: Arc<str>,
+    pub path: Arc<Path>,
+    // Screenshots of synced instances share the same canonical path
+    pub canonical_path: Arc<Path>,
+    pub created: i64,
+    pub size: u64,
+    pub thumbnail: Option<Arc<[u8]>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstanceDatapackSummary {
+    pub filename: Arc<str>,
+    pub path: Arc<Path>,
+    pub enabled: bool,
+    pub description: Arc<str>,
+    pub png_icon: Option<Arc<[u8]>>,
+    pub pack_format: Option<PackFormatRange>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct WorldDatapacks {
+    pub pack_format: Option<u32>,
+    pub datapacks: Arc<[InstanceDatapackSummary]>,
+}
+
 #[derive(Debug, Clone)]
 pub struct InstanceContentSummary {
     pub content_summary: Arc<ContentSummary>,
@@ -68,6 +224,7 @@ pub struct InstanceContentSummary {
     pub enabled: bool,
     pub content_source: ContentSource,
     pub disabled_children: Arc<AuxDisabledChildren>,
+    pub pinned: Option<AuxPinned>,
 }
 
 #[derive(Debug, Clone)]
@@ -80,6 +237,86 @@ pub struct ContentSummary {
     pub png_icon: Option<Arc<[u8]>>,
     pub update_status: Arc<AtomicContentUpdateStatus>,
     pub extra: ContentType,
+    pub mod_info: Option<Arc<ModInfo>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModInfo {
+    pub version: Arc<str>,
+    /// Other mod ids this jar provides, either as aliases or as jar-in-jar children
+    pub provides: Arc<[ProvidedMod]>,
+    pub dependencies: Arc<[ModDependency]>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ProvidedMod {
+    pub id: Arc<str>,
+    pub version: Arc<str>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModDependency {
+    pub id: Arc<str>,
+    pub kind: ModDependencyKind,
+    /// Fabric version predicates or Maven version ranges, any of which is accepted
+    pub versions: Arc<[Arc<str>]>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModDependencyKind {
+    Required,
+    Optional,
+    Recommended,
+    Breaks,
+    Conflicts,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModDependencyIssue {
+    pub mod_name: Arc<str>,
+    pub filename: Arc<str>,
+    pub dependency: Arc<str>,
+    pub versions: Arc<str>,
+    pub kind: ModDependencyIssueKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModDependencyIssueKind {
+    Missing,
+    MissingRecommended,
+    WrongVersion { found: Arc<str> },
+    WrongRecommendedVersion { found: Arc<str> },
+    Breaks { found: Arc<str> },
+    Conflicts { found: Arc<str> },
+}
+
+impl ModDependencyIssueKind {
+    pub fn is_error(&self) -> bool {
+        matches!(self, Self::Missing | Self::WrongVersion { .. } | Self::Breaks { .. })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LaunchProblem {
+    pub content_id: InstanceContentID,
+    /// Set if the mod comes from an installed modpack instead of its own jar
+    pub modpack_child: Option<ModpackChildRef>,
+    pub mod_name: Arc<str>,
+    pub kind: LaunchProblemKind,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModpackChildRef {
+    pub id: Option<Arc<str>>,
+    pub name: Option<Arc<str>>,
+    pub filename: Arc<str>,
+}
+
+#[derive(Debug, Clone)]
+pub enum LaunchProblemKind {
+    WrongLoader { loader: Loader },
+    WrongMinecraftVersion { versions: Arc<str> },
+    Duplicate { id: Arc<str>, kept: Arc<str> },
 }
 
 #[derive(Debug, Clone)]
@@ -93,7 +330,12 @@ pub enum ContentType {
         summaries: Arc<[Option<Arc<ContentSummary>>]>,
         overrides: Arc<[(SafePath, Arc<[u8]>)]>,
     },
-    ResourcePack,
+    ResourcePack {
+        pack_format: Option<PackFormatRange>,
+    },
+    ShaderPack {
+        has_properties: bool,
+    },
 }
 
 
@@ -106,12 +348,14 @@ pub enum ContentUpdateStatus {
     ErrorInvalidHash,
     AlreadyUpToDate,
     Modrinth,
+    Curseforge,
+    GithubRelease,
 }
 
 impl ContentUpdateStatus {
     pub fn can_update(&self) -> bool {
         match self {
-            ContentUpdateStatus::Modrinth => true,
+            ContentUpdateStatus::Modrinth | ContentUpdateStatus::Curseforge | ContentUpdateStatus::GithubRelease => true,
             _ => false,
         }
     }
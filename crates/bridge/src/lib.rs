@@ -1,4 +1,6 @@
 pub mod account;
+pub mod content_library;
+pub mod content_update;
 pub mod game_output;
 pub mod handle;
 pub mod install;
@@ -6,7 +8,10 @@ pub mod instance;
 pub mod keep_alive;
 pub mod message;
 pub mod meta;
+pub mod migration;
 pub mod modal_action;
+pub mod mod_version;
+pub mod nbt_file;
 pub mod safe_path;
 pub mod serial;
 pub mod notify_signal;
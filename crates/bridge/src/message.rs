@@ -6,9 +6,10 @@ use std::{
 
 use enumset::{EnumSet, EnumSetType};
 use schema::{
-    backend_config::{BackendConfig, SyncTarget}, instance::{
-        InstanceConfiguration, InstanceJvmBinaryConfiguration, InstanceJvmFlagsConfiguration,
-        InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration,
+    aux::AuxPinned, backend_config::{BackendConfig, SyncTarget}, instance::{
+        InstanceConfiguration, InstanceDefaults, InstanceEnvVarsConfiguration, InstanceJvmBinaryConfiguration,
+        InstanceJvmFlagsConfiguration, InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration,
+        InstanceResolutionConfiguration,
     }, loader::Loader, pandora_update::{UpdateManifest, UpdateManifestExe, UpdatePrompt}
 };
 use ustr::Ustr;
@@ -16,15 +17,20 @@ use uuid::Uuid;
 
 use crate::{
     account::Account,
+    content_library::ContentLibrary,
+    content_update::{ContentUpdateEntry, ContentUpdatePlan},
     game_output::GameOutputLogLevel,
     install::ContentInstall,
     instance::{
-        InstanceContentID, InstanceContentSummary, InstanceID, InstanceServerSummary, InstanceStatus,
-        InstanceWorldSummary,
+        InstanceContentID, InstanceContentSummary, InstanceID, InstanceScreenshotSummary, InstanceServerSummary, InstanceSnapshotSummary,
+        InstanceStatus, InstanceWorldBackupSummary, InstanceWorldSummary, LaunchProblem, ModDependencyIssue, ServerListEdit, ServerStatus,
+        ResourcePackOrder, WorldDatapacks,
     },
     keep_alive::{KeepAlive, KeepAliveHandle},
     meta::{MetadataRequest, MetadataResult},
+    migration::InstanceMigrationPlan,
     modal_action::ModalAction,
+    nbt_file::NbtFile,
 };
 
 #[derive(Debug)]
@@ -37,6 +43,7 @@ pub enum MessageToBackend {
         name: Ustr,
         version: Ustr,
         loader: Loader,
+        template: Option<InstanceID>,
     },
     DeleteInstance {
         id: InstanceID,
@@ -53,6 +60,19 @@ pub enum MessageToBackend {
         id: InstanceID,
         loader: Loader
     },
+    PlanInstanceMigration {
+        id: InstanceID,
+        minecraft_version: Ustr,
+        loader: Loader,
+        modal_action: ModalAction,
+        channel: tokio::sync::oneshot::Sender<InstanceMigrationPlan>,
+    },
+    ApplyInstanceMigration {
+        id: InstanceID,
+        plan: InstanceMigrationPlan,
+        disable_incompatible: bool,
+        modal_action: ModalAction,
+    },
     SetInstancePreferredLoaderVersion {
         id: InstanceID,
         loader_version: Option<&'static str>
@@ -73,9 +93,37 @@ pub enum MessageToBackend {
         id: InstanceID,
         linux_wrapper: InstanceLinuxWrapperConfiguration,
     },
+    SetInstanceEnvVars {
+        id: InstanceID,
+        env_vars: InstanceEnvVarsConfiguration,
+    },
+    SetInstanceResolution {
+        id: InstanceID,
+        resolution: InstanceResolutionConfiguration,
+    },
+    SetInstanceGroup {
+        id: InstanceID,
+        group: Option<Arc<str>>,
+    },
+    SetInstanceTags {
+        id: InstanceID,
+        tags: Vec<Arc<str>>,
+    },
+    SetInstanceIcon {
+        id: InstanceID,
+        icon: Option<Arc<[u8]>>,
+    },
+    SetInstanceDescription {
+        id: InstanceID,
+        description: Arc<str>,
+    },
     KillInstance {
         id: InstanceID,
     },
+    CheckLaunchProblems {
+        id: InstanceID,
+        channel: tokio::sync::oneshot::Sender<Vec<LaunchProblem>>,
+    },
     StartInstance {
         id: InstanceID,
         quick_play: Option<QuickPlayLaunch>,
@@ -87,12 +135,135 @@ pub enum MessageToBackend {
     RequestLoadServers {
         id: InstanceID,
     },
+    EditServerList {
+        id: InstanceID,
+        edit: ServerListEdit,
+    },
+    CopyServer {
+        id: InstanceID,
+        index: usize,
+        target: InstanceID,
+    },
+    PingServer {
+        address: Arc<str>,
+        refresh: bool,
+        channel: tokio::sync::oneshot::Sender<Option<ServerStatus>>,
+    },
     RequestLoadMods {
         id: InstanceID,
     },
+    CheckModDependencies {
+        id: InstanceID,
+        channel: tokio::sync::oneshot::Sender<Vec<ModDependencyIssue>>,
+    },
     RequestLoadResourcePacks {
         id: InstanceID,
     },
+    RequestLoadShaderPacks {
+        id: InstanceID,
+    },
+    GetSelectedShaderPack {
+        id: InstanceID,
+        channel: tokio::sync::oneshot::Sender<Option<Arc<str>>>,
+    },
+    GetResourcePackOrder {
+        id: InstanceID,
+        channel: tokio::sync::oneshot::Sender<ResourcePackOrder>,
+    },
+    SetResourcePackOrder {
+        id: InstanceID,
+        enabled: Vec<Arc<str>>,
+        incompatible: Vec<Arc<str>>,
+        channel: tokio::sync::oneshot::Sender<ResourcePackOrder>,
+    },
+    RequestLoadSnapshots {
+        id: InstanceID,
+    },
+    CreateInstanceSnapshot {
+        id: InstanceID,
+        note: Arc<str>,
+        include_worlds: bool,
+        modal_action: ModalAction,
+    },
+    RestoreInstanceSnapshot {
+        id: InstanceID,
+        snapshot: Arc<str>,
+        modal_action: ModalAction,
+    },
+    DeleteInstanceSnapshot {
+        id: InstanceID,
+        snapshot: Arc<str>,
+    },
+    RequestLoadWorldBackups {
+        id: InstanceID,
+    },
+    BackupWorld {
+        id: InstanceID,
+        world: Arc<str>,
+        modal_action: ModalAction,
+    },
+    RestoreWorldBackup {
+        id: InstanceID,
+        backup: Arc<str>,
+        modal_action: ModalAction,
+    },
+    DeleteWorldBackup {
+        id: InstanceID,
+        backup: Arc<str>,
+    },
+    RenameWorld {
+        id: InstanceID,
+        world: Arc<str>,
+        name: Arc<str>,
+    },
+    DeleteWorld {
+        id: InstanceID,
+        world: Arc<str>,
+    },
+    CopyWorld {
+        id: InstanceID,
+        world: Arc<str>,
+        target: InstanceID,
+        move_world: bool,
+        modal_action: ModalAction,
+    },
+    RequestLoadWorldDatapacks {
+        id: InstanceID,
+        world: Arc<str>,
+    },
+    GetDataVersion {
+        minecraft_version: Ustr,
+        channel: tokio::sync::oneshot::Sender<Option<i32>>,
+    },
+    SetDatapackEnabled {
+        id: InstanceID,
+        world: Arc<str>,
+        datapack: Arc<str>,
+        enabled: bool,
+    },
+    RequestLoadScreenshots {
+        id: InstanceID,
+    },
+    DeleteScreenshot {
+        id: InstanceID,
+        screenshot: Arc<str>,
+    },
+    ExportScreenshot {
+        id: InstanceID,
+        screenshot: Arc<str>,
+        target: PathBuf,
+    },
+    GetGameOptions {
+        id: InstanceID,
+        channel: tokio::sync::oneshot::Sender<Vec<(Arc<str>, Arc<str>)>>,
+    },
+    SetGameOptions {
+        id: InstanceID,
+        options: Vec<(Arc<str>, Arc<str>)>,
+    },
+    ApplyGameOptionsToAllInstances {
+        options: Vec<(Arc<str>, Arc<str>)>,
+    },
     SetContentEnabled {
         id: InstanceID,
         content_ids: Vec<InstanceContentID>,
@@ -106,6 +277,12 @@ pub enum MessageToBackend {
         child_filename: Arc<str>,
         enabled: bool,
     },
+    SetContentPinned {
+        id: InstanceID,
+        content_ids: Vec<InstanceContentID>,
+        /// None to unpin, otherwise the note explaining why the content is pinned
+        pinned: Option<AuxPinned>,
+    },
     DeleteContent {
         id: InstanceID,
         content_ids: Vec<InstanceContentID>,
@@ -124,6 +301,27 @@ pub enum MessageToBackend {
         content_id: InstanceContentID,
         modal_action: ModalAction,
     },
+    PlanContentUpdates {
+        id: InstanceID,
+        modal_action: ModalAction,
+        channel: tokio::sync::oneshot::Sender<ContentUpdatePlan>,
+    },
+    ApplyContentUpdates {
+        id: InstanceID,
+        entries: Arc<[ContentUpdateEntry]>,
+        modal_action: ModalAction,
+    },
+    RollbackContentUpdates {
+        id: InstanceID,
+        modal_action: ModalAction,
+    },
+    GetContentLibrary {
+        channel: tokio::sync::oneshot::Sender<ContentLibrary>,
+    },
+    PruneContentLibrary {
+        modal_action: ModalAction,
+        channel: tokio::sync::oneshot::Sender<ContentLibrary>,
+    },
     Sleep5s,
     ReadLog {
         path: Arc<Path>,
@@ -139,6 +337,18 @@ pub enum MessageToBackend {
     GetBackendConfiguration {
         channel: tokio::sync::oneshot::Sender<BackendConfig>,
     },
+    ReadNbtFile {
+        path: Arc<Path>,
+        modal_action: ModalAction,
+        channel: tokio::sync::oneshot::Sender<NbtFile>,
+    },
+    WriteNbtFile {
+        path: Arc<Path>,
+        file: NbtFile,
+        backup: bool,
+        modal_action: ModalAction,
+        channel: tokio::sync::oneshot::Sender<()>,
+    },
     SetSyncing {
         target: SyncTarget,
         value: bool,
@@ -166,6 +376,15 @@ pub enum MessageToBackend {
     SetOpenGameOutputAfterLaunching {
         value: bool,
     },
+    SetInstanceDefaults {
+        defaults: InstanceDefaults,
+    },
+    SetWorldBackupsToKeep {
+        value: u32,
+    },
+    SetCurseforgeApiKey {
+        key: Option<Arc<str>>,
+    },
     CreateInstanceShortcut {
         id: InstanceID,
         path: PathBuf
@@ -183,10 +402,13 @@ pub enum MessageToFrontend {
         name: Ustr,
         dot_minecraft_folder: Arc<Path>,
         configuration: InstanceConfiguration,
+        icon: Option<Arc<[u8]>>,
+        description: Arc<str>,
         worlds_state: Arc<AtomicBridgeDataLoadState>,
         servers_state: Arc<AtomicBridgeDataLoadState>,
         mods_state: Arc<AtomicBridgeDataLoadState>,
         resource_packs_state: Arc<AtomicBridgeDataLoadState>,
+        shader_packs_state: Arc<AtomicBridgeDataLoadState>,
     },
     InstanceRemoved {
         id: InstanceID,
@@ -196,6 +418,8 @@ pub enum MessageToFrontend {
         name: Ustr,
         dot_minecraft_folder: Arc<Path>,
         configuration: InstanceConfiguration,
+        icon: Option<Arc<[u8]>>,
+        description: Arc<str>,
         status: InstanceStatus,
     },
     InstanceWorldsUpdated {
@@ -214,6 +438,27 @@ pub enum MessageToFrontend {
         id: InstanceID,
         resource_packs: Arc<[InstanceContentSummary]>,
     },
+    InstanceShaderPacksUpdated {
+        id: InstanceID,
+        shader_packs: Arc<[InstanceContentSummary]>,
+    },
+    InstanceSnapshotsUpdated {
+        id: InstanceID,
+        snapshots: Arc<[InstanceSnapshotSummary]>,
+    },
+    InstanceWorldBackupsUpdated {
+        id: InstanceID,
+        backups: Arc<[InstanceWorldBackupSummary]>,
+    },
+    InstanceWorldDatapacksUpdated {
+        id: InstanceID,
+        world: Arc<str>,
+        datapacks: WorldDatapacks,
+    },
+    InstanceScreenshotsUpdated {
+        id: InstanceID,
+        screenshots: Arc<[InstanceScreenshotSummary]>,
+    },
     CreateGameOutputWindow {
         id: usize,
         keep_alive: KeepAlive,
@@ -237,6 +482,10 @@ pub enum MessageToFrontend {
     MoveInstanceToTop {
         id: InstanceID,
     },
+    InstanceRenamed {
+        old_name: Ustr,
+        new_name: Ustr,
+    },
     MetadataResult {
         request: MetadataRequest,
         result: Result<MetadataResult, Arc<str>>,
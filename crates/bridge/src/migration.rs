@@ -0,0 +1,40 @@
+use std::{path::Path, sync::Arc};
+
+use schema::loader::Loader;
+use ustr::Ustr;
+
+use crate::install::ContentInstallFile;
+
+#[derive(Debug, Clone)]
+pub struct InstanceMigrationPlan {
+    pub minecraft_version: Ustr,
+    pub loader: Loader,
+    pub entries: Arc<[InstanceMigrationEntry]>,
+}
+
+impl InstanceMigrationPlan {
+    pub fn count(&self, filter: impl Fn(&InstanceMigrationStatus) -> bool) -> usize {
+        self.entries.iter().filter(|entry| filter(&entry.status)).count()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InstanceMigrationEntry {
+    pub name: Arc<str>,
+    pub path: Arc<Path>,
+    pub enabled: bool,
+    pub status: InstanceMigrationStatus,
+}
+
+#[derive(Debug, Clone)]
+pub enum InstanceMigrationStatus {
+    // The installed file is already the latest version for the target
+    Compatible,
+    Update {
+        version: Arc<str>,
+        install: ContentInstallFile,
+    },
+    Incompatible,
+    // Manually installed content can't be checked
+    Unknown,
+}
@@ -0,0 +1,256 @@
+use std::{cmp::Ordering, sync::Arc};
+
+/// Checks a mod version against Fabric version predicates, or Maven version ranges as used by Forge and NeoForge
+pub fn version_matches(version: &str, predicates: &[Arc<str>], maven: bool) -> bool {
+    // Unexpanded build placeholders, e.g. ${file.jarVersion}
+    if version.contains("${") {
+        return true;
+    }
+
+    predicates.is_empty() || predicates.iter().any(|predicate| {
+        if maven {
+            maven_range_matches(version, predicate)
+        } else {
+            predicate.split_whitespace().all(|constraint| fabric_constraint_matches(version, constraint))
+        }
+    })
+}
+
+fn fabric_constraint_matches(version: &str, constraint: &str) -> bool {
+    if constraint == "*" {
+        return true;
+    }
+
+    let (operator, target) = [">=", "<=", ">", "<", "=", "~", "^"].iter()
+        .find_map(|operator| constraint.strip_prefix(operator).map(|target| (*operator, target)))
+        .unwrap_or(("", constraint));
+
+    // A trailing dash allows pre-releases of the target version to match
+    let (version, target) = match target.strip_suffix('-') {
+        Some(target) => (split_pre_release(version).0, target),
+        None => (version, target),
+    };
+
+    if operator.is_empty() && target.split('.').any(|part| matches!(part, "x" | "X" | "*")) {
+        let mut parts = split_pre_release(version).0.split(['.', '-']);
+        return target.split('.')
+            .take_while(|part| !matches!(*part, "x" | "X" | "*"))
+            .all(|part| compare_part(parts.next().unwrap_or("0"), part) == Ordering::Equal);
+    }
+
+    let ordering = compare_versions(version, target);
+    match operator {
+        ">=" => ordering != Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        "<" => ordering == Ordering::Less,
+        "~" => ordering != Ordering::Less && next_version(target, 1).is_none_or(|upper| compare_versions(version, &upper) == Ordering::Less),
+        "^" => ordering != Ordering::Less && next_version(target, 0).is_none_or(|upper| compare_versions(version, &upper) == Ordering::Less),
+        _ => ordering == Ordering::Equal,
+    }
+}
+
+// The smallest version that no longer matches a ~ (minor) or ^ (major) constraint
+fn next_version(target: &str, index: usize) -> Option<String> {
+    let parts: Vec<&str> = split_pre_release(target).0.split('.').collect();
+    let index = index.min(parts.len() - 1);
+    let bumped = parts[index].parse::<u64>().ok()? + 1;
+
+    let mut next = parts[..index].join(".");
+    if !next.is_empty() {
+        next.push('.');
+    }
+    next.push_str(&bumped.to_string());
+    Some(next)
+}
+
+fn maven_range_matches(version: &str, range: &str) -> bool {
+    let range = range.trim();
+    // A plain version without brackets is only a recommendation and accepts anything
+    if range.contains("${") || !range.starts_with(['[', '(']) {
+        return true;
+    }
+
+    let mut rest = range;
+    while let Some(start) = rest.find(['[', '(']) {
+        let Some(end) = rest[start..].find([']', ')']).map(|end| start + end) else {
+            break;
+        };
+        let inclusive_lower = rest[start..].starts_with('[');
+        let inclusive_upper = rest[end..].starts_with(']');
+
+        let matched = match rest[start + 1..end].split_once(',') {
+            None => compare_versions(version, rest[start + 1..end].trim()) == Ordering::Equal,
+            Some((lower, upper)) => {
+                let lower = lower.trim();
+                let upper = upper.trim();
+                let above_lower = lower.is_empty() || match compare_versions(version, lower) {
+                    Ordering::Greater => true,
+                    Ordering::Equal => inclusive_lower,
+                    Ordering::Less => false,
+                };
+                let below_upper = upper.is_empty() || match compare_versions(version, upper) {
+                    Ordering::Less => true,
+                    Ordering::Equal => inclusive_upper,
+                    Ordering::Greater => false,
+                };
+                above_lower && below_upper
+            },
+        };
+        if matched {
+            return true;
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    false
+}
+
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a_core, a_pre) = split_pre_release(a);
+    let (b_core, b_pre) = split_pre_release(b);
+
+    compare_parts(a_core, b_core).then_with(|| match (a_pre, b_pre) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a_pre), Some(b_pre)) => compare_parts(a_pre, b_pre),
+    })
+}
+
+// Build metadata is ignored, and a dash only starts a pre-release if it isn't followed by a number (e.g. 1.20.1-47.2.0)
+fn split_pre_release(version: &str) -> (&str, Option<&str>) {
+    let version = version.split_once('+').map_or(version, |(version, _)| version);
+    let version = version.strip_prefix('v').filter(|rest| rest.starts_with(|c: char| c.is_ascii_digit())).unwrap_or(version);
+
+    let mut search_from = 0;
+    while let Some(index) = version[search_from..].find('-').map(|index| search_from + index) {
+        if !version[index + 1..].starts_with(|c: char| c.is_ascii_digit()) {
+            return (&version[..index], Some(&version[index + 1..]));
+        }
+        search_from = index + 1;
+    }
+    (version, None)
+}
+
+fn compare_parts(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split(['.', '-']);
+    let mut b_parts = b.split(['.', '-']);
+    loop {
+        match (a_parts.next(), b_parts.next()) {
+            (None, None) => return Ordering::Equal,
+            (a_part, b_part) => {
+                let ordering = compare_part(a_part.unwrap_or("0"), b_part.unwrap_or("0"));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            },
+        }
+    }
+}
+
+fn compare_part(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Greater,
+        (Err(_), Ok(_)) => Ordering::Less,
+        (Err(_), Err(_)) => a.cmp(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(version: &str, predicates: &[&str], maven: bool) -> bool {
+        let predicates: Vec<Arc<str>> = predicates.iter().map(|predicate| Arc::from(*predicate)).collect();
+        version_matches(version, &predicates, maven)
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.2.3", "1.2.3"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2", "1.2.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.10.0", "1.9.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.0-beta", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0-beta.2", "1.0.0-beta.10"), Ordering::Less);
+        assert_eq!(compare_versions("1.0.0+build.5", "1.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("v2.0.0", "2.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.20.1-47.2.0", "1.20.1-47.1.0"), Ordering::Greater);
+        assert_eq!(compare_versions("1.20.1-47.2.0", "1.20.1"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_fabric_constraint_matches() {
+        assert!(fabric_constraint_matches("1.2.3", "*"));
+        assert!(fabric_constraint_matches("1.2.3", ">=1.2.0"));
+        assert!(!fabric_constraint_matches("1.1.9", ">=1.2.0"));
+        assert!(fabric_constraint_matches("1.2.3", "<2"));
+        assert!(!fabric_constraint_matches("1.2.3", ">1.2.3"));
+        assert!(fabric_constraint_matches("1.2.3", "=1.2.3"));
+        assert!(fabric_constraint_matches("1.2.3", "1.2.3"));
+        assert!(!fabric_constraint_matches("1.2.4", "1.2.3"));
+    }
+
+    #[test]
+    fn test_fabric_tilde_and_caret() {
+        assert!(fabric_constraint_matches("1.2.5", "~1.2.3"));
+        assert!(!fabric_constraint_matches("1.3.0", "~1.2.3"));
+        assert!(!fabric_constraint_matches("1.2.2", "~1.2.3"));
+        assert!(fabric_constraint_matches("1.9.0", "^1.2.3"));
+        assert!(!fabric_constraint_matches("2.0.0", "^1.2.3"));
+        assert!(!fabric_constraint_matches("1.2.2", "^1.2.3"));
+    }
+
+    #[test]
+    fn test_fabric_wildcards() {
+        assert!(fabric_constraint_matches("1.20.4", "1.20.x"));
+        assert!(fabric_constraint_matches("1.20", "1.20.X"));
+        assert!(!fabric_constraint_matches("1.21.0", "1.20.x"));
+        assert!(fabric_constraint_matches("1.5.0", "1.*"));
+        assert!(!fabric_constraint_matches("2.0.0", "1.x"));
+    }
+
+    #[test]
+    fn test_fabric_trailing_dash() {
+        assert!(!fabric_constraint_matches("1.0.0-beta.1", ">=1.0.0"));
+        assert!(fabric_constraint_matches("1.0.0-beta.1", ">=1.0.0-"));
+        assert!(fabric_constraint_matches("1.0.1", ">=1.0.0-"));
+        assert!(!fabric_constraint_matches("0.9.0-beta.1", ">=1.0.0-"));
+    }
+
+    #[test]
+    fn test_maven_range_matches() {
+        assert!(maven_range_matches("47.2.0", "[47,)"));
+        assert!(!maven_range_matches("46.0.1", "[47,)"));
+        assert!(maven_range_matches("1.0", "[1.0]"));
+        assert!(!maven_range_matches("1.1", "[1.0]"));
+        assert!(maven_range_matches("1.5", "(1.0,2.0)"));
+        assert!(!maven_range_matches("1.0", "(1.0,2.0)"));
+        assert!(!maven_range_matches("2.0", "(1.0,2.0)"));
+        assert!(maven_range_matches("2.0", "[1.0,2.0]"));
+        assert!(maven_range_matches("1.20.1-47.2.0", "[1.20.1-47.1.0,)"));
+        assert!(maven_range_matches("anything", "1.0"));
+        assert!(maven_range_matches("1.0", "[${forge_version},)"));
+    }
+
+    #[test]
+    fn test_maven_multi_range() {
+        assert!(maven_range_matches("0.5", "(,1.0],[1.2,)"));
+        assert!(!maven_range_matches("1.1", "(,1.0],[1.2,)"));
+        assert!(maven_range_matches("1.2", "(,1.0],[1.2,)"));
+        assert!(maven_range_matches("3.0", "(,1.0],[1.2,)"));
+    }
+
+    #[test]
+    fn test_version_matches() {
+        assert!(matches("1.2.3", &[], false));
+        assert!(matches("${file.jarVersion}", &["<1.0"], false));
+        assert!(matches("1.2.3", &[">=1.0 <2.0"], false));
+        assert!(!matches("2.1.0", &[">=1.0 <2.0"], false));
+        assert!(matches("2.1.0", &[">=1.0 <2.0", "2.1.x"], false));
+        assert!(matches("47.2.0", &["[47,)"], true));
+        assert!(!matches("46.0.0", &["[47,)"], true));
+    }
+}
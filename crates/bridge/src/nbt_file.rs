@@ -0,0 +1,22 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NbtCompression {
+    Uncompressed,
+    Gzip,
+    Zlib,
+}
+
+impl NbtCompression {
+    pub fn name(self) -> &'static str {
+        match self {
+            NbtCompression::Uncompressed => "Uncompressed",
+            NbtCompression::Gzip => "Gzip",
+            NbtCompression::Zlib => "Zlib",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct NbtFile {
+    pub nbt: nbt::NBT,
+    pub compression: NbtCompression,
+}
@@ -3,14 +3,15 @@ use std::{hash::{DefaultHasher, Hash, Hasher}, sync::{
 }};
 
 use bridge::{
-    handle::BackendHandle, instance::{AtomicContentUpdateStatus, InstanceID, InstanceContentID, InstanceContentSummary, ContentType, ContentSummary}, message::MessageToBackend
+    handle::BackendHandle, instance::{AtomicContentUpdateStatus, InstanceID, InstanceContentID, InstanceContentSummary, ContentType, ContentSummary}, message::MessageToBackend, mod_version::compare_versions
 };
 use gpui::{prelude::*, *};
 use gpui_component::{
-    button::{Button, ButtonVariants}, h_flex, list::{ListDelegate, ListItem, ListState}, switch::Switch, v_flex, ActiveTheme as _, Icon, IconName, IndexPath, Sizable
+    button::{Button, ButtonVariants}, h_flex, input::{Input, InputState}, list::{ListDelegate, ListItem, ListState}, switch::Switch, v_flex, ActiveTheme as _, Icon, IconName, IndexPath, Sizable, WindowExt
 };
 use parking_lot::Mutex;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+use schema::aux::AuxPinned;
 
 use crate::{interface_config::InterfaceConfig, png_render_cache};
 
@@ -25,6 +26,13 @@ struct ContentEntryChild {
     parent_enabled: bool,
 }
 
+struct DuplicateMod {
+    id: Arc<str>,
+    description: SharedString,
+    /// Standalone copies that aren't the newest version, deleted when keeping only the newest
+    older: Arc<[(InstanceContentID, Arc<str>)]>,
+}
+
 enum SummaryOrChild {
     Summary(InstanceContentSummary),
     Child(ContentEntryChild),
@@ -43,6 +51,8 @@ pub struct ContentListDelegate {
     selected: FxHashSet<u64>,
     selected_range: FxHashSet<u64>,
     last_clicked_non_range: Option<u64>,
+    pack_format: Option<u32>,
+    duplicates: FxHashMap<u64, DuplicateMod>,
 }
 
 impl ContentListDelegate {
@@ -60,9 +70,15 @@ impl ContentListDelegate {
             selected: FxHashSet::default(),
             selected_range: FxHashSet::default(),
             last_clicked_non_range: None,
+            pack_format: None,
+            duplicates: FxHashMap::default(),
         }
     }
 
+    pub fn set_pack_format(&mut self, pack_format: Option<u32>) {
+        self.pack_format = pack_format;
+    }
+
     pub fn render_summary(&self, summary: &InstanceContentSummary, selected: bool, expanded: bool, can_expand: bool, ix: usize, cx: &mut Context<ListState<Self>>) -> ListItem {
         let icon = if let Some(png_icon) = summary.content_summary.png_icon.as_ref() {
             png_render_cache::render(Arc::clone(png_icon), cx)
@@ -130,7 +146,38 @@ impl ContentListDelegate {
             }))
         };
 
-        let update_button = match summary.content_summary.update_status.load(Ordering::Relaxed) {
+        let pin_button = if let Some(pinned) = &summary.pinned {
+            let tooltip = if let Some(note) = &pinned.note {
+                SharedString::from(format!("Pinned: {}\nClick to unpin", note))
+            } else {
+                SharedString::new_static("Pinned, excluded from updates\nClick to unpin")
+            };
+            let backend_handle = self.backend_handle.clone();
+            Button::new(("pin", element_id)).info().icon(Icon::default().path("icons/pin.svg"))
+                .tooltip(tooltip)
+                .on_click(cx.listener(move |this, _, _, cx| {
+                    cx.stop_propagation();
+                    let content_ids = this.delegate().selected_or_single(element_id, content_id);
+                    backend_handle.send(MessageToBackend::SetContentPinned { id, content_ids, pinned: None });
+                }))
+        } else {
+            let backend_handle = self.backend_handle.clone();
+            Button::new(("pin", element_id)).ghost().icon(Icon::default().path("icons/pin.svg"))
+                .tooltip("Pin this version to exclude it from updates")
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    cx.stop_propagation();
+                    let content_ids = this.delegate().selected_or_single(element_id, content_id);
+                    open_pin_dialog(id, content_ids, backend_handle.clone(), window, cx);
+                }))
+        };
+
+        // Pinned content is never offered an update
+        let update_status = if summary.pinned.is_some() {
+            bridge::instance::ContentUpdateStatus::Unknown
+        } else {
+            summary.content_summary.update_status.load(Ordering::Relaxed)
+        };
+        let update_button = match update_status {
             bridge::instance::ContentUpdateStatus::Unknown => None,
             bridge::instance::ContentUpdateStatus::ManualInstall => Some(
                 Button::new(("update", element_id)).warning().icon(Icon::default().path("icons/file-question-mark.svg"))
@@ -148,11 +195,16 @@ impl ContentListDelegate {
                 Button::new(("update", element_id)).icon(Icon::default().path("icons/check.svg"))
                     .tooltip("Up-to-date as of last check")
             ),
-            bridge::instance::ContentUpdateStatus::Modrinth => {
+            status @ (bridge::instance::ContentUpdateStatus::Modrinth | bridge::instance::ContentUpdateStatus::Curseforge | bridge::instance::ContentUpdateStatus::GithubRelease) => {
                 let loading = self.updating.lock().contains(&element_id);
+                let tooltip = match status {
+                    bridge::instance::ContentUpdateStatus::GithubRelease => "Download update from GitHub",
+                    bridge::instance::ContentUpdateStatus::Curseforge => "Download update from CurseForge",
+                    _ => "Download update from Modrinth",
+                };
                 Some(
                     Button::new(("update", element_id)).success().loading(loading).icon(Icon::default().path("icons/download.svg"))
-                        .tooltip("Download update from Modrinth").on_click({
+                        .tooltip(tooltip).on_click({
                             let backend_handle = self.backend_handle.clone();
                             let updating = self.updating.clone();
                             cx.listener(move |this, _, window, cx| {
@@ -162,7 +214,9 @@ impl ContentListDelegate {
                                 let delegate = this.delegate_mut();
                                 if delegate.is_selected(element_id) {
                                     for summary in &delegate.content {
-                                        if delegate.is_selected(summary.filename_hash) && summary.content_summary.update_status.load(Ordering::Relaxed).can_update() {
+                                        if delegate.is_selected(summary.filename_hash) && summary.pinned.is_none()
+                                            && summary.content_summary.update_status.load(Ordering::Relaxed).can_update()
+                                        {
                                             updating.insert(summary.filename_hash);
                                             crate::root::update_single_mod(id, summary.id, &backend_handle, window, cx);
                                         }
@@ -180,6 +234,38 @@ impl ContentListDelegate {
             },
         };
 
+        let incompatible_button = match (&summary.content_summary.extra, self.pack_format) {
+            (ContentType::ResourcePack { pack_format: Some(range) }, Some(pack_format)) if !range.contains(pack_format) => {
+                let supported = if range.min == range.max {
+                    range.min.to_string()
+                } else {
+                    format!("{}-{}", range.min, range.max)
+                };
+                Some(Button::new(("incompatible", element_id)).warning().icon(Icon::default().path("icons/triangle-alert.svg"))
+                    .tooltip(SharedString::from(format!("Made for pack format {}, this version uses {}", supported, pack_format))))
+            },
+            _ => None,
+        };
+
+        let duplicate = self.duplicates.get(&element_id);
+        let duplicate_button = duplicate.map(|duplicate| {
+            let button = Button::new(("duplicate", element_id)).warning().icon(Icon::default().path("icons/copy.svg"));
+            if duplicate.older.is_empty() {
+                button.tooltip(duplicate.description.clone())
+            } else {
+                button.tooltip(SharedString::from(format!("{}\nClick to keep only the newest version", duplicate.description)))
+                    .on_click({
+                        let backend_handle = self.backend_handle.clone();
+                        let mod_id = duplicate.id.clone();
+                        let older = duplicate.older.clone();
+                        move |_, window, cx| {
+                            cx.stop_propagation();
+                            open_keep_newest_dialog(id, mod_id.clone(), older.clone(), backend_handle.clone(), window, cx);
+                        }
+                    })
+            }
+        });
+
         let backend_handle = self.backend_handle.clone();
 
         let toggle_control = Switch::new(("toggle", element_id))
@@ -247,13 +333,15 @@ impl ContentListDelegate {
             .child(desc1)
             .when_some(desc2, |div, desc2| div.child(desc2))
             .border_1()
+            .when(duplicate.is_some() && !selected, |content| content.border_color(cx.theme().warning))
             .when(selected, |content| content.border_color(cx.theme().selection).bg(cx.theme().selection.alpha(0.2)));
 
-        if let Some(update_button) = update_button {
-            item_content = item_content.child(h_flex().absolute().right_4().gap_2().child(update_button).child(delete_button))
-        } else {
-            item_content = item_content.child(delete_button.absolute().right_4())
-        }
+        item_content = item_content.child(h_flex().absolute().right_4().gap_2()
+            .children(duplicate_button)
+            .children(incompatible_button)
+            .child(pin_button)
+            .children(update_button)
+            .child(delete_button));
 
         ListItem::new(("item", element_id)).p_1().child(item_content).on_click(cx.listener(move |this, click: &ClickEvent, _, cx| {
             cx.stop_propagation();
@@ -406,6 +494,7 @@ impl ContentListDelegate {
             png_icon: None,
             update_status: Arc::new(AtomicContentUpdateStatus::new(bridge::instance::ContentUpdateStatus::Unknown)),
             extra: ContentType::Fabric,
+            mod_info: None,
         });
 
         for modification in new_content.iter() {
@@ -461,6 +550,7 @@ impl ContentListDelegate {
         }
         drop(updating);
 
+        self.duplicates = find_duplicates(&mods);
         self.content = mods.clone();
         self.children = children;
         self.searched = None;
@@ -511,6 +601,17 @@ impl ContentListDelegate {
         self.searched = Some(searched);
     }
 
+    /// The selected content if the element is part of the selection, otherwise only the element itself
+    fn selected_or_single(&self, element_id: u64, content_id: InstanceContentID) -> Vec<InstanceContentID> {
+        if self.is_selected(element_id) {
+            self.content.iter().filter_map(|summary| {
+                self.is_selected(summary.filename_hash).then(|| summary.id)
+            }).collect()
+        } else {
+            vec![content_id]
+        }
+    }
+
     fn is_selected(&self, element_id: u64) -> bool {
         self.selected.contains(&element_id) || self.selected_range.contains(&element_id)
     }
@@ -595,6 +696,161 @@ impl ListDelegate for ContentListDelegate {
     }
 }
 
+// Groups enabled mods by id, counting mods bundled inside other jars, so that standalone copies can be flagged
+fn find_duplicates(content: &[InstanceContentSummary]) -> FxHashMap<u64, DuplicateMod> {
+    struct ModCopy<'a> {
+        summary: &'a InstanceContentSummary,
+        version: &'a str,
+        bundled: bool,
+    }
+
+    let mut by_id: FxHashMap<&str, Vec<ModCopy>> = FxHashMap::default();
+    for summary in content.iter().filter(|summary| summary.enabled) {
+        let Some(mod_info) = &summary.content_summary.mod_info else {
+            continue;
+        };
+        if let Some(id) = &summary.content_summary.id {
+            by_id.entry(id).or_default().push(ModCopy { summary, version: &mod_info.version, bundled: false });
+        }
+        for provided in mod_info.provides.iter() {
+            let copies = by_id.entry(&provided.id).or_default();
+            if !copies.iter().any(|copy| copy.summary.filename_hash == summary.filename_hash) {
+                copies.push(ModCopy { summary, version: &provided.version, bundled: true });
+            }
+        }
+    }
+
+    let mut duplicates = FxHashMap::default();
+    for (id, mut copies) in by_id {
+        if copies.len() < 2 || copies.iter().all(|copy| copy.bundled) {
+            continue;
+        }
+
+        // Standalone jars come last so they are kept over a bundled copy of the same version
+        copies.sort_by_key(|copy| !copy.bundled);
+        let newest = copies.iter().enumerate()
+            .max_by(|(_, a), (_, b)| compare_versions(a.version, b.version))
+            .map(|(index, _)| index)
+            .unwrap();
+
+        let older: Arc<[(InstanceContentID, Arc<str>)]> = copies.iter().enumerate()
+            .filter(|(index, copy)| *index != newest && !copy.bundled)
+            .map(|(_, copy)| (copy.summary.id, copy.summary.filename.clone()))
+            .collect();
+
+        for (index, copy) in copies.iter().enumerate() {
+            if copy.bundled {
+                continue;
+            }
+            let others = copies.iter().enumerate()
+                .filter(|(other, _)| *other != index)
+                .map(|(_, other)| if other.bundled {
+                    format!("{} bundled in {}", other.version, other.summary.filename)
+                } else {
+                    format!("{} in {}", other.version, other.summary.filename)
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            duplicates.insert(copy.summary.filename_hash, DuplicateMod {
+                id: id.into(),
+                description: format!("{} is also installed as {}", id, others).into(),
+                older: older.clone(),
+            });
+        }
+    }
+    duplicates
+}
+
+fn open_keep_newest_dialog(
+    id: InstanceID,
+    mod_id: Arc<str>,
+    older: Arc<[(InstanceContentID, Arc<str>)]>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let title = SharedString::new(format!("Keep only the newest version of {}?", mod_id));
+    let files = older.iter().map(|(_, filename)| &**filename).collect::<Vec<_>>().join(", ");
+    let message = SharedString::new(format!("The following files will be deleted: {}", files));
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let buttons = h_flex()
+            .w_full()
+            .gap_2()
+            .child(Button::new("delete").flex_1().label("Delete older versions").danger().on_click({
+                let backend_handle = backend_handle.clone();
+                let older = older.clone();
+                move |_, window, cx| {
+                    backend_handle.send(MessageToBackend::DeleteContent {
+                        id,
+                        content_ids: older.iter().map(|(content_id, _)| *content_id).collect(),
+                    });
+                    window.close_all_dialogs(cx);
+                }
+            }))
+            .child(Button::new("cancel").flex_1().label("Cancel").on_click(|_, window, cx| {
+                window.close_all_dialogs(cx);
+            }));
+
+        dialog
+            .title(title.clone())
+            .child(v_flex()
+                .gap_2()
+                .child(message.clone())
+                .child(buttons))
+    });
+}
+
+fn open_pin_dialog(
+    id: InstanceID,
+    content_ids: Vec<InstanceContentID>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let input_state = cx.new(|cx| InputState::new(window, cx).placeholder("Reason (optional), e.g. 0.6 breaks Create"));
+
+    let title = if content_ids.len() == 1 {
+        SharedString::new_static("Pin this version?")
+    } else {
+        SharedString::new(format!("Pin {} items?", content_ids.len()))
+    };
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let buttons = h_flex()
+            .w_full()
+            .gap_2()
+            .child(Button::new("pin").flex_1().label("Pin").success().on_click({
+                let backend_handle = backend_handle.clone();
+                let content_ids = content_ids.clone();
+                let input_state = input_state.clone();
+                move |_, window, cx| {
+                    let note = input_state.read(cx).value();
+                    let note = note.trim();
+                    backend_handle.send(MessageToBackend::SetContentPinned {
+                        id,
+                        content_ids: content_ids.clone(),
+                        pinned: Some(AuxPinned {
+                            note: (!note.is_empty()).then(|| note.into()),
+                        }),
+                    });
+                    window.close_all_dialogs(cx);
+                }
+            }))
+            .child(Button::new("cancel").flex_1().label("Cancel").on_click(|_, window, cx| {
+                window.close_all_dialogs(cx);
+            }));
+
+        dialog
+            .title(title.clone())
+            .child(v_flex()
+                .gap_2()
+                .child("Pinned content is skipped when checking for updates and when updating in bulk.")
+                .child(Input::new(&input_state))
+                .child(buttons))
+    });
+}
+
 fn create_descriptions(name: Option<Arc<str>>, version: Arc<str>, authors: Arc<str>, filename: Arc<str>) -> (Div, Option<Div>) {
     if name.is_none() && authors.is_empty() {
         let description1 = v_flex()
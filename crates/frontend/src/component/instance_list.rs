@@ -1,25 +1,95 @@
-use bridge::handle::BackendHandle;
+use bridge::{handle::BackendHandle, instance::InstanceID, message::MessageToBackend};
 use gpui::{prelude::*, *};
 use gpui_component::{
-    Sizable,
+    ActiveTheme as _, IconName, Sizable,
     button::{Button, ButtonVariants},
     h_flex,
-    table::{Column, ColumnSort, TableDelegate, TableState},
+    table::{Column, TableDelegate, TableState},
 };
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     entity::{
-        instance::{InstanceAddedEvent, InstanceEntry, InstanceModifiedEvent, InstanceRemovedEvent}, DataEntities
-    }, pages::instance::instance_page::InstanceSubpageType, root, ui
+        instance::{InstanceAddedEvent, InstanceEntry, InstanceModifiedEvent, InstanceMovedToTopEvent, InstanceRemovedEvent}, DataEntities
+    }, interface_config::InterfaceConfig, pages::instance::instance_page::InstanceSubpageType, root, ui
 };
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceListSort {
+    #[default]
+    LastPlayed,
+    Name,
+    Version,
+    Loader,
+    Manual,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct InstanceListConfig {
+    #[serde(default, deserialize_with = "schema::try_deserialize")]
+    pub sort: InstanceListSort,
+    #[serde(default, deserialize_with = "schema::try_deserialize")]
+    pub sort_descending: bool,
+    #[serde(default, deserialize_with = "schema::try_deserialize")]
+    pub tag_filter: Option<SharedString>,
+    #[serde(default, deserialize_with = "schema::try_deserialize")]
+    pub groups: Vec<SharedString>,
+    #[serde(default, deserialize_with = "schema::try_deserialize")]
+    pub collapsed_groups: Vec<SharedString>,
+    #[serde(default, deserialize_with = "schema::try_deserialize")]
+    pub collapse_ungrouped: bool,
+    #[serde(default, deserialize_with = "schema::try_deserialize")]
+    pub manual_order: Vec<SharedString>,
+}
+
+enum InstanceListRow {
+    Group {
+        name: Option<SharedString>,
+        count: usize,
+        collapsed: bool,
+    },
+    Instance(usize),
+}
+
+#[derive(Clone, Copy)]
+enum DropTarget {
+    Group(usize),
+    Before(usize),
+}
+
+#[derive(Clone)]
+pub struct DraggedInstance {
+    id: InstanceID,
+    name: SharedString,
+}
+
+impl Render for DraggedInstance {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
+        div()
+            .px_2()
+            .py_1()
+            .bg(theme.background)
+            .border_1()
+            .border_color(theme.border)
+            .rounded(theme.radius)
+            .child(self.name.clone())
+    }
+}
+
 pub struct InstanceList {
     columns: Vec<Column>,
+    // Ordered by last played, most recent first
     items: Vec<InstanceEntry>,
+    rows: Vec<InstanceListRow>,
+    filter: SharedString,
     backend_handle: BackendHandle,
     _instance_added_subscription: Subscription,
     _instance_removed_subscription: Subscription,
     _instance_modified_subscription: Subscription,
+    _instance_moved_to_top_subscription: Subscription,
 }
 
 impl InstanceList {
@@ -29,21 +99,33 @@ impl InstanceList {
         cx.new(|cx| {
             let _instance_added_subscription = cx.subscribe::<_, InstanceAddedEvent>(&instances, |table: &mut TableState<InstanceList>, _, event, cx| {
                 table.delegate_mut().items.insert(0, event.instance.clone());
+                table.delegate_mut().rebuild_rows(cx);
                 cx.notify();
             });
             let _instance_removed_subscription = cx.subscribe::<_, InstanceRemovedEvent>(&instances, |table, _, event, cx| {
                 table.delegate_mut().items.retain(|instance| {
                     instance.id != event.id
                 });
+                table.delegate_mut().rebuild_rows(cx);
                 cx.notify();
             });
             let _instance_modified_subscription = cx.subscribe::<_, InstanceModifiedEvent>(&instances, |table, _, event, cx| {
                 if let Some(entry) = table.delegate_mut().items.iter_mut().find(|entry| entry.id == event.instance.id) {
                     *entry = event.instance.clone();
+                    table.delegate_mut().rebuild_rows(cx);
+                    cx.notify();
+                }
+            });
+            let _instance_moved_to_top_subscription = cx.subscribe::<_, InstanceMovedToTopEvent>(&instances, |table, _, event, cx| {
+                let items = &mut table.delegate_mut().items;
+                if let Some(index) = items.iter().position(|entry| entry.id == event.instance.id) {
+                    let entry = items.remove(index);
+                    items.insert(0, entry);
+                    table.delegate_mut().rebuild_rows(cx);
                     cx.notify();
                 }
             });
-            let instance_list = Self {
+            let mut instance_list = Self {
                 columns: vec![
                     Column::new("controls", "")
                         .width(150.)
@@ -53,27 +135,339 @@ impl InstanceList {
                     Column::new("name", "Name")
                         .width(150.)
                         .fixed_left()
-                        .sortable()
                         .resizable(true),
                     Column::new("version", "Version")
                         .width(150.)
                         .fixed_left()
-                        .sortable()
                         .resizable(true),
                     Column::new("loader", "Loader")
                         .width(150.)
                         .fixed_left()
                         .resizable(true),
+                    Column::new("tags", "Tags")
+                        .width(250.)
+                        .resizable(true),
                 ],
                 items,
+                rows: Vec::new(),
+                filter: SharedString::default(),
                 backend_handle: data.backend_handle.clone(),
                 _instance_added_subscription,
                 _instance_removed_subscription,
                 _instance_modified_subscription,
+                _instance_moved_to_top_subscription,
             };
+            instance_list.rebuild_rows(cx);
             TableState::new(instance_list, window, cx)
         })
     }
+
+    pub fn set_filter(&mut self, filter: SharedString, cx: &App) {
+        self.filter = filter;
+        self.rebuild_rows(cx);
+    }
+
+    pub fn rebuild_rows(&mut self, cx: &App) {
+        let config = &InterfaceConfig::get(cx).instance_list;
+
+        let filter = self.filter.to_lowercase();
+        let visible: Vec<usize> = self.ordered_indices(config)
+            .into_iter()
+            .filter(|ix| matches_filter(&self.items[*ix], &filter, &config.tag_filter))
+            .collect();
+
+        self.rows.clear();
+
+        let groups = self.group_names(config);
+        if groups.is_empty() {
+            self.rows.extend(visible.into_iter().map(InstanceListRow::Instance));
+            return;
+        }
+
+        for group in std::iter::once(None).chain(groups.into_iter().map(Some)) {
+            let members: Vec<usize> = visible.iter().copied().filter(|ix| {
+                self.items[*ix].configuration.group.as_deref() == group.as_ref().map(SharedString::as_str)
+            }).collect();
+
+            let collapsed = if let Some(group) = &group {
+                config.collapsed_groups.contains(group)
+            } else {
+                config.collapse_ungrouped
+            };
+
+            self.rows.push(InstanceListRow::Group {
+                name: group,
+                count: members.len(),
+                collapsed,
+            });
+            if !collapsed {
+                self.rows.extend(members.into_iter().map(InstanceListRow::Instance));
+            }
+        }
+    }
+
+    fn ordered_indices(&self, config: &InstanceListConfig) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.items.len()).collect();
+
+        match config.sort {
+            InstanceListSort::LastPlayed => {},
+            InstanceListSort::Name => indices.sort_by(|a, b| {
+                lexical_sort::natural_lexical_cmp(&self.items[*a].name, &self.items[*b].name)
+            }),
+            InstanceListSort::Version => indices.sort_by(|a, b| {
+                let (a, b) = (&self.items[*a].configuration, &self.items[*b].configuration);
+                lexical_sort::natural_lexical_cmp(&a.minecraft_version, &b.minecraft_version)
+            }),
+            InstanceListSort::Loader => indices.sort_by(|a, b| {
+                let (a, b) = (&self.items[*a], &self.items[*b]);
+                a.configuration.loader.name().cmp(b.configuration.loader.name())
+                    .then_with(|| lexical_sort::natural_lexical_cmp(&a.name, &b.name))
+            }),
+            InstanceListSort::Manual => {
+                let positions: FxHashMap<&str, usize> = config.manual_order.iter()
+                    .enumerate()
+                    .map(|(index, name)| (name.as_str(), index))
+                    .collect();
+                // Instances that were never manually placed keep their last played order at the end
+                indices.sort_by_key(|ix| positions.get(self.items[*ix].name.as_str()).copied().unwrap_or(usize::MAX));
+            },
+        }
+
+        if config.sort_descending {
+            indices.reverse();
+        }
+
+        indices
+    }
+
+    fn group_names(&self, config: &InstanceListConfig) -> Vec<SharedString> {
+        let mut groups = config.groups.clone();
+
+        let mut extra_groups = Vec::new();
+        for item in &self.items {
+            if let Some(group) = &item.configuration.group
+                && !groups.iter().any(|existing| existing.as_str() == &**group)
+                && !extra_groups.iter().any(|existing: &SharedString| existing.as_str() == &**group)
+            {
+                extra_groups.push(SharedString::new(group.clone()));
+            }
+        }
+        extra_groups.sort_by(|a, b| lexical_sort::natural_lexical_cmp(a, b));
+
+        groups.extend(extra_groups);
+        groups
+    }
+
+    fn toggle_group_collapsed(&mut self, group: Option<SharedString>, cx: &mut App) {
+        let config = &mut InterfaceConfig::get_mut(cx).instance_list;
+        if let Some(group) = group {
+            if let Some(index) = config.collapsed_groups.iter().position(|existing| *existing == group) {
+                config.collapsed_groups.remove(index);
+            } else {
+                config.collapsed_groups.push(group);
+            }
+        } else {
+            config.collapse_ungrouped = !config.collapse_ungrouped;
+        }
+        self.rebuild_rows(cx);
+    }
+
+    fn remove_group(&mut self, group: SharedString, cx: &mut App) {
+        for item in &mut self.items {
+            if item.configuration.group.as_deref() == Some(group.as_str()) {
+                item.configuration.group = None;
+                self.backend_handle.send(MessageToBackend::SetInstanceGroup {
+                    id: item.id,
+                    group: None,
+                });
+            }
+        }
+
+        let config = &mut InterfaceConfig::get_mut(cx).instance_list;
+        config.groups.retain(|existing| *existing != group);
+        config.collapsed_groups.retain(|existing| *existing != group);
+
+        self.rebuild_rows(cx);
+    }
+
+    fn drop_instance(&mut self, dragged: &DraggedInstance, target: DropTarget, cx: &mut App) {
+        let Some(dragged_index) = self.items.iter().position(|item| item.id == dragged.id) else {
+            return;
+        };
+
+        let new_group = match target {
+            DropTarget::Group(row) => {
+                let Some(InstanceListRow::Group { name, .. }) = self.rows.get(row) else {
+                    return;
+                };
+                name.as_ref().map(|name| name.as_str().into())
+            },
+            DropTarget::Before(row) => {
+                let Some(InstanceListRow::Instance(target_index)) = self.rows.get(row) else {
+                    return;
+                };
+                let target_index = *target_index;
+                if target_index == dragged_index {
+                    return;
+                }
+
+                // Reordering switches to manual sorting, starting from the order currently shown
+                let config = &InterfaceConfig::get(cx).instance_list;
+                let mut order: Vec<SharedString> = self.ordered_indices(config)
+                    .into_iter()
+                    .filter(|ix| *ix != dragged_index)
+                    .map(|ix| self.items[ix].name.clone())
+                    .collect();
+                let insert_at = order.iter().position(|name| *name == self.items[target_index].name).unwrap_or(order.len());
+                order.insert(insert_at, dragged.name.clone());
+
+                let config = &mut InterfaceConfig::get_mut(cx).instance_list;
+                config.manual_order = order;
+                config.sort = InstanceListSort::Manual;
+                config.sort_descending = false;
+
+                self.items[target_index].configuration.group.clone()
+            },
+        };
+
+        let item = &mut self.items[dragged_index];
+        if item.configuration.group != new_group {
+            item.configuration.group = new_group.clone();
+            self.backend_handle.send(MessageToBackend::SetInstanceGroup {
+                id: item.id,
+                group: new_group,
+            });
+        }
+
+        self.rebuild_rows(cx);
+    }
+
+    fn render_group_td(&self, row_ix: usize, col_ix: usize, cx: &mut Context<TableState<Self>>) -> AnyElement {
+        let Some(InstanceListRow::Group { name, count, collapsed }) = self.rows.get(row_ix) else {
+            return "Unknown".into_any_element();
+        };
+
+        let Some(col) = self.columns.get(col_ix) else {
+            return "Unknown".into_any_element();
+        };
+
+        match col.key.as_ref() {
+            "controls" => {
+                let icon = if *collapsed { IconName::ChevronRight } else { IconName::ChevronDown };
+                h_flex()
+                    .size_full()
+                    .gap_2()
+                    .child(Button::new(("toggle_group", row_ix)).small().ghost().icon(icon).on_click(cx.listener({
+                        let name = name.clone();
+                        move |table, _, _, cx| {
+                            table.delegate_mut().toggle_group_collapsed(name.clone(), cx);
+                            cx.notify();
+                        }
+                    })))
+                    .when_some(name.clone(), |this, name| {
+                        this.child(Button::new(("remove_group", row_ix)).small().ghost().icon(IconName::Close).on_click(cx.listener(move |table, _, _, cx| {
+                            table.delegate_mut().remove_group(name.clone(), cx);
+                            cx.notify();
+                        })))
+                    })
+                    .into_any_element()
+            },
+            "name" => {
+                let title = name.clone().unwrap_or(SharedString::new_static("Ungrouped"));
+                div().font_bold().child(format!("{} ({})", title, count)).into_any_element()
+            },
+            _ => div().into_any_element(),
+        }
+    }
+
+    fn render_instance_td(&self, row_ix: usize, col_ix: usize, cx: &mut Context<TableState<Self>>) -> AnyElement {
+        let Some(InstanceListRow::Instance(index)) = self.rows.get(row_ix) else {
+            return "Unknown".into_any_element();
+        };
+        let item = &self.items[*index];
+
+        let Some(col) = self.columns.get(col_ix) else {
+            return "Unknown".into_any_element();
+        };
+
+        match col.key.as_ref() {
+            "name" => {
+                let dragged = DraggedInstance {
+                    id: item.id,
+                    name: item.name.clone(),
+                };
+                h_flex()
+                    .id(("drag_instance", row_ix))
+                    .size_full()
+                    .gap_2()
+                    .cursor_grab()
+                    .on_drag(dragged, |dragged, _, _, cx| cx.new(|_| dragged.clone()))
+                    .child(ui::instance_icon(item.icon.as_ref(), cx).size_5().min_w_5().min_h_5())
+                    .child(item.name.clone())
+                    .into_any_element()
+            },
+            "version" => item.configuration.minecraft_version.as_str().into_any_element(),
+            "controls" => {
+                let backend_handle = self.backend_handle.clone();
+                h_flex()
+                    .size_full()
+                    .gap_2()
+                    .border_r_4()
+                    .child(Button::new("start").w(relative(0.5)).small().success().label("Start").on_click({
+                        let name = item.name.clone();
+                        let id = item.id;
+                        move |_, window, cx| {
+                            root::start_instance(id, name.clone(), None, &backend_handle, window, cx);
+                        }
+                    }))
+                    .child(Button::new("view").w(relative(0.5)).small().info().label("View").on_click({
+                        let id = item.id;
+                        move |_, window, cx| {
+                            root::switch_page(ui::PageType::InstancePage(id, InstanceSubpageType::Quickplay),
+                                &[ui::PageType::Instances], window, cx);
+                        }
+                    }))
+                    .into_any_element()
+            },
+            "loader" => item.configuration.loader.name().into_any_element(),
+            "tags" => {
+                h_flex()
+                    .gap_1()
+                    .children(item.configuration.tags.iter().enumerate().map(|(tag_ix, tag)| {
+                        let tag = SharedString::new(tag.clone());
+                        Button::new(SharedString::new(format!("tag-{row_ix}-{tag_ix}")))
+                            .small()
+                            .compact()
+                            .outline()
+                            .label(tag.clone())
+                            .on_click(cx.listener(move |table, _, _, cx| {
+                                InterfaceConfig::get_mut(cx).instance_list.tag_filter = Some(tag.clone());
+                                table.delegate_mut().rebuild_rows(cx);
+                                cx.notify();
+                            }))
+                    }))
+                    .into_any_element()
+            },
+            _ => "Unknown".into_any_element(),
+        }
+    }
+}
+
+fn matches_filter(item: &InstanceEntry, filter: &str, tag_filter: &Option<SharedString>) -> bool {
+    if let Some(tag_filter) = tag_filter
+        && !item.configuration.tags.iter().any(|tag| &**tag == tag_filter.as_str())
+    {
+        return false;
+    }
+
+    if filter.is_empty() {
+        return true;
+    }
+
+    item.name.to_lowercase().contains(filter)
+        || item.configuration.minecraft_version.to_lowercase().contains(filter)
+        || item.configuration.loader.name().to_lowercase().contains(filter)
+        || item.configuration.tags.iter().any(|tag| tag.to_lowercase().contains(filter))
 }
 
 impl TableDelegate for InstanceList {
@@ -82,68 +476,50 @@ impl TableDelegate for InstanceList {
     }
 
     fn rows_count(&self, _cx: &App) -> usize {
-        self.items.len()
+        self.rows.len()
     }
 
     fn column(&self, col_ix: usize, _cx: &App) -> gpui_component::table::Column {
         self.columns[col_ix].clone()
     }
 
-    fn perform_sort(
-        &mut self,
-        col_ix: usize,
-        sort: gpui_component::table::ColumnSort,
-        _window: &mut Window,
-        _cx: &mut Context<TableState<Self>>,
-    ) {
-        if let Some(col) = self.columns.get_mut(col_ix) {
-            match col.key.as_ref() {
-                "name" => self.items.sort_by(|a, b| match sort {
-                    ColumnSort::Descending => lexical_sort::natural_lexical_cmp(&a.name, &b.name).reverse(),
-                    _ => lexical_sort::natural_lexical_cmp(&a.name, &b.name),
-                }),
-                "version" => self.items.sort_by(|a, b| match sort {
-                    ColumnSort::Descending => lexical_sort::natural_lexical_cmp(&a.configuration.minecraft_version, &b.configuration.minecraft_version).reverse(),
-                    _ => lexical_sort::natural_lexical_cmp(&a.configuration.minecraft_version, &b.configuration.minecraft_version),
-                }),
-                _ => {},
-            }
-        }
+    fn render_td(&mut self, row_ix: usize, col_ix: usize, _window: &mut Window, cx: &mut Context<TableState<Self>>) -> impl IntoElement {
+        let (content, target) = match self.rows.get(row_ix) {
+            Some(InstanceListRow::Group { .. }) => (self.render_group_td(row_ix, col_ix, cx), DropTarget::Group(row_ix)),
+            Some(InstanceListRow::Instance(_)) => (self.render_instance_td(row_ix, col_ix, cx), DropTarget::Before(row_ix)),
+            None => return "Unknown".into_any_element(),
+        };
+
+        div()
+            .size_full()
+            .flex()
+            .items_center()
+            .drag_over::<DraggedInstance>(|style, _, _, cx| style.bg(cx.theme().drop_target))
+            .on_drop(cx.listener(move |table, dragged: &DraggedInstance, _, cx| {
+                table.delegate_mut().drop_instance(dragged, target, cx);
+                cx.notify();
+            }))
+            .child(content)
+            .into_any_element()
     }
+}
 
-    fn render_td(&mut self, row_ix: usize, col_ix: usize, _window: &mut Window, _cx: &mut Context<TableState<Self>>) -> impl IntoElement {
-        let item = &self.items[row_ix];
-        if let Some(col) = self.columns.get(col_ix) {
-            match col.key.as_ref() {
-                "name" => item.name.clone().into_any_element(),
-                "version" => item.configuration.minecraft_version.as_str().into_any_element(),
-                "controls" => {
-                    let backend_handle = self.backend_handle.clone();
-                    h_flex()
-                        .size_full()
-                        .gap_2()
-                        .border_r_4()
-                        .child(Button::new("start").w(relative(0.5)).small().success().label("Start").on_click({
-                            let name = item.name.clone();
-                            let id = item.id;
-                            move |_, window, cx| {
-                                root::start_instance(id, name.clone(), None, &backend_handle, window, cx);
-                            }
-                        }))
-                        .child(Button::new("view").w(relative(0.5)).small().info().label("View").on_click({
-                            let id = item.id;
-                            move |_, window, cx| {
-                                root::switch_page(ui::PageType::InstancePage(id, InstanceSubpageType::Quickplay),
-                                    &[ui::PageType::Instances], window, cx);
-                            }
-                        }))
-                        .into_any_element()
-                },
-                "loader" => item.configuration.loader.name().into_any_element(),
-                _ => "Unknown".into_any_element(),
-            }
-        } else {
-            "Unknown".into_any_element()
+impl InstanceListSort {
+    pub fn name(self) -> &'static str {
+        match self {
+            InstanceListSort::LastPlayed => "Last Played",
+            InstanceListSort::Name => "Name",
+            InstanceListSort::Version => "Version",
+            InstanceListSort::Loader => "Loader",
+            InstanceListSort::Manual => "Manual",
         }
     }
+
+    pub const ALL: [InstanceListSort; 5] = [
+        InstanceListSort::LastPlayed,
+        InstanceListSort::Name,
+        InstanceListSort::Version,
+        InstanceListSort::Loader,
+        InstanceListSort::Manual,
+    ];
 }
@@ -7,4 +7,5 @@ pub mod named_dropdown;
 pub mod page_path;
 pub mod progress_bar;
 pub mod readonly_text_field;
+pub mod screenshot_grid;
 pub mod search_helper;
@@ -23,7 +23,10 @@ impl PagePath {
         for i in 0..pages.len() {
             let title = match pages[i] {
                 PageType::Instances => "Instances".into(),
+                PageType::Screenshots => "Screenshots".into(),
                 PageType::Syncing => "Syncing".into(),
+                PageType::NbtEditor => "NBT Editor".into(),
+                PageType::ContentLibrary => "Content Library".into(),
                 PageType::Modrinth { installing_for, .. } => {
                     if installing_for.is_some() {
                         "Add from Modrinth".into()
@@ -31,6 +34,13 @@ impl PagePath {
                         "Modrinth".into()
                     }
                 },
+                PageType::Curseforge { installing_for, .. } => {
+                    if installing_for.is_some() {
+                        "Add from CurseForge".into()
+                    } else {
+                        "CurseForge".into()
+                    }
+                },
                 PageType::InstancePage(instance_id, _) => {
                     InstanceEntries::find_title_by_id(&data.instances, instance_id, cx)
                         .unwrap_or("<instance name>".into())
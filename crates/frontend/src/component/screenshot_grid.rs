@@ -0,0 +1,148 @@
+use std::{path::Path, sync::Arc};
+
+use bridge::{handle::BackendHandle, instance::{InstanceID, InstanceScreenshotSummary}, message::MessageToBackend};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    notification::NotificationType,
+    v_flex,
+};
+
+use crate::png_render_cache;
+
+#[derive(Clone)]
+pub struct ScreenshotEntry {
+    pub instance: InstanceID,
+    pub instance_name: Option<SharedString>,
+    pub summary: InstanceScreenshotSummary,
+}
+
+pub fn render_screenshot_grid(screenshots: &[ScreenshotEntry], backend_handle: &BackendHandle, cx: &mut App) -> Div {
+    h_flex()
+        .flex_wrap()
+        .items_start()
+        .gap_3()
+        .children(screenshots.iter().enumerate().map(|(index, entry)| render_screenshot(index, entry, backend_handle, cx)))
+}
+
+fn render_screenshot(index: usize, entry: &ScreenshotEntry, backend_handle: &BackendHandle, cx: &mut App) -> Div {
+    let summary = &entry.summary;
+
+    let thumbnail = if let Some(thumbnail) = summary.thumbnail.as_ref() {
+        png_render_cache::render(Arc::clone(thumbnail), cx)
+    } else {
+        gpui::img(ImageSource::Resource(Resource::Embedded("images/missing.png".into())))
+    };
+
+    let theme = cx.theme();
+
+    let created = chrono::DateTime::from_timestamp_millis(summary.created)
+        .map(|date_time| date_time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+    let details = match &entry.instance_name {
+        Some(instance_name) => format!("{}, {}", instance_name, created),
+        None => format!("{}, {}", created, crate::format_bytes(summary.size)),
+    };
+
+    let id = entry.instance;
+
+    let open_button = Button::new(("open", index)).label("Open").info().small().compact().on_click({
+        let path = summary.path.clone();
+        move |_, window, cx| {
+            if let Err(err) = open::that_detached(&*path) {
+                window.push_notification((NotificationType::Error, SharedString::from(format!("Unable to open screenshot: {err}"))), cx);
+            }
+        }
+    });
+
+    let copy_button = Button::new(("copy", index)).label("Copy").info().small().compact().on_click({
+        let path = summary.path.clone();
+        move |_, window, cx| {
+            copy_screenshot_to_clipboard(path.clone(), window, cx);
+        }
+    });
+
+    let export_button = Button::new(("export", index)).label("Export").info().small().compact().on_click({
+        let filename = summary.filename.clone();
+        let backend_handle = backend_handle.clone();
+        move |_, _, cx| {
+            let user_dirs = directories::UserDirs::new();
+            let directory = user_dirs.as_ref()
+                .and_then(directories::UserDirs::picture_dir).unwrap_or(Path::new("."));
+
+            let receiver = cx.prompt_for_new_path(directory, Some(&filename));
+            let filename = filename.clone();
+            let backend_handle = backend_handle.clone();
+            cx.spawn(async move |_| {
+                let Ok(Ok(Some(target))) = receiver.await else {
+                    return;
+                };
+                backend_handle.send(MessageToBackend::ExportScreenshot { id, screenshot: filename, target });
+            }).detach();
+        }
+    });
+
+    let delete_button = Button::new(("delete", index)).label("Delete").danger().small().compact().on_click({
+        let filename = summary.filename.clone();
+        let backend_handle = backend_handle.clone();
+        move |_, _, _| {
+            backend_handle.send(MessageToBackend::DeleteScreenshot { id, screenshot: filename.clone() });
+        }
+    });
+
+    v_flex()
+        .w_64()
+        .gap_1()
+        .p_2()
+        .border_1()
+        .rounded(theme.radius)
+        .border_color(theme.border)
+        .child(div()
+            .id(("thumbnail", index))
+            .w_full()
+            .h_36()
+            .flex()
+            .justify_center()
+            .cursor_pointer()
+            .child(thumbnail.h_full())
+            .on_click({
+                let path = summary.path.clone();
+                move |_, window, cx| {
+                    if let Err(err) = open::that_detached(&*path) {
+                        window.push_notification((NotificationType::Error, SharedString::from(format!("Unable to open screenshot: {err}"))), cx);
+                    }
+                }
+            }))
+        .child(div().text_sm().truncate().child(SharedString::from(summary.filename.clone())))
+        .child(div().text_xs().truncate().text_color(theme.muted_foreground).child(SharedString::from(details)))
+        .child(h_flex()
+            .gap_1()
+            .child(open_button)
+            .child(copy_button)
+            .child(export_button)
+            .child(delete_button))
+}
+
+fn copy_screenshot_to_clipboard(path: Arc<Path>, window: &mut Window, cx: &mut App) {
+    let format = match path.extension().and_then(|extension| extension.to_str()).map(str::to_ascii_lowercase).as_deref() {
+        Some("jpg" | "jpeg") => ImageFormat::Jpeg,
+        _ => ImageFormat::Png,
+    };
+
+    // Full size screenshots can take a while to read, so the UI thread only writes the result to the clipboard
+    let read = cx.background_spawn(async move { std::fs::read(&*path) });
+    window.spawn(cx, async move |cx| {
+        let result = read.await;
+        _ = cx.update(move |window, cx| match result {
+            Ok(bytes) => {
+                cx.write_to_clipboard(ClipboardItem::new_image(&Image::from_bytes(format, bytes)));
+                window.push_notification((NotificationType::Success, "Screenshot copied to clipboard"), cx);
+            },
+            Err(err) => {
+                window.push_notification((NotificationType::Error, SharedString::from(format!("Unable to copy screenshot: {err}"))), cx);
+            },
+        });
+    }).detach();
+}
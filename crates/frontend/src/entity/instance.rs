@@ -1,12 +1,13 @@
 use std::{path::Path, sync::Arc};
 
 use bridge::{
-    instance::{InstanceID, InstanceContentSummary, InstanceServerSummary, InstanceStatus, InstanceWorldSummary},
+    instance::{InstanceID, InstanceContentSummary, InstanceScreenshotSummary, InstanceServerSummary, InstanceSnapshotSummary, InstanceStatus, InstanceWorldBackupSummary, InstanceWorldSummary, WorldDatapacks},
     message::AtomicBridgeDataLoadState,
 };
 use gpui::{prelude::*, *};
 use gpui_component::select::SelectItem;
 use indexmap::IndexMap;
+use rustc_hash::FxHashMap;
 use schema::{instance::InstanceConfiguration, loader::Loader};
 
 pub struct InstanceEntries {
@@ -20,10 +21,13 @@ impl InstanceEntries {
         name: SharedString,
         dot_minecraft_folder: Arc<Path>,
         configuration: InstanceConfiguration,
+        icon: Option<Arc<[u8]>>,
+        description: SharedString,
         worlds_state: Arc<AtomicBridgeDataLoadState>,
         servers_state: Arc<AtomicBridgeDataLoadState>,
         mods_state: Arc<AtomicBridgeDataLoadState>,
         resource_packs_state: Arc<AtomicBridgeDataLoadState>,
+        shader_packs_state: Arc<AtomicBridgeDataLoadState>,
         cx: &mut App,
     ) {
         entity.update(cx, |entries, cx| {
@@ -33,6 +37,8 @@ impl InstanceEntries {
                 title: "".into(),
                 dot_minecraft_folder,
                 configuration,
+                icon,
+                description,
                 status: InstanceStatus::NotRunning,
                 worlds_state,
                 worlds: cx.new(|_| [].into()),
@@ -42,6 +48,12 @@ impl InstanceEntries {
                 mods: cx.new(|_| [].into()),
                 resource_packs_state,
                 resource_packs: cx.new(|_| [].into()),
+                shader_packs_state,
+                shader_packs: cx.new(|_| [].into()),
+                snapshots: cx.new(|_| [].into()),
+                world_backups: cx.new(|_| [].into()),
+                world_datapacks: cx.new(|_| FxHashMap::default()),
+                screenshots: cx.new(|_| [].into()),
             };
             instance.title = instance.create_title().into();
 
@@ -87,6 +99,8 @@ impl InstanceEntries {
         name: SharedString,
         dot_minecraft_folder: Arc<Path>,
         configuration: InstanceConfiguration,
+        icon: Option<Arc<[u8]>>,
+        description: SharedString,
         status: InstanceStatus,
         cx: &mut App,
     ) {
@@ -96,6 +110,8 @@ impl InstanceEntries {
                     instance.name = name.clone();
                     instance.dot_minecraft_folder = dot_minecraft_folder.clone();
                     instance.configuration = configuration.clone();
+                    instance.icon = icon.clone();
+                    instance.description = description.clone();
                     instance.status = status;
                     instance.title = instance.create_title().into();
                     cx.notify();
@@ -170,6 +186,71 @@ impl InstanceEntries {
         });
     }
 
+    pub fn set_shader_packs(entity: &Entity<Self>, id: InstanceID, shader_packs: Arc<[InstanceContentSummary]>, cx: &mut App) {
+        entity.update(cx, |entries, cx| {
+            if let Some(instance) = entries.entries.get_mut(&id) {
+                instance.update(cx, |instance, cx| {
+                    instance.shader_packs.update(cx, |existing_shader_packs, cx| {
+                        *existing_shader_packs = shader_packs;
+                        cx.notify();
+                    })
+                });
+            }
+        });
+    }
+
+    pub fn set_snapshots(entity: &Entity<Self>, id: InstanceID, snapshots: Arc<[InstanceSnapshotSummary]>, cx: &mut App) {
+        entity.update(cx, |entries, cx| {
+            if let Some(instance) = entries.entries.get_mut(&id) {
+                instance.update(cx, |instance, cx| {
+                    instance.snapshots.update(cx, |existing_snapshots, cx| {
+                        *existing_snapshots = snapshots;
+                        cx.notify();
+                    })
+                });
+            }
+        });
+    }
+
+    pub fn set_world_backups(entity: &Entity<Self>, id: InstanceID, world_backups: Arc<[InstanceWorldBackupSummary]>, cx: &mut App) {
+        entity.update(cx, |entries, cx| {
+            if let Some(instance) = entries.entries.get_mut(&id) {
+                instance.update(cx, |instance, cx| {
+                    instance.world_backups.update(cx, |existing_world_backups, cx| {
+                        *existing_world_backups = world_backups;
+                        cx.notify();
+                    })
+                });
+            }
+        });
+    }
+
+    pub fn set_world_datapacks(entity: &Entity<Self>, id: InstanceID, world: Arc<str>, datapacks: WorldDatapacks, cx: &mut App) {
+        entity.update(cx, |entries, cx| {
+            if let Some(instance) = entries.entries.get_mut(&id) {
+                instance.update(cx, |instance, cx| {
+                    instance.world_datapacks.update(cx, |existing_world_datapacks, cx| {
+                        existing_world_datapacks.insert(world, datapacks);
+                        cx.notify();
+                    })
+                });
+            }
+        });
+    }
+
+    pub fn set_screenshots(entity: &Entity<Self>, id: InstanceID, screenshots: Arc<[InstanceScreenshotSummary]>, cx: &mut App) {
+        entity.update(cx, |entries, cx| {
+            if let Some(instance) = entries.entries.get_mut(&id) {
+                instance.update(cx, |instance, cx| {
+                    instance.screenshots.update(cx, |existing_screenshots, cx| {
+                        *existing_screenshots = screenshots;
+                        cx.notify();
+                    })
+                });
+            }
+        });
+    }
+
     pub fn move_to_top(entity: &Entity<Self>, id: InstanceID, cx: &mut App) {
         entity.update(cx, |entries, cx| {
             if let Some(index) = entries.entries.get_index_of(&id) {
@@ -190,6 +271,8 @@ pub struct InstanceEntry {
     pub title: SharedString,
     pub dot_minecraft_folder: Arc<Path>,
     pub configuration: InstanceConfiguration,
+    pub icon: Option<Arc<[u8]>>,
+    pub description: SharedString,
     pub status: InstanceStatus,
     pub worlds_state: Arc<AtomicBridgeDataLoadState>,
     pub worlds: Entity<Arc<[InstanceWorldSummary]>>,
@@ -199,6 +282,12 @@ pub struct InstanceEntry {
     pub mods: Entity<Arc<[InstanceContentSummary]>>,
     pub resource_packs_state: Arc<AtomicBridgeDataLoadState>,
     pub resource_packs: Entity<Arc<[InstanceContentSummary]>>,
+    pub shader_packs_state: Arc<AtomicBridgeDataLoadState>,
+    pub shader_packs: Entity<Arc<[InstanceContentSummary]>>,
+    pub snapshots: Entity<Arc<[InstanceSnapshotSummary]>>,
+    pub world_backups: Entity<Arc<[InstanceWorldBackupSummary]>>,
+    pub world_datapacks: Entity<FxHashMap<Arc<str>, WorldDatapacks>>,
+    pub screenshots: Entity<Arc<[InstanceScreenshotSummary]>>,
 }
 
 impl SelectItem for InstanceEntry {
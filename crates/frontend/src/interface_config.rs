@@ -5,7 +5,7 @@ use rand::RngCore;
 use schema::modrinth::ModrinthProjectType;
 use serde::{Deserialize, Serialize};
 
-use crate::ui::SerializedPageType;
+use crate::{component::instance_list::InstanceListConfig, ui::SerializedPageType};
 
 struct InterfaceConfigHolder {
     config: InterfaceConfig,
@@ -37,6 +37,8 @@ pub struct InterfaceConfig {
     pub modrinth_page_project_type: ModrinthProjectType,
     #[serde(default, deserialize_with = "schema::try_deserialize")]
     pub hide_main_window_on_launch: bool,
+    #[serde(default, deserialize_with = "schema::try_deserialize")]
+    pub instance_list: InstanceListConfig,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
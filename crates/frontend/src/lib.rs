@@ -296,6 +296,18 @@ pub(crate) fn is_single_component_path(path: &str) -> bool {
     components.count() == 1
 }
 
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    if bytes < 1000 {
+        format!("{} bytes", bytes)
+    } else if bytes < 1000*1000 {
+        format!("{}kB", bytes/1000)
+    } else if bytes < 1000*1000*1000 {
+        format!("{}MB", bytes/1000/1000)
+    } else {
+        format!("{}GB", bytes/1000/1000/1000)
+    }
+}
+
 #[inline]
 pub(crate) fn labelled(label: &'static str, element: impl IntoElement) -> Div {
     gpui_component::v_flex().gap_0p5().child(div().text_sm().font_medium().child(label)).child(element)
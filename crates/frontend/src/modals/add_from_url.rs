@@ -0,0 +1,65 @@
+use bridge::{
+    handle::BackendHandle,
+    install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget},
+    instance::InstanceID,
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    button::{Button, ButtonVariants}, h_flex, input::{Input, InputState}, v_flex, WindowExt
+};
+use schema::{content::ContentSource, loader::Loader};
+use ustr::Ustr;
+
+pub fn open_add_from_url(
+    instance: InstanceID,
+    loader: Loader,
+    minecraft_version: Ustr,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let input_state = cx.new(|cx| InputState::new(window, cx).placeholder("https://github.com/owner/repo/releases"));
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let buttons = h_flex()
+            .w_full()
+            .gap_2()
+            .child(Button::new("install").flex_1().label("Install").success().on_click({
+                let backend_handle = backend_handle.clone();
+                let input_state = input_state.clone();
+                move |_, window, cx| {
+                    let url = input_state.read(cx).value();
+                    let url = url.trim();
+                    if url.is_empty() {
+                        return;
+                    }
+                    window.close_all_dialogs(cx);
+
+                    let content_install = ContentInstall {
+                        target: InstallTarget::Instance(instance),
+                        loader_hint: loader,
+                        version_hint: Some(minecraft_version.into()),
+                        files: [ContentInstallFile {
+                            replace_old: None,
+                            path: ContentInstallPath::Automatic,
+                            download: ContentDownload::UnverifiedUrl { url: url.into() },
+                            content_source: ContentSource::Url { url: url.into() },
+                        }].into(),
+                    };
+                    crate::root::start_install(content_install, &backend_handle, window, cx);
+                }
+            }))
+            .child(Button::new("cancel").flex_1().label("Cancel").on_click(|_, window, cx| {
+                window.close_all_dialogs(cx);
+            }));
+
+        dialog
+            .title("Add from URL")
+            .child(v_flex()
+                .gap_2()
+                .child("Mods, resource packs and shader packs are placed in the right folder once downloaded.")
+                .child("GitHub release links are checked for newer releases when checking for updates.")
+                .child(Input::new(&input_state))
+                .child(buttons))
+    });
+}
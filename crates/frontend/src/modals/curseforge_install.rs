@@ -0,0 +1,269 @@
+use std::sync::Arc;
+
+use bridge::{install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget}, instance::InstanceID, meta::MetadataRequest, safe_path::SafePath};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    button::{Button, ButtonVariants}, checkbox::Checkbox, dialog::Dialog, h_flex, notification::NotificationType, select::{SearchableVec, Select, SelectState}, spinner::Spinner, v_flex, IndexPath, WindowExt
+};
+use relative_path::RelativePath;
+use schema::{content::ContentSource, curseforge::{CurseforgeClass, CurseforgeFile, CurseforgeModFilesRequest, CurseforgeModFilesResult}, loader::Loader};
+
+use crate::{
+    component::{error_alert::ErrorAlert, instance_dropdown::InstanceDropdown},
+    entity::{
+        instance::InstanceEntry, metadata::{AsMetadataResult, FrontendMetadata, FrontendMetadataResult, FrontendMetadataState}, DataEntities
+    },
+    root,
+};
+
+struct InstallDialog {
+    title: SharedString,
+    data: DataEntities,
+    project_id: u32,
+    class: CurseforgeClass,
+    instance: InstanceEntry,
+    files: Arc<[CurseforgeFile]>,
+    file_select_state: Entity<SelectState<SearchableVec<SharedString>>>,
+    install_dependencies: bool,
+}
+
+pub fn open(
+    name: &str,
+    project_id: u32,
+    class: CurseforgeClass,
+    install_for: Option<InstanceID>,
+    data: &DataEntities,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let title = SharedString::new(format!("Install {}", name));
+
+    if let Some(install_for) = install_for {
+        let Some(instance) = data.instances.read(cx).entries.get(&install_for) else {
+            open_error_dialog(title, "Unable to find instance".into(), window, cx);
+            return;
+        };
+        let instance = instance.read(cx).clone();
+        open_for_instance(title, project_id, class, instance, data.clone(), window, cx);
+        return;
+    }
+
+    let entries: Arc<[InstanceEntry]> = data.instances.read(cx).entries.iter()
+        .map(|(_, instance)| instance.read(cx).clone())
+        .collect();
+    if entries.is_empty() {
+        open_error_dialog(title, "Create an instance to install content into first".into(), window, cx);
+        return;
+    }
+
+    let instances = InstanceDropdown::create(entries, window, cx);
+    instances.update(cx, |dropdown, cx| {
+        dropdown.set_selected_index(Some(IndexPath::default()), window, cx)
+    });
+
+    let data = data.clone();
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let selected_instance = instances.read(cx).selected_value().cloned();
+
+        dialog.title(title.clone()).child(h_flex()
+            .gap_2()
+            .child(Select::new(&instances).placeholder("Select an instance").title_prefix("Instance: "))
+            .when_some(selected_instance, |content, instance| {
+                let title = title.clone();
+                let data = data.clone();
+                content.child(Button::new("instance").success().h_full().label("Add to instance").on_click(move |_, window, cx| {
+                    window.close_all_dialogs(cx);
+                    open_for_instance(title.clone(), project_id, class, instance.clone(), data.clone(), window, cx);
+                }))
+            }))
+    });
+}
+
+fn open_for_instance(
+    title: SharedString,
+    project_id: u32,
+    class: CurseforgeClass,
+    instance: InstanceEntry,
+    data: DataEntities,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    // Only mods are uploaded for a specific loader
+    let mod_loader_type = if class == CurseforgeClass::Mod {
+        instance.configuration.loader.as_curseforge_loader()
+    } else {
+        None
+    };
+
+    let project_files = FrontendMetadata::request(
+        &data.metadata,
+        MetadataRequest::CurseforgeModFiles(CurseforgeModFilesRequest {
+            mod_id: project_id,
+            game_version: Some(instance.configuration.minecraft_version.as_str().into()),
+            mod_loader_type,
+        }),
+        cx,
+    );
+
+    open_from_entity(title, project_files, project_id, class, instance, data, window, cx);
+}
+
+fn open_from_entity(
+    title: SharedString,
+    project_files: Entity<FrontendMetadataState>,
+    project_id: u32,
+    class: CurseforgeClass,
+    instance: InstanceEntry,
+    data: DataEntities,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let result: FrontendMetadataResult<CurseforgeModFilesResult> = project_files.read(cx).result();
+    match result {
+        FrontendMetadataResult::Loading => {
+            let _subscription = window.observe(&project_files, cx, move |project_files, window, cx| {
+                window.close_all_dialogs(cx);
+                open_from_entity(title.clone(), project_files, project_id, class, instance.clone(), data.clone(), window, cx);
+            });
+            window.open_dialog(cx, move |dialog, _, _| {
+                let _ = &_subscription;
+                dialog.title(title.clone()).child(h_flex().gap_2().child("Loading files...").child(Spinner::new()))
+            });
+        },
+        FrontendMetadataResult::Loaded(result) => {
+            let mut files = result.data.to_vec();
+            files.sort_by(|a, b| b.file_date.cmp(&a.file_date));
+
+            if files.is_empty() {
+                let error_message = SharedString::from(format!("No files found for {}", instance.configuration.minecraft_version));
+                open_error_dialog(title, error_message, window, cx);
+                return;
+            }
+
+            let names: Vec<SharedString> = files.iter().map(|file| {
+                if let Some(release_type) = file.release_type_name() {
+                    SharedString::new(format!("{} ({})", file.display_name, release_type))
+                } else {
+                    SharedString::new(file.display_name.clone())
+                }
+            }).collect();
+
+            let file_select_state = cx.new(|cx| {
+                let mut select_state = SelectState::new(SearchableVec::new(names), None, window, cx).searchable(true);
+                select_state.set_selected_index(Some(IndexPath::default()), window, cx);
+                select_state
+            });
+
+            let install_dialog = InstallDialog {
+                title,
+                data,
+                project_id,
+                class,
+                instance,
+                files: files.into(),
+                file_select_state,
+                install_dependencies: true,
+            };
+            install_dialog.show(window, cx);
+        },
+        FrontendMetadataResult::Error(message) => {
+            window.open_dialog(cx, move |modal, _, _| {
+                modal.title(title.clone()).child(ErrorAlert::new("error", "Error requesting from CurseForge".into(), message.clone()))
+            });
+        },
+    }
+}
+
+fn open_error_dialog(title: SharedString, text: SharedString, window: &mut Window, cx: &mut App) {
+    window.open_dialog(cx, move |modal, _, _| {
+        modal.title(title.clone()).child(text.clone())
+    });
+}
+
+impl InstallDialog {
+    fn show(self, window: &mut Window, cx: &mut App) {
+        let install_dialog = cx.new(|_| self);
+        window.open_dialog(cx, move |modal, window, cx| {
+            install_dialog.update(cx, |this, cx| this.render(modal, window, cx))
+        });
+    }
+
+    fn render(&mut self, modal: Dialog, _window: &mut Window, cx: &mut Context<Self>) -> Dialog {
+        let modal = modal.title(self.title.clone());
+
+        let selected_file = self.file_select_state.read(cx).selected_index(cx)
+            .and_then(|index| self.files.get(index.row))
+            .cloned();
+
+        let required_dependencies = selected_file.as_ref()
+            .map(|file| file.dependencies.iter().filter(|dep| dep.is_required()).count())
+            .unwrap_or(0);
+
+        let content = v_flex()
+            .gap_2()
+            .child(format!("Installing into {}", self.instance.title))
+            .child(Select::new(&self.file_select_state).title_prefix("File: "))
+            .when(required_dependencies > 0, |content| {
+                content.child(Checkbox::new("install_deps").checked(self.install_dependencies).label(if required_dependencies == 1 {
+                    SharedString::new_static("Install 1 dependency")
+                } else {
+                    SharedString::new(format!("Install {} dependencies", required_dependencies))
+                }).on_click(cx.listener(|dialog, value, _, _| {
+                    dialog.install_dependencies = *value;
+                })))
+            })
+            .child(Button::new("install").success().label("Install").on_click(cx.listener(move |this, _, window, cx| {
+                let Some(selected_file) = selected_file.as_ref() else {
+                    window.push_notification((NotificationType::Error, "No file selected"), cx);
+                    return;
+                };
+
+                let folder = match this.class {
+                    CurseforgeClass::Mod | CurseforgeClass::Modpack => "mods",
+                    CurseforgeClass::Resourcepack => "resourcepacks",
+                    CurseforgeClass::Shader => "shaderpacks",
+                };
+                let path = RelativePath::new(folder).join(&*selected_file.file_name);
+                let Some(path) = SafePath::from_relative_path(&path) else {
+                    window.push_notification((NotificationType::Error, "Invalid/dangerous filename"), cx);
+                    return;
+                };
+
+                // Files list the loaders they were uploaded for among their game versions
+                let mut loader_hint = Loader::Unknown;
+                if this.class == CurseforgeClass::Mod && this.instance.configuration.loader != Loader::Vanilla {
+                    loader_hint = this.instance.configuration.loader;
+                } else if this.class == CurseforgeClass::Mod {
+                    for loader in [Loader::Fabric, Loader::Forge, Loader::NeoForge] {
+                        if let Some(curseforge_loader) = loader.as_curseforge_loader()
+                            && selected_file.game_versions.iter().any(|version| &**version == curseforge_loader.name())
+                        {
+                            loader_hint = loader;
+                            break;
+                        }
+                    }
+                }
+
+                let content_install = ContentInstall {
+                    target: InstallTarget::Instance(this.instance.id),
+                    loader_hint,
+                    version_hint: Some(this.instance.configuration.minecraft_version.as_str().into()),
+                    files: [ContentInstallFile {
+                        replace_old: None,
+                        path: ContentInstallPath::Safe(path),
+                        download: ContentDownload::Curseforge {
+                            project_id: this.project_id,
+                            file_id: Some(selected_file.id),
+                            install_dependencies: this.install_dependencies,
+                        },
+                        content_source: ContentSource::CurseforgeProject { project: this.project_id },
+                    }].into(),
+                };
+
+                window.close_dialog(cx);
+                root::start_install(content_install, &this.data.backend_handle, window, cx);
+            })));
+
+        modal.child(content)
+    }
+}
@@ -0,0 +1,108 @@
+use bridge::{
+    handle::BackendHandle,
+    instance::{InstanceID, LaunchProblem, LaunchProblemKind},
+    message::{MessageToBackend, QuickPlayLaunch},
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _, Icon, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex, v_flex,
+};
+use rustc_hash::FxHashSet;
+
+pub fn open_launch_problems(
+    id: InstanceID,
+    name: SharedString,
+    quick_play: Option<QuickPlayLaunch>,
+    problems: Vec<LaunchProblem>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let title = SharedString::new(format!("Problems found before launching {}", name));
+
+    let messages: Vec<SharedString> = problems.iter().map(|problem| {
+        let mod_name = &problem.mod_name;
+        match &problem.kind {
+            LaunchProblemKind::WrongLoader { loader } => format!("{mod_name} is a {} mod", loader.name()),
+            LaunchProblemKind::WrongMinecraftVersion { versions } => format!("{mod_name} requires Minecraft {versions}"),
+            LaunchProblemKind::Duplicate { id, kept } => format!("{mod_name} is a duplicate of {kept} ({id})"),
+        }.into()
+    }).collect();
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let theme = cx.theme();
+
+        let buttons = h_flex()
+            .w_full()
+            .gap_2()
+            .child(Button::new("disable").flex_1().label("Disable offending mods").warning().on_click({
+                let backend_handle = backend_handle.clone();
+                let problems = problems.clone();
+                let name = name.clone();
+                let quick_play = quick_play.clone();
+                move |_, window, cx| {
+                    disable_offending_mods(id, &problems, &backend_handle);
+                    window.close_all_dialogs(cx);
+                    crate::root::launch_instance(id, name.clone(), quick_play.clone(), &backend_handle, window, cx);
+                }
+            }))
+            .child(Button::new("launch").flex_1().label("Launch anyway").danger().on_click({
+                let backend_handle = backend_handle.clone();
+                let name = name.clone();
+                let quick_play = quick_play.clone();
+                move |_, window, cx| {
+                    window.close_all_dialogs(cx);
+                    crate::root::launch_instance(id, name.clone(), quick_play.clone(), &backend_handle, window, cx);
+                }
+            }))
+            .child(Button::new("cancel").flex_1().label("Cancel").on_click(|_, window, cx| {
+                window.close_all_dialogs(cx);
+            }));
+
+        dialog
+            .title(title.clone())
+            .child(v_flex()
+                .gap_2()
+                .child("The game will likely crash or not load some mods with the following problems:")
+                .child(v_flex()
+                    .id("problems")
+                    .gap_1()
+                    .max_h_64()
+                    .overflow_y_scroll()
+                    .children(messages.iter().map(|message| {
+                        h_flex()
+                            .gap_2()
+                            .child(Icon::empty().path("icons/triangle-alert.svg").text_color(theme.warning))
+                            .child(div().text_sm().child(message.clone()))
+                    })))
+                .child(buttons))
+    });
+}
+
+fn disable_offending_mods(id: InstanceID, problems: &[LaunchProblem], backend_handle: &BackendHandle) {
+    let mut content_ids = FxHashSet::default();
+    for problem in problems {
+        if let Some(child) = &problem.modpack_child {
+            backend_handle.send(MessageToBackend::SetContentChildEnabled {
+                id,
+                content_id: problem.content_id,
+                child_id: child.id.clone(),
+                child_name: child.name.clone(),
+                child_filename: child.filename.clone(),
+                enabled: false,
+            });
+        } else {
+            content_ids.insert(problem.content_id);
+        }
+    }
+
+    if !content_ids.is_empty() {
+        backend_handle.send(MessageToBackend::SetContentEnabled {
+            id,
+            content_ids: content_ids.into_iter().collect(),
+            enabled: false,
+        });
+    }
+}
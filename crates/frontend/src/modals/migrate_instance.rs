@@ -0,0 +1,153 @@
+use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+
+use bridge::{
+    handle::BackendHandle,
+    instance::InstanceID,
+    message::MessageToBackend,
+    migration::{InstanceMigrationPlan, InstanceMigrationStatus},
+    modal_action::ModalAction,
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    button::{Button, ButtonVariants}, checkbox::Checkbox, h_flex, scroll::ScrollableElement, v_flex, ActiveTheme as _, WindowExt
+};
+use schema::loader::Loader;
+use ustr::Ustr;
+
+use crate::modals;
+
+pub fn open_migrate_instance(
+    instance: InstanceID,
+    minecraft_version: Ustr,
+    loader: Loader,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let (send, recv) = tokio::sync::oneshot::channel();
+    let modal_action = ModalAction::default();
+
+    backend_handle.send(MessageToBackend::PlanInstanceMigration {
+        id: instance,
+        minecraft_version,
+        loader,
+        modal_action: modal_action.clone(),
+        channel: send,
+    });
+
+    modals::generic::show_modal(window, cx, "Checking mod compatibility".into(), "Error checking mod compatibility".into(), modal_action);
+
+    window.spawn(cx, async move |cx| {
+        let Ok(plan) = recv.await else {
+            return;
+        };
+        _ = cx.update(move |window, cx| {
+            window.close_all_dialogs(cx);
+            open_migration_plan(instance, plan, backend_handle, window, cx);
+        });
+    }).detach();
+}
+
+fn open_migration_plan(
+    instance: InstanceID,
+    plan: InstanceMigrationPlan,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let title = SharedString::new(format!("Migrate to {} {}", plan.loader.name(), plan.minecraft_version));
+
+    let updates = plan.count(|status| matches!(status, InstanceMigrationStatus::Update { .. }));
+    let incompatible = plan.count(|status| matches!(status, InstanceMigrationStatus::Incompatible));
+    // Plans without mods (e.g. Vanilla) still change the version and loader, so they're confirmed as well
+    let summary = if plan.entries.is_empty() {
+        SharedString::new_static("No mods need to be changed. A snapshot will be created before migrating")
+    } else {
+        SharedString::new(format!(
+            "{} mods will be updated, {} are already compatible, {} have no compatible version and {} could not be checked. A snapshot will be created before migrating",
+            updates,
+            plan.count(|status| matches!(status, InstanceMigrationStatus::Compatible)),
+            incompatible,
+            plan.count(|status| matches!(status, InstanceMigrationStatus::Unknown)),
+        ))
+    };
+
+    let disable_incompatible = Arc::new(AtomicBool::new(true));
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let theme = cx.theme();
+
+        let mut rows = Vec::with_capacity(plan.entries.len());
+        for entry in plan.entries.iter() {
+            let (status, color) = match &entry.status {
+                InstanceMigrationStatus::Compatible => (SharedString::new_static("Compatible"), theme.success),
+                InstanceMigrationStatus::Update { version, .. } => (SharedString::new(format!("Update to {}", version)), theme.info),
+                InstanceMigrationStatus::Incompatible => (SharedString::new_static("No compatible version"), theme.danger),
+                InstanceMigrationStatus::Unknown => (SharedString::new_static("Unknown, not installed from Modrinth"), theme.warning),
+            };
+            rows.push(h_flex()
+                .gap_4()
+                .child(div().flex_1().child(SharedString::new(entry.name.clone())))
+                .child(div().text_color(color).child(status)));
+        }
+
+        let disabled = disable_incompatible.load(Ordering::Relaxed);
+
+        let content = v_flex()
+            .gap_2()
+            .child(summary.clone())
+            .when(!rows.is_empty(), |this| this.child(v_flex()
+                .id("migration_entries")
+                .max_h_96()
+                .p_2()
+                .border_1()
+                .rounded(theme.radius)
+                .border_color(theme.border)
+                .children(rows)
+                .overflow_y_scrollbar()))
+            .when(incompatible > 0, |this| {
+                this.child(Checkbox::new("disable_incompatible").label("Disable mods without a compatible version").checked(disabled).on_click({
+                    let disable_incompatible = disable_incompatible.clone();
+                    move |value, window, _| {
+                        disable_incompatible.store(*value, Ordering::Relaxed);
+                        window.refresh();
+                    }
+                }))
+            })
+            .child(h_flex()
+                .gap_2()
+                .child(Button::new("cancel").label("Cancel").on_click(|_, window, cx| {
+                    window.close_all_dialogs(cx);
+                }))
+                .child(Button::new("apply").label("Migrate instance").success().on_click({
+                    let plan = plan.clone();
+                    let backend_handle = backend_handle.clone();
+                    let disable_incompatible = disable_incompatible.clone();
+                    move |_, window, cx| {
+                        window.close_all_dialogs(cx);
+                        let disable_incompatible = disable_incompatible.load(Ordering::Relaxed);
+                        apply_migration(instance, plan.clone(), disable_incompatible, &backend_handle, window, cx);
+                    }
+                })));
+
+        dialog.title(title.clone()).child(content)
+    });
+}
+
+fn apply_migration(
+    instance: InstanceID,
+    plan: InstanceMigrationPlan,
+    disable_incompatible: bool,
+    backend_handle: &BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let modal_action = ModalAction::default();
+    backend_handle.send(MessageToBackend::ApplyInstanceMigration {
+        id: instance,
+        plan,
+        disable_incompatible,
+        modal_action: modal_action.clone(),
+    });
+    modals::generic::show_modal(window, cx, "Migrating instance".into(), "Error migrating instance".into(), modal_action);
+}
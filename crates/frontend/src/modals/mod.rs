@@ -1,6 +1,11 @@
+pub mod add_from_url;
+pub mod curseforge_install;
 pub mod generic;
 pub mod modrinth_install;
 pub mod modrinth_install_auto;
 pub mod delete_instance;
+pub mod launch_problems;
+pub mod migrate_instance;
 pub mod settings;
+pub mod update_all;
 pub mod update_prompt;
@@ -1,6 +1,6 @@
 use std::{cmp::Ordering, sync::Arc};
 
-use bridge::{install::{ContentDownload, ContentInstall, ContentInstallFile, InstallTarget}, instance::InstanceID, meta::MetadataRequest, safe_path::SafePath};
+use bridge::{install::{ContentDownload, ContentInstall, ContentInstallFile, InstallTarget}, instance::{InstanceID, InstanceWorldSummary}, message::MessageToBackend, meta::MetadataRequest, safe_path::SafePath};
 use enumset::EnumSet;
 use gpui::{prelude::*, *};
 use gpui_component::{
@@ -54,6 +54,9 @@ struct InstallDialog {
     install_dependencies: bool,
 
     mod_version_select_state: Option<Entity<SelectState<SearchableVec<ModVersionItem>>>>,
+
+    world_select_state: Option<Entity<SelectState<SearchableVec<SharedString>>>>,
+    _observe_worlds_subscription: Option<Subscription>,
 }
 
 pub fn open(
@@ -211,6 +214,8 @@ fn open_from_entity(
                     install_dependencies: true,
                     mod_version_select_state: None,
                     last_selected_loader: None,
+                    world_select_state: None,
+                    _observe_worlds_subscription: None,
                 };
                 install_dialog.show(window, cx);
             } else {
@@ -272,6 +277,8 @@ fn open_from_entity(
                     install_dependencies: true,
                     mod_version_select_state: None,
                     last_selected_loader: None,
+                    world_select_state: None,
+                    _observe_worlds_subscription: None,
                 };
                 install_dialog.show(window, cx);
             }
@@ -302,12 +309,14 @@ impl InstallDialog {
         let modal = modal.title(self.title.clone());
 
         if self.target.is_none() {
+            // Datapacks need an existing world to be installed into
             let create_instance_label = match self.project_type {
-                ModrinthProjectType::Mod => "Create new instance with this mod",
-                ModrinthProjectType::Modpack => "Create new instance with this modpack",
-                ModrinthProjectType::Resourcepack => "Create new instance with this resourcepack",
-                ModrinthProjectType::Shader => "Create new instance with this shader",
-                ModrinthProjectType::Other => "Create new instance with this file",
+                ModrinthProjectType::Mod => Some("Create new instance with this mod"),
+                ModrinthProjectType::Modpack => Some("Create new instance with this modpack"),
+                ModrinthProjectType::Resourcepack => Some("Create new instance with this resourcepack"),
+                ModrinthProjectType::Shader => Some("Create new instance with this shader"),
+                ModrinthProjectType::Datapack => None,
+                ModrinthProjectType::Other => Some("Create new instance with this file"),
             };
 
             let content = v_flex()
@@ -346,19 +355,57 @@ impl InstallDialog {
                             ))
                         });
 
-                    content.child(button_and_dropdown).child("— OR —")
+                    content.child(button_and_dropdown).when(create_instance_label.is_some(), |content| content.child("— OR —"))
                 })
-                .child(Button::new("create").success().label(create_instance_label).on_click(cx.listener(
-                    |this, _, _, _| {
-                        this.target = Some(InstallTarget::NewInstance {
-                            name: "New Instance".into(),
-                        });
-                    },
-                )));
+                .when_some(create_instance_label, |content, create_instance_label| {
+                    content.child(Button::new("create").success().label(create_instance_label).on_click(cx.listener(
+                        |this, _, _, _| {
+                            this.target = Some(InstallTarget::NewInstance {
+                                name: "New Instance".into(),
+                            });
+                        },
+                    )))
+                });
 
             return modal.child(content);
         }
 
+        if self.project_type == ModrinthProjectType::Datapack
+            && self.world_select_state.is_none()
+            && let Some(InstallTarget::Instance(instance_id)) = self.target
+            && let Some(instance) = self.data.instances.read(cx).entries.get(&instance_id).cloned()
+        {
+            let instance = instance.read(cx);
+            if instance.worlds_state.load(std::sync::atomic::Ordering::SeqCst).should_send_load_request() {
+                self.data.backend_handle.send(MessageToBackend::RequestLoadWorlds { id: instance_id });
+            }
+
+            let worlds = instance.worlds.clone();
+            // Worlds are loaded lazily, so keep the list updated while the dialog is open
+            let world_select_state = cx.new(|cx| {
+                let mut select_state = SelectState::new(SearchableVec::new(world_names(worlds.read(cx))), None, window, cx).searchable(true);
+                select_state.set_selected_index(Some(IndexPath::default()), window, cx);
+                select_state
+            });
+            self._observe_worlds_subscription = Some(cx.observe_in(&worlds, window, {
+                let world_select_state = world_select_state.clone();
+                move |_, worlds, window, cx| {
+                    let names = world_names(worlds.read(cx));
+                    world_select_state.update(cx, |select_state, cx| {
+                        let previous = select_state.selected_value().cloned();
+                        select_state.set_items(SearchableVec::new(names), window, cx);
+                        if let Some(previous) = previous {
+                            select_state.set_selected_value(&previous, window, cx);
+                        }
+                        if select_state.selected_index(cx).is_none() {
+                            select_state.set_selected_index(Some(IndexPath::default()), window, cx);
+                        }
+                    });
+                }
+            }));
+            self.world_select_state = Some(world_select_state);
+        }
+
         if self.minecraft_version_select_state.is_none() {
             if let Some(minecraft_version) = self.fixed_minecraft_version.clone() {
                 self.minecraft_version_select_state = Some(cx.new(|cx| {
@@ -572,10 +619,18 @@ impl InstallDialog {
             ModrinthProjectType::Modpack => "Modpack version: ",
             ModrinthProjectType::Resourcepack => "Pack version: ",
             ModrinthProjectType::Shader => "Shader version: ",
+            ModrinthProjectType::Datapack => "Datapack version: ",
             ModrinthProjectType::Other => "File version: ",
         };
 
-        let required_dependencies = selected_mod_version.as_ref().and_then(|version| {
+        let selected_world = self
+            .world_select_state
+            .as_ref()
+            .and_then(|state| state.read(cx).selected_value())
+            .cloned();
+
+        // Dependencies are installed automatically, which datapacks can't be
+        let required_dependencies = selected_mod_version.as_ref().filter(|_| self.project_type != ModrinthProjectType::Datapack).and_then(|version| {
             version.dependencies.as_ref().map(|deps| {
                 deps
                     .iter()
@@ -599,6 +654,9 @@ impl InstallDialog {
                     .disabled(self.fixed_loader.is_some() || self.skip_loader_check_for_mod_version)
                     .title_prefix("Loader: "),
             )
+            .when_some(self.world_select_state.as_ref(), |modal, worlds| {
+                modal.child(Select::new(worlds).placeholder("No worlds found").title_prefix("World: "))
+            })
             .when_some(self.mod_version_select_state.as_ref(), |modal, mod_versions| {
                 modal
                     .child(Select::new(mod_versions).title_prefix(mod_version_prefix))
@@ -629,6 +687,13 @@ impl InstallDialog {
                                 ModrinthProjectType::Modpack => RelativePath::new("mods").join(&*install_file.filename),
                                 ModrinthProjectType::Resourcepack => RelativePath::new("resourcepacks").join(&*install_file.filename),
                                 ModrinthProjectType::Shader => RelativePath::new("shaderpacks").join(&*install_file.filename),
+                                ModrinthProjectType::Datapack => {
+                                    let Some(world) = selected_world.as_ref() else {
+                                        window.push_notification((NotificationType::Error, "No world selected"), cx);
+                                        return;
+                                    };
+                                    RelativePath::new("saves").join(world.as_str()).join("datapacks").join(&*install_file.filename)
+                                },
                                 ModrinthProjectType::Other => {
                                     window.push_notification((NotificationType::Error, "Unable to install 'other' project type"), cx);
                                     return;
@@ -709,6 +774,13 @@ impl InstallDialog {
     }
 }
 
+fn world_names(worlds: &[InstanceWorldSummary]) -> Vec<SharedString> {
+    worlds.iter()
+        .filter_map(|world| world.level_path.file_name()?.to_str())
+        .map(SharedString::new)
+        .collect()
+}
+
 #[derive(Clone)]
 struct ModVersionItem {
     name: SharedString,
@@ -194,6 +194,10 @@ fn handle_project_versions(
                 ModrinthProjectType::Modpack => RelativePath::new("mods").join(&*install_file.filename),
                 ModrinthProjectType::Resourcepack => RelativePath::new("resourcepacks").join(&*install_file.filename),
                 ModrinthProjectType::Shader => RelativePath::new("shaderpacks").join(&*install_file.filename),
+                ModrinthProjectType::Datapack => {
+                    push_error(title.clone(), key, "Datapacks must be installed into a specific world".into(), window, cx);
+                    return true;
+                },
                 ModrinthProjectType::Other => {
                     push_error(title.clone(), key, "Unable to install 'other' project type".into(), window, cx);
                     return true;
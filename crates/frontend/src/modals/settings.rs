@@ -2,8 +2,8 @@ use std::{path::Path, sync::Arc};
 
 use bridge::{handle::BackendHandle, message::MessageToBackend};
 use gpui::*;
-use gpui_component::{button::{Button, ButtonVariants}, checkbox::Checkbox, select::{SearchableVec, Select, SelectEvent, SelectState}, sheet::Sheet, spinner::Spinner, tab::{Tab, TabBar, TabVariant}, v_flex, ActiveTheme, IconName, Sizable, ThemeRegistry};
-use schema::backend_config::BackendConfig;
+use gpui_component::{button::{Button, ButtonVariants}, checkbox::Checkbox, h_flex, input::{Input, InputEvent, InputState, NumberInput}, select::{SearchableVec, Select, SelectEvent, SelectState}, sheet::Sheet, spinner::Spinner, tab::{Tab, TabBar, TabVariant}, v_flex, ActiveTheme, Disableable, IconName, Sizable, ThemeRegistry};
+use schema::{backend_config::BackendConfig, instance::InstanceDefaults};
 
 use crate::{entity::DataEntities, interface_config::InterfaceConfig};
 
@@ -14,6 +14,26 @@ struct Settings {
     pending_request: bool,
     backend_config: Option<BackendConfig>,
     get_configuration_task: Option<Task<()>>,
+    selected_tab: usize,
+    loaded_game_settings: bool,
+    memory_min_input_state: Entity<InputState>,
+    memory_max_input_state: Entity<InputState>,
+    jvm_flags_input_state: Entity<InputState>,
+    env_vars_input_state: Entity<InputState>,
+    resolution_width_input_state: Entity<InputState>,
+    resolution_height_input_state: Entity<InputState>,
+    world_backups_input_state: Entity<InputState>,
+    curseforge_api_key_input_state: Entity<InputState>,
+    _select_jvm_binary_task: Task<()>,
+}
+
+// Only turns a default into an explicit value once it differs from the built-in default
+fn update_default<T: Clone + Default + PartialEq>(value: &mut Option<T>, modify: impl FnOnce(&mut T)) {
+    let mut new_value = value.clone().unwrap_or_default();
+    modify(&mut new_value);
+    if value.is_some() || new_value != T::default() {
+        *value = Some(new_value);
+    }
 }
 
 pub fn build_settings_sheet(data: &DataEntities, window: &mut Window, cx: &mut App) -> impl Fn(Sheet, &mut Window, &mut App) -> Sheet + 'static {
@@ -42,6 +62,23 @@ pub fn build_settings_sheet(data: &DataEntities, window: &mut Window, cx: &mut A
             gpui_component::Theme::global_mut(cx).apply_config(&theme);
         }).detach();
 
+        let memory_min_input_state = cx.new(|cx| InputState::new(window, cx));
+        cx.subscribe(&memory_min_input_state, Settings::on_instance_defaults_changed).detach();
+        let memory_max_input_state = cx.new(|cx| InputState::new(window, cx));
+        cx.subscribe(&memory_max_input_state, Settings::on_instance_defaults_changed).detach();
+        let jvm_flags_input_state = cx.new(|cx| InputState::new(window, cx).auto_grow(1, 8));
+        cx.subscribe(&jvm_flags_input_state, Settings::on_instance_defaults_changed).detach();
+        let env_vars_input_state = cx.new(|cx| InputState::new(window, cx).auto_grow(1, 8).placeholder("KEY=VALUE, one per line"));
+        cx.subscribe(&env_vars_input_state, Settings::on_instance_defaults_changed).detach();
+        let resolution_width_input_state = cx.new(|cx| InputState::new(window, cx));
+        cx.subscribe(&resolution_width_input_state, Settings::on_instance_defaults_changed).detach();
+        let resolution_height_input_state = cx.new(|cx| InputState::new(window, cx));
+        cx.subscribe(&resolution_height_input_state, Settings::on_instance_defaults_changed).detach();
+        let world_backups_input_state = cx.new(|cx| InputState::new(window, cx));
+        cx.subscribe(&world_backups_input_state, Settings::on_world_backups_changed).detach();
+        let curseforge_api_key_input_state = cx.new(|cx| InputState::new(window, cx).masked(true));
+        cx.subscribe(&curseforge_api_key_input_state, Settings::on_curseforge_api_key_changed).detach();
+
         let mut settings = Settings {
             theme_folder,
             theme_select,
@@ -49,6 +86,17 @@ pub fn build_settings_sheet(data: &DataEntities, window: &mut Window, cx: &mut A
             pending_request: false,
             backend_config: None,
             get_configuration_task: None,
+            selected_tab: 0,
+            loaded_game_settings: false,
+            memory_min_input_state,
+            memory_max_input_state,
+            jvm_flags_input_state,
+            env_vars_input_state,
+            resolution_width_input_state,
+            resolution_height_input_state,
+            world_backups_input_state,
+            curseforge_api_key_input_state,
+            _select_jvm_binary_task: Task::ready(()),
         };
 
         settings.update_backend_configuration(cx);
@@ -59,12 +107,18 @@ pub fn build_settings_sheet(data: &DataEntities, window: &mut Window, cx: &mut A
     move |sheet, window, cx| {
         let tab_bar = TabBar::new("bar")
             .prefix(div().w_4())
-            .selected_index(0)
+            .selected_index(settings.read(cx).selected_tab)
             .underline()
             .child(Tab::new().label("Interface"))
-            // .child(Tab::new().label("Game"))
-            .on_click(|index, window, cx| {
-                // todo: switch
+            .child(Tab::new().label("Game"))
+            .on_click({
+                let settings = settings.clone();
+                move |index, _, cx| {
+                    settings.update(cx, |settings, cx| {
+                        settings.selected_tab = *index;
+                        cx.notify();
+                    });
+                }
             });
 
         sheet
@@ -106,10 +160,245 @@ impl Settings {
             channel: send,
         });
     }
+
+    fn set_instance_defaults(&mut self, cx: &mut Context<Self>, modify: impl FnOnce(&mut InstanceDefaults)) {
+        let Some(backend_config) = &mut self.backend_config else {
+            return;
+        };
+
+        let mut defaults = backend_config.instance_defaults.clone();
+        modify(&mut defaults);
+        if defaults == backend_config.instance_defaults {
+            return;
+        }
+
+        backend_config.instance_defaults = defaults.clone();
+        self.backend_handle.send(MessageToBackend::SetInstanceDefaults { defaults });
+        cx.notify();
+    }
+
+    fn on_instance_defaults_changed(&mut self, _: Entity<InputState>, event: &InputEvent, cx: &mut Context<Self>) {
+        // Defaults are saved once a value is fully entered, not for every character typed
+        if !matches!(event, InputEvent::Blur | InputEvent::PressEnter { .. }) {
+            return;
+        }
+
+        let parse = |state: &Entity<InputState>| state.read(cx).value().parse::<u32>().ok();
+        let memory_min = parse(&self.memory_min_input_state);
+        let memory_max = parse(&self.memory_max_input_state);
+        let resolution_width = parse(&self.resolution_width_input_state);
+        let resolution_height = parse(&self.resolution_height_input_state);
+        let jvm_flags: Arc<str> = self.jvm_flags_input_state.read(cx).value().as_str().into();
+        let env_vars: Arc<str> = self.env_vars_input_state.read(cx).value().as_str().into();
+
+        self.set_instance_defaults(cx, |defaults| {
+            update_default(&mut defaults.memory, |memory| {
+                let min = memory_min.unwrap_or(memory.min);
+                let max = memory_max.unwrap_or(memory.max);
+                // A minimum above the maximum would prevent the game from starting, so the previous values are kept
+                if min <= max {
+                    memory.min = min;
+                    memory.max = max;
+                }
+            });
+            update_default(&mut defaults.jvm_flags, |config| config.flags = jvm_flags);
+            update_default(&mut defaults.env_vars, |config| config.variables = env_vars);
+            update_default(&mut defaults.resolution, |resolution| {
+                resolution.width = resolution_width.unwrap_or(resolution.width);
+                resolution.height = resolution_height.unwrap_or(resolution.height);
+            });
+        });
+    }
+
+    fn on_world_backups_changed(&mut self, state: Entity<InputState>, event: &InputEvent, cx: &mut Context<Self>) {
+        if !matches!(event, InputEvent::Blur | InputEvent::PressEnter { .. }) {
+            return;
+        }
+        let Ok(value) = state.read(cx).value().parse::<u32>() else {
+            return;
+        };
+        let Some(backend_config) = &mut self.backend_config else {
+            return;
+        };
+        if backend_config.world_backups_to_keep() != value {
+            backend_config.world_backups_to_keep = Some(value);
+            self.backend_handle.send(MessageToBackend::SetWorldBackupsToKeep { value });
+        }
+    }
+
+    fn on_curseforge_api_key_changed(&mut self, state: Entity<InputState>, event: &InputEvent, cx: &mut Context<Self>) {
+        // The key is saved once it's fully entered, not for every character typed or pasted
+        if !matches!(event, InputEvent::Blur | InputEvent::PressEnter { .. }) {
+            return;
+        }
+        let value = state.read(cx).value();
+        let key: Option<Arc<str>> = Some(value.trim()).filter(|key| !key.is_empty()).map(Arc::from);
+        let Some(backend_config) = &mut self.backend_config else {
+            return;
+        };
+        if backend_config.curseforge_api_key != key {
+            backend_config.curseforge_api_key = key.clone();
+            self.backend_handle.send(MessageToBackend::SetCurseforgeApiKey { key });
+        }
+    }
+
+    fn load_game_settings(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(backend_config) = &self.backend_config else {
+            return;
+        };
+        let defaults = backend_config.instance_defaults.clone();
+        let world_backups = backend_config.world_backups_to_keep();
+        let curseforge_api_key = backend_config.curseforge_api_key.clone().unwrap_or_default();
+        self.loaded_game_settings = true;
+
+        let memory = defaults.memory.unwrap_or_default();
+        let jvm_flags = defaults.jvm_flags.clone().unwrap_or_default();
+        let env_vars = defaults.env_vars.clone().unwrap_or_default();
+        let resolution = defaults.resolution.unwrap_or_default();
+
+        let values = [
+            (&self.memory_min_input_state, SharedString::new(memory.min.to_string())),
+            (&self.memory_max_input_state, SharedString::new(memory.max.to_string())),
+            (&self.jvm_flags_input_state, SharedString::new(jvm_flags.flags)),
+            (&self.env_vars_input_state, SharedString::new(env_vars.variables)),
+            (&self.resolution_width_input_state, SharedString::new(resolution.width.to_string())),
+            (&self.resolution_height_input_state, SharedString::new(resolution.height.to_string())),
+            (&self.world_backups_input_state, SharedString::new(world_backups.to_string())),
+            (&self.curseforge_api_key_input_state, SharedString::new(curseforge_api_key)),
+        ];
+        for (state, value) in values {
+            state.update(cx, |state, cx| state.set_value(value, window, cx));
+        }
+    }
+
+    fn select_jvm_binary(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Select JVM binary".into())
+        });
+
+        let this_entity = cx.entity();
+        self._select_jvm_binary_task = window.spawn(cx, async move |cx| {
+            let Ok(Ok(Some(paths))) = receiver.await else {
+                return;
+            };
+            _ = cx.update_window_entity(&this_entity, move |this, _, cx| {
+                let path: Option<Arc<Path>> = paths.first().map(|v| v.as_path().into());
+                this.set_instance_defaults(cx, |defaults| {
+                    update_default(&mut defaults.jvm_binary, |config| config.path = path);
+                });
+            });
+        });
+    }
+
+    fn render_game_tab(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Div {
+        if !self.loaded_game_settings {
+            self.load_game_settings(window, cx);
+        }
+
+        let Some(backend_config) = &self.backend_config else {
+            return v_flex().px_4().py_3().child(Spinner::new().large());
+        };
+
+        let defaults = &backend_config.instance_defaults;
+        let memory_enabled = defaults.memory.is_some_and(|config| config.enabled);
+        let jvm_flags_enabled = defaults.jvm_flags.as_ref().is_some_and(|config| config.enabled);
+        let jvm_binary = defaults.jvm_binary.clone().unwrap_or_default();
+        let env_vars_enabled = defaults.env_vars.as_ref().is_some_and(|config| config.enabled);
+        let resolution_enabled = defaults.resolution.is_some_and(|config| config.enabled);
+
+        let jvm_binary_label = if let Some(path) = &jvm_binary.path {
+            SharedString::new(path.to_string_lossy())
+        } else {
+            SharedString::new_static("<unset>")
+        };
+
+        let content = v_flex()
+            .px_4()
+            .py_3()
+            .gap_3()
+            .child(div().text_sm().text_color(cx.theme().muted_foreground)
+                .child("Defaults used by instances which don't override these settings themselves"))
+            .child(v_flex()
+                .gap_1()
+                .child(Checkbox::new("default-memory").label("Set Memory").checked(memory_enabled).on_click(cx.listener(|settings, value: &bool, _, cx| {
+                    settings.set_instance_defaults(cx, |defaults| update_default(&mut defaults.memory, |config| config.enabled = *value));
+                })))
+                .child(h_flex()
+                    .gap_1()
+                    .child(NumberInput::new(&self.memory_min_input_state).small().suffix("MiB").disabled(!memory_enabled))
+                    .child("Min"))
+                .child(h_flex()
+                    .gap_1()
+                    .child(NumberInput::new(&self.memory_max_input_state).small().suffix("MiB").disabled(!memory_enabled))
+                    .child("Max")))
+            .child(v_flex()
+                .gap_1()
+                .child(Checkbox::new("default-jvm-flags").label("Add JVM Flags").checked(jvm_flags_enabled).on_click(cx.listener(|settings, value: &bool, _, cx| {
+                    settings.set_instance_defaults(cx, |defaults| update_default(&mut defaults.jvm_flags, |config| config.enabled = *value));
+                })))
+                .child(Input::new(&self.jvm_flags_input_state).disabled(!jvm_flags_enabled)))
+            .child(v_flex()
+                .gap_1()
+                .child(Checkbox::new("default-jvm-binary").label("Override JVM Binary").checked(jvm_binary.enabled).on_click(cx.listener(|settings, value: &bool, _, cx| {
+                    settings.set_instance_defaults(cx, |defaults| update_default(&mut defaults.jvm_binary, |config| config.enabled = *value));
+                })))
+                .child(Button::new("default-select-jvm-binary").success().label(jvm_binary_label).disabled(!jvm_binary.enabled).on_click(cx.listener(|settings, _, window, cx| {
+                    settings.select_jvm_binary(window, cx);
+                }))))
+            .child(v_flex()
+                .gap_1()
+                .child(Checkbox::new("default-env-vars").label("Set Environment Variables").checked(env_vars_enabled).on_click(cx.listener(|settings, value: &bool, _, cx| {
+                    settings.set_instance_defaults(cx, |defaults| update_default(&mut defaults.env_vars, |config| config.enabled = *value));
+                })))
+                .child(Input::new(&self.env_vars_input_state).disabled(!env_vars_enabled)))
+            .child(v_flex()
+                .gap_1()
+                .child(Checkbox::new("default-resolution").label("Set Window Size").checked(resolution_enabled).on_click(cx.listener(|settings, value: &bool, _, cx| {
+                    settings.set_instance_defaults(cx, |defaults| update_default(&mut defaults.resolution, |config| config.enabled = *value));
+                })))
+                .child(h_flex()
+                    .gap_1()
+                    .child(Input::new(&self.resolution_width_input_state).small().disabled(!resolution_enabled))
+                    .child("x")
+                    .child(Input::new(&self.resolution_height_input_state).small().disabled(!resolution_enabled))));
+
+        let content = content.child(crate::labelled("Backups to keep per world (0 keeps all)",
+            NumberInput::new(&self.world_backups_input_state).small()))
+            .child(crate::labelled("CurseForge API key (needed to search and install from CurseForge)",
+                Input::new(&self.curseforge_api_key_input_state).small()));
+
+        #[cfg(target_os = "linux")]
+        let content = {
+            let linux_wrapper = defaults.linux_wrapper.unwrap_or_default();
+            content.child(v_flex()
+                .gap_1()
+                .child(Checkbox::new("default-linux-wrapper").label("Override Linux Performance").checked(linux_wrapper.enabled).on_click(cx.listener(|settings, value: &bool, _, cx| {
+                    settings.set_instance_defaults(cx, |defaults| update_default(&mut defaults.linux_wrapper, |config| config.enabled = *value));
+                })))
+                .child(Checkbox::new("default-use-mangohud").label("Use MangoHud").checked(linux_wrapper.use_mangohud).disabled(!linux_wrapper.enabled).on_click(cx.listener(|settings, value: &bool, _, cx| {
+                    settings.set_instance_defaults(cx, |defaults| update_default(&mut defaults.linux_wrapper, |config| config.use_mangohud = *value));
+                })))
+                .child(Checkbox::new("default-use-gamemode").label("Use GameMode").checked(linux_wrapper.use_gamemode).disabled(!linux_wrapper.enabled).on_click(cx.listener(|settings, value: &bool, _, cx| {
+                    settings.set_instance_defaults(cx, |defaults| update_default(&mut defaults.linux_wrapper, |config| config.use_gamemode = *value));
+                })))
+                .child(Checkbox::new("default-use-discrete-gpu").label("Use Discrete GPU").checked(linux_wrapper.use_discrete_gpu).disabled(!linux_wrapper.enabled).on_click(cx.listener(|settings, value: &bool, _, cx| {
+                    settings.set_instance_defaults(cx, |defaults| update_default(&mut defaults.linux_wrapper, |config| config.use_discrete_gpu = *value));
+                }))))
+        };
+
+        content
+    }
 }
 
 impl Render for Settings {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.selected_tab == 1 {
+            return self.render_game_tab(window, cx);
+        }
+
         let interface_config = InterfaceConfig::get(cx);
 
         let mut div = v_flex()
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use bridge::{
+    content_update::{ContentUpdateEntry, ContentUpdatePlan},
+    handle::BackendHandle,
+    instance::InstanceID,
+    message::MessageToBackend,
+    modal_action::ModalAction,
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    button::{Button, ButtonVariants}, checkbox::Checkbox, h_flex, scroll::ScrollableElement, v_flex, ActiveTheme as _, WindowExt
+};
+use parking_lot::Mutex;
+use rustc_hash::FxHashSet;
+
+use crate::modals;
+
+pub fn open_update_all(
+    instance: InstanceID,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let (send, recv) = tokio::sync::oneshot::channel();
+    let modal_action = ModalAction::default();
+
+    backend_handle.send(MessageToBackend::PlanContentUpdates {
+        id: instance,
+        modal_action: modal_action.clone(),
+        channel: send,
+    });
+
+    modals::generic::show_modal(window, cx, "Checking for updates".into(), "Error checking for updates".into(), modal_action);
+
+    window.spawn(cx, async move |cx| {
+        let Ok(plan) = recv.await else {
+            return;
+        };
+        _ = cx.update(move |window, cx| {
+            window.close_all_dialogs(cx);
+            open_update_plan(instance, plan, backend_handle, window, cx);
+        });
+    }).detach();
+}
+
+fn open_update_plan(
+    instance: InstanceID,
+    plan: ContentUpdatePlan,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let title = if plan.entries.is_empty() {
+        SharedString::new_static("Everything is up-to-date")
+    } else {
+        SharedString::new(format!("{} updates available", plan.entries.len()))
+    };
+
+    let last_batch = plan.last_batch.as_ref().map(|batch| {
+        let created = chrono::DateTime::from_timestamp_millis(batch.created)
+            .map(|date_time| date_time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+        SharedString::new(format!("Roll back {} updates from {}", batch.count, created))
+    });
+
+    // Indices of the entries that were unticked
+    let unticked: Arc<Mutex<FxHashSet<usize>>> = Default::default();
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let theme = cx.theme();
+        let unticked_entries = unticked.lock().clone();
+
+        let mut rows = Vec::with_capacity(plan.entries.len());
+        for (index, entry) in plan.entries.iter().enumerate() {
+            let label = SharedString::new(format!("{}: {} → {}", entry.name, entry.old_version, entry.new_version));
+            let checkbox = Checkbox::new(("entry", index)).label(label).checked(!unticked_entries.contains(&index)).on_click({
+                let unticked = unticked.clone();
+                move |value, window, _| {
+                    if *value {
+                        unticked.lock().remove(&index);
+                    } else {
+                        unticked.lock().insert(index);
+                    }
+                    window.refresh();
+                }
+            });
+
+            let changelogs = entry.changelogs.iter().map(|changelog| {
+                v_flex()
+                    .pl_6()
+                    .child(div().text_sm().font_semibold().child(SharedString::new(changelog.version.clone())))
+                    .child(div().text_xs().text_color(theme.muted_foreground).child(SharedString::new(changelog.changelog.clone())))
+            });
+
+            rows.push(v_flex()
+                .gap_1()
+                .child(checkbox)
+                .children(changelogs));
+        }
+
+        let selected: Arc<[ContentUpdateEntry]> = plan.entries.iter().enumerate()
+            .filter(|(index, _)| !unticked_entries.contains(index))
+            .map(|(_, entry)| entry.clone())
+            .collect();
+
+        let content = v_flex()
+            .gap_2()
+            .when(!plan.entries.is_empty(), |this| {
+                this.child("The replaced files are kept, so the whole batch can be rolled back afterwards")
+                    .child(v_flex()
+                        .id("update_entries")
+                        .max_h_96()
+                        .p_2()
+                        .gap_3()
+                        .border_1()
+                        .rounded(theme.radius)
+                        .border_color(theme.border)
+                        .children(rows)
+                        .overflow_y_scrollbar())
+            })
+            .child(h_flex()
+                .gap_2()
+                .child(Button::new("cancel").label("Cancel").on_click(|_, window, cx| {
+                    window.close_all_dialogs(cx);
+                }))
+                .when_some(last_batch.clone(), |this, last_batch| {
+                    this.child(Button::new("rollback").label(last_batch).warning().on_click({
+                        let backend_handle = backend_handle.clone();
+                        move |_, window, cx| {
+                            window.close_all_dialogs(cx);
+                            let modal_action = ModalAction::default();
+                            backend_handle.send(MessageToBackend::RollbackContentUpdates {
+                                id: instance,
+                                modal_action: modal_action.clone(),
+                            });
+                            modals::generic::show_modal(window, cx, "Rolling back updates".into(), "Error rolling back updates".into(), modal_action);
+                        }
+                    }))
+                })
+                .when(!plan.entries.is_empty(), |this| {
+                    let label = SharedString::new(format!("Update {} selected", selected.len()));
+                    this.child(Button::new("apply").label(label).success().disabled(selected.is_empty()).on_click({
+                        let backend_handle = backend_handle.clone();
+                        move |_, window, cx| {
+                            window.close_all_dialogs(cx);
+                            let modal_action = ModalAction::default();
+                            backend_handle.send(MessageToBackend::ApplyContentUpdates {
+                                id: instance,
+                                entries: selected.clone(),
+                                modal_action: modal_action.clone(),
+                            });
+                            modals::generic::show_modal(window, cx, "Updating content".into(), "Error updating content".into(), modal_action);
+                        }
+                    }))
+                }));
+
+        dialog.title(title.clone()).child(content)
+    });
+}
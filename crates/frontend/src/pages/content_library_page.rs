@@ -0,0 +1,301 @@
+use std::sync::Arc;
+
+use bridge::{
+    content_library::{ContentLibrary, ContentLibraryEntry},
+    handle::BackendHandle,
+    install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget},
+    instance::ContentType,
+    message::MessageToBackend,
+    modal_action::ModalAction,
+    safe_path::SafePath,
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    button::{Button, ButtonVariants}, h_flex, notification::NotificationType, scroll::ScrollableElement, select::Select, spinner::Spinner, v_flex, ActiveTheme as _, Disableable, IconName, IndexPath, Sizable, WindowExt
+};
+use relative_path::RelativePath;
+use rustc_hash::FxHashSet;
+use schema::{content::ContentSource, loader::Loader};
+
+use crate::{
+    component::instance_dropdown::InstanceDropdown,
+    entity::{instance::{InstanceEntries, InstanceEntry}, DataEntities},
+    modals, png_render_cache, root, ui,
+};
+
+pub struct ContentLibraryPage {
+    backend_handle: BackendHandle,
+    instances: Entity<InstanceEntries>,
+    library: Option<ContentLibrary>,
+    _get_library_task: Task<()>,
+}
+
+impl ContentLibraryPage {
+    pub fn new(data: &DataEntities, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut page = Self {
+            backend_handle: data.backend_handle.clone(),
+            instances: data.instances.clone(),
+            library: None,
+            _get_library_task: Task::ready(()),
+        };
+
+        page.reload(cx);
+
+        page
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        let (send, recv) = tokio::sync::oneshot::channel();
+        self.wait_for_library(recv, cx);
+        self.backend_handle.send(MessageToBackend::GetContentLibrary {
+            channel: send,
+        });
+    }
+
+    fn open_prune_dialog(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(library) = &self.library else {
+            return;
+        };
+
+        let prunable_count = library.entries.iter().filter(|entry| entry.is_prunable()).count();
+        let message = SharedString::new(format!(
+            "{} unused files using {} will be moved to the trash",
+            prunable_count,
+            crate::format_bytes(library.prunable_size)
+        ));
+
+        let page = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let content = v_flex()
+                .gap_2()
+                .child(message.clone())
+                .child(Button::new("confirm").label("Prune unused").danger().on_click({
+                    let page = page.clone();
+                    move |_, window, cx| {
+                        window.close_all_dialogs(cx);
+                        page.update(cx, |page, cx| page.prune(window, cx));
+                    }
+                }));
+
+            dialog.title("Prune Content Library").child(content)
+        });
+    }
+
+    fn prune(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (send, recv) = tokio::sync::oneshot::channel();
+        let modal_action = ModalAction::default();
+
+        self.wait_for_library(recv, cx);
+        self.backend_handle.send(MessageToBackend::PruneContentLibrary {
+            modal_action: modal_action.clone(),
+            channel: send,
+        });
+
+        modals::generic::show_modal(window, cx, "Pruning content library".into(), "Error pruning content library".into(), modal_action);
+    }
+
+    fn wait_for_library(&mut self, recv: tokio::sync::oneshot::Receiver<ContentLibrary>, cx: &mut Context<Self>) {
+        self.library = None;
+        self._get_library_task = cx.spawn(async move |page, cx| {
+            let result: ContentLibrary = recv.await.unwrap_or_default();
+            let _ = page.update(cx, move |page, cx| {
+                page.library = Some(result);
+                cx.notify();
+            });
+        });
+    }
+
+    fn render_entry(&self, index: usize, entry: &ContentLibraryEntry, cx: &mut Context<Self>) -> Div {
+        let icon = if let Some(png_icon) = entry.summary.as_ref().and_then(|summary| summary.png_icon.as_ref()) {
+            png_render_cache::render(Arc::clone(png_icon), cx)
+        } else {
+            gpui::img(ImageSource::Resource(Resource::Embedded("images/default_mod.png".into())))
+        };
+
+        let file_name = entry_file_name(entry);
+        let name: SharedString = entry.summary.as_ref()
+            .and_then(|summary| summary.name.clone())
+            .map(SharedString::from)
+            .unwrap_or_else(|| file_name.clone().into());
+        let version: SharedString = entry.summary.as_ref()
+            .map(|summary| SharedString::from(summary.version_str.clone()))
+            .unwrap_or_default();
+
+        let usage = if entry.links.is_empty() {
+            if entry.retained {
+                SharedString::new_static("Kept for an installed modpack or to roll back an update")
+            } else {
+                SharedString::new_static("Not used by any instance")
+            }
+        } else {
+            let mut seen = FxHashSet::default();
+            let names: Vec<&str> = entry.links.iter()
+                .filter(|link| seen.insert(link.instance_name.clone()))
+                .map(|link| &*link.instance_name)
+                .collect();
+            if entry.links.len() == 1 {
+                SharedString::new(format!("Used by {}", names.join(", ")))
+            } else {
+                SharedString::new(format!("Used by {} ({} links)", names.join(", "), entry.links.len()))
+            }
+        };
+
+        let usage_color = if entry.is_prunable() {
+            cx.theme().danger
+        } else {
+            cx.theme().muted_foreground
+        };
+
+        let install_button = Button::new(("install", index)).success().small().icon(IconName::Plus).label("Add to instance")
+            .disabled(entry.summary.is_none())
+            .on_click({
+                let entry = entry.clone();
+                let instances = self.instances.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_install_dialog(entry.clone(), &instances, backend_handle.clone(), window, cx);
+                }
+            });
+
+        h_flex()
+            .gap_3()
+            .p_2()
+            .border_b_1()
+            .border_color(cx.theme().border)
+            .child(icon.size_12().min_w_12().min_h_12())
+            .child(v_flex()
+                .flex_1()
+                .overflow_hidden()
+                .child(h_flex().gap_2().child(div().font_bold().child(name)).child(version))
+                .child(div().text_sm().text_color(cx.theme().muted_foreground).child(file_name))
+                .child(div().text_sm().text_color(usage_color).child(usage)))
+            .child(div().text_color(cx.theme().muted_foreground).child(crate::format_bytes(entry.size)))
+            .child(install_button)
+    }
+}
+
+impl Render for ContentLibraryPage {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let prunable_size = self.library.as_ref().map(|library| library.prunable_size).unwrap_or(0);
+
+        let title = h_flex()
+            .gap_8()
+            .child("Content Library")
+            .child(h_flex().gap_2()
+                .child(Button::new("refresh").label("Refresh").disabled(self.library.is_none()).on_click(cx.listener(|page, _, _, cx| {
+                    page.reload(cx);
+                })))
+                .child(Button::new("prune").danger().label("Prune unused").disabled(prunable_size == 0).on_click(cx.listener(|page, _, window, cx| {
+                    page.open_prune_dialog(window, cx);
+                }))));
+
+        let Some(library) = &self.library else {
+            let content = v_flex().size_full().p_3().gap_3()
+                .child("Scanning content library...")
+                .child(Spinner::new().with_size(gpui_component::Size::Large));
+            return ui::page(cx, title).child(content).overflow_y_scrollbar();
+        };
+
+        let prunable_count = library.entries.iter().filter(|entry| entry.is_prunable()).count();
+        let stats = v_flex()
+            .gap_1()
+            .child(format!("{} files using {}", library.entries.len(), crate::format_bytes(library.total_size)))
+            .child(format!("{} saved by sharing files between instances", crate::format_bytes(library.saved_size)))
+            .when(prunable_count > 0, |stats| {
+                stats.child(div().text_color(cx.theme().danger)
+                    .child(format!("{} unused files using {} can be pruned", prunable_count, crate::format_bytes(library.prunable_size))))
+            });
+
+        let mut content = v_flex().p_3().gap_3().child(stats);
+        if library.entries.is_empty() {
+            content = content.child(div().text_color(cx.theme().muted_foreground).child("The content library is empty"));
+        } else {
+            let entries = library.entries.clone();
+            content = content.child(v_flex().children(entries.iter().enumerate().map(|(index, entry)| {
+                self.render_entry(index, entry, cx)
+            })));
+        }
+
+        ui::page(cx, title).child(content).overflow_y_scrollbar()
+    }
+}
+
+fn entry_file_name(entry: &ContentLibraryEntry) -> Arc<str> {
+    // Prefer the name the file has in an instance over the hash it's stored as
+    let linked_name = entry.links.iter().find_map(|link| {
+        let file_name = RelativePath::new(&*link.path.replace('\\', "/")).file_name()?.to_string();
+        Some(file_name.strip_suffix(".disabled").map(str::to_string).unwrap_or(file_name))
+    });
+
+    linked_name.map(Arc::from).unwrap_or_else(|| {
+        entry.path.file_name().map(|name| name.to_string_lossy().into()).unwrap_or_else(|| hex::encode(entry.hash).into())
+    })
+}
+
+fn open_install_dialog(entry: ContentLibraryEntry, instances: &Entity<InstanceEntries>, backend_handle: BackendHandle, window: &mut Window, cx: &mut App) {
+    let Some(summary) = entry.summary.clone() else {
+        return;
+    };
+
+    let title = SharedString::new(format!("Install {}", summary.name.as_deref().unwrap_or("content")));
+
+    let entries: Arc<[InstanceEntry]> = instances.read(cx).entries.iter()
+        .map(|(_, instance)| instance.read(cx).clone())
+        .collect();
+    if entries.is_empty() {
+        window.open_dialog(cx, move |modal, _, _| {
+            modal.title(title.clone()).child("Create an instance to install content into first")
+        });
+        return;
+    }
+
+    let instances = InstanceDropdown::create(entries, window, cx);
+    instances.update(cx, |dropdown, cx| {
+        dropdown.set_selected_index(Some(IndexPath::default()), window, cx)
+    });
+
+    let (folder, loader_hint) = match summary.extra {
+        ContentType::Fabric => ("mods", Loader::Fabric),
+        ContentType::Forge => ("mods", Loader::Forge),
+        ContentType::NeoForge => ("mods", Loader::NeoForge),
+        ContentType::JavaModule | ContentType::ModrinthModpack { .. } => ("mods", Loader::Unknown),
+        ContentType::ResourcePack { .. } => ("resourcepacks", Loader::Unknown),
+        ContentType::ShaderPack { .. } => ("shaderpacks", Loader::Unknown),
+    };
+    let file_name = entry_file_name(&entry);
+
+    window.open_dialog(cx, move |dialog, _, cx| {
+        let selected_instance = instances.read(cx).selected_value().cloned();
+
+        dialog.title(title.clone()).child(h_flex()
+            .gap_2()
+            .child(Select::new(&instances).placeholder("Select an instance").title_prefix("Instance: "))
+            .when_some(selected_instance, |content, instance| {
+                let entry = entry.clone();
+                let file_name = file_name.clone();
+                let backend_handle = backend_handle.clone();
+                content.child(Button::new("instance").success().h_full().label("Add to instance").on_click(move |_, window, cx| {
+                    let path = RelativePath::new(folder).join(&*file_name);
+                    let Some(path) = SafePath::from_relative_path(&path) else {
+                        window.push_notification((NotificationType::Error, "Invalid/dangerous filename"), cx);
+                        return;
+                    };
+
+                    let content_install = ContentInstall {
+                        target: InstallTarget::Instance(instance.id),
+                        loader_hint,
+                        version_hint: None,
+                        files: [ContentInstallFile {
+                            replace_old: None,
+                            path: ContentInstallPath::Safe(path),
+                            download: ContentDownload::File { path: entry.path.to_path_buf() },
+                            content_source: ContentSource::Manual,
+                        }].into(),
+                    };
+
+                    window.close_all_dialogs(cx);
+                    root::start_install(content_install, &backend_handle, window, cx);
+                }))
+            }))
+    });
+}
@@ -0,0 +1,472 @@
+use std::{ops::Range, sync::Arc, time::Duration};
+
+use bridge::{instance::InstanceID, meta::MetadataRequest};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme, Icon, IconName, Selectable, button::{Button, ButtonGroup, ButtonVariants}, h_flex, input::{Input, InputEvent, InputState}, scroll::{ScrollableElement, Scrollbar}, skeleton::Skeleton, v_flex
+};
+use rustc_hash::FxHashSet;
+use schema::{content::ContentSource, curseforge::{
+    CurseforgeClass, CurseforgeMod, CurseforgeModLoaderType, CurseforgeSearchRequest, CurseforgeSearchResult
+}};
+
+use crate::{
+    component::{error_alert::ErrorAlert, page_path::PagePath}, entity::{
+        DataEntities, metadata::{AsMetadataResult, FrontendMetadata, FrontendMetadataResult}
+    }, ui
+};
+
+const PAGE_SIZE: usize = 20;
+
+pub struct CurseforgeSearchPage {
+    data: DataEntities,
+    hits: Vec<CurseforgeMod>,
+    page_path: PagePath,
+    install_for: Option<InstanceID>,
+    loading: Option<Subscription>,
+    pending_clear: bool,
+    total_hits: usize,
+    search_state: Entity<InputState>,
+    _search_input_subscription: Subscription,
+    _delayed_clear_task: Task<()>,
+    filter_class: CurseforgeClass,
+    filter_loader: Option<CurseforgeModLoaderType>,
+    game_version: Option<Arc<str>>,
+    installed_projects: FxHashSet<u32>,
+    last_search: Arc<str>,
+    scroll_handle: UniformListScrollHandle,
+    search_error: Option<SharedString>,
+    image_cache: Entity<RetainAllImageCache>,
+}
+
+impl CurseforgeSearchPage {
+    pub fn new(install_for: Option<InstanceID>, class: Option<CurseforgeClass>, page_path: PagePath, data: &DataEntities, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let filter_class = match class {
+            Some(CurseforgeClass::Modpack) | None => CurseforgeClass::Mod,
+            Some(class) => class,
+        };
+
+        let search_state = cx.new(|cx| InputState::new(window, cx).placeholder(search_placeholder(filter_class)).clean_on_escape());
+
+        let mut game_version = None;
+        let mut filter_loader = None;
+        let mut installed_projects = FxHashSet::default();
+
+        // Only results that can be installed into the instance are shown
+        if let Some(install_for) = install_for {
+            if let Some(entry) = data.instances.read(cx).entries.get(&install_for) {
+                let instance = entry.read(cx);
+                game_version = Some(instance.configuration.minecraft_version.as_str().into());
+                filter_loader = instance.configuration.loader.as_curseforge_loader();
+
+                let mods = instance.mods.read(cx);
+                for summary in mods.iter() {
+                    if let ContentSource::CurseforgeProject { project } = summary.content_source {
+                        installed_projects.insert(project);
+                    }
+                }
+            }
+        }
+
+        let _search_input_subscription = cx.subscribe_in(&search_state, window, Self::on_search_input_event);
+
+        let mut page = Self {
+            data: data.clone(),
+            hits: Vec::new(),
+            page_path,
+            install_for,
+            loading: None,
+            pending_clear: false,
+            total_hits: 1,
+            search_state,
+            _search_input_subscription,
+            _delayed_clear_task: Task::ready(()),
+            filter_class,
+            filter_loader,
+            game_version,
+            installed_projects,
+            last_search: Arc::from(""),
+            scroll_handle: UniformListScrollHandle::new(),
+            search_error: None,
+            image_cache: RetainAllImageCache::new(cx),
+        };
+        page.load_more(cx);
+        page
+    }
+
+    fn on_search_input_event(
+        &mut self,
+        state: &Entity<InputState>,
+        event: &InputEvent,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let InputEvent::Change = event else {
+            return;
+        };
+
+        let search = state.read(cx).text().to_string();
+        let search = search.trim();
+
+        if &*self.last_search == search {
+            return;
+        }
+
+        self.last_search = Arc::from(search);
+        self.reload(cx);
+    }
+
+    fn set_class(&mut self, class: CurseforgeClass, window: &mut Window, cx: &mut Context<Self>) {
+        if self.filter_class == class {
+            return;
+        }
+        self.filter_class = class;
+        self.search_state.update(cx, |state, cx| {
+            state.set_placeholder(search_placeholder(class), window, cx)
+        });
+        self.reload(cx);
+    }
+
+    fn set_filter_loader(&mut self, loader: Option<CurseforgeModLoaderType>, _window: &mut Window, cx: &mut Context<Self>) {
+        if self.filter_loader == loader {
+            return;
+        }
+        self.filter_loader = loader;
+        self.reload(cx);
+    }
+
+    fn reload(&mut self, cx: &mut Context<Self>) {
+        self.pending_clear = true;
+        self.loading = None;
+
+        self._delayed_clear_task = cx.spawn(async |page, cx| {
+            gpui::Timer::after(Duration::from_millis(300)).await;
+            let _ = page.update(cx, |page, cx| {
+                if page.pending_clear {
+                    page.pending_clear = false;
+                    page.hits.clear();
+                    page.total_hits = 1;
+                    cx.notify();
+                }
+            });
+        });
+
+        self.load_more(cx);
+    }
+
+    fn load_more(&mut self, cx: &mut Context<Self>) {
+        if self.loading.is_some() {
+            return;
+        }
+        self.search_error = None;
+
+        let query = if self.last_search.is_empty() {
+            None
+        } else {
+            Some(self.last_search.clone())
+        };
+
+        let mod_loader_type = if self.filter_class == CurseforgeClass::Mod {
+            self.filter_loader
+        } else {
+            None
+        };
+
+        let request = CurseforgeSearchRequest {
+            query,
+            class_id: self.filter_class,
+            game_version: self.game_version.clone(),
+            mod_loader_type,
+            index: if self.pending_clear { 0 } else { self.hits.len() },
+            page_size: PAGE_SIZE,
+        };
+
+        let data = FrontendMetadata::request(&self.data.metadata, MetadataRequest::CurseforgeSearch(request), cx);
+
+        let result: FrontendMetadataResult<CurseforgeSearchResult> = data.read(cx).result();
+        match result {
+            FrontendMetadataResult::Loading => {
+                let subscription = cx.observe(&data, |page, data, cx| {
+                    let result: FrontendMetadataResult<CurseforgeSearchResult> = data.read(cx).result();
+                    match result {
+                        FrontendMetadataResult::Loading => {},
+                        FrontendMetadataResult::Loaded(result) => {
+                            page.apply_search_data(result);
+                            page.loading = None;
+                            cx.notify();
+                        },
+                        FrontendMetadataResult::Error(shared_string) => {
+                            page.search_error = Some(shared_string);
+                            page.loading = None;
+                            cx.notify();
+                        },
+                    }
+                });
+                self.loading = Some(subscription);
+            },
+            FrontendMetadataResult::Loaded(result) => {
+                self.apply_search_data(result);
+            },
+            FrontendMetadataResult::Error(shared_string) => {
+                self.search_error = Some(shared_string);
+            },
+        }
+    }
+
+    fn apply_search_data(&mut self, search_result: &CurseforgeSearchResult) {
+        if self.pending_clear {
+            self.pending_clear = false;
+            self.hits.clear();
+            self.total_hits = 1;
+            self._delayed_clear_task = Task::ready(());
+        }
+
+        self.hits.extend(search_result.data.iter().cloned());
+
+        // CurseForge refuses to page past 10000 results
+        self.total_hits = search_result.pagination.total_count.min(10_000);
+        if search_result.pagination.result_count < PAGE_SIZE {
+            self.total_hits = self.hits.len();
+        }
+    }
+
+    fn render_items(&mut self, visible_range: Range<usize>, _window: &mut Window, cx: &mut Context<Self>) -> Vec<Div> {
+        let theme = cx.theme();
+        let mut should_load_more = false;
+        let items = visible_range
+            .map(|index| {
+                let Some(hit) = self.hits.get(index) else {
+                    if let Some(search_error) = self.search_error.clone() {
+                        return div()
+                            .pl_3()
+                            .pt_3()
+                            .child(ErrorAlert::new("search_error", "Error requesting from CurseForge".into(), search_error));
+                    } else {
+                        should_load_more = true;
+                        return div()
+                            .pl_3()
+                            .pt_3()
+                            .child(Skeleton::new().w_full().h(px(28.0 * 4.0)).rounded_lg());
+                    }
+                };
+
+                let image = if let Some(icon_url) = hit.logo.as_ref().and_then(|logo| logo.thumbnail_url.as_ref())
+                    && !icon_url.is_empty()
+                {
+                    gpui::img(SharedUri::from(icon_url))
+                        .with_fallback(|| Skeleton::new().rounded_lg().size_16().into_any_element())
+                } else {
+                    gpui::img(ImageSource::Resource(Resource::Embedded(
+                        "images/default_mod.png".into(),
+                    )))
+                };
+
+                let name = SharedString::new(hit.name.clone());
+                let author = hit.authors.first().map(|author| format!("by {}", author.name)).unwrap_or_default();
+                let description = hit
+                    .summary
+                    .as_ref()
+                    .map(|summary| SharedString::new(summary.replace("\n", " ")))
+                    .unwrap_or(SharedString::new_static("No Description"));
+
+                const GRAY: Hsla = Hsla { h: 0.0, s: 0.0, l: 0.5, a: 1.0 };
+                let author_line = div().text_color(GRAY).text_sm().pb_px().child(author);
+
+                let downloads = h_flex()
+                    .gap_0p5()
+                    .child(Icon::empty().path("icons/download.svg"))
+                    .child(crate::pages::modrinth_page::format_downloads(hit.download_count as usize));
+
+                let installed = self.installed_projects.contains(&hit.id);
+                let class = hit.class_id.and_then(CurseforgeClass::from_id).unwrap_or(self.filter_class);
+
+                let buttons = ButtonGroup::new(("buttons", index))
+                    .layout(Axis::Vertical)
+                    .child(
+                        Button::new(("install", index))
+                            .label(if installed { "Reinstall" } else { "Install" })
+                            .icon(Icon::empty().path("icons/download.svg"))
+                            .success()
+                            .on_click({
+                                let data = self.data.clone();
+                                let name = name.clone();
+                                let project_id = hit.id;
+                                let install_for = self.install_for;
+
+                                move |_, window, cx| {
+                                    crate::modals::curseforge_install::open(
+                                        name.as_str(),
+                                        project_id,
+                                        class,
+                                        install_for,
+                                        &data,
+                                        window,
+                                        cx
+                                    );
+                                }
+                            }),
+                    )
+                    .child(
+                        Button::new(("open", index))
+                            .label("Open Page")
+                            .icon(IconName::Globe)
+                            .info()
+                            .on_click({
+                                let slug = hit.slug.clone();
+                                move |_, _, cx| {
+                                    cx.open_url(&format!(
+                                        "https://www.curseforge.com/minecraft/{}/{}",
+                                        class.url_segment(), slug
+                                    ));
+                                }
+                            }),
+                    );
+
+                let item = h_flex()
+                    .rounded_lg()
+                    .px_4()
+                    .py_2()
+                    .gap_4()
+                    .h_32()
+                    .bg(theme.background)
+                    .border_color(theme.border)
+                    .border_1()
+                    .size_full()
+                    .child(image.rounded_lg().size_16().min_w_16().min_h_16())
+                    .child(
+                        v_flex()
+                            .h(px(104.0))
+                            .flex_grow()
+                            .gap_1()
+                            .overflow_hidden()
+                            .child(
+                                h_flex()
+                                    .gap_1()
+                                    .items_end()
+                                    .line_clamp(1)
+                                    .text_lg()
+                                    .child(name)
+                                    .child(author_line),
+                            )
+                            .child(
+                                div()
+                                    .flex_auto()
+                                    .line_height(px(20.0))
+                                    .line_clamp(3)
+                                    .child(description),
+                            ),
+                    )
+                    .child(v_flex().gap_2().child(downloads).child(buttons));
+
+                div().pl_3().pt_3().child(item)
+            })
+            .collect();
+
+        if should_load_more {
+            self.load_more(cx);
+        }
+
+        items
+    }
+}
+
+impl Render for CurseforgeSearchPage {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let can_load_more = self.total_hits > self.hits.len();
+        let scroll_handle = self.scroll_handle.clone();
+
+        let item_count = self.hits.len() + if can_load_more || self.search_error.is_some() { 1 } else { 0 };
+
+        let list = h_flex()
+            .image_cache(self.image_cache.clone())
+            .size_full()
+            .overflow_y_hidden()
+            .child(
+                uniform_list(
+                    "uniform-list",
+                    item_count,
+                    cx.processor(Self::render_items),
+                )
+                .size_full()
+                .track_scroll(&scroll_handle),
+            )
+            .child(
+                div()
+                    .w_3()
+                    .h_full()
+                    .py_3()
+                    .child(Scrollbar::vertical(&scroll_handle)),
+            );
+
+        let theme = cx.theme();
+        let content = v_flex()
+            .size_full()
+            .gap_3()
+            .child(h_flex().w_full().gap_3().child(Input::new(&self.search_state)))
+            .child(div().size_full().rounded_lg().border_1().border_color(theme.border).child(list));
+
+        // Modpacks aren't offered since the CurseForge modpack format can't be installed
+        let class_button_group = ButtonGroup::new("type")
+            .layout(Axis::Vertical)
+            .outline()
+            .child(Button::new("mods").label("Mods").selected(self.filter_class == CurseforgeClass::Mod))
+            .child(
+                Button::new("resourcepacks")
+                    .label("Resourcepacks")
+                    .selected(self.filter_class == CurseforgeClass::Resourcepack),
+            )
+            .child(Button::new("shaders").label("Shaders").selected(self.filter_class == CurseforgeClass::Shader))
+            .on_click(cx.listener(|page, clicked: &Vec<usize>, window, cx| match clicked[0] {
+                0 => page.set_class(CurseforgeClass::Mod, window, cx),
+                1 => page.set_class(CurseforgeClass::Resourcepack, window, cx),
+                2 => page.set_class(CurseforgeClass::Shader, window, cx),
+                _ => {},
+            }));
+
+        // The loader is fixed when installing into an instance
+        let loader_button_group = if self.filter_class == CurseforgeClass::Mod && self.install_for.is_none() {
+            const LOADERS: [CurseforgeModLoaderType; 4] = [
+                CurseforgeModLoaderType::Fabric,
+                CurseforgeModLoaderType::Forge,
+                CurseforgeModLoaderType::NeoForge,
+                CurseforgeModLoaderType::Quilt,
+            ];
+            Some(ButtonGroup::new("loader_group")
+                .layout(Axis::Vertical)
+                .outline()
+                .children(LOADERS.iter().map(|loader| {
+                    Button::new(loader.name()).label(loader.name()).selected(self.filter_loader == Some(*loader))
+                }))
+                .on_click(cx.listener(|page, clicked: &Vec<usize>, window, cx| {
+                    let loader = clicked.first().and_then(|index| LOADERS.get(*index)).copied();
+                    // Clicking the selected loader again removes the filter
+                    let loader = if loader == page.filter_loader { None } else { loader };
+                    page.set_filter_loader(loader, window, cx);
+                })))
+        } else {
+            None
+        };
+
+        let parameters = h_flex()
+            .h_full()
+            .min_h_0()
+            .flex_1()
+            .overflow_y_scrollbar()
+            .child(v_flex().h_full().gap_3()
+                .child(class_button_group)
+                .when_some(loader_button_group, |this, group| this.child(group))
+            );
+
+        ui::page(cx, self.page_path.create_breadcrumb(&self.data, cx))
+            .child(h_flex().flex_1().min_h_0().size_full().p_3().gap_3().child(parameters).child(content))
+    }
+}
+
+fn search_placeholder(class: CurseforgeClass) -> &'static str {
+    match class {
+        CurseforgeClass::Mod => "Search mods...",
+        CurseforgeClass::Modpack => "Search modpacks...",
+        CurseforgeClass::Resourcepack => "Search resourcepacks...",
+        CurseforgeClass::Shader => "Search shaders...",
+    }
+}
@@ -0,0 +1,374 @@
+use std::{
+    path::Path,
+    sync::{Arc, atomic::Ordering},
+};
+
+use bridge::{
+    handle::BackendHandle,
+    install::{ContentDownload, ContentInstall, ContentInstallFile, ContentInstallPath, InstallTarget},
+    instance::{InstanceDatapackSummary, InstanceID, InstanceStatus, InstanceWorldSummary, WorldDatapacks},
+    message::{AtomicBridgeDataLoadState, MessageToBackend},
+    serial::AtomicOptionSerial,
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _, Disableable, IndexPath, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    list::{ListDelegate, ListItem, ListState},
+    notification::{Notification, NotificationType},
+    select::{SearchableVec, Select, SelectEvent, SelectState},
+    switch::Switch,
+    v_flex,
+};
+use rustc_hash::FxHashMap;
+use schema::{content::ContentSource, loader::Loader, modrinth::ModrinthProjectType};
+use ustr::Ustr;
+
+use crate::{entity::instance::InstanceEntry, png_render_cache, root, ui::PageType};
+
+use super::instance_page::InstanceSubpageType;
+
+pub struct InstanceDatapacksSubpage {
+    instance: Entity<InstanceEntry>,
+    instance_id: InstanceID,
+    instance_loader: Loader,
+    instance_version: Ustr,
+    backend_handle: BackendHandle,
+    worlds_state: Arc<AtomicBridgeDataLoadState>,
+    worlds_serial: AtomicOptionSerial,
+    world_datapacks: Entity<FxHashMap<Arc<str>, WorldDatapacks>>,
+    world_select_state: Entity<SelectState<SearchableVec<SharedString>>>,
+    selected_world: Option<Arc<str>>,
+    datapack_list: Entity<ListState<DatapacksListDelegate>>,
+    _add_from_file_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl InstanceDatapacksSubpage {
+    pub fn new(
+        instance: &Entity<InstanceEntry>,
+        backend_handle: BackendHandle,
+        window: &mut gpui::Window,
+        cx: &mut gpui::Context<Self>,
+    ) -> Self {
+        let entry = instance.read(cx);
+        let instance_id = entry.id;
+        let instance_loader = entry.configuration.loader;
+        let instance_version = entry.configuration.minecraft_version;
+        let running = entry.status != InstanceStatus::NotRunning;
+        let worlds_state = Arc::clone(&entry.worlds_state);
+        let worlds = entry.worlds.clone();
+        let world_datapacks = entry.world_datapacks.clone();
+
+        let world_select_state = cx.new(|cx| {
+            let mut select_state = SelectState::new(SearchableVec::new(world_names(worlds.read(cx))), None, window, cx)
+                .searchable(true);
+            select_state.set_selected_index(Some(IndexPath::default()), window, cx);
+            select_state
+        });
+        let selected_world = world_select_state.read(cx).selected_value().map(|name| Arc::from(name.as_str()));
+
+        let datapacks_list_delegate = DatapacksListDelegate {
+            id: instance_id,
+            world: None,
+            backend_handle: backend_handle.clone(),
+            running,
+            pack_format: None,
+            datapacks: Vec::new(),
+            searched: Vec::new(),
+        };
+        let datapack_list = cx.new(|cx| ListState::new(datapacks_list_delegate, window, cx).selectable(false).searchable(true));
+
+        let _subscriptions = vec![
+            cx.subscribe_in(&world_select_state, window, Self::on_world_selected),
+            cx.observe_in(&worlds, window, |page, worlds, window, cx| {
+                let names = world_names(worlds.read(cx));
+                page.world_select_state.update(cx, |select_state, cx| {
+                    let previous = select_state.selected_value().cloned();
+                    select_state.set_items(SearchableVec::new(names), window, cx);
+                    if let Some(previous) = previous {
+                        select_state.set_selected_value(&previous, window, cx);
+                    }
+                    if select_state.selected_index(cx).is_none() {
+                        select_state.set_selected_index(Some(IndexPath::default()), window, cx);
+                    }
+                });
+                let selected = page.world_select_state.read(cx).selected_value().map(|name| Arc::from(name.as_str()));
+                page.select_world(selected, cx);
+            }),
+            cx.observe(&world_datapacks, |page, _, cx| {
+                page.update_datapack_list(cx);
+            }),
+            cx.observe(instance, |page, instance, cx| {
+                let running = instance.read(cx).status != InstanceStatus::NotRunning;
+                page.datapack_list.update(cx, |list, cx| {
+                    list.delegate_mut().running = running;
+                    cx.notify();
+                });
+                cx.notify();
+            }),
+        ];
+
+        let mut page = Self {
+            instance: instance.clone(),
+            instance_id,
+            instance_loader,
+            instance_version,
+            backend_handle,
+            worlds_state,
+            worlds_serial: AtomicOptionSerial::default(),
+            world_datapacks,
+            world_select_state,
+            selected_world: None,
+            datapack_list,
+            _add_from_file_task: None,
+            _subscriptions,
+        };
+        page.select_world(selected_world, cx);
+        page
+    }
+
+    fn on_world_selected(
+        &mut self,
+        _state: &Entity<SelectState<SearchableVec<SharedString>>>,
+        event: &SelectEvent<SearchableVec<SharedString>>,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let SelectEvent::Confirm(value) = event;
+        self.select_world(value.as_ref().map(|name| Arc::from(name.as_str())), cx);
+    }
+
+    fn select_world(&mut self, world: Option<Arc<str>>, cx: &mut Context<Self>) {
+        if self.selected_world == world {
+            return;
+        }
+        self.selected_world = world.clone();
+        if let Some(world) = world {
+            self.backend_handle.send(MessageToBackend::RequestLoadWorldDatapacks { id: self.instance_id, world });
+        }
+        self.update_datapack_list(cx);
+    }
+
+    fn update_datapack_list(&mut self, cx: &mut Context<Self>) {
+        let world_datapacks = self.selected_world.as_ref()
+            .and_then(|world| self.world_datapacks.read(cx).get(world).cloned())
+            .unwrap_or_default();
+        let world = self.selected_world.clone();
+        self.datapack_list.update(cx, |list, cx| {
+            let delegate = list.delegate_mut();
+            delegate.world = world;
+            delegate.pack_format = world_datapacks.pack_format;
+            delegate.datapacks = world_datapacks.datapacks.to_vec();
+            delegate.searched = delegate.datapacks.clone();
+            cx.notify();
+        });
+        cx.notify();
+    }
+}
+
+impl Render for InstanceDatapacksSubpage {
+    fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
+        let theme = cx.theme();
+
+        let state = self.worlds_state.load(Ordering::SeqCst);
+        if state.should_send_load_request() {
+            self.backend_handle.send_with_serial(MessageToBackend::RequestLoadWorlds { id: self.instance_id }, &self.worlds_serial);
+        }
+
+        let running = self.instance.read(cx).status != InstanceStatus::NotRunning;
+        let has_world = self.selected_world.is_some();
+
+        let header = h_flex()
+            .gap_3()
+            .mb_1()
+            .ml_1()
+            .child(div().text_lg().child("Datapacks"))
+            .child(div().w_64().child(Select::new(&self.world_select_state).small().placeholder("No worlds found").title_prefix("World: ")))
+            .child(Button::new("addmr").label("Add from Modrinth").success().compact().small().on_click({
+                let instance = self.instance_id;
+                move |_, window, cx| {
+                    let page = PageType::Modrinth {
+                        installing_for: Some(instance),
+                        project_type: Some(ModrinthProjectType::Datapack)
+                    };
+                    let path = &[PageType::Instances, PageType::InstancePage(instance, InstanceSubpageType::Datapacks)];
+                    root::switch_page(page, path, window, cx);
+                }
+            }))
+            .child(Button::new("addfile").label("Add from file").success().compact().small().disabled(!has_world).on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance = self.instance_id;
+                cx.listener(move |this, _, window, cx| {
+                    let Some(world) = this.selected_world.clone() else {
+                        return;
+                    };
+
+                    let receiver = cx.prompt_for_paths(PathPromptOptions {
+                        files: true,
+                        directories: false,
+                        multiple: true,
+                        prompt: Some("Select datapacks to install".into())
+                    });
+
+                    let backend_handle = backend_handle.clone();
+                    let entity = cx.entity();
+                    let add_from_file_task = window.spawn(cx, async move |cx| {
+                        let Ok(result) = receiver.await else {
+                            return;
+                        };
+                        _ = cx.update_window_entity(&entity, move |this, window, cx| {
+                            match result {
+                                Ok(Some(paths)) => {
+                                    let datapacks_path = Path::new("saves").join(&*world).join("datapacks");
+                                    let content_install = ContentInstall {
+                                        target: InstallTarget::Instance(instance),
+                                        loader_hint: this.instance_loader,
+                                        version_hint: Some(this.instance_version.into()),
+                                        files: paths.into_iter().filter_map(|path| {
+                                            Some(ContentInstallFile {
+                                                replace_old: None,
+                                                path: ContentInstallPath::Raw(datapacks_path.join(path.file_name()?).into()),
+                                                download: ContentDownload::File { path },
+                                                content_source: ContentSource::Manual,
+                                            })
+                                        }).collect(),
+                                    };
+                                    crate::root::start_install(content_install, &backend_handle, window, cx);
+                                },
+                                Ok(None) => {},
+                                Err(error) => {
+                                    let error = format!("{}", error);
+                                    let notification = Notification::new()
+                                        .autohide(false)
+                                        .with_type(NotificationType::Error)
+                                        .title(error);
+                                    window.push_notification(notification, cx);
+                                },
+                            }
+                        });
+                    });
+                    this._add_from_file_task = Some(add_from_file_task);
+                })
+            }));
+
+        v_flex()
+            .p_4()
+            .gap_1()
+            .size_full()
+            .child(header)
+            .when(running, |this| {
+                this.child(div().ml_1().text_sm().text_color(theme.muted_foreground).child("Datapacks can't be toggled while the instance is running"))
+            })
+            .child(
+                div()
+                    .size_full()
+                    .border_1()
+                    .rounded(theme.radius)
+                    .border_color(theme.border)
+                    .child(self.datapack_list.clone()),
+            )
+    }
+}
+
+fn world_names(worlds: &[InstanceWorldSummary]) -> Vec<SharedString> {
+    worlds.iter()
+        .filter_map(|world| world.level_path.file_name()?.to_str())
+        .map(SharedString::new)
+        .collect()
+}
+
+pub struct DatapacksListDelegate {
+    id: InstanceID,
+    world: Option<Arc<str>>,
+    backend_handle: BackendHandle,
+    running: bool,
+    pack_format: Option<u32>,
+    datapacks: Vec<InstanceDatapackSummary>,
+    searched: Vec<InstanceDatapackSummary>,
+}
+
+impl ListDelegate for DatapacksListDelegate {
+    type Item = ListItem;
+
+    fn items_count(&self, _section: usize, _cx: &App) -> usize {
+        self.searched.len()
+    }
+
+    fn render_item(&mut self, ix: IndexPath, _window: &mut Window, cx: &mut Context<ListState<Self>>) -> Option<Self::Item> {
+        let summary = self.searched.get(ix.row)?;
+
+        let icon = if let Some(png_icon) = summary.png_icon.as_ref() {
+            png_render_cache::render(Arc::clone(png_icon), cx)
+        } else {
+            gpui::img(ImageSource::Resource(Resource::Embedded("images/default_mod.png".into())))
+        };
+
+        let theme = cx.theme();
+
+        // Minecraft will still load mismatched datapacks, but they often break
+        let mismatch = match (summary.pack_format, self.pack_format) {
+            (Some(range), Some(format)) if !range.contains(format) => {
+                let supported = if range.min == range.max {
+                    format!("{}", range.min)
+                } else {
+                    format!("{}-{}", range.min, range.max)
+                };
+                Some(SharedString::from(format!("Made for pack format {}, this version uses {}", supported, format)))
+            },
+            _ => None,
+        };
+
+        let description = v_flex()
+            .min_w_0()
+            .child(SharedString::from(summary.filename.clone()))
+            .child(div().text_sm().text_color(theme.muted_foreground).truncate().child(SharedString::from(summary.description.clone())))
+            .when_some(mismatch, |this, mismatch| {
+                this.child(div().text_sm().text_color(theme.warning).child(mismatch))
+            });
+
+        let toggle_control = Switch::new(("toggle", ix.row))
+            .checked(summary.enabled)
+            .disabled(self.running || self.world.is_none())
+            .on_click({
+                let id = self.id;
+                let world = self.world.clone();
+                let datapack = summary.filename.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |checked, _, _| {
+                    let Some(world) = world.clone() else {
+                        return;
+                    };
+                    backend_handle.send(MessageToBackend::SetDatapackEnabled {
+                        id,
+                        world,
+                        datapack: datapack.clone(),
+                        enabled: *checked,
+                    });
+                }
+            })
+            .px_2();
+
+        let item = ListItem::new(ix).p_1().child(
+            h_flex()
+                .gap_2()
+                .px_2()
+                .child(icon.size_12().min_w_12().min_h_12())
+                .child(description)
+                .child(div().flex_1())
+                .child(toggle_control),
+        );
+
+        Some(item)
+    }
+
+    fn set_selected_index(&mut self, _ix: Option<IndexPath>, _window: &mut Window, _cx: &mut Context<ListState<Self>>) {
+    }
+
+    fn perform_search(&mut self, query: &str, _window: &mut Window, _cx: &mut Context<ListState<Self>>) -> Task<()> {
+        self.searched = self.datapacks.iter().filter(|d| d.filename.contains(query)).cloned().collect();
+
+        Task::ready(())
+    }
+}
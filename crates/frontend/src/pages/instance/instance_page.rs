@@ -10,7 +10,7 @@ use gpui_component::{
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    component::page_path::PagePath, entity::{DataEntities, instance::InstanceEntry}, pages::instance::{logs_subpage::InstanceLogsSubpage, mods_subpage::InstanceModsSubpage, quickplay_subpage::InstanceQuickplaySubpage, resource_packs_subpage::InstanceResourcePacksSubpage, settings_subpage::InstanceSettingsSubpage}, root, ui
+    component::page_path::PagePath, entity::{DataEntities, instance::InstanceEntry}, pages::instance::{datapacks_subpage::InstanceDatapacksSubpage, logs_subpage::InstanceLogsSubpage, mods_subpage::InstanceModsSubpage, options_subpage::InstanceOptionsSubpage, quickplay_subpage::InstanceQuickplaySubpage, resource_packs_subpage::InstanceResourcePacksSubpage, screenshots_subpage::InstanceScreenshotsSubpage, settings_subpage::InstanceSettingsSubpage, shader_packs_subpage::InstanceShaderPacksSubpage, snapshots_subpage::InstanceSnapshotsSubpage, worlds_subpage::InstanceWorldsSubpage}, root, ui
 };
 
 pub struct InstancePage {
@@ -51,11 +51,20 @@ impl Render for InstancePage {
             InstanceSubpage::Logs(_) => 1,
             InstanceSubpage::Mods(_) => 2,
             InstanceSubpage::ResourcePacks(_) => 3,
-            InstanceSubpage::Settings(_) => 4,
+            InstanceSubpage::ShaderPacks(_) => 4,
+            InstanceSubpage::Worlds(_) => 5,
+            InstanceSubpage::Datapacks(_) => 6,
+            InstanceSubpage::Screenshots(_) => 7,
+            InstanceSubpage::Snapshots(_) => 8,
+            InstanceSubpage::Options(_) => 9,
+            InstanceSubpage::Settings(_) => 10,
         };
 
         let play_icon = Icon::empty().path("icons/play.svg");
 
+        let icon = self.instance.read(cx).icon.clone();
+        let icon = ui::instance_icon(icon.as_ref(), cx).size_8().min_w_8().min_h_8();
+
         let instance = self.instance.read(cx);
         let id = instance.id;
         let name = instance.name.clone();
@@ -93,7 +102,7 @@ impl Render for InstancePage {
         });
 
         let breadcrumb = self.page_path.create_breadcrumb(&self.data, cx);
-        ui::page(cx, h_flex().gap_8().child(breadcrumb).child(h_flex().gap_3().child(button).child(open_dot_minecraft_button)))
+        ui::page(cx, h_flex().gap_8().child(h_flex().gap_3().child(icon).child(breadcrumb)).child(h_flex().gap_3().child(button).child(open_dot_minecraft_button)))
             .child(
                 TabBar::new("bar")
                     .prefix(div().w_4())
@@ -103,6 +112,12 @@ impl Render for InstancePage {
                     .child(Tab::new().label("Logs"))
                     .child(Tab::new().label("Mods"))
                     .child(Tab::new().label("Resource Packs"))
+                    .child(Tab::new().label("Shader Packs"))
+                    .child(Tab::new().label("Worlds"))
+                    .child(Tab::new().label("Datapacks"))
+                    .child(Tab::new().label("Screenshots"))
+                    .child(Tab::new().label("Snapshots"))
+                    .child(Tab::new().label("Options"))
                     .child(Tab::new().label("Settings"))
                     .on_click(cx.listener(|page, index, window, cx| {
                         let page_type = match *index {
@@ -110,7 +125,13 @@ impl Render for InstancePage {
                             1 => InstanceSubpageType::Logs,
                             2 => InstanceSubpageType::Mods,
                             3 => InstanceSubpageType::ResourcePacks,
-                            4 => InstanceSubpageType::Settings,
+                            4 => InstanceSubpageType::ShaderPacks,
+                            5 => InstanceSubpageType::Worlds,
+                            6 => InstanceSubpageType::Datapacks,
+                            7 => InstanceSubpageType::Screenshots,
+                            8 => InstanceSubpageType::Snapshots,
+                            9 => InstanceSubpageType::Options,
+                            10 => InstanceSubpageType::Settings,
                             _ => {
                                 return;
                             },
@@ -129,6 +150,12 @@ pub enum InstanceSubpageType {
     Logs,
     Mods,
     ResourcePacks,
+    ShaderPacks,
+    Worlds,
+    Datapacks,
+    Screenshots,
+    Snapshots,
+    Options,
     Settings,
 }
 
@@ -143,17 +170,35 @@ impl InstanceSubpageType {
     ) -> InstanceSubpage {
         match self {
             InstanceSubpageType::Quickplay => InstanceSubpage::Quickplay(cx.new(|cx| {
-                InstanceQuickplaySubpage::new(instance, backend_handle, window, cx)
+                InstanceQuickplaySubpage::new(instance, data, backend_handle, window, cx)
             })),
             InstanceSubpageType::Logs => InstanceSubpage::Logs(cx.new(|cx| {
                 InstanceLogsSubpage::new(instance, backend_handle, window, cx)
             })),
             InstanceSubpageType::Mods => InstanceSubpage::Mods(cx.new(|cx| {
-                InstanceModsSubpage::new(instance, backend_handle, window, cx)
+                InstanceModsSubpage::new(instance, data, backend_handle, window, cx)
             })),
             InstanceSubpageType::ResourcePacks => InstanceSubpage::ResourcePacks(cx.new(|cx| {
                 InstanceResourcePacksSubpage::new(instance, backend_handle, window, cx)
             })),
+            InstanceSubpageType::ShaderPacks => InstanceSubpage::ShaderPacks(cx.new(|cx| {
+                InstanceShaderPacksSubpage::new(instance, backend_handle, window, cx)
+            })),
+            InstanceSubpageType::Worlds => InstanceSubpage::Worlds(cx.new(|cx| {
+                InstanceWorldsSubpage::new(instance, data, backend_handle, window, cx)
+            })),
+            InstanceSubpageType::Datapacks => InstanceSubpage::Datapacks(cx.new(|cx| {
+                InstanceDatapacksSubpage::new(instance, backend_handle, window, cx)
+            })),
+            InstanceSubpageType::Screenshots => InstanceSubpage::Screenshots(cx.new(|cx| {
+                InstanceScreenshotsSubpage::new(instance, backend_handle, window, cx)
+            })),
+            InstanceSubpageType::Snapshots => InstanceSubpage::Snapshots(cx.new(|cx| {
+                InstanceSnapshotsSubpage::new(instance, backend_handle, window, cx)
+            })),
+            InstanceSubpageType::Options => InstanceSubpage::Options(cx.new(|cx| {
+                InstanceOptionsSubpage::new(instance, backend_handle, window, cx)
+            })),
             InstanceSubpageType::Settings => InstanceSubpage::Settings(cx.new(|cx| {
                 InstanceSettingsSubpage::new(instance, data, backend_handle, window, cx)
             })),
@@ -167,6 +212,12 @@ pub enum InstanceSubpage {
     Logs(Entity<InstanceLogsSubpage>),
     Mods(Entity<InstanceModsSubpage>),
     ResourcePacks(Entity<InstanceResourcePacksSubpage>),
+    ShaderPacks(Entity<InstanceShaderPacksSubpage>),
+    Worlds(Entity<InstanceWorldsSubpage>),
+    Datapacks(Entity<InstanceDatapacksSubpage>),
+    Screenshots(Entity<InstanceScreenshotsSubpage>),
+    Snapshots(Entity<InstanceSnapshotsSubpage>),
+    Options(Entity<InstanceOptionsSubpage>),
     Settings(Entity<InstanceSettingsSubpage>),
 }
 
@@ -177,6 +228,12 @@ impl InstanceSubpage {
             InstanceSubpage::Logs(_) => InstanceSubpageType::Logs,
             InstanceSubpage::Mods(_) => InstanceSubpageType::Mods,
             InstanceSubpage::ResourcePacks(_) => InstanceSubpageType::ResourcePacks,
+            InstanceSubpage::ShaderPacks(_) => InstanceSubpageType::ShaderPacks,
+            InstanceSubpage::Worlds(_) => InstanceSubpageType::Worlds,
+            InstanceSubpage::Datapacks(_) => InstanceSubpageType::Datapacks,
+            InstanceSubpage::Screenshots(_) => InstanceSubpageType::Screenshots,
+            InstanceSubpage::Snapshots(_) => InstanceSubpageType::Snapshots,
+            InstanceSubpage::Options(_) => InstanceSubpageType::Options,
             InstanceSubpage::Settings(_) => InstanceSubpageType::Settings,
         }
     }
@@ -187,6 +244,12 @@ impl InstanceSubpage {
             Self::Logs(entity) => entity.into_any_element(),
             Self::Mods(entity) => entity.into_any_element(),
             Self::ResourcePacks(entity) => entity.into_any_element(),
+            Self::ShaderPacks(entity) => entity.into_any_element(),
+            Self::Worlds(entity) => entity.into_any_element(),
+            Self::Datapacks(entity) => entity.into_any_element(),
+            Self::Screenshots(entity) => entity.into_any_element(),
+            Self::Snapshots(entity) => entity.into_any_element(),
+            Self::Options(entity) => entity.into_any_element(),
             Self::Settings(entity) => entity.into_any_element(),
         }
     }
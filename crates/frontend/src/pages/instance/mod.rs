@@ -1,6 +1,12 @@
+pub mod datapacks_subpage;
 pub mod instance_page;
 pub mod logs_subpage;
 pub mod mods_subpage;
+pub mod options_subpage;
 pub mod quickplay_subpage;
 pub mod resource_packs_subpage;
+pub mod screenshots_subpage;
 pub mod settings_subpage;
+pub mod shader_packs_subpage;
+pub mod snapshots_subpage;
+pub mod worlds_subpage;
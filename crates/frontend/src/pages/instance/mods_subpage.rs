@@ -3,7 +3,7 @@ use std::{hash::{DefaultHasher, Hash, Hasher}, path::Path, sync::{
 }};
 
 use bridge::{
-    handle::BackendHandle, install::{ContentDownload, ContentInstall, ContentInstallFile, InstallTarget}, instance::{AtomicContentUpdateStatus, InstanceID, InstanceContentID, InstanceContentSummary, ContentType, ContentSummary}, message::{AtomicBridgeDataLoadState, MessageToBackend}, serial::AtomicOptionSerial
+    handle::BackendHandle, install::{ContentDownload, ContentInstall, ContentInstallFile, InstallTarget}, instance::{AtomicContentUpdateStatus, InstanceID, InstanceContentID, InstanceContentSummary, ContentType, ContentSummary, ModDependencyIssue, ModDependencyIssueKind}, message::{AtomicBridgeDataLoadState, MessageToBackend}, serial::AtomicOptionSerial
 };
 use gpui::{prelude::*, *};
 use gpui_component::{
@@ -11,10 +11,10 @@ use gpui_component::{
 };
 use parking_lot::Mutex;
 use rustc_hash::FxHashSet;
-use schema::{content::ContentSource, loader::Loader, modrinth::ModrinthProjectType};
+use schema::{content::ContentSource, curseforge::CurseforgeClass, loader::Loader, modrinth::ModrinthProjectType};
 use ustr::Ustr;
 
-use crate::{component::content_list::ContentListDelegate, entity::instance::InstanceEntry, interface_config::InterfaceConfig, png_render_cache, root, ui::PageType};
+use crate::{component::content_list::ContentListDelegate, entity::{DataEntities, instance::InstanceEntry}, interface_config::InterfaceConfig, png_render_cache, root, ui::PageType};
 
 use super::instance_page::InstanceSubpageType;
 
@@ -27,12 +27,17 @@ pub struct InstanceModsSubpage {
     mods_state: Arc<AtomicBridgeDataLoadState>,
     mod_list: Entity<ListState<ContentListDelegate>>,
     load_serial: AtomicOptionSerial,
+    data: DataEntities,
+    dependency_issues: Vec<ModDependencyIssue>,
     _add_from_file_task: Option<Task<()>>,
+    _check_dependencies_task: Task<()>,
+    _observe_mods_subscription: Subscription,
 }
 
 impl InstanceModsSubpage {
     pub fn new(
         instance: &Entity<InstanceEntry>,
+        data: &DataEntities,
         backend_handle: BackendHandle,
         window: &mut gpui::Window,
         cx: &mut gpui::Context<Self>,
@@ -50,6 +55,10 @@ impl InstanceModsSubpage {
 
         let mods = instance.mods.clone();
 
+        let _observe_mods_subscription = cx.observe(&mods, |page, _, cx| {
+            page.check_dependencies(cx);
+        });
+
         let mod_list = cx.new(move |cx| {
             cx.observe(&mods, |list: &mut ListState<ContentListDelegate>, mods, cx| {
                 let actual_mods = mods.read(cx);
@@ -60,7 +69,7 @@ impl InstanceModsSubpage {
             ListState::new(mods_list_delegate, window, cx).selectable(false).searchable(true)
         });
 
-        Self {
+        let mut page = Self {
             instance: instance_id,
             instance_title,
             instance_loader,
@@ -69,15 +78,122 @@ impl InstanceModsSubpage {
             mods_state,
             mod_list,
             load_serial: AtomicOptionSerial::default(),
+            data: data.clone(),
+            dependency_issues: Vec::new(),
             _add_from_file_task: None,
+            _check_dependencies_task: Task::ready(()),
+            _observe_mods_subscription,
+        };
+        page.check_dependencies(cx);
+        page
+    }
+
+    fn check_dependencies(&mut self, cx: &mut Context<Self>) {
+        let (send, recv) = tokio::sync::oneshot::channel();
+        self.backend_handle.send(MessageToBackend::CheckModDependencies {
+            id: self.instance,
+            channel: send,
+        });
+
+        self._check_dependencies_task = cx.spawn(async move |page, cx| {
+            let Ok(issues) = recv.await else {
+                return;
+            };
+            _ = page.update(cx, |page, cx| {
+                page.dependency_issues = issues;
+                cx.notify();
+            });
+        });
+    }
+
+    fn render_dependency_issues(&self, cx: &mut Context<Self>) -> Option<Stateful<Div>> {
+        if self.dependency_issues.is_empty() {
+            return None;
         }
+
+        let theme = cx.theme();
+        let errors = self.dependency_issues.iter().filter(|issue| issue.kind.is_error()).count();
+        let title = match errors {
+            0 => format!("{} mod dependency warnings", self.dependency_issues.len()),
+            1 => "1 mod dependency problem, the game will likely fail to start".to_string(),
+            errors => format!("{errors} mod dependency problems, the game will likely fail to start"),
+        };
+
+        let rows = self.dependency_issues.iter().enumerate().map(|(index, issue)| {
+            let mod_name = &issue.mod_name;
+            let dependency = &issue.dependency;
+            let versions = &issue.versions;
+            let message = match &issue.kind {
+                ModDependencyIssueKind::Missing => format!("{mod_name} requires {dependency} ({versions}), which is not installed"),
+                ModDependencyIssueKind::MissingRecommended => format!("{mod_name} recommends {dependency} ({versions}), which is not installed"),
+                ModDependencyIssueKind::WrongVersion { found } => format!("{mod_name} requires {dependency} ({versions}), but {found} is installed"),
+                ModDependencyIssueKind::WrongRecommendedVersion { found } => format!("{mod_name} recommends {dependency} ({versions}), but {found} is installed"),
+                ModDependencyIssueKind::Breaks { found } => format!("{mod_name} is incompatible with {dependency} {found}"),
+                ModDependencyIssueKind::Conflicts { found } => format!("{mod_name} may not work correctly with {dependency} {found}"),
+            };
+            let color = if issue.kind.is_error() { theme.danger } else { theme.warning };
+
+            let install = matches!(issue.kind, ModDependencyIssueKind::Missing | ModDependencyIssueKind::MissingRecommended).then(|| {
+                Button::new(("install-dependency", index)).label("Install from Modrinth").success().compact().xsmall().on_click({
+                    let dependency = dependency.clone();
+                    let data = self.data.clone();
+                    let instance = self.instance;
+                    move |_, window, cx| {
+                        crate::modals::modrinth_install_auto::open(
+                            &dependency,
+                            modrinth_slug_for_mod_id(&dependency),
+                            ModrinthProjectType::Mod,
+                            instance,
+                            &data,
+                            window,
+                            cx
+                        );
+                    }
+                })
+            });
+
+            h_flex()
+                .gap_2()
+                .child(Icon::empty().path("icons/triangle-alert.svg").text_color(color))
+                .child(div().flex_1().text_sm().child(SharedString::from(message)))
+                .children(install)
+        });
+
+        Some(v_flex()
+            .id("dependency-issues")
+            .gap_1()
+            .p_2()
+            .mb_2()
+            .max_h_48()
+            .border_1()
+            .rounded(theme.radius)
+            .border_color(if errors > 0 { theme.danger } else { theme.warning })
+            .child(div().font_medium().child(SharedString::from(title)))
+            .children(rows)
+            .overflow_y_scroll())
+    }
+}
+
+// Mod ids usually match the Modrinth slug, apart from a few common libraries
+fn modrinth_slug_for_mod_id(id: &str) -> Arc<str> {
+    let is_fabric_api_module = id.starts_with("fabric-")
+        && (id == "fabric-api-base" || id.rsplit_once("-v").is_some_and(|(_, version)| version.parse::<u32>().is_ok()));
+    if is_fabric_api_module {
+        return "fabric-api".into();
+    }
+
+    match id {
+        "fabric" => "fabric-api".into(),
+        "cloth-config2" | "cloth_config" => "cloth-config".into(),
+        "architectury" => "architectury-api".into(),
+        "kotlinforforge" => "kotlin-for-forge".into(),
+        "yet_another_config_lib_v3" => "yacl".into(),
+        _ => id.replace('_', "-").into(),
     }
 }
 
 impl Render for InstanceModsSubpage {
     fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
-        let theme = cx.theme();
-
         let state = self.mods_state.load(Ordering::SeqCst);
         if state.should_send_load_request() {
             self.backend_handle.send_with_serial(MessageToBackend::RequestLoadMods { id: self.instance }, &self.load_serial);
@@ -95,6 +211,13 @@ impl Render for InstanceModsSubpage {
                     crate::root::start_update_check(instance_id, &backend_handle, window, cx);
                 }
             }))
+            .child(Button::new("updateall").label("Update all...").success().compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance_id = self.instance;
+                move |_, window, cx| {
+                    crate::modals::update_all::open_update_all(instance_id, backend_handle.clone(), window, cx);
+                }
+            }))
             .child(Button::new("addmr").label("Add from Modrinth").success().compact().small().on_click({
                 let instance = self.instance;
                 move |_, window, cx| {
@@ -106,6 +229,26 @@ impl Render for InstanceModsSubpage {
                     root::switch_page(page, path, window, cx);
                 }
             }))
+            .child(Button::new("addcf").label("Add from CurseForge").success().compact().small().on_click({
+                let instance = self.instance;
+                move |_, window, cx| {
+                    let page = crate::ui::PageType::Curseforge {
+                        installing_for: Some(instance),
+                        class: Some(CurseforgeClass::Mod)
+                    };
+                    let path = &[PageType::Instances, PageType::InstancePage(instance, InstanceSubpageType::Mods)];
+                    root::switch_page(page, path, window, cx);
+                }
+            }))
+            .child(Button::new("addurl").label("Add from URL").success().compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance = self.instance;
+                let loader = self.instance_loader;
+                let version = self.instance_version;
+                move |_, window, cx| {
+                    crate::modals::add_from_url::open_add_from_url(instance, loader, version, backend_handle.clone(), window, cx);
+                }
+            }))
             .child(Button::new("addfile").label("Add from file").success().compact().small().on_click({
                 let backend_handle = self.backend_handle.clone();
                 let instance = self.instance;
@@ -157,7 +300,10 @@ impl Render for InstanceModsSubpage {
                 })
             }));
 
-        v_flex().p_4().size_full().child(header).child(
+        let dependency_issues = self.render_dependency_issues(cx);
+        let theme = cx.theme();
+
+        v_flex().p_4().size_full().child(header).children(dependency_issues).child(
             div()
                 .id("mod-list-area")
                 .size_full()
@@ -0,0 +1,385 @@
+use std::sync::Arc;
+
+use bridge::{
+    handle::BackendHandle,
+    instance::{InstanceID, InstanceStatus},
+    message::MessageToBackend,
+};
+use enumset::{EnumSet, EnumSetType};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _, Disableable, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputEvent, InputState},
+    notification::NotificationType,
+    scroll::ScrollableElement,
+    v_flex,
+};
+use rustc_hash::FxHashMap;
+
+use crate::entity::instance::InstanceEntry;
+
+const KEYBIND_PREFIX: &str = "key_";
+const UNBOUND_KEY: &str = "key.keyboard.unknown";
+
+#[derive(EnumSetType, Debug)]
+enum OptionGroup {
+    RenderDistance,
+    Fov,
+    GuiScale,
+    Language,
+    KeyBindings,
+}
+
+impl OptionGroup {
+    fn name(self) -> &'static str {
+        match self {
+            OptionGroup::RenderDistance => "Render distance",
+            OptionGroup::Fov => "FOV",
+            OptionGroup::GuiScale => "GUI scale",
+            OptionGroup::Language => "Language",
+            OptionGroup::KeyBindings => "Key bindings",
+        }
+    }
+}
+
+pub struct InstanceOptionsSubpage {
+    instance: Entity<InstanceEntry>,
+    instance_id: InstanceID,
+    backend_handle: BackendHandle,
+    loaded: bool,
+    options: Vec<(Arc<str>, Arc<str>)>,
+    render_distance_input: Entity<InputState>,
+    fov_input: Entity<InputState>,
+    gui_scale_input: Entity<InputState>,
+    language_input: Entity<InputState>,
+    keybinds: Vec<(Arc<str>, Entity<InputState>)>,
+    apply_groups: EnumSet<OptionGroup>,
+    _load_task: Task<()>,
+    _keybind_subscriptions: Vec<Subscription>,
+    _observe_instance_subscription: Subscription,
+}
+
+impl InstanceOptionsSubpage {
+    pub fn new(
+        instance: &Entity<InstanceEntry>,
+        backend_handle: BackendHandle,
+        window: &mut gpui::Window,
+        cx: &mut gpui::Context<Self>,
+    ) -> Self {
+        let instance_id = instance.read(cx).id;
+
+        let _observe_instance_subscription = cx.observe(instance, |_, _, cx| cx.notify());
+
+        let mut page = Self {
+            instance: instance.clone(),
+            instance_id,
+            backend_handle,
+            loaded: false,
+            options: Vec::new(),
+            render_distance_input: cx.new(|cx| InputState::new(window, cx)),
+            fov_input: cx.new(|cx| InputState::new(window, cx)),
+            gui_scale_input: cx.new(|cx| InputState::new(window, cx).placeholder("0 (Auto)")),
+            language_input: cx.new(|cx| InputState::new(window, cx).placeholder("en_us")),
+            keybinds: Vec::new(),
+            apply_groups: OptionGroup::RenderDistance | OptionGroup::Fov | OptionGroup::GuiScale | OptionGroup::Language,
+            _load_task: Task::ready(()),
+            _keybind_subscriptions: Vec::new(),
+            _observe_instance_subscription,
+        };
+        page.load(window, cx);
+        page
+    }
+
+    fn load(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let (send, recv) = tokio::sync::oneshot::channel();
+        self.backend_handle.send(MessageToBackend::GetGameOptions {
+            id: self.instance_id,
+            channel: send,
+        });
+
+        self._load_task = cx.spawn_in(window, async move |page, cx| {
+            let Ok(options) = recv.await else {
+                return;
+            };
+            _ = page.update_in(cx, |page, window, cx| {
+                page.set_options(options, window, cx);
+            });
+        });
+    }
+
+    fn set_options(&mut self, options: Vec<(Arc<str>, Arc<str>)>, window: &mut Window, cx: &mut Context<Self>) {
+        let values: FxHashMap<&str, &str> = options.iter().map(|(key, value)| (&**key, &**value)).collect();
+
+        let fov = values.get("fov")
+            .and_then(|value| value.parse::<f64>().ok())
+            .map(|fov| (70.0 + fov * 40.0).round().to_string())
+            .unwrap_or_default();
+
+        for (input, value) in [
+            (&self.render_distance_input, values.get("renderDistance").map(|value| value.to_string()).unwrap_or_default()),
+            (&self.fov_input, fov),
+            (&self.gui_scale_input, values.get("guiScale").map(|value| value.to_string()).unwrap_or_default()),
+            (&self.language_input, values.get("lang").map(|value| value.to_string()).unwrap_or_default()),
+        ] {
+            input.update(cx, |input, cx| input.set_value(value, window, cx));
+        }
+
+        self.keybinds = options.iter()
+            .filter(|(key, _)| key.starts_with(KEYBIND_PREFIX))
+            .map(|(key, value)| {
+                let value = value.to_string();
+                (key.clone(), cx.new(|cx| InputState::new(window, cx).default_value(value)))
+            })
+            .collect();
+        self._keybind_subscriptions = self.keybinds.iter()
+            .map(|(_, input)| cx.subscribe(input, |_, _, event: &InputEvent, cx| {
+                if let InputEvent::Change = event {
+                    cx.notify();
+                }
+            }))
+            .collect();
+
+        self.options = options;
+        self.loaded = true;
+        cx.notify();
+    }
+
+    fn collect_options(&self, groups: EnumSet<OptionGroup>, cx: &App) -> Result<Vec<(Arc<str>, Arc<str>)>, SharedString> {
+        let mut options: Vec<(Arc<str>, Arc<str>)> = Vec::new();
+
+        if groups.contains(OptionGroup::RenderDistance) {
+            let value = self.render_distance_input.read(cx).value();
+            let value = value.trim();
+            if !value.is_empty() {
+                match value.parse::<u32>() {
+                    Ok(render_distance) if (2..=32).contains(&render_distance) => {
+                        options.push(("renderDistance".into(), render_distance.to_string().into()));
+                    },
+                    _ => return Err("Render distance must be a number between 2 and 32".into()),
+                }
+            }
+        }
+        if groups.contains(OptionGroup::Fov) {
+            let value = self.fov_input.read(cx).value();
+            let value = value.trim();
+            if !value.is_empty() {
+                match value.parse::<u32>() {
+                    // Stored as a value between -1 and 1, mapping to 30-110 degrees
+                    Ok(fov) if (30..=110).contains(&fov) => {
+                        // Keep the existing value if it rounds to the same angle, to avoid rewriting it with less precision
+                        let existing = self.options.iter()
+                            .find(|(key, _)| &**key == "fov")
+                            .map(|(_, value)| value.clone())
+                            .filter(|value| value.parse::<f64>().is_ok_and(|existing| (70.0 + existing * 40.0).round() == fov as f64));
+                        let value = existing.unwrap_or_else(|| ((fov as f64 - 70.0) / 40.0).to_string().into());
+                        options.push(("fov".into(), value));
+                    },
+                    _ => return Err("FOV must be a number between 30 and 110".into()),
+                }
+            }
+        }
+        if groups.contains(OptionGroup::GuiScale) {
+            let value = self.gui_scale_input.read(cx).value();
+            let value = value.trim();
+            if !value.is_empty() {
+                match value.parse::<u32>() {
+                    Ok(gui_scale) => options.push(("guiScale".into(), gui_scale.to_string().into())),
+                    _ => return Err("GUI scale must be a number, 0 for auto".into()),
+                }
+            }
+        }
+        if groups.contains(OptionGroup::Language) {
+            let value = self.language_input.read(cx).value();
+            let value = value.trim();
+            if !value.is_empty() {
+                if value.contains(char::is_whitespace) {
+                    return Err("Language must be a language code, e.g. en_us".into());
+                }
+                options.push(("lang".into(), value.into()));
+            }
+        }
+        if groups.contains(OptionGroup::KeyBindings) {
+            for (key, input) in &self.keybinds {
+                let value = input.read(cx).value();
+                let value = value.trim();
+                if !value.is_empty() {
+                    options.push((key.clone(), value.into()));
+                }
+            }
+        }
+
+        Ok(options)
+    }
+
+    fn save(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let options = match self.collect_options(EnumSet::all(), cx) {
+            Ok(options) => options,
+            Err(err) => {
+                window.push_notification((NotificationType::Error, err), cx);
+                return;
+            },
+        };
+
+        let original: FxHashMap<&str, &str> = self.options.iter().map(|(key, value)| (&**key, &**value)).collect();
+        let changed: Vec<_> = options.into_iter()
+            .filter(|(key, value)| original.get(&**key) != Some(&&**value))
+            .collect();
+        if changed.is_empty() {
+            return;
+        }
+
+        self.backend_handle.send(MessageToBackend::SetGameOptions {
+            id: self.instance_id,
+            options: changed.clone(),
+        });
+
+        let mut options = std::mem::take(&mut self.options);
+        for (key, value) in changed {
+            if let Some(existing) = options.iter_mut().find(|(existing, _)| *existing == key) {
+                existing.1 = value;
+            } else {
+                options.push((key, value));
+            }
+        }
+        self.options = options;
+        cx.notify();
+    }
+
+    fn open_apply_to_all(&self, window: &mut Window, cx: &mut Context<Self>) {
+        let page = cx.entity();
+        window.open_dialog(cx, move |dialog, _, cx| {
+            let groups = page.read(cx).apply_groups;
+
+            let content = v_flex()
+                .gap_2()
+                .child("Choose which options to copy to every instance. Running instances are skipped")
+                .children(EnumSet::<OptionGroup>::all().iter().map(|group| {
+                    Checkbox::new(group.name()).label(group.name()).checked(groups.contains(group)).on_click({
+                        let page = page.clone();
+                        move |value, _, cx| {
+                            page.update(cx, |page, cx| {
+                                if *value {
+                                    page.apply_groups.insert(group);
+                                } else {
+                                    page.apply_groups.remove(group);
+                                }
+                                cx.notify();
+                            });
+                        }
+                    })
+                }))
+                .child(Button::new("apply").label("Apply to all instances").success().disabled(groups.is_empty()).on_click({
+                    let page = page.clone();
+                    move |_, window, cx| {
+                        let page = page.read(cx);
+                        match page.collect_options(page.apply_groups, cx) {
+                            Ok(options) => {
+                                page.backend_handle.send(MessageToBackend::ApplyGameOptionsToAllInstances { options });
+                                window.close_all_dialogs(cx);
+                            },
+                            Err(err) => window.push_notification((NotificationType::Error, err), cx),
+                        }
+                    }
+                }));
+
+            dialog.title("Apply Options to All Instances").child(content)
+        });
+    }
+}
+
+impl Render for InstanceOptionsSubpage {
+    fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
+        let theme = cx.theme();
+        let running = self.instance.read(cx).status != InstanceStatus::NotRunning;
+
+        let header = h_flex()
+            .gap_3()
+            .mb_1()
+            .ml_1()
+            .child(div().text_lg().child("Game Options"))
+            .child(Button::new("reload").label("Reload").info().compact().small().on_click(cx.listener(|page, _, window, cx| {
+                page.load(window, cx);
+            })))
+            .child(Button::new("save").label("Save").success().compact().small().disabled(!self.loaded || running).on_click(cx.listener(|page, _, window, cx| {
+                page.save(window, cx);
+            })))
+            .child(Button::new("applyall").label("Apply to all instances...").info().compact().small().disabled(!self.loaded).on_click(cx.listener(|page, _, window, cx| {
+                page.open_apply_to_all(window, cx);
+            })));
+
+        if !self.loaded {
+            return v_flex().p_4().size_full().child(header);
+        }
+
+        let general = v_flex()
+            .gap_2()
+            .child(crate::labelled("Render distance (chunks)", Input::new(&self.render_distance_input).small()))
+            .child(crate::labelled("FOV (degrees)", Input::new(&self.fov_input).small()))
+            .child(crate::labelled("GUI scale", Input::new(&self.gui_scale_input).small()))
+            .child(crate::labelled("Language", Input::new(&self.language_input).small()));
+
+        let mut keybind_users: FxHashMap<SharedString, Vec<&str>> = FxHashMap::default();
+        for (key, input) in &self.keybinds {
+            let value = input.read(cx).value();
+            if !value.is_empty() && &*value != UNBOUND_KEY {
+                keybind_users.entry(value.clone()).or_default().push(&key[KEYBIND_PREFIX.len()..]);
+            }
+        }
+
+        let keybinds = v_flex()
+            .gap_1()
+            .children(self.keybinds.iter().map(|(key, input)| {
+                let name = &key[KEYBIND_PREFIX.len()..];
+                let value = input.read(cx).value();
+                let conflicts: Vec<&str> = keybind_users.get(&value)
+                    .map(|users| users.iter().copied().filter(|user| *user != name).collect())
+                    .unwrap_or_default();
+
+                v_flex()
+                    .child(h_flex()
+                        .gap_2()
+                        .child(div().w_1_2().text_sm().truncate().child(SharedString::from(name.to_string())))
+                        .child(div().w_1_2().child(Input::new(input).small())))
+                    .when(!conflicts.is_empty(), |this| {
+                        this.child(div().text_xs().text_color(theme.warning).child(SharedString::from(format!("Conflicts with {}", conflicts.join(", ")))))
+                    })
+            }));
+
+        v_flex()
+            .p_4()
+            .gap_1()
+            .size_full()
+            .child(header)
+            .when(running, |this| {
+                this.child(div().ml_1().text_sm().text_color(theme.muted_foreground).child("Options can't be saved while the instance is running"))
+            })
+            .child(h_flex()
+                .size_full()
+                .items_start()
+                .gap_4()
+                .child(v_flex()
+                    .w_1_3()
+                    .h_full()
+                    .gap_4()
+                    .p_3()
+                    .border_1()
+                    .rounded(theme.radius)
+                    .border_color(theme.border)
+                    .child(general)
+                    .overflow_y_scrollbar())
+                .child(v_flex()
+                    .flex_1()
+                    .h_full()
+                    .gap_1()
+                    .p_3()
+                    .border_1()
+                    .rounded(theme.radius)
+                    .border_color(theme.border)
+                    .child(div().text_sm().font_medium().child("Key bindings"))
+                    .child(keybinds)
+                    .overflow_y_scrollbar()))
+    }
+}
@@ -5,19 +5,23 @@ use std::{
 
 use bridge::{
     handle::BackendHandle,
-    instance::{InstanceID, InstanceServerSummary, InstanceWorldSummary},
+    instance::{InstanceID, InstanceServerSummary, InstanceWorldSummary, ServerListEdit, ServerStatus},
     message::{AtomicBridgeDataLoadState, MessageToBackend, QuickPlayLaunch}, serial::AtomicOptionSerial,
 };
 use gpui::{prelude::*, *};
 use gpui_component::{
-    ActiveTheme as _, Icon, IndexPath,
+    ActiveTheme as _, Disableable, Icon, IconName, IndexPath, Sizable, WindowExt,
     button::{Button, ButtonVariants},
     h_flex,
+    input::{Input, InputState},
     list::{ListDelegate, ListItem, ListState},
+    select::{SearchableVec, Select, SelectState},
+    text::TextView,
     v_flex,
 };
+use rustc_hash::FxHashMap;
 
-use crate::{entity::instance::InstanceEntry, png_render_cache, root};
+use crate::{entity::{DataEntities, instance::{InstanceEntries, InstanceEntry}}, png_render_cache, root};
 
 pub struct InstanceQuickplaySubpage {
     instance: InstanceID,
@@ -28,17 +32,29 @@ pub struct InstanceQuickplaySubpage {
     server_list: Entity<ListState<ServersListDelegate>>,
     worlds_serial: AtomicOptionSerial,
     servers_serial: AtomicOptionSerial,
+    description: SharedString,
+    _observe_instance_subscription: Subscription,
 }
 
 impl InstanceQuickplaySubpage {
     pub fn new(
         instance: &Entity<InstanceEntry>,
+        data: &DataEntities,
         backend_handle: BackendHandle,
         mut window: &mut gpui::Window,
         cx: &mut gpui::Context<Self>,
     ) -> Self {
+        let _observe_instance_subscription = cx.observe(instance, |page, instance, cx| {
+            let description = instance.read(cx).description.clone();
+            if page.description != description {
+                page.description = description;
+                cx.notify();
+            }
+        });
+
         let instance = instance.read(cx);
         let instance_id = instance.id;
+        let description = instance.description.clone();
 
         let worlds_state = Arc::clone(&instance.worlds_state);
         let servers_state = Arc::clone(&instance.servers_state);
@@ -55,8 +71,10 @@ impl InstanceQuickplaySubpage {
             id: instance_id,
             name: instance.name.clone(),
             backend_handle: backend_handle.clone(),
+            instances: data.instances.clone(),
             servers: instance.servers.read(cx).to_vec(),
             searched: instance.servers.read(cx).to_vec(),
+            statuses: FxHashMap::default(),
         };
 
         let worlds = instance.worlds.clone();
@@ -81,10 +99,13 @@ impl InstanceQuickplaySubpage {
                 let delegate = list.delegate_mut();
                 delegate.servers = servers.clone();
                 delegate.searched = servers;
+                delegate.ping_servers(false, cx);
                 cx.notify();
             }).detach();
 
-            ListState::new(servers_list_delegate, window, cx).selectable(false).searchable(true)
+            let mut list = ListState::new(servers_list_delegate, window, cx).selectable(false).searchable(true);
+            list.delegate_mut().ping_servers(false, cx);
+            list
         });
 
         Self {
@@ -96,12 +117,18 @@ impl InstanceQuickplaySubpage {
             server_list,
             worlds_serial: AtomicOptionSerial::default(),
             servers_serial: AtomicOptionSerial::default(),
+            description,
+            _observe_instance_subscription,
         }
     }
 }
 
 impl Render for InstanceQuickplaySubpage {
-    fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
+    fn render(&mut self, window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
+        let description = (!self.description.trim().is_empty()).then(|| {
+            TextView::markdown("description", self.description.clone(), window, cx)
+        });
+
         let theme = cx.theme();
 
         let state = self.worlds_state.load(Ordering::SeqCst);
@@ -115,9 +142,33 @@ impl Render for InstanceQuickplaySubpage {
         }
 
         let worlds_header = div().mb_1().ml_1().text_lg().child("Worlds");
-        let servers_header = div().mb_1().ml_1().text_lg().child("Servers");
-
-        v_flex().p_4().gap_4().size_full().child(
+        let servers_header = h_flex()
+            .mb_1()
+            .ml_1()
+            .gap_2()
+            .child(div().text_lg().child("Servers"))
+            .child(Button::new("add-server").label("Add server").success().small().compact().on_click({
+                let id = self.instance;
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_edit_server(id, None, backend_handle.clone(), window, cx);
+                }
+            }))
+            .child(Button::new("refresh-servers").label("Refresh").info().small().compact().on_click({
+                let server_list = self.server_list.clone();
+                move |_, _, cx| {
+                    server_list.update(cx, |list, cx| {
+                        list.delegate_mut().ping_servers(true, cx);
+                        cx.notify();
+                    });
+                }
+            }));
+
+        v_flex().p_4().gap_4().size_full()
+            .when_some(description, |this, description| {
+                this.child(div().max_h_64().overflow_y_hidden().px_1().child(description))
+            })
+            .child(
             h_flex()
                 .size_full()
                 .gap_4()
@@ -226,8 +277,48 @@ pub struct ServersListDelegate {
     id: InstanceID,
     name: SharedString,
     backend_handle: BackendHandle,
+    instances: Entity<InstanceEntries>,
     servers: Vec<InstanceServerSummary>,
     searched: Vec<InstanceServerSummary>,
+    statuses: FxHashMap<Arc<str>, ServerPingState>,
+}
+
+enum ServerPingState {
+    Pinging,
+    Online(ServerStatus),
+    Offline,
+}
+
+impl ServersListDelegate {
+    fn ping_servers(&mut self, refresh: bool, cx: &mut Context<ListState<Self>>) {
+        for server in &self.servers {
+            match self.statuses.get(&server.ip) {
+                Some(ServerPingState::Pinging) => continue,
+                Some(_) if !refresh => continue,
+                _ => {},
+            }
+            self.statuses.insert(server.ip.clone(), ServerPingState::Pinging);
+
+            let (send, recv) = tokio::sync::oneshot::channel();
+            self.backend_handle.send(MessageToBackend::PingServer {
+                address: server.ip.clone(),
+                refresh,
+                channel: send,
+            });
+
+            let address = server.ip.clone();
+            cx.spawn(async move |list, cx| {
+                let state = match recv.await {
+                    Ok(Some(status)) => ServerPingState::Online(status),
+                    _ => ServerPingState::Offline,
+                };
+                let _ = list.update(cx, move |list, cx| {
+                    list.delegate_mut().statuses.insert(address, state);
+                    cx.notify();
+                });
+            }).detach();
+        }
+    }
 }
 
 impl ListDelegate for ServersListDelegate {
@@ -239,16 +330,54 @@ impl ListDelegate for ServersListDelegate {
 
     fn render_item(&mut self, ix: IndexPath, _window: &mut Window, cx: &mut Context<ListState<Self>>) -> Option<Self::Item> {
         let summary = self.searched.get(ix.row)?;
+        let status = self.statuses.get(&summary.ip);
 
-        let icon = if let Some(png_icon) = summary.png_icon.as_ref() {
+        let favicon = match status {
+            Some(ServerPingState::Online(status)) => status.favicon.as_ref(),
+            _ => None,
+        };
+        let icon = if let Some(png_icon) = favicon.or(summary.png_icon.as_ref()) {
             png_render_cache::render(Arc::clone(png_icon), cx)
         } else {
             gpui::img(ImageSource::Resource(Resource::Embedded("images/default_world.png".into())))
         };
 
+        let theme = cx.theme();
+        let motd = match status {
+            Some(ServerPingState::Online(status)) if !status.motd.trim().is_empty() => Some(SharedString::from(status.motd.clone())),
+            _ => None,
+        };
         let description = v_flex()
+            .min_w_0()
+            .flex_1()
             .child(SharedString::from(summary.name.clone()))
-            .child(div().text_color(Hsla { h: 0.0, s: 0.0, l: 0.5, a: 1.0}).child(SharedString::from(summary.ip.clone())));
+            .child(div().text_color(Hsla { h: 0.0, s: 0.0, l: 0.5, a: 1.0}).child(SharedString::from(summary.ip.clone())))
+            .when_some(motd, |this, motd| {
+                this.child(div().text_sm().text_color(theme.muted_foreground).truncate().child(motd))
+            });
+
+        let status = match status {
+            None | Some(ServerPingState::Pinging) => {
+                v_flex().items_end().text_color(theme.muted_foreground).child("Pinging...")
+            },
+            Some(ServerPingState::Offline) => {
+                v_flex().items_end().text_color(theme.danger).child("Offline")
+            },
+            Some(ServerPingState::Online(status)) => {
+                let latency_color = match status.latency_ms {
+                    0..150 => theme.success,
+                    150..300 => theme.warning,
+                    _ => theme.danger,
+                };
+                v_flex()
+                    .items_end()
+                    .child(div().text_color(latency_color).child(format!("{} ms", status.latency_ms)))
+                    .child(format!("{}/{} players", status.players_online, status.players_max))
+                    .when(!status.version.is_empty(), |this| {
+                        this.child(div().text_sm().text_color(theme.muted_foreground).child(SharedString::from(status.version.clone())))
+                    })
+            },
+        };
 
         let play_icon = Icon::empty().path("icons/play.svg");
 
@@ -256,6 +385,73 @@ impl ListDelegate for ServersListDelegate {
         let name = self.name.clone();
         let backend_handle = self.backend_handle.clone();
         let target = OsString::from(summary.ip.to_string());
+
+        // Hidden servers aren't part of the list, so move past the neighbouring visible entry instead
+        let position = self.servers.iter().position(|server| server.index == summary.index);
+        let previous = position.and_then(|position| position.checked_sub(1)).and_then(|position| self.servers.get(position)).map(|server| server.index);
+        let next = position.and_then(|position| self.servers.get(position + 1)).map(|server| server.index);
+        let index = summary.index;
+
+        let move_button = |button_id: (&'static str, usize), icon: IconName, to: Option<usize>| {
+            let backend_handle = self.backend_handle.clone();
+            Button::new(button_id)
+                .icon(icon)
+                .small()
+                .compact()
+                .disabled(to.is_none())
+                .on_click(move |_, _, _| {
+                    if let Some(to) = to {
+                        backend_handle.send(MessageToBackend::EditServerList {
+                            id,
+                            edit: ServerListEdit::Move { from: index, to },
+                        });
+                    }
+                })
+        };
+        let up_button = move_button(("up", ix.row), IconName::ArrowUp, previous);
+        let down_button = move_button(("down", ix.row), IconName::ArrowDown, next);
+
+        let edit_button = Button::new(("edit", ix.row))
+            .label("Edit")
+            .info()
+            .small()
+            .compact()
+            .on_click({
+                let server = (index, summary.name.clone(), summary.ip.clone());
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_edit_server(id, Some(server.clone()), backend_handle.clone(), window, cx);
+                }
+            });
+
+        let copy_button = Button::new(("copy", ix.row))
+            .label("Copy to...")
+            .info()
+            .small()
+            .compact()
+            .on_click({
+                let instances = self.instances.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_copy_server(id, index, &instances, backend_handle.clone(), window, cx);
+                }
+            });
+
+        let remove_button = Button::new(("remove", ix.row))
+            .icon(Icon::default().path("icons/trash-2.svg"))
+            .danger()
+            .small()
+            .compact()
+            .on_click({
+                let backend_handle = self.backend_handle.clone();
+                move |_, _, _| {
+                    backend_handle.send(MessageToBackend::EditServerList {
+                        id,
+                        edit: ServerListEdit::Remove { index },
+                    });
+                }
+            });
+
         let item = ListItem::new(ix).p_1().child(
             h_flex()
                 .gap_1()
@@ -274,7 +470,13 @@ impl ListDelegate for ServersListDelegate {
                         .px_2(),
                 )
                 .child(icon.size_16().min_w_16().min_h_16())
-                .child(description),
+                .child(description)
+                .child(status.px_2())
+                .child(up_button)
+                .child(down_button)
+                .child(edit_button)
+                .child(copy_button)
+                .child(remove_button),
         );
 
         Some(item)
@@ -289,3 +491,96 @@ impl ListDelegate for ServersListDelegate {
         Task::ready(())
     }
 }
+
+fn open_edit_server(
+    id: InstanceID,
+    server: Option<(usize, Arc<str>, Arc<str>)>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let (name, ip) = server.as_ref().map(|(_, name, ip)| (name.to_string(), ip.to_string())).unwrap_or_default();
+    let name_input_state = cx.new(|cx| InputState::new(window, cx).placeholder("Minecraft Server").default_value(name));
+    let ip_input_state = cx.new(|cx| InputState::new(window, cx).placeholder("example.com:25565").default_value(ip));
+    let index = server.map(|(index, _, _)| index);
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let content = v_flex()
+            .gap_2()
+            .child(crate::labelled("Name", Input::new(&name_input_state)))
+            .child(crate::labelled("Address", Input::new(&ip_input_state)))
+            .child(Button::new("save").label(if index.is_some() { "Save server" } else { "Add server" }).success().on_click({
+                let name_input_state = name_input_state.clone();
+                let ip_input_state = ip_input_state.clone();
+                let backend_handle = backend_handle.clone();
+                move |_, window, cx| {
+                    let name = name_input_state.read(cx).value();
+                    let ip = ip_input_state.read(cx).value();
+                    let ip = ip.trim();
+                    if ip.is_empty() {
+                        return;
+                    }
+                    let name: Arc<str> = if name.trim().is_empty() {
+                        "Minecraft Server".into()
+                    } else {
+                        name.trim().into()
+                    };
+
+                    let edit = match index {
+                        Some(index) => ServerListEdit::Edit { index, name, ip: ip.into() },
+                        None => ServerListEdit::Add { name, ip: ip.into() },
+                    };
+                    backend_handle.send(MessageToBackend::EditServerList { id, edit });
+                    window.close_all_dialogs(cx);
+                }
+            }));
+
+        dialog.title(if index.is_some() { "Edit Server" } else { "Add Server" }).child(content)
+    });
+}
+
+fn open_copy_server(
+    id: InstanceID,
+    index: usize,
+    instances: &Entity<InstanceEntries>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let targets: Arc<[(SharedString, InstanceID)]> = instances.read(cx).entries.iter()
+        .filter(|(target, _)| **target != id)
+        .map(|(target, entry)| (entry.read(cx).name.clone(), *target))
+        .collect();
+
+    let target_select = cx.new(|cx| {
+        let items = targets.iter().map(|(name, _)| name.clone()).collect();
+        SelectState::new(SearchableVec::new(items), None, window, cx).searchable(true)
+    });
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let content = v_flex()
+            .gap_2()
+            .child(crate::labelled("Instance", Select::new(&target_select).w_full()))
+            .child(Button::new("confirm").label("Copy server").success().on_click({
+                let targets = targets.clone();
+                let target_select = target_select.clone();
+                let backend_handle = backend_handle.clone();
+                move |_, window, cx| {
+                    let Some(selected) = target_select.read(cx).selected_value() else {
+                        return;
+                    };
+                    let Some((_, target)) = targets.iter().find(|(name, _)| name == selected) else {
+                        return;
+                    };
+                    backend_handle.send(MessageToBackend::CopyServer {
+                        id,
+                        index,
+                        target: *target,
+                    });
+                    window.close_all_dialogs(cx);
+                }
+            }));
+
+        dialog.title("Copy Server").child(content)
+    });
+}
@@ -3,15 +3,15 @@ use std::{hash::{DefaultHasher, Hash, Hasher}, path::Path, sync::{
 }};
 
 use bridge::{
-    handle::BackendHandle, install::{ContentDownload, ContentInstall, ContentInstallFile, InstallTarget}, instance::{AtomicContentUpdateStatus, InstanceID, InstanceContentID, InstanceContentSummary, ContentType, ContentSummary}, message::{AtomicBridgeDataLoadState, MessageToBackend}, serial::AtomicOptionSerial
+    handle::BackendHandle, install::{ContentDownload, ContentInstall, ContentInstallFile, InstallTarget}, instance::{AtomicContentUpdateStatus, InstanceID, InstanceContentID, InstanceContentSummary, InstanceStatus, ContentType, ContentSummary, ResourcePackOrder}, message::{AtomicBridgeDataLoadState, MessageToBackend}, serial::AtomicOptionSerial
 };
 use gpui::{prelude::*, *};
 use gpui_component::{
-    ActiveTheme as _, Icon, IconName, IndexPath, Sizable, WindowExt, breadcrumb::{Breadcrumb, BreadcrumbItem}, button::{Button, ButtonVariants}, h_flex, input::SelectAll, list::{ListDelegate, ListItem, ListState}, notification::{Notification, NotificationType}, switch::Switch, v_flex
+    ActiveTheme as _, Disableable, Icon, IconName, IndexPath, Sizable, WindowExt, breadcrumb::{Breadcrumb, BreadcrumbItem}, button::{Button, ButtonVariants}, h_flex, input::SelectAll, list::{ListDelegate, ListItem, ListState}, notification::{Notification, NotificationType}, scroll::ScrollableElement, switch::Switch, v_flex
 };
 use parking_lot::Mutex;
 use rustc_hash::FxHashSet;
-use schema::{content::ContentSource, loader::Loader, modrinth::ModrinthProjectType};
+use schema::{content::ContentSource, curseforge::CurseforgeClass, loader::Loader, modrinth::ModrinthProjectType};
 use ustr::Ustr;
 
 use crate::{component::content_list::ContentListDelegate, entity::instance::InstanceEntry, interface_config::InterfaceConfig, png_render_cache, root, ui::PageType};
@@ -20,14 +20,21 @@ use super::instance_page::InstanceSubpageType;
 
 pub struct InstanceResourcePacksSubpage {
     instance: InstanceID,
+    entry: Entity<InstanceEntry>,
     instance_title: SharedString,
     instance_loader: Loader,
     instance_version: Ustr,
     backend_handle: BackendHandle,
     resource_packs_state: Arc<AtomicBridgeDataLoadState>,
+    resource_packs: Entity<Arc<[InstanceContentSummary]>>,
     resource_pack_list: Entity<ListState<ContentListDelegate>>,
     load_serial: AtomicOptionSerial,
+    pack_order: Option<ResourcePackOrder>,
+    last_status: InstanceStatus,
     _add_from_file_task: Option<Task<()>>,
+    _load_pack_order_task: Task<()>,
+    _observe_resource_packs_subscription: Subscription,
+    _observe_instance_subscription: Subscription,
 }
 
 impl InstanceResourcePacksSubpage {
@@ -37,11 +44,13 @@ impl InstanceResourcePacksSubpage {
         window: &mut gpui::Window,
         cx: &mut gpui::Context<Self>,
     ) -> Self {
+        let entry = instance.clone();
         let instance = instance.read(cx);
         let instance_title = instance.title().into();
         let instance_loader = instance.configuration.loader;
         let instance_version = instance.configuration.minecraft_version;
         let instance_id = instance.id;
+        let last_status = instance.status;
 
         let resource_packs_state = Arc::clone(&instance.resource_packs_state);
 
@@ -50,34 +59,211 @@ impl InstanceResourcePacksSubpage {
 
         let resource_packs = instance.resource_packs.clone();
 
-        let resource_pack_list = cx.new(move |cx| {
-            cx.observe(&resource_packs, |list: &mut ListState<ContentListDelegate>, resource_packs, cx| {
-                let actual_resource_packs = resource_packs.read(cx);
-                list.delegate_mut().set_content(actual_resource_packs);
-                cx.notify();
-            }).detach();
+        // Packs can be added or removed outside the launcher, so refresh the in-game order along with the folder
+        let _observe_resource_packs_subscription = cx.observe(&resource_packs, |page, _, cx| {
+            page.load_pack_order(cx);
+        });
+        // The game saves the order it was left in when closing
+        let _observe_instance_subscription = cx.observe(&entry, |page, entry, cx| {
+            let status = entry.read(cx).status;
+            if status != page.last_status {
+                page.last_status = status;
+                if status == InstanceStatus::NotRunning {
+                    page.load_pack_order(cx);
+                }
+            }
+            cx.notify();
+        });
 
-            ListState::new(resource_packs_list_delegate, window, cx).selectable(false).searchable(true)
+        let resource_pack_list = cx.new({
+            let resource_packs = resource_packs.clone();
+            move |cx| {
+                cx.observe(&resource_packs, |list: &mut ListState<ContentListDelegate>, resource_packs, cx| {
+                    let actual_resource_packs = resource_packs.read(cx);
+                    list.delegate_mut().set_content(actual_resource_packs);
+                    cx.notify();
+                }).detach();
+
+                ListState::new(resource_packs_list_delegate, window, cx).selectable(false).searchable(true)
+            }
         });
 
-        Self {
+        let mut page = Self {
             instance: instance_id,
+            entry,
             instance_title,
             instance_loader,
             instance_version,
             backend_handle,
             resource_packs_state,
+            resource_packs,
             resource_pack_list,
             load_serial: AtomicOptionSerial::default(),
+            pack_order: None,
+            last_status,
             _add_from_file_task: None,
+            _load_pack_order_task: Task::ready(()),
+            _observe_resource_packs_subscription,
+            _observe_instance_subscription,
+        };
+        page.load_pack_order(cx);
+        page
+    }
+
+    fn load_pack_order(&mut self, cx: &mut Context<Self>) {
+        let (send, recv) = tokio::sync::oneshot::channel();
+        self.backend_handle.send(MessageToBackend::GetResourcePackOrder {
+            id: self.instance,
+            channel: send,
+        });
+        self.receive_pack_order(recv, cx);
+    }
+
+    fn receive_pack_order(&mut self, recv: tokio::sync::oneshot::Receiver<ResourcePackOrder>, cx: &mut Context<Self>) {
+        self._load_pack_order_task = cx.spawn(async move |page, cx| {
+            let Ok(pack_order) = recv.await else {
+                return;
+            };
+            _ = page.update(cx, |page, cx| {
+                page.resource_pack_list.update(cx, |list, cx| {
+                    list.delegate_mut().set_pack_format(pack_order.pack_format);
+                    cx.notify();
+                });
+                page.pack_order = Some(pack_order);
+                cx.notify();
+            });
+        });
+    }
+
+    fn pack_id(&self, filename: &str) -> Arc<str> {
+        // Before 1.13 packs were referenced by their filename alone, afterwards they are prefixed with their source
+        let legacy = self.pack_order.as_ref().is_some_and(|order| {
+            order.pack_format.is_none() && !order.enabled.is_empty()
+                && order.enabled.iter().all(|pack| &**pack != "vanilla" && !pack.starts_with("file/"))
+        });
+        if legacy {
+            filename.into()
+        } else {
+            format!("file/{filename}").into()
         }
     }
+
+    fn is_incompatible(&self, pack_id: &str, cx: &App) -> bool {
+        let Some(pack_format) = self.pack_order.as_ref().and_then(|order| order.pack_format) else {
+            return false;
+        };
+        let filename = pack_id.strip_prefix("file/").unwrap_or(pack_id);
+        self.resource_packs.read(cx).iter()
+            .find(|summary| &*summary.filename == filename)
+            .is_some_and(|summary| matches!(summary.content_summary.extra,
+                ContentType::ResourcePack { pack_format: Some(range) } if !range.contains(pack_format)))
+    }
+
+    fn update_pack_order(&mut self, cx: &mut Context<Self>, update: impl FnOnce(&mut ResourcePackOrder)) {
+        let Some(order) = &self.pack_order else {
+            return;
+        };
+        // The shown order is only replaced once the backend replies with what it saved
+        let mut order = order.clone();
+        update(&mut order);
+
+        let (send, recv) = tokio::sync::oneshot::channel();
+        self.backend_handle.send(MessageToBackend::SetResourcePackOrder {
+            id: self.instance,
+            enabled: order.enabled,
+            incompatible: order.incompatible,
+            channel: send,
+        });
+        self.receive_pack_order(recv, cx);
+    }
+
+    fn activate_pack(&mut self, filename: &str, cx: &mut Context<Self>) {
+        let pack_id = self.pack_id(filename);
+        // The game drops incompatible packs from the list unless they were explicitly accepted
+        let incompatible = self.is_incompatible(&pack_id, cx);
+        self.update_pack_order(cx, |order| {
+            if !order.enabled.contains(&pack_id) {
+                order.enabled.push(pack_id.clone());
+            }
+            if incompatible && !order.incompatible.contains(&pack_id) {
+                order.incompatible.push(pack_id);
+            }
+        });
+    }
+
+    fn deactivate_pack(&mut self, index: usize, cx: &mut Context<Self>) {
+        self.update_pack_order(cx, |order| {
+            if index < order.enabled.len() {
+                let pack_id = order.enabled.remove(index);
+                order.incompatible.retain(|pack| *pack != pack_id);
+            }
+        });
+    }
+
+    fn move_pack(&mut self, index: usize, up: bool, cx: &mut Context<Self>) {
+        self.update_pack_order(cx, |order| {
+            let other = if up { index.checked_sub(1) } else { Some(index + 1) };
+            if let Some(other) = other && other < order.enabled.len() && index < order.enabled.len() {
+                order.enabled.swap(index, other);
+            }
+        });
+    }
+
+    fn render_pack_order(&self, cx: &mut Context<Self>) -> Div {
+        let theme = cx.theme();
+        let running = self.entry.read(cx).status != InstanceStatus::NotRunning;
+
+        let Some(order) = &self.pack_order else {
+            return v_flex();
+        };
+
+        let last = order.enabled.len().saturating_sub(1);
+        // Packs later in the list are applied on top, so show them first like the game does
+        let active = order.enabled.iter().enumerate().rev().map(|(index, pack_id)| {
+            let incompatible = self.is_incompatible(pack_id, cx);
+            h_flex()
+                .gap_1()
+                .child(Button::new(("packup", index)).icon(IconName::ChevronUp).compact().xsmall().disabled(running || index == last).on_click(cx.listener(move |page, _, _, cx| {
+                    page.move_pack(index, false, cx);
+                })))
+                .child(Button::new(("packdown", index)).icon(IconName::ChevronDown).compact().xsmall().disabled(running || index == 0).on_click(cx.listener(move |page, _, _, cx| {
+                    page.move_pack(index, true, cx);
+                })))
+                .child(div().flex_1().text_sm().truncate()
+                    .when(incompatible, |this| this.text_color(theme.warning))
+                    .child(SharedString::from(pack_id.strip_prefix("file/").unwrap_or(pack_id).to_string())))
+                .child(Button::new(("deactivate", index)).icon(IconName::Close).compact().xsmall().disabled(running).on_click(cx.listener(move |page, _, _, cx| {
+                    page.deactivate_pack(index, cx);
+                })))
+        }).collect::<Vec<_>>();
+
+        let inactive = self.resource_packs.read(cx).iter()
+            .filter(|summary| summary.enabled && !order.enabled.contains(&self.pack_id(&summary.filename)))
+            .map(|summary| {
+                let filename = summary.filename.clone();
+                h_flex()
+                    .gap_1()
+                    .child(div().flex_1().text_sm().truncate().child(SharedString::from(filename.clone())))
+                    .child(Button::new(("activate", summary.filename_hash)).label("Activate").success().compact().xsmall().disabled(running).on_click(cx.listener(move |page, _, _, cx| {
+                        page.activate_pack(&filename, cx);
+                    })))
+            }).collect::<Vec<_>>();
+
+        v_flex()
+            .gap_1()
+            .child(div().text_sm().font_medium().child("Active in game (top has the highest priority)"))
+            .when(running, |this| {
+                this.child(div().text_sm().text_color(theme.muted_foreground).child("The order can't be changed while the instance is running"))
+            })
+            .children(active)
+            .when(!inactive.is_empty(), |this| {
+                this.child(div().mt_3().text_sm().font_medium().child("Not active")).children(inactive)
+            })
+    }
 }
 
 impl Render for InstanceResourcePacksSubpage {
     fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
-        let theme = cx.theme();
-
         let state = self.resource_packs_state.load(Ordering::SeqCst);
         if state.should_send_load_request() {
             self.backend_handle.send_with_serial(MessageToBackend::RequestLoadResourcePacks { id: self.instance }, &self.load_serial);
@@ -95,6 +281,13 @@ impl Render for InstanceResourcePacksSubpage {
                     crate::root::start_update_check(instance_id, &backend_handle, window, cx);
                 }
             }))
+            .child(Button::new("updateall").label("Update all...").success().compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance_id = self.instance;
+                move |_, window, cx| {
+                    crate::modals::update_all::open_update_all(instance_id, backend_handle.clone(), window, cx);
+                }
+            }))
             .child(Button::new("addmr").label("Add from Modrinth").success().compact().small().on_click({
                 let instance = self.instance;
                 move |_, window, cx| {
@@ -106,6 +299,26 @@ impl Render for InstanceResourcePacksSubpage {
                     root::switch_page(page, path, window, cx);
                 }
             }))
+            .child(Button::new("addcf").label("Add from CurseForge").success().compact().small().on_click({
+                let instance = self.instance;
+                move |_, window, cx| {
+                    let page = crate::ui::PageType::Curseforge {
+                        installing_for: Some(instance),
+                        class: Some(CurseforgeClass::Resourcepack)
+                    };
+                    let path = &[PageType::Instances, PageType::InstancePage(instance, InstanceSubpageType::ResourcePacks)];
+                    root::switch_page(page, path, window, cx);
+                }
+            }))
+            .child(Button::new("addurl").label("Add from URL").success().compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance = self.instance;
+                let loader = self.instance_loader;
+                let version = self.instance_version;
+                move |_, window, cx| {
+                    crate::modals::add_from_url::open_add_from_url(instance, loader, version, backend_handle.clone(), window, cx);
+                }
+            }))
             .child(Button::new("addfile").label("Add from file").success().compact().small().on_click({
                 let backend_handle = self.backend_handle.clone();
                 let instance = self.instance;
@@ -157,7 +370,10 @@ impl Render for InstanceResourcePacksSubpage {
                 })
             }));
 
-        v_flex().p_4().size_full().child(header).child(
+        let pack_order = self.render_pack_order(cx);
+        let theme = cx.theme();
+
+        v_flex().p_4().size_full().child(header).child(h_flex().size_full().gap_4().child(
             div()
                 .id("pack-list-area")
                 .size_full()
@@ -183,6 +399,16 @@ impl Render for InstanceResourcePacksSubpage {
                         })
                     }
                 }),
-        )
+        ).child(
+            v_flex()
+                .w_1_3()
+                .h_full()
+                .p_3()
+                .border_1()
+                .rounded(theme.radius)
+                .border_color(theme.border)
+                .child(pack_order)
+                .overflow_y_scrollbar(),
+        ))
     }
 }
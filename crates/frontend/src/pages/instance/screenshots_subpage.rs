@@ -0,0 +1,105 @@
+use std::sync::Arc;
+
+use bridge::{
+    handle::BackendHandle,
+    instance::{InstanceID, InstanceScreenshotSummary, InstanceStatus},
+    message::MessageToBackend,
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _, Sizable,
+    button::{Button, ButtonVariants},
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+
+use crate::{component::screenshot_grid::{self, ScreenshotEntry}, entity::instance::InstanceEntry};
+
+pub struct InstanceScreenshotsSubpage {
+    instance_id: InstanceID,
+    backend_handle: BackendHandle,
+    screenshots: Entity<Arc<[InstanceScreenshotSummary]>>,
+    status: InstanceStatus,
+    _observe_instance_subscription: Subscription,
+    _observe_screenshots_subscription: Subscription,
+}
+
+impl InstanceScreenshotsSubpage {
+    pub fn new(
+        instance: &Entity<InstanceEntry>,
+        backend_handle: BackendHandle,
+        _window: &mut gpui::Window,
+        cx: &mut gpui::Context<Self>,
+    ) -> Self {
+        let entry = instance.read(cx);
+        let instance_id = entry.id;
+        let status = entry.status;
+        let screenshots = entry.screenshots.clone();
+
+        // Screenshots are taken while the game is running, so reload once it stops
+        let _observe_instance_subscription = cx.observe(instance, |page, instance, cx| {
+            let status = instance.read(cx).status;
+            if page.status != status {
+                page.status = status;
+                if status == InstanceStatus::NotRunning {
+                    page.backend_handle.send(MessageToBackend::RequestLoadScreenshots { id: page.instance_id });
+                }
+            }
+        });
+        let _observe_screenshots_subscription = cx.observe(&screenshots, |_, _, cx| cx.notify());
+
+        backend_handle.send(MessageToBackend::RequestLoadScreenshots { id: instance_id });
+
+        Self {
+            instance_id,
+            backend_handle,
+            screenshots,
+            status,
+            _observe_instance_subscription,
+            _observe_screenshots_subscription,
+        }
+    }
+}
+
+impl Render for InstanceScreenshotsSubpage {
+    fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
+        let screenshots: Vec<ScreenshotEntry> = self.screenshots.read(cx).iter().map(|summary| ScreenshotEntry {
+            instance: self.instance_id,
+            instance_name: None,
+            summary: summary.clone(),
+        }).collect();
+
+        let header = h_flex()
+            .gap_3()
+            .mb_1()
+            .ml_1()
+            .child(div().text_lg().child("Screenshots"))
+            .child(Button::new("refresh").label("Refresh").info().compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let id = self.instance_id;
+                move |_, _, _| {
+                    backend_handle.send(MessageToBackend::RequestLoadScreenshots { id });
+                }
+            }));
+
+        let content = if screenshots.is_empty() {
+            div().p_2().text_color(cx.theme().muted_foreground).child("No screenshots have been taken in this instance")
+        } else {
+            screenshot_grid::render_screenshot_grid(&screenshots, &self.backend_handle, cx).p_2()
+        };
+
+        let theme = cx.theme();
+        v_flex()
+            .p_4()
+            .size_full()
+            .child(header)
+            .child(div()
+                .size_full()
+                .border_1()
+                .rounded(theme.radius)
+                .border_color(theme.border)
+                .child(content)
+                .overflow_y_scrollbar())
+    }
+}
@@ -7,9 +7,20 @@ use gpui::{prelude::*, *};
 use gpui_component::{
     ActiveTheme as _, Disableable, Selectable, Sizable, WindowExt, button::{Button, ButtonGroup, ButtonVariants}, checkbox::Checkbox, h_flex, input::{Input, InputEvent, InputState, NumberInput, NumberInputEvent}, notification::{Notification, NotificationType}, select::{SearchableVec, Select, SelectEvent, SelectState}, spinner::Spinner, v_flex
 };
-use schema::{fabric_loader_manifest::FabricLoaderManifest, forge::{ForgeMavenManifest, NeoforgeMavenManifest}, instance::{InstanceJvmBinaryConfiguration, InstanceJvmFlagsConfiguration, InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration}, loader::Loader, version_manifest::MinecraftVersionManifest};
+use schema::{fabric_loader_manifest::FabricLoaderManifest, forge::{ForgeMavenManifest, NeoforgeMavenManifest}, instance::{InstanceEnvVarsConfiguration, InstanceJvmBinaryConfiguration, InstanceJvmFlagsConfiguration, InstanceLinuxWrapperConfiguration, InstanceMemoryConfiguration, InstanceResolutionConfiguration}, loader::Loader, version_manifest::MinecraftVersionManifest};
 
-use crate::{entity::{DataEntities, instance::InstanceEntry, metadata::{AsMetadataResult, FrontendMetadata, FrontendMetadataResult, FrontendMetadataState, TypelessFrontendMetadataResult}}, interface_config::InterfaceConfig, pages::instances_page::VersionList};
+use crate::{entity::{DataEntities, instance::InstanceEntry, metadata::{AsMetadataResult, FrontendMetadata, FrontendMetadataResult, FrontendMetadataState, TypelessFrontendMetadataResult}}, interface_config::InterfaceConfig, pages::instances_page::VersionList, ui};
+
+const BUILTIN_ICONS: &[&str] = &[
+    "grass_block",
+    "stone",
+    "planks",
+    "creeper",
+    "tnt",
+    "diamond",
+    "water",
+    "lava",
+];
 
 #[derive(PartialEq, Eq)]
 enum NewNameChangeState {
@@ -33,8 +44,18 @@ pub struct InstanceSettingsSubpage {
     memory_max_input_state: Entity<InputState>,
     jvm_flags_enabled: bool,
     jvm_flags_input_state: Entity<InputState>,
+    group_input_state: Entity<InputState>,
+    tags_input_state: Entity<InputState>,
+    description_input_state: Entity<InputState>,
     jvm_binary_enabled: bool,
     jvm_binary_path: Option<Arc<Path>>,
+    env_vars_enabled: bool,
+    env_vars_input_state: Entity<InputState>,
+    resolution_enabled: bool,
+    resolution_width_input_state: Entity<InputState>,
+    resolution_height_input_state: Entity<InputState>,
+    #[cfg(target_os = "linux")]
+    linux_wrapper_enabled: bool,
     #[cfg(target_os = "linux")]
     use_mangohud: bool,
     #[cfg(target_os = "linux")]
@@ -49,6 +70,7 @@ pub struct InstanceSettingsSubpage {
     backend_handle: BackendHandle,
     _observe_loader_version_subscription: Option<Subscription>,
     _select_file_task: Task<()>,
+    _select_icon_task: Task<()>,
 }
 
 impl InstanceSettingsSubpage {
@@ -68,6 +90,11 @@ impl InstanceSettingsSubpage {
         let jvm_flags = entry.configuration.jvm_flags.clone().unwrap_or_default();
         let jvm_binary = entry.configuration.jvm_binary.clone().unwrap_or_default();
         let linux_wrapper = entry.configuration.linux_wrapper.unwrap_or_default();
+        let env_vars = entry.configuration.env_vars.clone().unwrap_or_default();
+        let resolution = entry.configuration.resolution.unwrap_or_default();
+        let group = entry.configuration.group.clone().unwrap_or_default();
+        let tags = entry.configuration.tags.join(", ");
+        let description = entry.description.clone();
 
         let new_name_input_state = cx.new(|cx| InputState::new(window, cx));
         cx.subscribe(&new_name_input_state, Self::on_new_name_input).detach();
@@ -78,9 +105,21 @@ impl InstanceSettingsSubpage {
         cx.observe_in(&minecraft_versions, window, |page, versions, window, cx| {
             page.update_minecraft_versions(versions, window, cx);
         }).detach();
-        cx.subscribe(&version_select_state, Self::on_minecraft_version_selected).detach();
+        cx.subscribe_in(&version_select_state, window, Self::on_minecraft_version_selected).detach();
 
         cx.observe_in(instance, window, |page, instance, window, cx| {
+            let configuration = &instance.read(cx).configuration;
+            let (loader, minecraft_version) = (configuration.loader, configuration.minecraft_version);
+            if page.loader != loader {
+                page.loader = loader;
+                page.update_loader_versions(window, cx);
+            }
+            page.version_select_state.update(cx, |select_state, cx| {
+                if select_state.selected_value().map(|value| value.as_str()) != Some(minecraft_version.as_str()) {
+                    select_state.set_selected_value(&SharedString::new_static(minecraft_version.as_str()), window, cx);
+                }
+            });
+
             if page.loader_version_select_state.read(cx).selected_index(cx).is_none() {
                 let version = instance.read(cx).configuration.preferred_loader_version.map(|s| s.as_str()).unwrap_or("Latest");
                 page.loader_version_select_state.update(cx, |select_state, cx| {
@@ -112,6 +151,35 @@ impl InstanceSettingsSubpage {
         });
         cx.subscribe(&jvm_flags_input_state, Self::on_jvm_flags_changed).detach();
 
+        let env_vars_input_state = cx.new(|cx| {
+            InputState::new(window, cx).auto_grow(1, 8).placeholder("KEY=VALUE, one per line").default_value(env_vars.variables)
+        });
+        cx.subscribe(&env_vars_input_state, Self::on_env_vars_changed).detach();
+
+        let resolution_width_input_state = cx.new(|cx| {
+            InputState::new(window, cx).default_value(resolution.width.to_string())
+        });
+        cx.subscribe(&resolution_width_input_state, Self::on_resolution_changed).detach();
+        let resolution_height_input_state = cx.new(|cx| {
+            InputState::new(window, cx).default_value(resolution.height.to_string())
+        });
+        cx.subscribe(&resolution_height_input_state, Self::on_resolution_changed).detach();
+
+        let group_input_state = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("No group").default_value(group.to_string())
+        });
+        cx.subscribe(&group_input_state, Self::on_group_changed).detach();
+
+        let tags_input_state = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("Comma separated, e.g. modded, survival").default_value(tags)
+        });
+        cx.subscribe(&tags_input_state, Self::on_tags_changed).detach();
+
+        let description_input_state = cx.new(|cx| {
+            InputState::new(window, cx).auto_grow(3, 12).placeholder("Markdown description").default_value(description)
+        });
+        cx.subscribe(&description_input_state, Self::on_description_changed).detach();
+
         let mut page = Self {
             data: data.clone(),
             instance: instance.clone(),
@@ -126,8 +194,18 @@ impl InstanceSettingsSubpage {
             memory_max_input_state,
             jvm_flags_enabled: jvm_flags.enabled,
             jvm_flags_input_state,
+            group_input_state,
+            tags_input_state,
+            description_input_state,
             jvm_binary_enabled: jvm_binary.enabled,
             jvm_binary_path: jvm_binary.path.clone(),
+            env_vars_enabled: env_vars.enabled,
+            env_vars_input_state,
+            resolution_enabled: resolution.enabled,
+            resolution_width_input_state,
+            resolution_height_input_state,
+            #[cfg(target_os = "linux")]
+            linux_wrapper_enabled: linux_wrapper.enabled,
             #[cfg(target_os = "linux")]
             use_mangohud: linux_wrapper.use_mangohud,
             #[cfg(target_os = "linux")]
@@ -142,7 +220,8 @@ impl InstanceSettingsSubpage {
             backend_handle,
             loader_versions_state: TypelessFrontendMetadataResult::Loading,
             _observe_loader_version_subscription: None,
-            _select_file_task: Task::ready(())
+            _select_file_task: Task::ready(()),
+            _select_icon_task: Task::ready(()),
         };
         page.update_minecraft_versions(minecraft_versions, window, cx);
         page.update_loader_versions(window, cx);
@@ -303,9 +382,10 @@ impl InstanceSettingsSubpage {
 
     pub fn on_minecraft_version_selected(
         &mut self,
-        _state: Entity<SelectState<VersionList>>,
+        state: &Entity<SelectState<VersionList>>,
         event: &SelectEvent<VersionList>,
-        _cx: &mut Context<Self>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
     ) {
         let SelectEvent::Confirm(value) = event;
 
@@ -313,10 +393,19 @@ impl InstanceSettingsSubpage {
             return;
         };
 
-        self.backend_handle.send(MessageToBackend::SetInstanceMinecraftVersion {
-            id: self.instance_id,
-            version: value.as_str().into(),
+        let configuration = &self.instance.read(cx).configuration;
+        let (loader, current_version) = (configuration.loader, configuration.minecraft_version);
+        if value.as_str() == current_version.as_str() {
+            return;
+        }
+
+        // The selection only changes once the migration has been applied
+        state.update(cx, |select_state, cx| {
+            select_state.set_selected_value(&SharedString::new_static(current_version.as_str()), window, cx);
         });
+
+        crate::modals::migrate_instance::open_migrate_instance(self.instance_id, value.as_str().into(), loader,
+            self.backend_handle.clone(), window, cx);
     }
 
     pub fn on_loader_version_selected(
@@ -407,6 +496,153 @@ impl InstanceSettingsSubpage {
         }
     }
 
+    pub fn on_env_vars_changed(
+        &mut self,
+        _: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Change = event {
+            self.backend_handle.send(MessageToBackend::SetInstanceEnvVars {
+                id: self.instance_id,
+                env_vars: self.get_env_vars_configuration(cx)
+            });
+        }
+    }
+
+    pub fn on_resolution_changed(
+        &mut self,
+        _: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let InputEvent::Change = event {
+            self.backend_handle.send(MessageToBackend::SetInstanceResolution {
+                id: self.instance_id,
+                resolution: self.get_resolution_configuration(cx)
+            });
+        }
+    }
+
+    pub fn on_group_changed(
+        &mut self,
+        state: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        // Only saved once editing is done, so info_v1.json isn't rewritten on every keystroke
+        if !matches!(event, InputEvent::Blur | InputEvent::PressEnter { .. }) {
+            return;
+        }
+
+        let group = state.read(cx).value();
+        let group = group.trim();
+        let group: Option<Arc<str>> = (!group.is_empty()).then(|| group.into());
+        if self.instance.read(cx).configuration.group == group {
+            return;
+        }
+
+        self.backend_handle.send(MessageToBackend::SetInstanceGroup {
+            id: self.instance_id,
+            group,
+        });
+    }
+
+    pub fn on_tags_changed(
+        &mut self,
+        state: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if !matches!(event, InputEvent::Blur | InputEvent::PressEnter { .. }) {
+            return;
+        }
+
+        let value = state.read(cx).value();
+        let mut tags: Vec<Arc<str>> = Vec::new();
+        for tag in value.split(',') {
+            let tag = tag.trim();
+            if !tag.is_empty() && !tags.iter().any(|existing| &**existing == tag) {
+                tags.push(tag.into());
+            }
+        }
+        if self.instance.read(cx).configuration.tags == tags {
+            return;
+        }
+
+        self.backend_handle.send(MessageToBackend::SetInstanceTags {
+            id: self.instance_id,
+            tags,
+        });
+    }
+
+    pub fn on_description_changed(
+        &mut self,
+        state: Entity<InputState>,
+        event: &InputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        // Enter adds a new line, so the description is only saved once the field loses focus
+        if !matches!(event, InputEvent::Blur) {
+            return;
+        }
+
+        let description = state.read(cx).value();
+        if self.instance.read(cx).description == description {
+            return;
+        }
+
+        self.backend_handle.send(MessageToBackend::SetInstanceDescription {
+            id: self.instance_id,
+            description: description.as_str().into(),
+        });
+    }
+
+    fn select_icon_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Select PNG icon".into())
+        });
+
+        let this_entity = cx.entity();
+        self._select_icon_task = window.spawn(cx, async move |cx| {
+            let Ok(result) = receiver.await else {
+                return;
+            };
+            let result = match result {
+                Ok(Some(paths)) => {
+                    let Some(path) = paths.into_iter().next() else {
+                        return;
+                    };
+                    // The file may be large or on a slow drive, so it isn't read on the UI thread
+                    cx.background_spawn(async move { std::fs::read(path) }).await
+                        .map_err(|error| format!("Unable to read icon: {}", error))
+                },
+                Ok(None) => return,
+                Err(error) => Err(format!("{}", error)),
+            };
+            _ = cx.update_window_entity(&this_entity, move |this, window, cx| {
+                let error = match result {
+                    Ok(bytes) => {
+                        this.backend_handle.send(MessageToBackend::SetInstanceIcon {
+                            id: this.instance_id,
+                            icon: Some(bytes.into()),
+                        });
+                        return;
+                    },
+                    Err(error) => error,
+                };
+                let notification = Notification::new()
+                    .autohide(false)
+                    .with_type(NotificationType::Error)
+                    .title(error);
+                window.push_notification(notification, cx);
+            });
+        });
+    }
+
     fn get_jvm_flags_configuration(&self, cx: &App) -> InstanceJvmFlagsConfiguration {
         let flags = self.jvm_flags_input_state.read(cx).value();
 
@@ -423,9 +659,32 @@ impl InstanceSettingsSubpage {
         }
     }
 
+    fn get_env_vars_configuration(&self, cx: &App) -> InstanceEnvVarsConfiguration {
+        let variables = self.env_vars_input_state.read(cx).value();
+
+        InstanceEnvVarsConfiguration {
+            enabled: self.env_vars_enabled,
+            variables: variables.into(),
+        }
+    }
+
+    fn get_resolution_configuration(&self, cx: &App) -> InstanceResolutionConfiguration {
+        let width = self.resolution_width_input_state.read(cx).value().parse::<u32>()
+            .unwrap_or(InstanceResolutionConfiguration::DEFAULT_WIDTH);
+        let height = self.resolution_height_input_state.read(cx).value().parse::<u32>()
+            .unwrap_or(InstanceResolutionConfiguration::DEFAULT_HEIGHT);
+
+        InstanceResolutionConfiguration {
+            enabled: self.resolution_enabled,
+            width,
+            height,
+        }
+    }
+
     #[cfg(target_os = "linux")]
     fn get_linux_wrapper_configuration(&self) -> InstanceLinuxWrapperConfiguration {
         InstanceLinuxWrapperConfiguration {
+            enabled: self.linux_wrapper_enabled,
             use_mangohud: self.use_mangohud,
             use_gamemode: self.use_gamemode,
             use_discrete_gpu: self.use_discrete_gpu,
@@ -443,8 +702,51 @@ impl InstanceSettingsSubpage {
     }
 }
 
+impl InstanceSettingsSubpage {
+    fn render_icon_selector(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let current_icon = self.instance.read(cx).icon.clone();
+        let has_icon = current_icon.is_some();
+
+        let mut builtin_icons = h_flex().gap_1();
+        for (index, name) in BUILTIN_ICONS.iter().enumerate() {
+            let path = SharedString::new(format!("images/instance_icons/{}.png", name));
+            builtin_icons = builtin_icons.child(Button::new(("builtin_icon", index))
+                .ghost()
+                .child(gpui::img(ImageSource::Resource(Resource::Embedded(path.clone()))).size_6())
+                .on_click(cx.listener(move |page, _, _, _| {
+                    if let Some(file) = crate::Assets::get(&path) {
+                        page.backend_handle.send(MessageToBackend::SetInstanceIcon {
+                            id: page.instance_id,
+                            icon: Some(Arc::from(file.data.as_ref())),
+                        });
+                    }
+                })));
+        }
+
+        h_flex()
+            .gap_3()
+            .child(ui::instance_icon(current_icon.as_ref(), cx).size_12().min_w_12().min_h_12())
+            .child(v_flex()
+                .gap_1()
+                .child(builtin_icons)
+                .child(h_flex()
+                    .gap_2()
+                    .child(Button::new("select_icon").label("Choose PNG...").small().on_click(cx.listener(|page, _, window, cx| {
+                        page.select_icon_file(window, cx);
+                    })))
+                    .child(Button::new("remove_icon").label("Remove icon").small().danger().disabled(!has_icon).on_click(cx.listener(|page, _, _, _| {
+                        page.backend_handle.send(MessageToBackend::SetInstanceIcon {
+                            id: page.instance_id,
+                            icon: None,
+                        });
+                    })))))
+    }
+}
+
 impl Render for InstanceSettingsSubpage {
     fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
+        let icon_selector = self.render_icon_selector(cx);
+
         let theme = cx.theme();
 
         let header = h_flex()
@@ -456,6 +758,8 @@ impl Render for InstanceSettingsSubpage {
         let memory_override_enabled = self.memory_override_enabled;
         let jvm_flags_enabled = self.jvm_flags_enabled;
         let jvm_binary_enabled = self.jvm_binary_enabled;
+        let env_vars_enabled = self.env_vars_enabled;
+        let resolution_enabled = self.resolution_enabled;
 
         let jvm_binary_label = if let Some(path) = &self.jvm_binary_path {
             SharedString::new(path.to_string_lossy())
@@ -480,10 +784,8 @@ impl Render for InstanceSettingsSubpage {
                                 let backend_handle = self.backend_handle.clone();
                                 let new_name = self.new_name_input_state.read(cx).value();
                                 move |_, _, cx| {
-                                    let instance = instance.read(cx);
-                                    let id = instance.id;
                                     backend_handle.send(MessageToBackend::RenameInstance {
-                                        id,
+                                        id: instance.read(cx).id,
                                         name: new_name.as_str().into(),
                                     });
                                 }
@@ -491,7 +793,11 @@ impl Render for InstanceSettingsSubpage {
                         }
                     })
                 )
-            );
+            )
+            .child(crate::labelled("Group", Input::new(&self.group_input_state)))
+            .child(crate::labelled("Tags", Input::new(&self.tags_input_state)))
+            .child(crate::labelled("Icon", icon_selector))
+            .child(crate::labelled("Description", Input::new(&self.description_input_state)));
 
         match self.version_state {
             TypelessFrontendMetadataResult::Loading => {
@@ -537,21 +843,18 @@ impl Render for InstanceSettingsSubpage {
                 .on_click(cx.listener({
                     let backend_handle = self.backend_handle.clone();
                     move |page, selected: &Vec<usize>, window, cx| {
-                        let last_loader = page.loader;
-                        match selected.first() {
-                            Some(0) => page.loader = Loader::Vanilla,
-                            Some(1) => page.loader = Loader::Fabric,
-                            Some(2) => page.loader = Loader::Forge,
-                            Some(3) => page.loader = Loader::NeoForge,
-                            _ => {},
+                        let loader = match selected.first() {
+                            Some(0) => Loader::Vanilla,
+                            Some(1) => Loader::Fabric,
+                            Some(2) => Loader::Forge,
+                            Some(3) => Loader::NeoForge,
+                            _ => return,
                         };
-                        if page.loader != last_loader {
-                            backend_handle.send(MessageToBackend::SetInstanceLoader {
-                                id: page.instance_id,
-                                loader: page.loader,
-                            });
-                            page.update_loader_versions(window, cx);
-                            cx.notify();
+                        if page.loader != loader {
+                            // The loader is updated by the instance observer once the migration has been applied
+                            let minecraft_version = page.instance.read(cx).configuration.minecraft_version;
+                            crate::modals::migrate_instance::open_migrate_instance(page.instance_id, minecraft_version, loader,
+                                backend_handle.clone(), window, cx);
                         }
                     }
                 }))
@@ -580,6 +883,7 @@ impl Render for InstanceSettingsSubpage {
         let runtime_content = v_flex()
             .gap_4()
             .size_full()
+            .child(div().text_sm().text_color(theme.muted_foreground).child("Settings which aren't overridden use the defaults from the launcher settings"))
             .child(v_flex()
                 .gap_1()
                 .child(Checkbox::new("memory").label("Set Memory").checked(memory_override_enabled).on_click(cx.listener(|page, value, _, cx| {
@@ -664,13 +968,54 @@ impl Render for InstanceSettingsSubpage {
                     });
                     this._select_file_task = add_from_file_task;
                 })))
+            )
+            .child(v_flex()
+                .gap_1()
+                .child(Checkbox::new("env_vars").label("Set Environment Variables").checked(env_vars_enabled).on_click(cx.listener(|page, value, _, cx| {
+                    if page.env_vars_enabled != *value {
+                        page.env_vars_enabled = *value;
+                        page.backend_handle.send(MessageToBackend::SetInstanceEnvVars {
+                            id: page.instance_id,
+                            env_vars: page.get_env_vars_configuration(cx)
+                        });
+                        cx.notify();
+                    }
+                })))
+                .child(Input::new(&self.env_vars_input_state).disabled(!env_vars_enabled))
+            )
+            .child(v_flex()
+                .gap_1()
+                .child(Checkbox::new("resolution").label("Set Window Size").checked(resolution_enabled).on_click(cx.listener(|page, value, _, cx| {
+                    if page.resolution_enabled != *value {
+                        page.resolution_enabled = *value;
+                        page.backend_handle.send(MessageToBackend::SetInstanceResolution {
+                            id: page.instance_id,
+                            resolution: page.get_resolution_configuration(cx)
+                        });
+                        cx.notify();
+                    }
+                })))
+                .child(h_flex()
+                    .gap_1()
+                    .child(Input::new(&self.resolution_width_input_state).small().disabled(!resolution_enabled))
+                    .child("x")
+                    .child(Input::new(&self.resolution_height_input_state).small().disabled(!resolution_enabled)))
             );
 
         #[cfg(target_os = "linux")]
         let runtime_content = runtime_content.child(v_flex()
             .gap_1()
-            .child("Linux Performance")
-            .child(Checkbox::new("use_mangohud").label("Use MangoHud").checked(self.use_mangohud).disabled(!self.mangohud_available).on_click(cx.listener(|page, value, _, cx| {
+            .child(Checkbox::new("linux_wrapper").label("Override Linux Performance").checked(self.linux_wrapper_enabled).on_click(cx.listener(|page, value, _, cx| {
+                if page.linux_wrapper_enabled != *value {
+                    page.linux_wrapper_enabled = *value;
+                    page.backend_handle.send(MessageToBackend::SetInstanceLinuxWrapper {
+                        id: page.instance_id,
+                        linux_wrapper: page.get_linux_wrapper_configuration()
+                    });
+                    cx.notify();
+                }
+            })))
+            .child(Checkbox::new("use_mangohud").label("Use MangoHud").checked(self.use_mangohud).disabled(!self.linux_wrapper_enabled || !self.mangohud_available).on_click(cx.listener(|page, value, _, cx| {
                 if page.use_mangohud != *value {
                     page.use_mangohud = *value;
                     page.backend_handle.send(MessageToBackend::SetInstanceLinuxWrapper {
@@ -680,7 +1025,7 @@ impl Render for InstanceSettingsSubpage {
                     cx.notify();
                 }
             })))
-            .child(Checkbox::new("use_gamemode").label("Use GameMode").checked(self.use_gamemode).disabled(!self.gamemode_available).on_click(cx.listener(|page, value, _, cx| {
+            .child(Checkbox::new("use_gamemode").label("Use GameMode").checked(self.use_gamemode).disabled(!self.linux_wrapper_enabled || !self.gamemode_available).on_click(cx.listener(|page, value, _, cx| {
                 if page.use_gamemode != *value {
                     page.use_gamemode = *value;
                     page.backend_handle.send(MessageToBackend::SetInstanceLinuxWrapper {
@@ -690,7 +1035,7 @@ impl Render for InstanceSettingsSubpage {
                     cx.notify();
                 }
             })))
-            .child(Checkbox::new("use_discrete_gpu").label("Use Discrete GPU").checked(self.use_discrete_gpu).on_click(cx.listener(|page, value, _, cx| {
+            .child(Checkbox::new("use_discrete_gpu").label("Use Discrete GPU").checked(self.use_discrete_gpu).disabled(!self.linux_wrapper_enabled).on_click(cx.listener(|page, value, _, cx| {
                 if page.use_discrete_gpu != *value {
                     page.use_discrete_gpu = *value;
                     page.backend_handle.send(MessageToBackend::SetInstanceLinuxWrapper {
@@ -0,0 +1,241 @@
+use std::{path::Path, sync::{atomic::Ordering, Arc}};
+
+use bridge::{
+    handle::BackendHandle, install::{ContentDownload, ContentInstall, ContentInstallFile, InstallTarget}, instance::InstanceID, message::{AtomicBridgeDataLoadState, MessageToBackend}, serial::AtomicOptionSerial
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _, Sizable, WindowExt, button::{Button, ButtonVariants}, h_flex, input::SelectAll, list::ListState, notification::{Notification, NotificationType}, v_flex
+};
+use schema::{content::ContentSource, curseforge::CurseforgeClass, loader::Loader, modrinth::ModrinthProjectType};
+use ustr::Ustr;
+
+use crate::{component::content_list::ContentListDelegate, entity::instance::InstanceEntry, root, ui::PageType};
+
+use super::instance_page::InstanceSubpageType;
+
+pub struct InstanceShaderPacksSubpage {
+    instance: InstanceID,
+    instance_loader: Loader,
+    instance_version: Ustr,
+    backend_handle: BackendHandle,
+    shader_packs_state: Arc<AtomicBridgeDataLoadState>,
+    shader_pack_list: Entity<ListState<ContentListDelegate>>,
+    load_serial: AtomicOptionSerial,
+    selected_shader_pack: Option<Arc<str>>,
+    _add_from_file_task: Option<Task<()>>,
+    _observe_shader_packs_subscription: Subscription,
+}
+
+impl InstanceShaderPacksSubpage {
+    pub fn new(
+        instance: &Entity<InstanceEntry>,
+        backend_handle: BackendHandle,
+        window: &mut gpui::Window,
+        cx: &mut gpui::Context<Self>,
+    ) -> Self {
+        let instance = instance.read(cx);
+        let instance_loader = instance.configuration.loader;
+        let instance_version = instance.configuration.minecraft_version;
+        let instance_id = instance.id;
+
+        let shader_packs_state = Arc::clone(&instance.shader_packs_state);
+
+        let mut shader_packs_list_delegate = ContentListDelegate::new(instance_id, backend_handle.clone());
+        shader_packs_list_delegate.set_content(instance.shader_packs.read(cx));
+
+        let shader_packs = instance.shader_packs.clone();
+
+        // The selected shader pack is stored in the Iris/OptiFine config, so re-read it whenever the folder changes
+        let _observe_shader_packs_subscription = cx.observe(&shader_packs, |page, _, cx| {
+            page.request_selected_shader_pack(cx);
+        });
+
+        let shader_pack_list = cx.new(move |cx| {
+            cx.observe(&shader_packs, |list: &mut ListState<ContentListDelegate>, shader_packs, cx| {
+                let actual_shader_packs = shader_packs.read(cx);
+                list.delegate_mut().set_content(actual_shader_packs);
+                cx.notify();
+            }).detach();
+
+            ListState::new(shader_packs_list_delegate, window, cx).selectable(false).searchable(true)
+        });
+
+        let mut page = Self {
+            instance: instance_id,
+            instance_loader,
+            instance_version,
+            backend_handle,
+            shader_packs_state,
+            shader_pack_list,
+            load_serial: AtomicOptionSerial::default(),
+            selected_shader_pack: None,
+            _add_from_file_task: None,
+            _observe_shader_packs_subscription,
+        };
+        page.request_selected_shader_pack(cx);
+        page
+    }
+
+    fn request_selected_shader_pack(&mut self, cx: &mut Context<Self>) {
+        let (send, recv) = tokio::sync::oneshot::channel();
+        self.backend_handle.send(MessageToBackend::GetSelectedShaderPack {
+            id: self.instance,
+            channel: send,
+        });
+
+        cx.spawn(async move |page, cx| {
+            let Ok(selected) = recv.await else {
+                return;
+            };
+            _ = page.update(cx, |page, cx| {
+                page.selected_shader_pack = selected;
+                cx.notify();
+            });
+        }).detach();
+    }
+}
+
+impl Render for InstanceShaderPacksSubpage {
+    fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
+        let theme = cx.theme();
+
+        let state = self.shader_packs_state.load(Ordering::SeqCst);
+        if state.should_send_load_request() {
+            self.backend_handle.send_with_serial(MessageToBackend::RequestLoadShaderPacks { id: self.instance }, &self.load_serial);
+        }
+
+        let header = h_flex()
+            .gap_3()
+            .mb_1()
+            .ml_1()
+            .child(div().text_lg().child("Shader Packs"))
+            .child(div().text_sm().text_color(theme.muted_foreground).child(match &self.selected_shader_pack {
+                Some(selected) => SharedString::from(format!("Selected: {}", selected)),
+                None => SharedString::new_static("No shader selected"),
+            }))
+            .child(Button::new("update").label("Check for updates").success().compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance_id = self.instance;
+                move |_, window, cx| {
+                    crate::root::start_update_check(instance_id, &backend_handle, window, cx);
+                }
+            }))
+            .child(Button::new("updateall").label("Update all...").success().compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance_id = self.instance;
+                move |_, window, cx| {
+                    crate::modals::update_all::open_update_all(instance_id, backend_handle.clone(), window, cx);
+                }
+            }))
+            .child(Button::new("addmr").label("Add from Modrinth").success().compact().small().on_click({
+                let instance = self.instance;
+                move |_, window, cx| {
+                    let page = crate::ui::PageType::Modrinth {
+                        installing_for: Some(instance),
+                        project_type: Some(ModrinthProjectType::Shader)
+                    };
+                    let path = &[PageType::Instances, PageType::InstancePage(instance, InstanceSubpageType::ShaderPacks)];
+                    root::switch_page(page, path, window, cx);
+                }
+            }))
+            .child(Button::new("addcf").label("Add from CurseForge").success().compact().small().on_click({
+                let instance = self.instance;
+                move |_, window, cx| {
+                    let page = crate::ui::PageType::Curseforge {
+                        installing_for: Some(instance),
+                        class: Some(CurseforgeClass::Shader)
+                    };
+                    let path = &[PageType::Instances, PageType::InstancePage(instance, InstanceSubpageType::ShaderPacks)];
+                    root::switch_page(page, path, window, cx);
+                }
+            }))
+            .child(Button::new("addurl").label("Add from URL").success().compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance = self.instance;
+                let loader = self.instance_loader;
+                let version = self.instance_version;
+                move |_, window, cx| {
+                    crate::modals::add_from_url::open_add_from_url(instance, loader, version, backend_handle.clone(), window, cx);
+                }
+            }))
+            .child(Button::new("addfile").label("Add from file").success().compact().small().on_click({
+                let backend_handle = self.backend_handle.clone();
+                let instance = self.instance;
+                cx.listener(move |this, _, window, cx| {
+                    let receiver = cx.prompt_for_paths(PathPromptOptions {
+                        files: true,
+                        directories: false,
+                        multiple: true,
+                        prompt: Some("Select shader packs to install".into())
+                    });
+
+                    let backend_handle = backend_handle.clone();
+                    let entity = cx.entity();
+                    let add_from_file_task = window.spawn(cx, async move |cx| {
+                        let Ok(result) = receiver.await else {
+                            return;
+                        };
+                        _ = cx.update_window_entity(&entity, move |this, window, cx| {
+                            match result {
+                                Ok(Some(paths)) => {
+                                    let content_install = ContentInstall {
+                                        target: InstallTarget::Instance(instance),
+                                        loader_hint: this.instance_loader,
+                                        version_hint: Some(this.instance_version.into()),
+                                        files: paths.into_iter().filter_map(|path| {
+                                            Some(ContentInstallFile {
+                                                replace_old: None,
+                                                path: bridge::install::ContentInstallPath::Raw(Path::new("shaderpacks").join(path.file_name()?).into()),
+                                                download: ContentDownload::File { path },
+                                                content_source: ContentSource::Manual,
+                                            })
+                                        }).collect(),
+                                    };
+                                    crate::root::start_install(content_install, &backend_handle, window, cx);
+                                },
+                                Ok(None) => {},
+                                Err(error) => {
+                                    let error = format!("{}", error);
+                                    let notification = Notification::new()
+                                        .autohide(false)
+                                        .with_type(NotificationType::Error)
+                                        .title(error);
+                                    window.push_notification(notification, cx);
+                                },
+                            }
+                        });
+                    });
+                    this._add_from_file_task = Some(add_from_file_task);
+                })
+            }));
+
+        v_flex().p_4().size_full().child(header).child(
+            div()
+                .id("pack-list-area")
+                .size_full()
+                .border_1()
+                .rounded(theme.radius)
+                .border_color(theme.border)
+                .child(self.shader_pack_list.clone())
+                .on_click({
+                    let shader_pack_list = self.shader_pack_list.clone();
+                    move |_, _, cx| {
+                        cx.update_entity(&shader_pack_list, |list, _| {
+                            list.delegate_mut().clear_selection();
+                        })
+                    }
+                })
+                .key_context("Input")
+                .on_action({
+                    let shader_pack_list = self.shader_pack_list.clone();
+                    move |_: &SelectAll, _, cx| {
+                        cx.update_entity(&shader_pack_list, |list, cx| {
+                            list.delegate_mut().select_all();
+                            cx.notify();
+                        })
+                    }
+                }),
+        )
+    }
+}
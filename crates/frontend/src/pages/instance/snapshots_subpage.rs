@@ -0,0 +1,299 @@
+use bridge::{
+    handle::BackendHandle,
+    instance::{InstanceID, InstanceSnapshotSummary, InstanceStatus},
+    message::MessageToBackend,
+    modal_action::ModalAction,
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _, Disableable, IndexPath, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputState},
+    list::{ListDelegate, ListItem, ListState},
+    v_flex,
+};
+
+use crate::{entity::instance::InstanceEntry, modals};
+
+pub struct InstanceSnapshotsSubpage {
+    instance: Entity<InstanceEntry>,
+    instance_id: InstanceID,
+    backend_handle: BackendHandle,
+    note_input_state: Entity<InputState>,
+    include_worlds: bool,
+    snapshot_list: Entity<ListState<SnapshotsListDelegate>>,
+    _observe_instance_subscription: Subscription,
+}
+
+impl InstanceSnapshotsSubpage {
+    pub fn new(
+        instance: &Entity<InstanceEntry>,
+        backend_handle: BackendHandle,
+        window: &mut gpui::Window,
+        cx: &mut gpui::Context<Self>,
+    ) -> Self {
+        let entry = instance.read(cx);
+        let instance_id = entry.id;
+        let snapshots = entry.snapshots.clone();
+
+        let snapshots_list_delegate = SnapshotsListDelegate {
+            id: instance_id,
+            backend_handle: backend_handle.clone(),
+            running: entry.status != InstanceStatus::NotRunning,
+            snapshots: snapshots.read(cx).to_vec(),
+            searched: snapshots.read(cx).to_vec(),
+        };
+
+        let snapshot_list = cx.new(move |cx| {
+            cx.observe(&snapshots, |list: &mut ListState<SnapshotsListDelegate>, snapshots, cx| {
+                let snapshots = snapshots.read(cx).to_vec();
+                let delegate = list.delegate_mut();
+                delegate.snapshots = snapshots.clone();
+                delegate.searched = snapshots;
+                cx.notify();
+            }).detach();
+
+            ListState::new(snapshots_list_delegate, window, cx).selectable(false).searchable(true)
+        });
+
+        let _observe_instance_subscription = cx.observe(instance, |page, instance, cx| {
+            let running = instance.read(cx).status != InstanceStatus::NotRunning;
+            page.snapshot_list.update(cx, |list, cx| {
+                list.delegate_mut().running = running;
+                cx.notify();
+            });
+            cx.notify();
+        });
+
+        let note_input_state = cx.new(|cx| InputState::new(window, cx).placeholder("Note (optional)"));
+
+        backend_handle.send(MessageToBackend::RequestLoadSnapshots { id: instance_id });
+
+        Self {
+            instance: instance.clone(),
+            instance_id,
+            backend_handle,
+            note_input_state,
+            include_worlds: false,
+            snapshot_list,
+            _observe_instance_subscription,
+        }
+    }
+
+    fn create_snapshot(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let note = self.note_input_state.read(cx).value();
+        let note = note.trim();
+
+        let modal_action = ModalAction::default();
+
+        self.backend_handle.send(MessageToBackend::CreateInstanceSnapshot {
+            id: self.instance_id,
+            note: note.into(),
+            include_worlds: self.include_worlds,
+            modal_action: modal_action.clone(),
+        });
+
+        self.note_input_state.update(cx, |state, cx| {
+            state.set_value("", window, cx);
+        });
+
+        modals::generic::show_modal(window, cx, "Creating snapshot".into(), "Error creating snapshot".into(), modal_action);
+    }
+}
+
+impl Render for InstanceSnapshotsSubpage {
+    fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
+        let theme = cx.theme();
+
+        let header = h_flex()
+            .gap_3()
+            .mb_1()
+            .ml_1()
+            .child(div().text_lg().child("Snapshots"))
+            .child(div().w_64().child(Input::new(&self.note_input_state).small()))
+            .child(Checkbox::new("include_worlds").label("Include worlds").checked(self.include_worlds).on_click(cx.listener(|page, value, _, cx| {
+                page.include_worlds = *value;
+                cx.notify();
+            })))
+            .child(Button::new("create_snapshot").label("Create snapshot").success().compact().small().on_click(cx.listener(|page, _, window, cx| {
+                page.create_snapshot(window, cx);
+            })));
+
+        let running = self.instance.read(cx).status != InstanceStatus::NotRunning;
+
+        v_flex()
+            .p_4()
+            .size_full()
+            .child(header)
+            .when(running, |this| {
+                this.child(div().ml_1().mb_1().text_sm().text_color(theme.muted_foreground).child("Snapshots can't be restored while the instance is running"))
+            })
+            .child(
+                div()
+                    .size_full()
+                    .border_1()
+                    .rounded(theme.radius)
+                    .border_color(theme.border)
+                    .child(self.snapshot_list.clone()),
+            )
+    }
+}
+
+pub struct SnapshotsListDelegate {
+    id: InstanceID,
+    backend_handle: BackendHandle,
+    running: bool,
+    snapshots: Vec<InstanceSnapshotSummary>,
+    searched: Vec<InstanceSnapshotSummary>,
+}
+
+impl ListDelegate for SnapshotsListDelegate {
+    type Item = ListItem;
+
+    fn items_count(&self, _section: usize, _cx: &App) -> usize {
+        self.searched.len()
+    }
+
+    fn render_item(&mut self, ix: IndexPath, _window: &mut Window, _cx: &mut Context<ListState<Self>>) -> Option<Self::Item> {
+        let summary = self.searched.get(ix.row)?;
+
+        let created = chrono::DateTime::from_timestamp_millis(summary.created)
+            .map(|date_time| date_time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+
+        let mut subtitle = crate::format_bytes(summary.size);
+        if summary.includes_worlds {
+            subtitle.push_str(", includes worlds");
+        }
+
+        let title = if summary.note.is_empty() {
+            SharedString::from(created)
+        } else {
+            SharedString::from(format!("{} - {}", created, summary.note))
+        };
+
+        let description = v_flex()
+            .child(title)
+            .child(div().text_color(Hsla { h: 0.0, s: 0.0, l: 0.5, a: 1.0 }).child(SharedString::from(subtitle)));
+
+        let restore_button = Button::new(("restore", ix.row))
+            .label("Restore")
+            .info()
+            .small()
+            .compact()
+            .disabled(self.running)
+            .on_click({
+                let id = self.id;
+                let snapshot = summary.id.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_restore_snapshot(id, snapshot.clone(), backend_handle.clone(), window, cx);
+                }
+            });
+
+        let delete_button = Button::new(("delete", ix.row))
+            .label("Delete")
+            .danger()
+            .small()
+            .compact()
+            .on_click({
+                let id = self.id;
+                let snapshot = summary.id.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_delete_snapshot(id, snapshot.clone(), backend_handle.clone(), window, cx);
+                }
+            });
+
+        let item = ListItem::new(ix).p_1().child(
+            h_flex()
+                .gap_2()
+                .px_2()
+                .child(description)
+                .child(div().flex_1())
+                .child(restore_button)
+                .child(delete_button),
+        );
+
+        Some(item)
+    }
+
+    fn set_selected_index(&mut self, _ix: Option<IndexPath>, _window: &mut Window, _cx: &mut Context<ListState<Self>>) {
+    }
+
+    fn perform_search(&mut self, query: &str, _window: &mut Window, _cx: &mut Context<ListState<Self>>) -> Task<()> {
+        self.searched = self.snapshots.iter().filter(|s| s.note.contains(query) || s.id.contains(query)).cloned().collect();
+
+        Task::ready(())
+    }
+}
+
+fn open_restore_snapshot(
+    id: InstanceID,
+    snapshot: std::sync::Arc<str>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let message = SharedString::new(format!(
+        "This will replace the mods, config files and instance settings with the contents of the snapshot '{}'. Changes made since the snapshot was taken will be lost",
+        snapshot
+    ));
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let content = v_flex()
+            .gap_2()
+            .child(message.clone())
+            .child(Button::new("confirm").label("Restore snapshot").danger().on_click({
+                let backend_handle = backend_handle.clone();
+                let snapshot = snapshot.clone();
+                move |_, window, cx| {
+                    window.close_all_dialogs(cx);
+
+                    let modal_action = ModalAction::default();
+                    backend_handle.send(MessageToBackend::RestoreInstanceSnapshot {
+                        id,
+                        snapshot: snapshot.clone(),
+                        modal_action: modal_action.clone(),
+                    });
+                    modals::generic::show_modal(window, cx, "Restoring snapshot".into(), "Error restoring snapshot".into(), modal_action);
+                }
+            }));
+
+        dialog.title("Restore Snapshot").child(content)
+    });
+}
+
+fn open_delete_snapshot(
+    id: InstanceID,
+    snapshot: std::sync::Arc<str>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let message = SharedString::new(format!(
+        "The snapshot '{}' will be deleted permanently and can't be restored afterwards",
+        snapshot
+    ));
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let content = v_flex()
+            .gap_2()
+            .child(message.clone())
+            .child(Button::new("confirm").label("Delete snapshot").danger().on_click({
+                let backend_handle = backend_handle.clone();
+                let snapshot = snapshot.clone();
+                move |_, window, cx| {
+                    window.close_all_dialogs(cx);
+                    backend_handle.send(MessageToBackend::DeleteInstanceSnapshot {
+                        id,
+                        snapshot: snapshot.clone(),
+                    });
+                }
+            }));
+
+        dialog.title("Delete Snapshot").child(content)
+    });
+}
@@ -0,0 +1,651 @@
+use std::{
+    path::Path,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use bridge::{
+    handle::BackendHandle,
+    instance::{InstanceID, InstanceStatus, InstanceWorldBackupSummary, InstanceWorldSummary},
+    message::{AtomicBridgeDataLoadState, MessageToBackend},
+    modal_action::ModalAction,
+    serial::AtomicOptionSerial,
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _, Disableable, IndexPath, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    checkbox::Checkbox,
+    h_flex,
+    input::{Input, InputState},
+    list::{ListDelegate, ListItem, ListState},
+    select::{SearchableVec, Select, SelectState},
+    v_flex,
+};
+use ustr::Ustr;
+
+use crate::{
+    entity::{DataEntities, instance::{InstanceEntries, InstanceEntry}},
+    modals, png_render_cache,
+};
+
+pub struct InstanceWorldsSubpage {
+    instance: Entity<InstanceEntry>,
+    instance_id: InstanceID,
+    backend_handle: BackendHandle,
+    worlds_state: Arc<AtomicBridgeDataLoadState>,
+    worlds_serial: AtomicOptionSerial,
+    world_list: Entity<ListState<WorldsListDelegate>>,
+    backup_list: Entity<ListState<WorldBackupsListDelegate>>,
+    _observe_instance_subscription: Subscription,
+    _get_data_version_task: Task<()>,
+}
+
+impl InstanceWorldsSubpage {
+    pub fn new(
+        instance: &Entity<InstanceEntry>,
+        data: &DataEntities,
+        backend_handle: BackendHandle,
+        window: &mut gpui::Window,
+        cx: &mut gpui::Context<Self>,
+    ) -> Self {
+        let entry = instance.read(cx);
+        let instance_id = entry.id;
+        let running = entry.status != InstanceStatus::NotRunning;
+        let minecraft_version = entry.configuration.minecraft_version;
+        let worlds_state = Arc::clone(&entry.worlds_state);
+        let worlds = entry.worlds.clone();
+        let world_backups = entry.world_backups.clone();
+
+        let worlds_list_delegate = WorldsListDelegate {
+            id: instance_id,
+            backend_handle: backend_handle.clone(),
+            instances: data.instances.clone(),
+            running,
+            minecraft_version,
+            data_version: None,
+            worlds: worlds.read(cx).to_vec(),
+            searched: worlds.read(cx).to_vec(),
+        };
+
+        let backups_list_delegate = WorldBackupsListDelegate {
+            id: instance_id,
+            backend_handle: backend_handle.clone(),
+            running,
+            backups: world_backups.read(cx).to_vec(),
+            searched: world_backups.read(cx).to_vec(),
+        };
+
+        let world_list = cx.new(|cx| {
+            cx.observe(&worlds, |list: &mut ListState<WorldsListDelegate>, worlds, cx| {
+                let worlds = worlds.read(cx).to_vec();
+                let delegate = list.delegate_mut();
+                delegate.worlds = worlds.clone();
+                delegate.searched = worlds;
+                cx.notify();
+            }).detach();
+
+            ListState::new(worlds_list_delegate, window, cx).selectable(false).searchable(true)
+        });
+
+        let backup_list = cx.new(|cx| {
+            cx.observe(&world_backups, |list: &mut ListState<WorldBackupsListDelegate>, backups, cx| {
+                let backups = backups.read(cx).to_vec();
+                let delegate = list.delegate_mut();
+                delegate.backups = backups.clone();
+                delegate.searched = backups;
+                cx.notify();
+            }).detach();
+
+            ListState::new(backups_list_delegate, window, cx).selectable(false).searchable(true)
+        });
+
+        let _observe_instance_subscription = cx.observe(instance, |page, instance, cx| {
+            let instance = instance.read(cx);
+            let running = instance.status != InstanceStatus::NotRunning;
+            let minecraft_version = instance.configuration.minecraft_version;
+            let changed = page.world_list.update(cx, |list, cx| {
+                let delegate = list.delegate_mut();
+                let changed = delegate.running != running || delegate.minecraft_version != minecraft_version;
+                delegate.running = running;
+                delegate.minecraft_version = minecraft_version;
+                cx.notify();
+                changed
+            });
+            // The client is only downloaded when launching, so its data version may be known now
+            if changed {
+                page.load_data_version(minecraft_version, cx);
+            }
+            page.backup_list.update(cx, |list, cx| {
+                list.delegate_mut().running = running;
+                cx.notify();
+            });
+            cx.notify();
+        });
+
+        backend_handle.send(MessageToBackend::RequestLoadWorldBackups { id: instance_id });
+
+        let mut page = Self {
+            instance: instance.clone(),
+            instance_id,
+            backend_handle,
+            worlds_state,
+            worlds_serial: AtomicOptionSerial::default(),
+            world_list,
+            backup_list,
+            _observe_instance_subscription,
+            _get_data_version_task: Task::ready(()),
+        };
+
+        page.load_data_version(minecraft_version, cx);
+
+        page
+    }
+
+    fn load_data_version(&mut self, minecraft_version: Ustr, cx: &mut Context<Self>) {
+        let (send, recv) = tokio::sync::oneshot::channel();
+        self.backend_handle.send(MessageToBackend::GetDataVersion {
+            minecraft_version,
+            channel: send,
+        });
+
+        self._get_data_version_task = cx.spawn(async move |page, cx| {
+            let Ok(data_version) = recv.await else {
+                return;
+            };
+            _ = page.update(cx, |page, cx| {
+                page.world_list.update(cx, |list, cx| {
+                    list.delegate_mut().data_version = data_version;
+                    cx.notify();
+                });
+            });
+        });
+    }
+}
+
+impl Render for InstanceWorldsSubpage {
+    fn render(&mut self, _window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> impl gpui::IntoElement {
+        let theme = cx.theme();
+
+        let state = self.worlds_state.load(Ordering::SeqCst);
+        if state.should_send_load_request() {
+            self.backend_handle.send_with_serial(MessageToBackend::RequestLoadWorlds { id: self.instance_id }, &self.worlds_serial);
+        }
+
+        let running = self.instance.read(cx).status != InstanceStatus::NotRunning;
+
+        let worlds_header = div().mb_1().ml_1().text_lg().child("Worlds");
+        let backups_header = div().mb_1().ml_1().text_lg().child("Backups");
+
+        v_flex()
+            .p_4()
+            .gap_1()
+            .size_full()
+            .when(running, |this| {
+                this.child(div().ml_1().text_sm().text_color(theme.muted_foreground).child("Worlds can't be modified while the instance is running"))
+            })
+            .child(h_flex()
+                .size_full()
+                .gap_4()
+                .child(v_flex().size_full().child(worlds_header).child(
+                    div()
+                        .size_full()
+                        .border_1()
+                        .rounded(theme.radius)
+                        .border_color(theme.border)
+                        .child(self.world_list.clone()),
+                ))
+                .child(v_flex().size_full().child(backups_header).child(
+                    div()
+                        .size_full()
+                        .border_1()
+                        .rounded(theme.radius)
+                        .border_color(theme.border)
+                        .child(self.backup_list.clone()),
+                )))
+    }
+}
+
+pub struct WorldsListDelegate {
+    id: InstanceID,
+    backend_handle: BackendHandle,
+    instances: Entity<InstanceEntries>,
+    running: bool,
+    minecraft_version: Ustr,
+    data_version: Option<i32>,
+    worlds: Vec<InstanceWorldSummary>,
+    searched: Vec<InstanceWorldSummary>,
+}
+
+impl ListDelegate for WorldsListDelegate {
+    type Item = ListItem;
+
+    fn items_count(&self, _section: usize, _cx: &App) -> usize {
+        self.searched.len()
+    }
+
+    fn render_item(&mut self, ix: IndexPath, _window: &mut Window, cx: &mut Context<ListState<Self>>) -> Option<Self::Item> {
+        let summary = self.searched.get(ix.row)?;
+        let world: Arc<str> = summary.level_path.file_name()?.to_str()?.into();
+
+        let icon = if let Some(png_icon) = summary.png_icon.as_ref() {
+            png_render_cache::render(Arc::clone(png_icon), cx)
+        } else {
+            gpui::img(ImageSource::Resource(Resource::Embedded("images/default_world.png".into())))
+        };
+
+        let theme = cx.theme();
+        let details = &summary.details;
+
+        let size = details.size.map(crate::format_bytes);
+        let mut tags = Vec::new();
+        if let Some(game_mode) = details.game_mode {
+            tags.push(game_mode.name());
+        }
+        if details.hardcore {
+            tags.push("Hardcore");
+        }
+        if let Some(difficulty) = details.difficulty {
+            tags.push(difficulty.name());
+        }
+        if details.allow_commands {
+            tags.push("Cheats");
+        }
+        if let Some(size) = &size {
+            tags.push(size.as_str());
+        }
+
+        // Opening a world in an older version than it was last played in can corrupt it, upgrading is fine
+        let version = details.game_version.as_ref().map(|game_version| {
+            let downgrade = match (details.data_version, self.data_version) {
+                (Some(world_data_version), Some(data_version)) => world_data_version > data_version,
+                _ => **game_version != *self.minecraft_version.as_str(),
+            };
+            let color = if downgrade {
+                theme.warning
+            } else {
+                theme.muted_foreground
+            };
+            let text = match details.data_version {
+                Some(data_version) => format!("{} (data version {})", game_version, data_version),
+                None => game_version.to_string(),
+            };
+            div().text_color(color).child(SharedString::from(text))
+        });
+
+        let mut extra = Vec::new();
+        if let Some(seed) = details.seed {
+            extra.push(format!("Seed: {}", seed));
+        }
+        if !details.enabled_data_packs.is_empty() {
+            extra.push(format!("Data packs: {}", details.enabled_data_packs.join(", ")));
+        }
+
+        let description = v_flex()
+            .min_w_0()
+            .child(SharedString::from(summary.title.clone()))
+            .child(div().text_color(Hsla { h: 0.0, s: 0.0, l: 0.5, a: 1.0 }).child(SharedString::from(summary.subtitle.clone())))
+            .child(h_flex()
+                .gap_2()
+                .text_sm()
+                .text_color(theme.muted_foreground)
+                .child(SharedString::from(tags.join(" · ")))
+                .children(version))
+            .when(!extra.is_empty(), |this| {
+                this.child(div().text_sm().text_color(theme.muted_foreground).truncate().child(SharedString::from(extra.join(" · "))))
+            });
+
+        let id = self.id;
+
+        let backup_button = Button::new(("backup", ix.row))
+            .label("Backup")
+            .success()
+            .small()
+            .compact()
+            .disabled(self.running)
+            .on_click({
+                let world = world.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    let modal_action = ModalAction::default();
+                    backend_handle.send(MessageToBackend::BackupWorld {
+                        id,
+                        world: world.clone(),
+                        modal_action: modal_action.clone(),
+                    });
+                    modals::generic::show_modal(window, cx, "Backing up world".into(), "Error backing up world".into(), modal_action);
+                }
+            });
+
+        let rename_button = Button::new(("rename", ix.row))
+            .label("Rename")
+            .info()
+            .small()
+            .compact()
+            .disabled(self.running)
+            .on_click({
+                let world = world.clone();
+                let title = summary.title.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_rename_world(id, world.clone(), title.clone(), backend_handle.clone(), window, cx);
+                }
+            });
+
+        let copy_button = Button::new(("copy", ix.row))
+            .label("Copy to...")
+            .info()
+            .small()
+            .compact()
+            .disabled(self.running)
+            .on_click({
+                let world = world.clone();
+                let instances = self.instances.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_copy_world(id, world.clone(), &instances, backend_handle.clone(), window, cx);
+                }
+            });
+
+        let delete_button = Button::new(("delete", ix.row))
+            .label("Delete")
+            .danger()
+            .small()
+            .compact()
+            .disabled(self.running)
+            .on_click({
+                let title = summary.title.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_delete_world(id, world.clone(), title.clone(), backend_handle.clone(), window, cx);
+                }
+            });
+
+        let edit_button = Button::new(("edit", ix.row))
+            .label("Edit level.dat")
+            .info()
+            .small()
+            .compact()
+            .disabled(self.running)
+            .on_click({
+                let level_dat_path: Arc<Path> = summary.level_path.join("level.dat").into();
+                move |_, window, cx| {
+                    crate::root::open_nbt_file(level_dat_path.clone(), window, cx);
+                }
+            });
+
+        let item = ListItem::new(ix).p_1().child(
+            h_flex()
+                .gap_2()
+                .px_2()
+                .child(icon.size_12().min_w_12().min_h_12())
+                .child(description)
+                .child(div().flex_1())
+                .child(backup_button)
+                .child(rename_button)
+                .child(copy_button)
+                .child(edit_button)
+                .child(delete_button),
+        );
+
+        Some(item)
+    }
+
+    fn set_selected_index(&mut self, _ix: Option<IndexPath>, _window: &mut Window, _cx: &mut Context<ListState<Self>>) {
+    }
+
+    fn perform_search(&mut self, query: &str, _window: &mut Window, _cx: &mut Context<ListState<Self>>) -> Task<()> {
+        self.searched = self.worlds.iter().filter(|w| w.title.contains(query)).cloned().collect();
+
+        Task::ready(())
+    }
+}
+
+pub struct WorldBackupsListDelegate {
+    id: InstanceID,
+    backend_handle: BackendHandle,
+    running: bool,
+    backups: Vec<InstanceWorldBackupSummary>,
+    searched: Vec<InstanceWorldBackupSummary>,
+}
+
+impl ListDelegate for WorldBackupsListDelegate {
+    type Item = ListItem;
+
+    fn items_count(&self, _section: usize, _cx: &App) -> usize {
+        self.searched.len()
+    }
+
+    fn render_item(&mut self, ix: IndexPath, _window: &mut Window, _cx: &mut Context<ListState<Self>>) -> Option<Self::Item> {
+        let summary = self.searched.get(ix.row)?;
+
+        let created = chrono::DateTime::from_timestamp_millis(summary.created)
+            .map(|date_time| date_time.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+            .unwrap_or_default();
+
+        let description = v_flex()
+            .child(SharedString::from(summary.world.clone()))
+            .child(div().text_color(Hsla { h: 0.0, s: 0.0, l: 0.5, a: 1.0 })
+                .child(SharedString::from(format!("{}, {}", created, crate::format_bytes(summary.size)))));
+
+        let id = self.id;
+
+        let restore_button = Button::new(("restore", ix.row))
+            .label("Restore")
+            .info()
+            .small()
+            .compact()
+            .disabled(self.running)
+            .on_click({
+                let summary = summary.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, window, cx| {
+                    open_restore_world_backup(id, &summary, backend_handle.clone(), window, cx);
+                }
+            });
+
+        let delete_button = Button::new(("delete", ix.row))
+            .label("Delete")
+            .danger()
+            .small()
+            .compact()
+            .on_click({
+                let backup = summary.id.clone();
+                let backend_handle = self.backend_handle.clone();
+                move |_, _, _| {
+                    backend_handle.send(MessageToBackend::DeleteWorldBackup {
+                        id,
+                        backup: backup.clone(),
+                    });
+                }
+            });
+
+        let item = ListItem::new(ix).p_1().child(
+            h_flex()
+                .gap_2()
+                .px_2()
+                .child(description)
+                .child(div().flex_1())
+                .child(restore_button)
+                .child(delete_button),
+        );
+
+        Some(item)
+    }
+
+    fn set_selected_index(&mut self, _ix: Option<IndexPath>, _window: &mut Window, _cx: &mut Context<ListState<Self>>) {
+    }
+
+    fn perform_search(&mut self, query: &str, _window: &mut Window, _cx: &mut Context<ListState<Self>>) -> Task<()> {
+        self.searched = self.backups.iter().filter(|b| b.world.contains(query)).cloned().collect();
+
+        Task::ready(())
+    }
+}
+
+fn open_rename_world(
+    id: InstanceID,
+    world: Arc<str>,
+    title: Arc<str>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let input_state = cx.new(|cx| InputState::new(window, cx).default_value(title.to_string()));
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let content = v_flex()
+            .gap_2()
+            .child(Input::new(&input_state))
+            .child(Button::new("rename").label("Rename world").success().on_click({
+                let world = world.clone();
+                let input_state = input_state.clone();
+                let backend_handle = backend_handle.clone();
+                move |_, window, cx| {
+                    let name = input_state.read(cx).value();
+                    let name = name.trim();
+                    if name.is_empty() {
+                        return;
+                    }
+                    backend_handle.send(MessageToBackend::RenameWorld {
+                        id,
+                        world: world.clone(),
+                        name: name.into(),
+                    });
+                    window.close_all_dialogs(cx);
+                }
+            }));
+
+        dialog.title("Rename World").child(content)
+    });
+}
+
+fn open_delete_world(
+    id: InstanceID,
+    world: Arc<str>,
+    title: Arc<str>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let message = SharedString::new(format!("The world '{}' will be moved to the trash", title));
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let content = v_flex()
+            .gap_2()
+            .child(message.clone())
+            .child(Button::new("confirm").label("Delete world").danger().on_click({
+                let world = world.clone();
+                let backend_handle = backend_handle.clone();
+                move |_, window, cx| {
+                    backend_handle.send(MessageToBackend::DeleteWorld {
+                        id,
+                        world: world.clone(),
+                    });
+                    window.close_all_dialogs(cx);
+                }
+            }));
+
+        dialog.title("Delete World").child(content)
+    });
+}
+
+fn open_copy_world(
+    id: InstanceID,
+    world: Arc<str>,
+    instances: &Entity<InstanceEntries>,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let targets: Arc<[(SharedString, InstanceID)]> = instances.read(cx).entries.iter()
+        .filter(|(target, _)| **target != id)
+        .map(|(target, entry)| (entry.read(cx).name.clone(), *target))
+        .collect();
+
+    let target_select = cx.new(|cx| {
+        let items = targets.iter().map(|(name, _)| name.clone()).collect();
+        SelectState::new(SearchableVec::new(items), None, window, cx).searchable(true)
+    });
+
+    let move_world = Arc::new(AtomicBool::new(false));
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let content = v_flex()
+            .gap_2()
+            .child(crate::labelled("Instance", Select::new(&target_select).w_full()))
+            .child(Checkbox::new("move").label("Remove the world from this instance").checked(move_world.load(Ordering::Relaxed)).on_click({
+                let move_world = move_world.clone();
+                move |value, _, _| {
+                    move_world.store(*value, Ordering::Relaxed);
+                }
+            }))
+            .child(Button::new("confirm").label("Copy world").success().on_click({
+                let world = world.clone();
+                let targets = targets.clone();
+                let target_select = target_select.clone();
+                let move_world = move_world.clone();
+                let backend_handle = backend_handle.clone();
+                move |_, window, cx| {
+                    let Some(selected) = target_select.read(cx).selected_value() else {
+                        return;
+                    };
+                    let Some((_, target)) = targets.iter().find(|(name, _)| name == selected) else {
+                        return;
+                    };
+                    window.close_all_dialogs(cx);
+
+                    let modal_action = ModalAction::default();
+                    backend_handle.send(MessageToBackend::CopyWorld {
+                        id,
+                        world: world.clone(),
+                        target: *target,
+                        move_world: move_world.load(Ordering::Relaxed),
+                        modal_action: modal_action.clone(),
+                    });
+                    modals::generic::show_modal(window, cx, "Copying world".into(), "Error copying world".into(), modal_action);
+                }
+            }));
+
+        dialog.title("Copy World").child(content)
+    });
+}
+
+fn open_restore_world_backup(
+    id: InstanceID,
+    summary: &InstanceWorldBackupSummary,
+    backend_handle: BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    let message = SharedString::new(format!(
+        "This will replace the world '{}' with the backup. The current world will be moved to the trash",
+        summary.world
+    ));
+    let backup = summary.id.clone();
+
+    window.open_dialog(cx, move |dialog, _, _| {
+        let content = v_flex()
+            .gap_2()
+            .child(message.clone())
+            .child(Button::new("confirm").label("Restore backup").danger().on_click({
+                let backend_handle = backend_handle.clone();
+                let backup = backup.clone();
+                move |_, window, cx| {
+                    window.close_all_dialogs(cx);
+
+                    let modal_action = ModalAction::default();
+                    backend_handle.send(MessageToBackend::RestoreWorldBackup {
+                        id,
+                        backup: backup.clone(),
+                        modal_action: modal_action.clone(),
+                    });
+                    modals::generic::show_modal(window, cx, "Restoring world backup".into(), "Error restoring world backup".into(), modal_action);
+                }
+            }));
+
+        dialog.title("Restore World Backup").child(content)
+    });
+}
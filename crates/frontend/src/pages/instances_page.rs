@@ -3,16 +3,16 @@ use std::sync::{
     atomic::{AtomicBool, AtomicUsize, Ordering},
 };
 
-use bridge::{handle::BackendHandle, message::MessageToBackend};
+use bridge::{handle::BackendHandle, instance::InstanceID, message::MessageToBackend};
 use gpui::{prelude::*, *};
 use gpui_component::{
-    ActiveTheme as _, IconName, IndexPath, Selectable, WindowExt,
+    ActiveTheme as _, IconName, IndexPath, Selectable, Sizable, WindowExt,
     alert::Alert,
     button::{Button, ButtonGroup, ButtonVariants},
     checkbox::Checkbox,
     h_flex,
     input::{Input, InputEvent, InputState},
-    select::{Select, SelectDelegate, SelectItem, SelectState},
+    select::{SearchableVec, Select, SelectDelegate, SelectItem, SelectState},
     skeleton::Skeleton,
     table::{Table, TableState},
     v_flex,
@@ -20,13 +20,14 @@ use gpui_component::{
 use schema::{loader::Loader, version_manifest::{MinecraftVersionManifest, MinecraftVersionType}};
 
 use crate::{
-    component::{instance_list::InstanceList, page_path::PagePath},
+    component::{instance_list::{InstanceList, InstanceListSort}, page_path::PagePath},
     entity::{DataEntities, instance::InstanceEntries, metadata::{AsMetadataResult, FrontendMetadata, FrontendMetadataResult}},
-    ui,
+    interface_config::InterfaceConfig, ui,
 };
 
 pub struct InstancesPage {
     instance_table: Entity<TableState<InstanceList>>,
+    filter_input_state: Entity<InputState>,
 
     metadata: Entity<FrontendMetadata>,
     instances: Entity<InstanceEntries>,
@@ -38,8 +39,20 @@ impl InstancesPage {
     pub fn new(data: &DataEntities, window: &mut Window, cx: &mut Context<Self>) -> Self {
         let instance_table = InstanceList::create_table(data, window, cx);
 
+        let filter_input_state = cx.new(|cx| InputState::new(window, cx).placeholder("Filter by name, version, loader or tag"));
+        cx.subscribe(&filter_input_state, |page, state, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                let filter = state.read(cx).value();
+                page.instance_table.update(cx, |table, cx| {
+                    table.delegate_mut().set_filter(filter, cx);
+                    cx.notify();
+                });
+            }
+        }).detach();
+
         Self {
             instance_table,
+            filter_input_state,
             metadata: data.metadata.clone(),
             instances: data.instances.clone(),
             backend_handle: data.backend_handle.clone(),
@@ -57,11 +70,100 @@ impl Render for InstancesPage {
                 this.show_create_instance_modal(window, cx);
             }));
 
-        ui::page(cx, h_flex().gap_8().child("Instances").child(create_instance))
+        let create_group = Button::new("create_group")
+            .info()
+            .icon(IconName::Plus)
+            .label("Create Group")
+            .on_click(cx.listener(|this, _, window, cx| {
+                this.show_create_group_modal(window, cx);
+            }));
+
+        let config = &InterfaceConfig::get(cx).instance_list;
+
+        let sort_buttons = InstanceListSort::ALL.into_iter().enumerate().fold(ButtonGroup::new("sort").outline().small(), |group, (index, sort)| {
+            group.child(Button::new(("sort", index)).label(sort.name()).selected(config.sort == sort))
+        }).on_click(cx.listener(|this, selected: &Vec<usize>, _, cx| {
+            let Some(sort) = selected.first().and_then(|index| InstanceListSort::ALL.get(*index)) else {
+                return;
+            };
+            InterfaceConfig::get_mut(cx).instance_list.sort = *sort;
+            this.rebuild_instance_table(cx);
+        }));
+
+        let sort_direction_icon = if config.sort_descending { IconName::ArrowUp } else { IconName::ArrowDown };
+        let sort_direction = Button::new("sort_direction").outline().small().icon(sort_direction_icon).on_click(cx.listener(|this, _, _, cx| {
+            let config = &mut InterfaceConfig::get_mut(cx).instance_list;
+            config.sort_descending = !config.sort_descending;
+            this.rebuild_instance_table(cx);
+        }));
+
+        let tag_filter = config.tag_filter.clone().map(|tag| {
+            Button::new("clear_tag_filter").outline().small().icon(IconName::Close).label(format!("Tag: {}", tag)).on_click(cx.listener(|this, _, _, cx| {
+                InterfaceConfig::get_mut(cx).instance_list.tag_filter = None;
+                this.rebuild_instance_table(cx);
+            }))
+        });
+
+        let toolbar = h_flex()
+            .gap_2()
+            .px_4()
+            .pb_2()
+            .child(div().w_64().child(Input::new(&self.filter_input_state).small()))
+            .child(sort_buttons)
+            .child(sort_direction)
+            .children(tag_filter);
+
+        ui::page(cx, h_flex().gap_8().child("Instances").child(h_flex().gap_3().child(create_instance).child(create_group)))
+            .child(toolbar)
             .child(Table::new(&self.instance_table).bordered(false))
     }
 }
 
+impl InstancesPage {
+    fn rebuild_instance_table(&mut self, cx: &mut Context<Self>) {
+        self.instance_table.update(cx, |table, cx| {
+            table.delegate_mut().rebuild_rows(cx);
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    pub fn show_create_group_modal(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name_input_state = cx.new(|cx| InputState::new(window, cx).placeholder("Group name"));
+        let instance_table = self.instance_table.clone();
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            let content = v_flex()
+                .gap_2()
+                .child(Input::new(&name_input_state))
+                .child(Button::new("create").success().label("Create Group").on_click({
+                    let name_input_state = name_input_state.clone();
+                    let instance_table = instance_table.clone();
+                    move |_, window, cx| {
+                        let name = name_input_state.read(cx).value();
+                        let name = name.trim();
+                        if name.is_empty() {
+                            return;
+                        }
+
+                        let config = &mut InterfaceConfig::get_mut(cx).instance_list;
+                        if !config.groups.iter().any(|group| group.as_str() == name) {
+                            config.groups.push(SharedString::new(name));
+                        }
+
+                        instance_table.update(cx, |table, cx| {
+                            table.delegate_mut().rebuild_rows(cx);
+                            cx.notify();
+                        });
+                        window.close_all_dialogs(cx);
+                    }
+                }));
+
+            dialog.title("Create Group").child(content)
+        });
+    }
+}
+
 #[derive(Default)]
 pub struct VersionList {
     pub versions: Vec<SharedString>,
@@ -121,6 +223,17 @@ impl InstancesPage {
         let minecraft_version_dropdown =
             cx.new(|cx| SelectState::new(VersionList::default(), None, window, cx).searchable(true));
 
+        let no_template = SharedString::new_static("None");
+        let templates: Arc<[(SharedString, InstanceID)]> =
+            self.instances.read(cx).entries.iter().map(|(id, v)| (v.read(cx).name.clone(), *id)).collect();
+        let template_dropdown = cx.new(|cx| {
+            let mut items = vec![no_template.clone()];
+            items.extend(templates.iter().map(|(name, _)| name.clone()));
+            let mut select_state = SelectState::new(SearchableVec::new(items), None, window, cx).searchable(true);
+            select_state.set_selected_value(&no_template, window, cx);
+            select_state
+        });
+
         let unnamed_instance_name = SharedString::new_static("Unnamed Instance");
 
         let name_input_state = cx.new(|cx| InputState::new(window, cx).placeholder(unnamed_instance_name.clone()));
@@ -380,9 +493,12 @@ impl InstancesPage {
                     Input::new(&name_input_state).when(name_is_invalid, |this| this.border_color(cx.theme().danger)),
                 ))
                 .child(crate::labelled("Version", v_flex().gap_2().child(version_dropdown).child(show_snapshots_button)))
-                .child(crate::labelled("Modloader", loader_button_group));
+                .child(crate::labelled("Modloader", loader_button_group))
+                .child(crate::labelled("Copy settings, config and mods from", Select::new(&template_dropdown).w_full()));
 
             let text_input_state = name_input_state.clone();
+            let template_dropdown = template_dropdown.clone();
+            let templates = Arc::clone(&templates);
             let backend_handle = backend_handle.clone();
             let fallback_name_info = Arc::clone(&fallback_name_info);
 
@@ -413,10 +529,15 @@ impl InstancesPage {
                         name = fallback_name_info.actual.clone();
                     }
 
+                    let template = template_dropdown.read(cx).selected_value().and_then(|selected| {
+                        templates.iter().find(|(name, _)| name == selected).map(|(_, id)| *id)
+                    });
+
                     backend_handle.send(MessageToBackend::CreateInstance {
                         name: name.as_str().into(),
                         version: selected_version.as_str().into(),
                         loader: selected_loader_value,
+                        template,
                     });
 
                     true
@@ -1,4 +1,8 @@
+pub mod content_library_page;
+pub mod curseforge_page;
 pub mod instance;
 pub mod instances_page;
 pub mod modrinth_page;
+pub mod nbt_editor_page;
+pub mod screenshots_page;
 pub mod syncing_page;
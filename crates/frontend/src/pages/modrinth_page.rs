@@ -42,6 +42,7 @@ pub struct ModrinthSearchPage {
 struct InstalledMod {
     mod_id: InstanceContentID,
     status: Arc<AtomicContentUpdateStatus>,
+    pinned: bool,
 }
 
 impl ModrinthSearchPage {
@@ -66,6 +67,7 @@ impl ModrinthSearchPage {
                     installed.push(InstalledMod {
                         mod_id: summary.id,
                         status: summary.content_summary.update_status.clone(),
+                        pinned: summary.pinned.is_some(),
                     })
                 }
             }
@@ -145,6 +147,7 @@ impl ModrinthSearchPage {
                 ModrinthProjectType::Modpack => "Search modpacks...",
                 ModrinthProjectType::Resourcepack => "Search resourcepacks...",
                 ModrinthProjectType::Shader => "Search shaders...",
+                ModrinthProjectType::Datapack => "Search datapacks...",
                 ModrinthProjectType::Other => "Search...",
             };
             state.set_placeholder(placeholder, window, cx)
@@ -204,6 +207,7 @@ impl ModrinthSearchPage {
             ModrinthProjectType::Modpack => "modpack",
             ModrinthProjectType::Resourcepack => "resourcepack",
             ModrinthProjectType::Shader => "shader",
+            ModrinthProjectType::Datapack => "datapack",
         };
 
         let offset = if self.pending_clear { 0 } else { self.hits.len() };
@@ -295,8 +299,13 @@ impl ModrinthSearchPage {
             self._delayed_clear_task = Task::ready(());
         }
 
+        let filter_project_type = self.filter_project_type;
         self.hits.extend(search_result.hits.iter().map(|hit| {
             let mut hit = hit.clone();
+            // Modrinth reports datapacks as mods in search results
+            if filter_project_type == ModrinthProjectType::Datapack {
+                hit.project_type = ModrinthProjectType::Datapack;
+            }
             if let Some(description) = hit.description {
                 hit.description = Some(description.replace("\n", " ").into());
             }
@@ -543,6 +552,11 @@ impl ModrinthSearchPage {
     }
 
     fn get_primary_action(&self, project_id: &str, cx: &App) -> PrimaryAction {
+        // Datapacks are installed into a world, which always needs to be chosen
+        if self.filter_project_type == ModrinthProjectType::Datapack {
+            return PrimaryAction::Install;
+        }
+
         let install_latest = self.can_install_latest && !InterfaceConfig::get(cx).modrinth_install_normally;
 
         let installed = self.installed_mods_by_project.get(project_id);
@@ -554,14 +568,20 @@ impl ModrinthSearchPage {
 
             let mut action = PrimaryAction::CheckForUpdates;
             for installed_mod in installed {
-                match installed_mod.status.load(std::sync::atomic::Ordering::Relaxed) {
+                // Pinned mods are kept at their current version, so they count as up-to-date
+                let status = if installed_mod.pinned {
+                    ContentUpdateStatus::AlreadyUpToDate
+                } else {
+                    installed_mod.status.load(std::sync::atomic::Ordering::Relaxed)
+                };
+                match status {
                     ContentUpdateStatus::Unknown => {},
                     ContentUpdateStatus::AlreadyUpToDate => {
                         if !matches!(action, PrimaryAction::Update(..)) {
                             action = PrimaryAction::UpToDate;
                         }
                     },
-                    ContentUpdateStatus::Modrinth => {
+                    ContentUpdateStatus::Modrinth | ContentUpdateStatus::Curseforge | ContentUpdateStatus::GithubRelease => {
                         if let PrimaryAction::Update(vec) = &mut action {
                             vec.push(installed_mod.mod_id);
                         } else {
@@ -709,11 +729,13 @@ impl Render for ModrinthSearchPage {
                     .selected(self.filter_project_type == ModrinthProjectType::Resourcepack),
             )
             .child(Button::new("shaders").label("Shaders").selected(self.filter_project_type == ModrinthProjectType::Shader))
+            .child(Button::new("datapacks").label("Datapacks").selected(self.filter_project_type == ModrinthProjectType::Datapack))
             .on_click(cx.listener(|page, clicked: &Vec<usize>, window, cx| match clicked[0] {
                 0 => page.set_project_type(ModrinthProjectType::Mod, window, cx),
                 1 => page.set_project_type(ModrinthProjectType::Modpack, window, cx),
                 2 => page.set_project_type(ModrinthProjectType::Resourcepack, window, cx),
                 3 => page.set_project_type(ModrinthProjectType::Shader, window, cx),
+                4 => page.set_project_type(ModrinthProjectType::Datapack, window, cx),
                 _ => {},
             }));
 
@@ -742,6 +764,7 @@ impl Render for ModrinthSearchPage {
             ModrinthProjectType::Modpack => FILTER_MODPACK_CATEGORIES,
             ModrinthProjectType::Resourcepack => FILTER_RESOURCEPACK_CATEGORIES,
             ModrinthProjectType::Shader => FILTER_SHADERPACK_CATEGORIES,
+            ModrinthProjectType::Datapack => FILTER_MOD_CATEGORIES,
             ModrinthProjectType::Other => &[],
         };
 
@@ -785,7 +808,7 @@ impl Render for ModrinthSearchPage {
     }
 }
 
-fn format_downloads(downloads: usize) -> String {
+pub(crate) fn format_downloads(downloads: usize) -> String {
     if downloads >= 1_000_000_000 {
         format!("{}B Downloads", (downloads / 10_000_000) as f64 / 100.0)
     } else if downloads >= 1_000_000 {
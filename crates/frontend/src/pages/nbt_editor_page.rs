@@ -0,0 +1,729 @@
+use std::{ops::Range, path::Path, str::FromStr, sync::Arc};
+
+use bridge::{
+    handle::BackendHandle, message::MessageToBackend, modal_action::ModalAction, nbt_file::NbtFile,
+};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _, Disableable, Icon, IconName, IndexPath, Sizable, WindowExt,
+    button::{Button, ButtonVariants},
+    h_flex,
+    input::{Input, InputState},
+    notification::NotificationType,
+    scroll::{ScrollableElement, Scrollbar},
+    select::{SearchableVec, Select, SelectState},
+    v_flex,
+};
+use nbt::{NBTRef, NBTRefMut, TagType};
+use rustc_hash::FxHashSet;
+
+use crate::{entity::DataEntities, modals, ui};
+
+const TAG_TYPES: [TagType; 12] = [
+    nbt::TAG_BYTE_ID,
+    nbt::TAG_SHORT_ID,
+    nbt::TAG_INT_ID,
+    nbt::TAG_LONG_ID,
+    nbt::TAG_FLOAT_ID,
+    nbt::TAG_DOUBLE_ID,
+    nbt::TAG_STRING_ID,
+    nbt::TAG_BYTE_ARRAY_ID,
+    nbt::TAG_INT_ARRAY_ID,
+    nbt::TAG_LONG_ARRAY_ID,
+    nbt::TAG_COMPOUND_ID,
+    nbt::TAG_LIST_ID,
+];
+
+const MAX_VALUE_PREVIEW_LEN: usize = 120;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum NbtPathSegment {
+    Key(Arc<str>),
+    Index(usize),
+}
+
+struct NbtRow {
+    path: Vec<NbtPathSegment>,
+    depth: usize,
+    label: SharedString,
+    tag_type: TagType,
+    value: SharedString,
+    container: bool,
+}
+
+pub struct NbtEditorPage {
+    backend_handle: BackendHandle,
+    path: Option<Arc<Path>>,
+    file: Option<NbtFile>,
+    modified: bool,
+    revision: usize,
+    backed_up: bool,
+    expanded: FxHashSet<Vec<NbtPathSegment>>,
+    rows: Vec<NbtRow>,
+    snbt_input: Option<Entity<InputState>>,
+    scroll_handle: UniformListScrollHandle,
+    _select_file_task: Task<()>,
+    _open_task: Task<()>,
+    _save_task: Task<()>,
+}
+
+impl NbtEditorPage {
+    pub fn new(data: &DataEntities, _window: &mut Window, _cx: &mut Context<Self>) -> Self {
+        Self {
+            backend_handle: data.backend_handle.clone(),
+            path: None,
+            file: None,
+            modified: false,
+            revision: 0,
+            backed_up: false,
+            expanded: FxHashSet::default(),
+            rows: Vec::new(),
+            snbt_input: None,
+            scroll_handle: UniformListScrollHandle::new(),
+            _select_file_task: Task::ready(()),
+            _open_task: Task::ready(()),
+            _save_task: Task::ready(()),
+        }
+    }
+
+    pub fn open(&mut self, path: Arc<Path>, window: &mut Window, cx: &mut Context<Self>) {
+        if !self.modified {
+            self.load(path, window, cx);
+            return;
+        }
+
+        let message = SharedString::new(format!(
+            "Unsaved changes to {} will be lost",
+            self.path.as_ref().map(|path| path.to_string_lossy().into_owned()).unwrap_or_default()
+        ));
+
+        let page = cx.entity();
+        window.open_dialog(cx, move |dialog, _, _| {
+            let content = v_flex()
+                .gap_2()
+                .child(message.clone())
+                .child(Button::new("confirm").label("Discard changes").danger().on_click({
+                    let page = page.clone();
+                    let path = path.clone();
+                    move |_, window, cx| {
+                        window.close_all_dialogs(cx);
+                        page.update(cx, |page, cx| page.load(path.clone(), window, cx));
+                    }
+                }));
+
+            dialog.title("Unsaved Changes").child(content)
+        });
+    }
+
+    fn load(&mut self, path: Arc<Path>, window: &mut Window, cx: &mut Context<Self>) {
+        let (send, recv) = tokio::sync::oneshot::channel();
+        let modal_action = ModalAction::default();
+
+        self.backend_handle.send(MessageToBackend::ReadNbtFile {
+            path: path.clone(),
+            modal_action: modal_action.clone(),
+            channel: send,
+        });
+
+        modals::generic::show_modal(window, cx, "Opening NBT file".into(), "Error opening NBT file".into(), modal_action);
+
+        let this_entity = cx.entity();
+        self._open_task = window.spawn(cx, async move |cx| {
+            let Ok(file) = recv.await else {
+                return;
+            };
+            _ = cx.update_window_entity(&this_entity, move |this, window, cx| {
+                window.close_all_dialogs(cx);
+
+                this.path = Some(path);
+                this.file = Some(file);
+                this.modified = false;
+                this.backed_up = false;
+                this._save_task = Task::ready(());
+                this.snbt_input = None;
+                this.expanded.clear();
+                this.expanded.insert(Vec::new());
+                this.rebuild_rows();
+                cx.notify();
+            });
+        });
+    }
+
+    fn select_file(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let receiver = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+            prompt: Some("Open NBT file".into())
+        });
+
+        let this_entity = cx.entity();
+        self._select_file_task = window.spawn(cx, async move |cx| {
+            let Ok(Ok(Some(paths))) = receiver.await else {
+                return;
+            };
+            let Some(path) = paths.into_iter().next() else {
+                return;
+            };
+            _ = cx.update_window_entity(&this_entity, move |this, window, cx| {
+                this.open(path.into(), window, cx);
+            });
+        });
+    }
+
+    fn save(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.snbt_input.is_some() && !self.apply_snbt(window, cx) {
+            return;
+        }
+
+        let (Some(path), Some(file)) = (&self.path, &self.file) else {
+            return;
+        };
+
+        let (send, recv) = tokio::sync::oneshot::channel();
+        let modal_action = ModalAction::default();
+        self.backend_handle.send(MessageToBackend::WriteNbtFile {
+            path: path.clone(),
+            file: file.clone(),
+            backup: !self.backed_up,
+            modal_action: modal_action.clone(),
+            channel: send,
+        });
+        modals::generic::show_modal(window, cx, "Saving NBT file".into(), "Error saving NBT file".into(), modal_action);
+
+        // Changes are only marked as saved once the file has actually been written
+        let revision = self.revision;
+        self._save_task = cx.spawn(async move |page, cx| {
+            let Ok(()) = recv.await else {
+                return;
+            };
+            _ = page.update(cx, |page, cx| {
+                page.backed_up = true;
+                if page.revision == revision {
+                    page.modified = false;
+                }
+                cx.notify();
+            });
+        });
+    }
+
+    fn toggle_snbt(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.snbt_input.is_some() {
+            if self.apply_snbt(window, cx) {
+                self.snbt_input = None;
+            }
+        } else if let Some(file) = &self.file {
+            let snbt = nbt::stringified::to_snbt_string(&file.nbt);
+            self.snbt_input = Some(cx.new(|cx| InputState::new(window, cx).auto_grow(8, 40).default_value(snbt)));
+        }
+        cx.notify();
+    }
+
+    fn apply_snbt(&mut self, window: &mut Window, cx: &mut Context<Self>) -> bool {
+        let (Some(input), Some(file)) = (&self.snbt_input, &mut self.file) else {
+            return false;
+        };
+
+        let snbt = input.read(cx).value();
+        match nbt::stringified::from_snbt(&snbt) {
+            Ok(mut nbt) => {
+                nbt.root_name = file.nbt.root_name.clone();
+                if nbt != file.nbt {
+                    file.nbt = nbt;
+                    self.modified = true;
+                    self.revision += 1;
+                    self.rebuild_rows();
+                }
+                true
+            },
+            Err(err) => {
+                window.push_notification((NotificationType::Error, SharedString::from(format!("Invalid SNBT: {err}"))), cx);
+                false
+            },
+        }
+    }
+
+    fn rebuild_rows(&mut self) {
+        self.rows.clear();
+        let Some(file) = &self.file else {
+            return;
+        };
+
+        let label = if file.nbt.root_name.is_empty() {
+            SharedString::new_static("(root)")
+        } else {
+            SharedString::from(file.nbt.root_name.clone())
+        };
+        push_rows(&mut self.rows, &self.expanded, file.nbt.as_reference(), label, Vec::new(), 0);
+    }
+
+    fn toggle_expanded(&mut self, path: Vec<NbtPathSegment>, cx: &mut Context<Self>) {
+        if !self.expanded.remove(&path) {
+            self.expanded.insert(path);
+        }
+        self.rebuild_rows();
+        cx.notify();
+    }
+
+    fn modify(&mut self, path: &[NbtPathSegment], modify: impl FnOnce(NBTRefMut<'_>) -> Result<(), String>) -> Result<(), String> {
+        let Some(file) = &mut self.file else {
+            return Err("No file is open".into());
+        };
+        with_node_mut(file.nbt.as_reference_mut(), path, modify).unwrap_or_else(|| Err("Tag no longer exists".into()))?;
+        self.modified = true;
+        self.revision += 1;
+        self.rebuild_rows();
+        Ok(())
+    }
+
+    fn remove(&mut self, path: &[NbtPathSegment], cx: &mut Context<Self>) {
+        let Some((last, parent)) = path.split_last() else {
+            return;
+        };
+
+        let result = self.modify(parent, |node| match (node, last) {
+            (NBTRefMut::Compound(mut compound), NbtPathSegment::Key(key)) => {
+                compound.remove(key);
+                Ok(())
+            },
+            (NBTRefMut::List(mut list), NbtPathSegment::Index(index)) => {
+                list.remove(*index);
+                Ok(())
+            },
+            _ => Err("Tag no longer exists".into()),
+        });
+
+        if result.is_ok() {
+            // Indices of the following list elements shift, so collapse everything below a list
+            let collapse_from = if matches!(last, NbtPathSegment::Index(_)) { parent } else { path };
+            self.expanded.retain(|expanded| expanded.len() <= collapse_from.len() || !expanded.starts_with(collapse_from));
+            self.rebuild_rows();
+        }
+        cx.notify();
+    }
+
+    fn open_edit_value(&mut self, path: Vec<NbtPathSegment>, label: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(file) = &self.file else {
+            return;
+        };
+        let Some(value) = with_node(file.nbt.as_reference(), &path, value_to_string) else {
+            return;
+        };
+
+        let input_state = cx.new(|cx| InputState::new(window, cx).auto_grow(1, 8).default_value(value));
+        let title = SharedString::from(format!("Edit {}", label));
+        let page = cx.entity();
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            let content = v_flex()
+                .gap_2()
+                .child(Input::new(&input_state))
+                .child(Button::new("save").label("Save").success().on_click({
+                    let page = page.clone();
+                    let input_state = input_state.clone();
+                    let path = path.clone();
+                    move |_, window, cx| {
+                        let value = input_state.read(cx).value();
+                        let result = page.update(cx, |page, cx| {
+                            let result = page.modify(&path, |node| set_value(node, &value));
+                            cx.notify();
+                            result
+                        });
+                        match result {
+                            Ok(()) => window.close_all_dialogs(cx),
+                            Err(err) => window.push_notification((NotificationType::Error, SharedString::from(err)), cx),
+                        }
+                    }
+                }));
+
+            dialog.title(title.clone()).child(content)
+        });
+    }
+
+    fn open_add_tag(&mut self, path: Vec<NbtPathSegment>, label: SharedString, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(file) = &self.file else {
+            return;
+        };
+        let parent = with_node(file.nbt.as_reference(), &path, |node| match node {
+            NBTRef::Compound(_) => Some((true, None)),
+            NBTRef::List(list) => Some((false, (!list.is_empty()).then(|| list.children_type()))),
+            _ => None,
+        }).flatten();
+        let Some((is_compound, list_type)) = parent else {
+            return;
+        };
+
+        let types: Vec<TagType> = match list_type {
+            Some(tag_type) => vec![tag_type],
+            None => TAG_TYPES.to_vec(),
+        };
+        let type_names: Vec<SharedString> = types.iter().map(|tag_type| SharedString::new_static(tag_type.name())).collect();
+        let type_select = cx.new(|cx| {
+            let mut select_state = SelectState::new(SearchableVec::new(type_names), None, window, cx);
+            select_state.set_selected_index(Some(IndexPath::default()), window, cx);
+            select_state
+        });
+        let name_input_state = cx.new(|cx| InputState::new(window, cx).placeholder("Name"));
+        let value_input_state = cx.new(|cx| InputState::new(window, cx).placeholder("Value, arrays are comma-separated"));
+        let title = SharedString::from(format!("Add tag to {}", label));
+        let page = cx.entity();
+
+        window.open_dialog(cx, move |dialog, _, _| {
+            let content = v_flex()
+                .gap_2()
+                .when(is_compound, |this| this.child(crate::labelled("Name", Input::new(&name_input_state))))
+                .child(crate::labelled("Type", Select::new(&type_select).w_full()))
+                .child(crate::labelled("Value", Input::new(&value_input_state)))
+                .child(Button::new("add").label("Add tag").success().on_click({
+                    let page = page.clone();
+                    let types = types.clone();
+                    let type_select = type_select.clone();
+                    let name_input_state = name_input_state.clone();
+                    let value_input_state = value_input_state.clone();
+                    let path = path.clone();
+                    move |_, window, cx| {
+                        let Some(selected) = type_select.read(cx).selected_value() else {
+                            return;
+                        };
+                        let Some(tag_type) = types.iter().find(|tag_type| tag_type.name() == &**selected).copied() else {
+                            return;
+                        };
+                        let name = name_input_state.read(cx).value();
+                        let value = value_input_state.read(cx).value();
+
+                        let result = page.update(cx, |page, cx| {
+                            let result = page.modify(&path, |node| insert_tag(node, name.trim(), tag_type, &value));
+                            if result.is_ok() {
+                                page.expanded.insert(path.clone());
+                                page.rebuild_rows();
+                            }
+                            cx.notify();
+                            result
+                        });
+                        match result {
+                            Ok(()) => window.close_all_dialogs(cx),
+                            Err(err) => window.push_notification((NotificationType::Error, SharedString::from(err)), cx),
+                        }
+                    }
+                }));
+
+            dialog.title(title.clone()).child(content)
+        });
+    }
+
+    fn render_rows(&mut self, visible_range: Range<usize>, _window: &mut Window, cx: &mut Context<Self>) -> Vec<Div> {
+        let trash_icon = Icon::default().path("icons/trash-2.svg");
+
+        visible_range.filter_map(|index| {
+            let row = self.rows.get(index)?;
+            let theme = cx.theme();
+
+            let toggle = if row.container {
+                let expanded = self.expanded.contains(&row.path);
+                let path = row.path.clone();
+                Button::new(("toggle", index))
+                    .ghost()
+                    .xsmall()
+                    .icon(if expanded { IconName::ChevronDown } else { IconName::ChevronRight })
+                    .on_click(cx.listener(move |page, _, _, cx| {
+                        page.toggle_expanded(path.clone(), cx);
+                    }))
+                    .into_any_element()
+            } else {
+                div().w_6().into_any_element()
+            };
+
+            let mut actions = h_flex().gap_1();
+            if row.container {
+                let path = row.path.clone();
+                let label = row.label.clone();
+                actions = actions.child(Button::new(("add", index)).xsmall().icon(IconName::Plus).on_click(cx.listener(move |page, _, window, cx| {
+                    page.open_add_tag(path.clone(), label.clone(), window, cx);
+                })));
+            } else {
+                let path = row.path.clone();
+                let label = row.label.clone();
+                actions = actions.child(Button::new(("edit", index)).xsmall().label("Edit").on_click(cx.listener(move |page, _, window, cx| {
+                    page.open_edit_value(path.clone(), label.clone(), window, cx);
+                })));
+            }
+            if !row.path.is_empty() {
+                let path = row.path.clone();
+                actions = actions.child(Button::new(("remove", index)).xsmall().danger().icon(trash_icon.clone()).on_click(cx.listener(move |page, _, _, cx| {
+                    page.remove(&path, cx);
+                })));
+            }
+
+            Some(h_flex()
+                .h_8()
+                .w_full()
+                .pl(px(16.0 * row.depth as f32))
+                .pr_3()
+                .gap_2()
+                .hover(|this| this.bg(theme.list_hover))
+                .child(toggle)
+                .child(div().flex_shrink_0().child(row.label.clone()))
+                .child(div().flex_shrink_0().text_sm().text_color(theme.muted_foreground).child(SharedString::new_static(row.tag_type.name())))
+                .child(div().flex_1().min_w_0().truncate().child(row.value.clone()))
+                .child(actions))
+        }).collect()
+    }
+}
+
+impl Render for NbtEditorPage {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_file = self.file.is_some();
+
+        let title = h_flex()
+            .gap_8()
+            .child("NBT Editor")
+            .child(h_flex()
+                .gap_2()
+                .child(Button::new("open").label("Open file").on_click(cx.listener(|page, _, window, cx| {
+                    page.select_file(window, cx);
+                })))
+                .child(Button::new("toggle-snbt")
+                    .label(if self.snbt_input.is_some() { "Tree view" } else { "SNBT view" })
+                    .disabled(!has_file)
+                    .on_click(cx.listener(|page, _, window, cx| {
+                        page.toggle_snbt(window, cx);
+                    })))
+                .child(Button::new("save")
+                    .label("Save")
+                    .success()
+                    .disabled(!has_file || (!self.modified && self.snbt_input.is_none()))
+                    .on_click(cx.listener(|page, _, window, cx| {
+                        page.save(window, cx);
+                    }))));
+
+        let (Some(path), Some(file)) = (&self.path, &self.file) else {
+            return ui::page(cx, title).child(v_flex()
+                .size_full()
+                .p_3()
+                .text_color(cx.theme().muted_foreground)
+                .child("Open a .dat or .nbt file to view and edit it. A .bak copy of the original is kept when saving"));
+        };
+
+        let info = h_flex()
+            .gap_2()
+            .px_3()
+            .pt_3()
+            .text_sm()
+            .text_color(cx.theme().muted_foreground)
+            .child(SharedString::from(path.to_string_lossy().into_owned()))
+            .child(SharedString::new_static(file.compression.name()))
+            .when(self.modified, |this| this.child(div().text_color(cx.theme().warning).child("Unsaved changes")));
+
+        let content = if let Some(snbt_input) = &self.snbt_input {
+            v_flex()
+                .size_full()
+                .p_3()
+                .child(Input::new(snbt_input))
+                .overflow_y_scrollbar()
+                .into_any_element()
+        } else {
+            let scroll_handle = self.scroll_handle.clone();
+            h_flex()
+                .size_full()
+                .min_h_0()
+                .p_3()
+                .child(uniform_list("nbt-rows", self.rows.len(), cx.processor(Self::render_rows))
+                    .size_full()
+                    .track_scroll(&scroll_handle))
+                .child(div().w_3().h_full().child(Scrollbar::vertical(&scroll_handle)))
+                .into_any_element()
+        };
+
+        ui::page(cx, title).child(info).child(content)
+    }
+}
+
+fn push_rows(
+    rows: &mut Vec<NbtRow>,
+    expanded: &FxHashSet<Vec<NbtPathSegment>>,
+    node: NBTRef<'_>,
+    label: SharedString,
+    path: Vec<NbtPathSegment>,
+    depth: usize,
+) {
+    let is_expanded = expanded.contains(&path);
+    let tag_type = node.tag_type();
+
+    match node {
+        NBTRef::Compound(compound) => {
+            rows.push(NbtRow {
+                path: path.clone(),
+                depth,
+                label,
+                tag_type,
+                value: SharedString::from(format!("{} entries", compound.len())),
+                container: true,
+            });
+            if is_expanded {
+                for (key, child) in compound.entries() {
+                    let mut child_path = path.clone();
+                    child_path.push(NbtPathSegment::Key(key.into()));
+                    push_rows(rows, expanded, child, SharedString::from(key.to_string()), child_path, depth + 1);
+                }
+            }
+        },
+        NBTRef::List(list) => {
+            rows.push(NbtRow {
+                path: path.clone(),
+                depth,
+                label,
+                tag_type,
+                value: SharedString::from(format!("{} entries", list.len())),
+                container: true,
+            });
+            if is_expanded {
+                for (index, child) in list.iter().enumerate() {
+                    let mut child_path = path.clone();
+                    child_path.push(NbtPathSegment::Index(index));
+                    push_rows(rows, expanded, child, SharedString::from(format!("[{}]", index)), child_path, depth + 1);
+                }
+            }
+        },
+        _ => {
+            let mut value = value_to_string(node);
+            if let Some((index, _)) = value.char_indices().nth(MAX_VALUE_PREVIEW_LEN) {
+                value.truncate(index);
+                value.push('…');
+            }
+            rows.push(NbtRow {
+                path,
+                depth,
+                label,
+                tag_type,
+                value: SharedString::from(value),
+                container: false,
+            });
+        },
+    }
+}
+
+fn with_node<R>(node: NBTRef<'_>, path: &[NbtPathSegment], f: impl FnOnce(NBTRef<'_>) -> R) -> Option<R> {
+    let Some((first, rest)) = path.split_first() else {
+        return Some(f(node));
+    };
+    match (node, first) {
+        (NBTRef::Compound(compound), NbtPathSegment::Key(key)) => with_node(compound.find(key)?, rest, f),
+        (NBTRef::List(list), NbtPathSegment::Index(index)) => with_node(list.get(*index)?, rest, f),
+        _ => None,
+    }
+}
+
+fn with_node_mut<R>(node: NBTRefMut<'_>, path: &[NbtPathSegment], f: impl FnOnce(NBTRefMut<'_>) -> R) -> Option<R> {
+    let Some((first, rest)) = path.split_first() else {
+        return Some(f(node));
+    };
+    match (node, first) {
+        (NBTRefMut::Compound(mut compound), NbtPathSegment::Key(key)) => with_node_mut(compound.find_mut(key)?, rest, f),
+        (NBTRefMut::List(mut list), NbtPathSegment::Index(index)) => with_node_mut(list.get_mut(*index)?, rest, f),
+        _ => None,
+    }
+}
+
+fn join_values<T: ToString>(values: &[T]) -> String {
+    values.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+}
+
+fn value_to_string(node: NBTRef<'_>) -> String {
+    match node {
+        NBTRef::Byte(value) => value.to_string(),
+        NBTRef::Short(value) => value.to_string(),
+        NBTRef::Int(value) => value.to_string(),
+        NBTRef::Long(value) => value.to_string(),
+        NBTRef::Float(value) => value.to_string(),
+        NBTRef::Double(value) => value.to_string(),
+        NBTRef::String(value) => value.clone(),
+        NBTRef::ByteArray(values) => join_values(values),
+        NBTRef::IntArray(values) => join_values(values),
+        NBTRef::LongArray(values) => join_values(values),
+        NBTRef::List(_) | NBTRef::Compound(_) => String::new(),
+    }
+}
+
+fn parse_number<T: FromStr>(value: &str) -> Result<T, String> {
+    let value = value.trim();
+    value.parse().map_err(|_| format!("'{}' is not a valid value", value))
+}
+
+fn parse_array<T: FromStr>(value: &str) -> Result<Vec<T>, String> {
+    value.split(',').map(str::trim).filter(|value| !value.is_empty()).map(parse_number).collect()
+}
+
+fn set_value(node: NBTRefMut<'_>, value: &str) -> Result<(), String> {
+    match node {
+        NBTRefMut::Byte(v) => *v = parse_number(value)?,
+        NBTRefMut::Short(v) => *v = parse_number(value)?,
+        NBTRefMut::Int(v) => *v = parse_number(value)?,
+        NBTRefMut::Long(v) => *v = parse_number(value)?,
+        NBTRefMut::Float(v) => *v = parse_number(value)?,
+        NBTRefMut::Double(v) => *v = parse_number(value)?,
+        NBTRefMut::String(v) => *v = value.to_string(),
+        NBTRefMut::ByteArray(v) => *v = parse_array(value)?,
+        NBTRefMut::IntArray(v) => *v = parse_array(value)?,
+        NBTRefMut::LongArray(v) => *v = parse_array(value)?,
+        NBTRefMut::List(_) | NBTRefMut::Compound(_) => return Err("Lists and compounds can't be edited directly".into()),
+    }
+    Ok(())
+}
+
+fn insert_tag(node: NBTRefMut<'_>, name: &str, tag_type: TagType, value: &str) -> Result<(), String> {
+    match node {
+        NBTRefMut::Compound(mut compound) => {
+            if name.is_empty() {
+                return Err("Name must not be empty".into());
+            }
+            if compound.contains_key(name) {
+                return Err(format!("A tag named '{}' already exists", name));
+            }
+            match tag_type {
+                nbt::TAG_BYTE_ID => compound.insert_byte(name, parse_number(value)?),
+                nbt::TAG_SHORT_ID => compound.insert_short(name, parse_number(value)?),
+                nbt::TAG_INT_ID => compound.insert_int(name, parse_number(value)?),
+                nbt::TAG_LONG_ID => compound.insert_long(name, parse_number(value)?),
+                nbt::TAG_FLOAT_ID => compound.insert_float(name, parse_number(value)?),
+                nbt::TAG_DOUBLE_ID => compound.insert_double(name, parse_number(value)?),
+                nbt::TAG_STRING_ID => compound.insert_string(name, value.to_string()),
+                nbt::TAG_BYTE_ARRAY_ID => compound.insert_byte_array(name, parse_array(value)?),
+                nbt::TAG_INT_ARRAY_ID => compound.insert_int_array(name, parse_array(value)?),
+                nbt::TAG_LONG_ARRAY_ID => compound.insert_long_array(name, parse_array(value)?),
+                nbt::TAG_COMPOUND_ID => {
+                    compound.create_compound(name);
+                },
+                nbt::TAG_LIST_ID => {
+                    compound.create_list(name, nbt::TAG_END_ID);
+                },
+                _ => return Err(format!("Unable to add {} tags", tag_type.name())),
+            }
+        },
+        NBTRefMut::List(mut list) => {
+            if !list.is_empty() && list.children_type() != tag_type {
+                return Err(format!("This list can only contain {} tags", list.children_type().name()));
+            }
+            match tag_type {
+                nbt::TAG_BYTE_ID => list.insert_byte(parse_number(value)?),
+                nbt::TAG_SHORT_ID => list.insert_short(parse_number(value)?),
+                nbt::TAG_INT_ID => list.insert_int(parse_number(value)?),
+                nbt::TAG_LONG_ID => list.insert_long(parse_number(value)?),
+                nbt::TAG_FLOAT_ID => list.insert_float(parse_number(value)?),
+                nbt::TAG_DOUBLE_ID => list.insert_double(parse_number(value)?),
+                nbt::TAG_STRING_ID => list.insert_string(value.to_string()),
+                nbt::TAG_BYTE_ARRAY_ID => list.insert_byte_array(parse_array(value)?),
+                nbt::TAG_INT_ARRAY_ID => list.insert_int_array(parse_array(value)?),
+                nbt::TAG_LONG_ARRAY_ID => list.insert_long_array(parse_array(value)?),
+                nbt::TAG_COMPOUND_ID => {
+                    list.create_compound();
+                },
+                nbt::TAG_LIST_ID => {
+                    list.create_list(nbt::TAG_END_ID);
+                },
+                _ => return Err(format!("Unable to add {} tags", tag_type.name())),
+            }
+        },
+        _ => return Err("Tags can only be added to lists and compounds".into()),
+    }
+    Ok(())
+}
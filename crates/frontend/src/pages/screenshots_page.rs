@@ -0,0 +1,86 @@
+use bridge::{handle::BackendHandle, message::MessageToBackend};
+use gpui::{prelude::*, *};
+use gpui_component::{
+    ActiveTheme as _,
+    button::Button,
+    h_flex,
+    scroll::ScrollableElement,
+    v_flex,
+};
+use rustc_hash::FxHashSet;
+
+use crate::{component::screenshot_grid::{self, ScreenshotEntry}, entity::{DataEntities, instance::InstanceEntries}, ui};
+
+pub struct ScreenshotsPage {
+    backend_handle: BackendHandle,
+    instances: Entity<InstanceEntries>,
+    _observe_instances_subscription: Subscription,
+    _observe_screenshots_subscriptions: Vec<Subscription>,
+}
+
+impl ScreenshotsPage {
+    pub fn new(data: &DataEntities, _window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let _observe_instances_subscription = cx.observe(&data.instances, |page, _, cx| {
+            page.observe_screenshots(cx);
+        });
+
+        let mut page = Self {
+            backend_handle: data.backend_handle.clone(),
+            instances: data.instances.clone(),
+            _observe_instances_subscription,
+            _observe_screenshots_subscriptions: Vec::new(),
+        };
+        page.observe_screenshots(cx);
+        page.reload(cx);
+        page
+    }
+
+    fn observe_screenshots(&mut self, cx: &mut Context<Self>) {
+        let screenshots: Vec<_> = self.instances.read(cx).entries.values()
+            .map(|entry| entry.read(cx).screenshots.clone())
+            .collect();
+        self._observe_screenshots_subscriptions = screenshots.iter()
+            .map(|screenshots| cx.observe(screenshots, |_, _, cx| cx.notify()))
+            .collect();
+    }
+
+    fn reload(&self, cx: &App) {
+        for id in self.instances.read(cx).entries.keys() {
+            self.backend_handle.send(MessageToBackend::RequestLoadScreenshots { id: *id });
+        }
+    }
+}
+
+impl Render for ScreenshotsPage {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        // Instances with synced screenshots all list the same files, so each one is only shown once
+        let mut seen = FxHashSet::default();
+        let mut screenshots = Vec::new();
+        for (id, entry) in &self.instances.read(cx).entries {
+            let entry = entry.read(cx);
+            screenshots.extend(entry.screenshots.read(cx).iter()
+                .filter(|summary| seen.insert(summary.canonical_path.clone()))
+                .map(|summary| ScreenshotEntry {
+                    instance: *id,
+                    instance_name: Some(entry.name.clone()),
+                    summary: summary.clone(),
+                }));
+        }
+        screenshots.sort_by_key(|screenshot| -screenshot.summary.created);
+
+        let title = h_flex()
+            .gap_8()
+            .child("Screenshots")
+            .child(Button::new("refresh").label("Refresh").on_click(cx.listener(|page, _, _, cx| {
+                page.reload(cx);
+            })));
+
+        let content = if screenshots.is_empty() {
+            v_flex().p_3().text_color(cx.theme().muted_foreground).child("No screenshots have been taken in any instance")
+        } else {
+            screenshot_grid::render_screenshot_grid(&screenshots, &self.backend_handle, cx).p_3()
+        };
+
+        ui::page(cx, title).child(content).overflow_y_scrollbar()
+    }
+}
@@ -61,10 +61,13 @@ impl Processor {
                 name,
                 dot_minecraft_folder,
                 configuration,
+                icon,
+                description,
                 worlds_state,
                 servers_state,
                 mods_state,
                 resource_packs_state,
+                shader_packs_state,
             } => {
                 InstanceEntries::add(
                     &self.data.instances,
@@ -72,10 +75,13 @@ impl Processor {
                     name.as_str().into(),
                     dot_minecraft_folder,
                     configuration,
+                    icon,
+                    description.into(),
                     worlds_state,
                     servers_state,
                     mods_state,
                     resource_packs_state,
+                    shader_packs_state,
                     cx,
                 );
             },
@@ -87,6 +93,8 @@ impl Processor {
                 name,
                 dot_minecraft_folder,
                 configuration,
+                icon,
+                description,
                 status,
             } => {
                 if status == InstanceStatus::Running {
@@ -112,6 +120,8 @@ impl Processor {
                     name.as_str().into(),
                     dot_minecraft_folder,
                     configuration,
+                    icon,
+                    description.into(),
                     status,
                     cx,
                 );
@@ -128,6 +138,21 @@ impl Processor {
             MessageToFrontend::InstanceResourcePacksUpdated { id, resource_packs } => {
                 InstanceEntries::set_resource_packs(&self.data.instances, id, resource_packs, cx);
             },
+            MessageToFrontend::InstanceShaderPacksUpdated { id, shader_packs } => {
+                InstanceEntries::set_shader_packs(&self.data.instances, id, shader_packs, cx);
+            },
+            MessageToFrontend::InstanceSnapshotsUpdated { id, snapshots } => {
+                InstanceEntries::set_snapshots(&self.data.instances, id, snapshots, cx);
+            },
+            MessageToFrontend::InstanceWorldBackupsUpdated { id, backups } => {
+                InstanceEntries::set_world_backups(&self.data.instances, id, backups, cx);
+            },
+            MessageToFrontend::InstanceWorldDatapacksUpdated { id, world, datapacks } => {
+                InstanceEntries::set_world_datapacks(&self.data.instances, id, world, datapacks, cx);
+            },
+            MessageToFrontend::InstanceScreenshotsUpdated { id, screenshots } => {
+                InstanceEntries::set_screenshots(&self.data.instances, id, screenshots, cx);
+            },
             MessageToFrontend::AddNotification { .. } => {
                 self.with_main_window(message, cx, |_, message, window, cx| {
                     let MessageToFrontend::AddNotification { notification_type, message } = message else {
@@ -202,6 +227,13 @@ impl Processor {
             MessageToFrontend::MoveInstanceToTop { id } => {
                 InstanceEntries::move_to_top(&self.data.instances, id, cx);
             },
+            MessageToFrontend::InstanceRenamed { old_name, new_name } => {
+                // Keep the manual ordering of the instance list
+                let manual_order = &mut InterfaceConfig::get_mut(cx).instance_list.manual_order;
+                if let Some(name) = manual_order.iter_mut().find(|name| name.as_str() == old_name.as_str()) {
+                    *name = SharedString::new(new_name.as_str());
+                }
+            },
             MessageToFrontend::MetadataResult { request, result, keep_alive_handle } => {
                 FrontendMetadata::set(&self.data.metadata, request, result, keep_alive_handle, cx);
             },
@@ -11,7 +11,7 @@ use gpui::{prelude::*, *};
 use gpui_component::{breadcrumb::Breadcrumb, scroll::{ScrollableElement, ScrollbarAxis}, v_flex, Root, StyledExt};
 use parking_lot::RwLock;
 
-use crate::{entity::DataEntities, modals, ui::{LauncherUI, PageType}, CloseWindow, MAIN_FONT};
+use crate::{entity::DataEntities, modals, ui::{LauncherPage, LauncherUI, PageType}, CloseWindow, MAIN_FONT};
 
 pub struct LauncherRootGlobal {
     pub root: Entity<LauncherRoot>,
@@ -122,6 +122,34 @@ pub fn start_instance(
     backend_handle: &BackendHandle,
     window: &mut Window,
     cx: &mut App,
+) {
+    let (send, recv) = tokio::sync::oneshot::channel();
+    backend_handle.send(MessageToBackend::CheckLaunchProblems {
+        id,
+        channel: send,
+    });
+
+    let backend_handle = backend_handle.clone();
+    window.spawn(cx, async move |cx| {
+        let problems = recv.await.unwrap_or_default();
+        _ = cx.update(move |window, cx| {
+            if problems.is_empty() {
+                launch_instance(id, name, quick_play, &backend_handle, window, cx);
+            } else {
+                modals::launch_problems::open_launch_problems(id, name, quick_play, problems, backend_handle, window, cx);
+            }
+        });
+    }).detach();
+}
+
+/// Launches without checking the enabled mods first, see [start_instance]
+pub fn launch_instance(
+    id: InstanceID,
+    name: SharedString,
+    quick_play: Option<QuickPlayLaunch>,
+    backend_handle: &BackendHandle,
+    window: &mut Window,
+    cx: &mut App,
 ) {
     let modal_action = ModalAction::default();
 
@@ -217,3 +245,20 @@ pub fn switch_page(
         });
     });
 }
+
+pub fn open_nbt_file(
+    path: Arc<Path>,
+    window: &mut Window,
+    cx: &mut App,
+) {
+    cx.update_global::<LauncherRootGlobal, ()>(|global, cx| {
+        global.root.update(cx, |launcher_root, cx| {
+            launcher_root.ui.update(cx, |ui, cx| {
+                ui.switch_page(PageType::NbtEditor, &[], window, cx);
+                if let LauncherPage::NbtEditor(page) = ui.page() {
+                    page.update(cx, |page, cx| page.open(path, window, cx));
+                }
+            });
+        });
+    });
+}
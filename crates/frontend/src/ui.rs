@@ -6,14 +6,14 @@ use gpui_component::{
     ActiveTheme as _, Disableable, Icon, IconName, WindowExt, button::{Button, ButtonVariants}, h_flex, input::{Input, InputState}, resizable::{ResizablePanelEvent, ResizableState, h_resizable, resizable_panel}, scroll::ScrollableElement, sidebar::SidebarFooter, v_flex
 };
 use rand::Rng;
-use schema::modrinth::ModrinthProjectType;
+use schema::{curseforge::CurseforgeClass, modrinth::ModrinthProjectType};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use crate::{
     component::{menu::{MenuGroup, MenuGroupItem}, page_path::PagePath}, entity::{
         DataEntities, instance::{InstanceAddedEvent, InstanceEntries, InstanceModifiedEvent, InstanceMovedToTopEvent, InstanceRemovedEvent}
-    }, interface_config::InterfaceConfig, modals, pages::{instance::instance_page::{InstancePage, InstanceSubpageType}, instances_page::InstancesPage, modrinth_page::ModrinthSearchPage, syncing_page::SyncingPage}, png_render_cache, root
+    }, interface_config::InterfaceConfig, modals, pages::{content_library_page::ContentLibraryPage, curseforge_page::CurseforgeSearchPage, instance::instance_page::{InstancePage, InstanceSubpageType}, instances_page::InstancesPage, modrinth_page::ModrinthSearchPage, nbt_editor_page::NbtEditorPage, screenshots_page::ScreenshotsPage, syncing_page::SyncingPage}, png_render_cache, root
 };
 
 pub struct LauncherUI {
@@ -31,11 +31,18 @@ pub struct LauncherUI {
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum PageType {
     Instances,
+    Screenshots,
     Syncing,
+    NbtEditor,
+    ContentLibrary,
     Modrinth {
         installing_for: Option<InstanceID>,
         project_type: Option<ModrinthProjectType>,
     },
+    Curseforge {
+        installing_for: Option<InstanceID>,
+        class: Option<CurseforgeClass>,
+    },
     InstancePage(InstanceID, InstanceSubpageType),
 }
 
@@ -43,7 +50,10 @@ impl PageType {
     fn to_serialized(&self, data: &DataEntities, cx: &App) -> SerializedPageType {
         match self {
             PageType::Instances => SerializedPageType::Instances,
+            PageType::Screenshots => SerializedPageType::Screenshots,
             PageType::Syncing => SerializedPageType::Syncing,
+            PageType::NbtEditor => SerializedPageType::NbtEditor,
+            PageType::ContentLibrary => SerializedPageType::ContentLibrary,
             PageType::Modrinth { installing_for, .. } => {
                 if let Some(installing_for) = installing_for {
                     if let Some(name) = InstanceEntries::find_name_by_id(&data.instances, *installing_for, cx) {
@@ -52,6 +62,14 @@ impl PageType {
                 }
                 SerializedPageType::Modrinth { installing_for: None }
             },
+            PageType::Curseforge { installing_for, .. } => {
+                if let Some(installing_for) = installing_for {
+                    if let Some(name) = InstanceEntries::find_name_by_id(&data.instances, *installing_for, cx) {
+                        return SerializedPageType::Curseforge { installing_for: Some(name) };
+                    }
+                }
+                SerializedPageType::Curseforge { installing_for: None }
+            },
             PageType::InstancePage(id, _) => {
                 if let Some(name) = InstanceEntries::find_name_by_id(&data.instances, *id, cx) {
                     SerializedPageType::InstancePage(name)
@@ -65,7 +83,10 @@ impl PageType {
     fn from_serialized(serialized: &SerializedPageType, data: &DataEntities, cx: &App) -> Self {
         match serialized {
             SerializedPageType::Instances => PageType::Instances,
+            SerializedPageType::Screenshots => PageType::Screenshots,
             SerializedPageType::Syncing => PageType::Syncing,
+            SerializedPageType::NbtEditor => PageType::NbtEditor,
+            SerializedPageType::ContentLibrary => PageType::ContentLibrary,
             SerializedPageType::Modrinth { installing_for } => {
                 if let Some(installing_for) = installing_for {
                     if let Some(id) = InstanceEntries::find_id_by_name(&data.instances, installing_for, cx) {
@@ -74,6 +95,14 @@ impl PageType {
                 }
                 PageType::Modrinth { installing_for: None, project_type: None }
             },
+            SerializedPageType::Curseforge { installing_for } => {
+                if let Some(installing_for) = installing_for {
+                    if let Some(id) = InstanceEntries::find_id_by_name(&data.instances, installing_for, cx) {
+                        return PageType::Curseforge { installing_for: Some(id), class: None };
+                    }
+                }
+                PageType::Curseforge { installing_for: None, class: None }
+            },
             SerializedPageType::InstancePage(name) => {
                 if let Some(id) = InstanceEntries::find_id_by_name(&data.instances, name, cx) {
                     PageType::InstancePage(id, InstanceSubpageType::Quickplay)
@@ -90,21 +119,34 @@ impl PageType {
 pub enum SerializedPageType {
     #[default]
     Instances,
+    Screenshots,
     Syncing,
+    NbtEditor,
+    ContentLibrary,
     Modrinth {
         installing_for: Option<SharedString>,
     },
+    Curseforge {
+        installing_for: Option<SharedString>,
+    },
     InstancePage(SharedString),
 }
 
 #[derive(Clone)]
 pub enum LauncherPage {
     Instances(Entity<InstancesPage>),
+    Screenshots(Entity<ScreenshotsPage>),
     Syncing(Entity<SyncingPage>),
+    NbtEditor(Entity<NbtEditorPage>),
+    ContentLibrary(Entity<ContentLibraryPage>),
     Modrinth {
         installing_for: Option<InstanceID>,
         page: Entity<ModrinthSearchPage>,
     },
+    Curseforge {
+        installing_for: Option<InstanceID>,
+        page: Entity<CurseforgeSearchPage>,
+    },
     InstancePage(InstanceID, InstanceSubpageType, Entity<InstancePage>),
 }
 
@@ -112,8 +154,12 @@ impl LauncherPage {
     pub fn into_any_element(self) -> AnyElement {
         match self {
             LauncherPage::Instances(entity) => entity.into_any_element(),
+            LauncherPage::Screenshots(entity) => entity.into_any_element(),
             LauncherPage::Syncing(entity) => entity.into_any_element(),
+            LauncherPage::NbtEditor(entity) => entity.into_any_element(),
+            LauncherPage::ContentLibrary(entity) => entity.into_any_element(),
             LauncherPage::Modrinth { page, .. } => page.into_any_element(),
+            LauncherPage::Curseforge { page, .. } => page.into_any_element(),
             LauncherPage::InstancePage(_, _, entity) => entity.into_any_element(),
         }
     }
@@ -121,8 +167,12 @@ impl LauncherPage {
     pub fn page_type(&self) -> PageType {
         match self {
             LauncherPage::Instances(_) => PageType::Instances,
+            LauncherPage::Screenshots(_) => PageType::Screenshots,
             LauncherPage::Syncing(_) => PageType::Syncing,
+            LauncherPage::NbtEditor(_) => PageType::NbtEditor,
+            LauncherPage::ContentLibrary(_) => PageType::ContentLibrary,
             LauncherPage::Modrinth { installing_for, .. } => PageType::Modrinth { installing_for: *installing_for, project_type: None },
+            LauncherPage::Curseforge { installing_for, .. } => PageType::Curseforge { installing_for: *installing_for, class: None },
             LauncherPage::InstancePage(id, subpage, _) => PageType::InstancePage(*id, *subpage),
         }
     }
@@ -216,9 +266,18 @@ impl LauncherUI {
             PageType::Instances => {
                 LauncherPage::Instances(cx.new(|cx| InstancesPage::new(data, window, cx)))
             },
+            PageType::Screenshots => {
+                LauncherPage::Screenshots(cx.new(|cx| ScreenshotsPage::new(data, window, cx)))
+            },
             PageType::Syncing => {
                 LauncherPage::Syncing(cx.new(|cx| SyncingPage::new(data, window, cx)))
             },
+            PageType::NbtEditor => {
+                LauncherPage::NbtEditor(cx.new(|cx| NbtEditorPage::new(data, window, cx)))
+            },
+            PageType::ContentLibrary => {
+                LauncherPage::ContentLibrary(cx.new(|cx| ContentLibraryPage::new(data, window, cx)))
+            },
             PageType::Modrinth { installing_for, project_type } => {
                 let page = cx.new(|cx| {
                     ModrinthSearchPage::new(installing_for, project_type, path, data, window, cx)
@@ -228,6 +287,15 @@ impl LauncherUI {
                     page,
                 }
             },
+            PageType::Curseforge { installing_for, class } => {
+                let page = cx.new(|cx| {
+                    CurseforgeSearchPage::new(installing_for, class, path, data, window, cx)
+                });
+                LauncherPage::Curseforge {
+                    installing_for,
+                    page,
+                }
+            },
             PageType::InstancePage(id, subpage) => {
                 LauncherPage::InstancePage(id, subpage, cx.new(|cx| {
                     InstancePage::new(id, subpage, path, data, window, cx)
@@ -236,6 +304,10 @@ impl LauncherUI {
         }
     }
 
+    pub fn page(&self) -> &LauncherPage {
+        &self.page
+    }
+
     pub fn switch_page(&mut self, page: PageType, breadcrumbs: &[PageType], window: &mut Window, cx: &mut Context<Self>) {
         if self.page.page_type() == page {
             return;
@@ -261,6 +333,11 @@ impl Render for LauncherUI {
                 .active(page_type == PageType::Instances)
                 .on_click(cx.listener(|launcher, _, window, cx| {
                     launcher.switch_page(PageType::Instances, &[], window, cx);
+                })))
+            .child(MenuGroupItem::new("Screenshots")
+                .active(page_type == PageType::Screenshots)
+                .on_click(cx.listener(|launcher, _, window, cx| {
+                    launcher.switch_page(PageType::Screenshots, &[], window, cx);
                 })));
 
         let launcher_group = MenuGroup::new("Content")
@@ -269,10 +346,25 @@ impl Render for LauncherUI {
                 .on_click(cx.listener(|launcher, _, window, cx| {
                     launcher.switch_page(PageType::Modrinth { installing_for: None, project_type: None }, &[], window, cx);
                 })))
+            .child(MenuGroupItem::new("CurseForge")
+                .active(page_type == PageType::Curseforge { installing_for: None, class: None })
+                .on_click(cx.listener(|launcher, _, window, cx| {
+                    launcher.switch_page(PageType::Curseforge { installing_for: None, class: None }, &[], window, cx);
+                })))
+            .child(MenuGroupItem::new("Content Library")
+                .active(page_type == PageType::ContentLibrary)
+                .on_click(cx.listener(|launcher, _, window, cx| {
+                    launcher.switch_page(PageType::ContentLibrary, &[], window, cx);
+                })))
             .child(MenuGroupItem::new("Syncing")
                 .active(page_type == PageType::Syncing)
                 .on_click(cx.listener(|launcher, _, window, cx| {
                     launcher.switch_page(PageType::Syncing, &[], window, cx);
+                })))
+            .child(MenuGroupItem::new("NBT Editor")
+                .active(page_type == PageType::NbtEditor)
+                .on_click(cx.listener(|launcher, _, window, cx| {
+                    launcher.switch_page(PageType::NbtEditor, &[], window, cx);
                 })));
 
         let mut groups: heapless::Vec<MenuGroup, 3> = heapless::Vec::new();
@@ -521,3 +613,11 @@ pub fn page(cx: &App, title: impl IntoElement) -> gpui::Div {
             .child(div().left_4().child(title)),
     )
 }
+
+pub fn instance_icon(icon: Option<&Arc<[u8]>>, cx: &mut App) -> gpui::Img {
+    if let Some(icon) = icon {
+        png_render_cache::render(Arc::clone(icon), cx)
+    } else {
+        gpui::img(ImageSource::Resource(Resource::Embedded("images/instance_icons/grass_block.png".into())))
+    }
+}
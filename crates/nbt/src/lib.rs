@@ -26,6 +26,27 @@ pub const TAG_COMPOUND_ID: TagType = TagType(10);
 pub const TAG_INT_ARRAY_ID: TagType = TagType(11);
 pub const TAG_LONG_ARRAY_ID: TagType = TagType(12);
 
+impl TagType {
+    pub fn name(self) -> &'static str {
+        match self.0 {
+            0 => "End",
+            1 => "Byte",
+            2 => "Short",
+            3 => "Int",
+            4 => "Long",
+            5 => "Float",
+            6 => "Double",
+            7 => "Byte Array",
+            8 => "String",
+            9 => "List",
+            10 => "Compound",
+            11 => "Int Array",
+            12 => "Long Array",
+            _ => "Unknown",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct NBT {
     pub root_name: String,
@@ -208,7 +208,7 @@ impl<'a> CompoundRef<'a> {
         nbt
     }
 
-    fn clone_into(&self, mut into: CompoundRefMut<'_>) {
+    pub fn clone_into(&self, mut into: CompoundRefMut<'_>) {
         for (key, entry) in self.entries() {
             match entry {
                 NBTRef::Byte(v) => into.insert_byte(key, *v),
@@ -618,6 +618,14 @@ impl<'a> ListRef<'a> {
         self.get_self_node().1.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.get_self_node().1.is_empty()
+    }
+
+    pub fn children_type(&self) -> TagType {
+        self.children_type
+    }
+
     pub fn get(&self, index: usize) -> Option<NBTRef<'_>> {
         let (_, children) = self.get_self_node();
         let idx = children.get(index)?;
@@ -684,6 +692,13 @@ impl<'a> ListRefMut<'a> {
     }
 
     fn insert_node(&mut self, node: NBTNode) -> usize {
+        // The type of an empty list is decided by its first element
+        if let Some(NBTNode::List { type_id, children }) = self.nbt.nodes.get_mut(self.node_idx)
+            && children.is_empty()
+        {
+            *type_id = node.get_type();
+        }
+
         let (type_id, _) = self.get_self_node_mut();
         if type_id != node.get_type() {
             panic!("Tried to insert {:?} into a list of {:?}", node.get_type(), type_id);
@@ -713,12 +728,46 @@ impl<'a> ListRefMut<'a> {
         self.get_self_node().1.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.get_self_node().1.is_empty()
+    }
+
+    pub fn children_type(&self) -> TagType {
+        self.get_self_node().0
+    }
+
     pub fn get(&self, index: usize) -> Option<NBTRef<'_>> {
         let (_, children) = self.get_self_node();
         let idx = children.get(index)?;
         Some(self.nbt.get_reference(*idx))
     }
 
+    pub fn get_mut(&mut self, index: usize) -> Option<NBTRefMut<'_>> {
+        let (_, children) = self.get_self_node();
+        let idx = *children.get(index)?;
+        Some(self.nbt.get_reference_mut(idx))
+    }
+
+    pub fn remove(&mut self, index: usize) -> bool {
+        let (_, children) = self.get_self_node_mut();
+        if index >= children.len() {
+            return false;
+        }
+        let idx = children.remove(index);
+        self.nbt.remove_node(idx);
+        true
+    }
+
+    pub fn move_element(&mut self, from: usize, to: usize) -> bool {
+        let (_, children) = self.get_self_node_mut();
+        if from >= children.len() || to >= children.len() {
+            return false;
+        }
+        let idx = children.remove(from);
+        children.insert(to, idx);
+        true
+    }
+
     super::enumerate_basic_types!(super::get_list);
 
     pub fn get_numeric<T: num::FromPrimitive>(&self, index: usize) -> Option<T> {
@@ -808,3 +857,95 @@ impl<'a> Iterator for CompoundIterator<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{NBT, TAG_COMPOUND_ID, TAG_END_ID, TAG_INT_ID, TAG_STRING_ID};
+
+    fn list_values(nbt: &NBT) -> Vec<i32> {
+        let list = nbt.as_compound().unwrap().find_list("list", TAG_INT_ID).unwrap();
+        (0..list.len()).map(|index| *list.get_int(index).unwrap()).collect()
+    }
+
+    #[test]
+    fn test_list_move_and_remove() {
+        let mut nbt = NBT::new();
+        let mut root = nbt.as_compound_mut().unwrap();
+        let mut list = root.create_list("list", TAG_INT_ID);
+        for value in 0..4 {
+            list.insert_int(value);
+        }
+
+        assert!(list.move_element(0, 3));
+        assert!(list.move_element(2, 1));
+        assert!(!list.move_element(0, 4));
+        assert!(!list.move_element(4, 0));
+        assert_eq!(list_values(&nbt), [1, 3, 2, 0]);
+
+        let mut root = nbt.as_compound_mut().unwrap();
+        let mut list = root.find_list_mut("list", TAG_INT_ID).unwrap();
+        assert!(list.remove(1));
+        assert!(!list.remove(3));
+        assert_eq!(list_values(&nbt), [1, 2, 0]);
+    }
+
+    #[test]
+    fn test_list_remove_frees_children() {
+        let mut nbt = NBT::new();
+        let mut root = nbt.as_compound_mut().unwrap();
+        let mut list = root.create_list("list", TAG_COMPOUND_ID);
+        for name in ["a", "b"] {
+            let mut compound = list.create_compound();
+            compound.insert_string("name", name.to_string());
+            compound.create_list("tags", TAG_STRING_ID).insert_string("tag".to_string());
+        }
+
+        let node_count = nbt.nodes.len();
+        let mut root = nbt.as_compound_mut().unwrap();
+        assert!(root.find_list_mut("list", TAG_COMPOUND_ID).unwrap().remove(0));
+
+        // The compound, its name, the tags list and the tag inside it
+        assert_eq!(nbt.nodes.len(), node_count - 4);
+        let list = nbt.as_compound().unwrap().find_list("list", TAG_COMPOUND_ID).unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(list.get(0).unwrap().as_compound().unwrap().find_string("name").unwrap(), "b");
+    }
+
+    #[test]
+    fn test_list_get_mut() {
+        let mut nbt = NBT::new();
+        let mut root = nbt.as_compound_mut().unwrap();
+        let mut list = root.create_list("list", TAG_INT_ID);
+        list.insert_int(1);
+        list.insert_int(2);
+
+        *list.get_mut(1).unwrap().as_int().unwrap() = 5;
+        assert!(list.get_mut(2).is_none());
+        assert_eq!(list_values(&nbt), [1, 5]);
+    }
+
+    #[test]
+    fn test_insert_into_empty_list_sets_type() {
+        let mut nbt = NBT::new();
+        let mut root = nbt.as_compound_mut().unwrap();
+        let mut list = root.create_list("list", TAG_END_ID);
+        list.insert_string("first".to_string());
+        assert_eq!(list.children_type(), TAG_STRING_ID);
+
+        let encoded = crate::encode::write_named(&nbt);
+        let decoded = crate::decode::read_named(&mut encoded.as_slice()).unwrap();
+        assert_eq!(decoded, nbt);
+        let list = decoded.as_compound().unwrap().find_list("list", TAG_STRING_ID).unwrap();
+        assert_eq!(list.get_string(0).unwrap(), "first");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_wrong_type_into_list() {
+        let mut nbt = NBT::new();
+        let mut root = nbt.as_compound_mut().unwrap();
+        let mut list = root.create_list("list", TAG_STRING_ID);
+        list.insert_string("first".to_string());
+        list.insert_int(1);
+    }
+}
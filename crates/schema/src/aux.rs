@@ -8,6 +8,8 @@ pub struct AuxiliaryContentMeta {
     pub applied_overrides: AuxAppliedOverrides,
     #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
     pub disabled_children: AuxDisabledChildren,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub pinned: Option<AuxPinned>,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
@@ -21,3 +23,9 @@ pub struct AuxDisabledChildren {
     pub disabled_names: BTreeSet<Arc<str>>,
     pub disabled_filenames: BTreeSet<Arc<str>>,
 }
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuxPinned {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<Arc<str>>,
+}
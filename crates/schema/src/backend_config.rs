@@ -1,12 +1,44 @@
+use std::sync::Arc;
+
 use enumset::{EnumSet, EnumSetType};
 use serde::{Deserialize, Serialize};
 
+use crate::{curseforge::CURSEFORGE_API_URL, github::GITHUB_API_URL, instance::InstanceDefaults};
+
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
 pub struct BackendConfig {
     #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
     pub sync_targets: EnumSet<SyncTarget>,
     #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
     pub dont_open_game_output_when_launching: bool,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub instance_defaults: InstanceDefaults,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub world_backups_to_keep: Option<u32>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub github_api_url: Option<Arc<str>>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub curseforge_api_url: Option<Arc<str>>,
+    #[serde(default, skip_serializing_if = "crate::skip_if_default", deserialize_with = "crate::try_deserialize")]
+    pub curseforge_api_key: Option<Arc<str>>,
+}
+
+impl BackendConfig {
+    pub const DEFAULT_WORLD_BACKUPS_TO_KEEP: u32 = 5;
+
+    // 0 keeps every backup
+    pub fn world_backups_to_keep(&self) -> u32 {
+        self.world_backups_to_keep.unwrap_or(Self::DEFAULT_WORLD_BACKUPS_TO_KEEP)
+    }
+
+    // Can point at a mirror or a stand-in server instead of GitHub
+    pub fn github_api_url(&self) -> &str {
+        self.github_api_url.as_deref().unwrap_or(GITHUB_API_URL).trim_end_matches('/')
+    }
+
+    pub fn curseforge_api_url(&self) -> &str {
+        self.curseforge_api_url.as_deref().unwrap_or(CURSEFORGE_API_URL).trim_end_matches('/')
+    }
 }
 
 #[derive(Debug, enum_map::Enum, EnumSetType, strum::EnumIter)]
@@ -10,5 +10,18 @@ pub enum ContentSource {
     ModrinthUnknown,
     ModrinthProject {
         project: Arc<str>
-    }
+    },
+    Url {
+        url: Arc<str>
+    },
+    CurseforgeProject {
+        project: u32
+    },
+    GithubRelease {
+        owner: Arc<str>,
+        repo: Arc<str>,
+        tag: Arc<str>,
+        /// Asset name, with `*` in place of the release version
+        asset: Arc<str>,
+    },
 }
@@ -0,0 +1,239 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize, Serializer};
+
+pub const CURSEFORGE_API_URL: &str = "https://api.curseforge.com";
+pub const CURSEFORGE_MINECRAFT_GAME_ID: u32 = 432;
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CurseforgeClass {
+    Mod,
+    Modpack,
+    Resourcepack,
+    Shader,
+}
+
+impl CurseforgeClass {
+    pub fn id(self) -> u32 {
+        match self {
+            CurseforgeClass::Mod => 6,
+            CurseforgeClass::Modpack => 4471,
+            CurseforgeClass::Resourcepack => 12,
+            CurseforgeClass::Shader => 6552,
+        }
+    }
+
+    pub fn from_id(id: u32) -> Option<Self> {
+        match id {
+            6 => Some(CurseforgeClass::Mod),
+            4471 => Some(CurseforgeClass::Modpack),
+            12 => Some(CurseforgeClass::Resourcepack),
+            6552 => Some(CurseforgeClass::Shader),
+            _ => None,
+        }
+    }
+
+    pub fn url_segment(self) -> &'static str {
+        match self {
+            CurseforgeClass::Mod => "mc-mods",
+            CurseforgeClass::Modpack => "modpacks",
+            CurseforgeClass::Resourcepack => "texture-packs",
+            CurseforgeClass::Shader => "shaders",
+        }
+    }
+}
+
+impl Serialize for CurseforgeClass {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u32(self.id())
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CurseforgeModLoaderType {
+    Forge,
+    Fabric,
+    Quilt,
+    NeoForge,
+}
+
+impl CurseforgeModLoaderType {
+    pub fn id(self) -> u8 {
+        match self {
+            CurseforgeModLoaderType::Forge => 1,
+            CurseforgeModLoaderType::Fabric => 4,
+            CurseforgeModLoaderType::Quilt => 5,
+            CurseforgeModLoaderType::NeoForge => 6,
+        }
+    }
+
+    /// Name used in the game versions of files
+    pub fn name(self) -> &'static str {
+        match self {
+            CurseforgeModLoaderType::Forge => "Forge",
+            CurseforgeModLoaderType::Fabric => "Fabric",
+            CurseforgeModLoaderType::Quilt => "Quilt",
+            CurseforgeModLoaderType::NeoForge => "NeoForge",
+        }
+    }
+}
+
+impl Serialize for CurseforgeModLoaderType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.id())
+    }
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeSearchRequest {
+    #[serde(rename = "searchFilter")]
+    pub query: Option<Arc<str>>,
+    pub class_id: CurseforgeClass,
+    pub game_version: Option<Arc<str>>,
+    pub mod_loader_type: Option<CurseforgeModLoaderType>,
+    pub index: usize,
+    pub page_size: usize,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeModFilesRequest {
+    #[serde(skip)]
+    pub mod_id: u32,
+    pub game_version: Option<Arc<str>>,
+    pub mod_loader_type: Option<CurseforgeModLoaderType>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgePagination {
+    pub index: usize,
+    pub page_size: usize,
+    pub result_count: usize,
+    pub total_count: usize,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseforgeSearchResult {
+    pub data: Arc<[CurseforgeMod]>,
+    pub pagination: CurseforgePagination,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeMod {
+    pub id: u32,
+    pub name: Arc<str>,
+    pub slug: Arc<str>,
+    #[serde(default)]
+    pub summary: Option<Arc<str>>,
+    #[serde(default)]
+    pub download_count: f64,
+    #[serde(default)]
+    pub class_id: Option<u32>,
+    #[serde(default)]
+    pub logo: Option<CurseforgeLogo>,
+    #[serde(default)]
+    pub authors: Arc<[CurseforgeAuthor]>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeLogo {
+    pub thumbnail_url: Option<Arc<str>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseforgeAuthor {
+    pub name: Arc<str>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseforgeModFilesResult {
+    pub data: Arc<[CurseforgeFile]>,
+    pub pagination: CurseforgePagination,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseforgeFileResult {
+    pub data: CurseforgeFile,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeFile {
+    pub id: u32,
+    pub mod_id: u32,
+    pub display_name: Arc<str>,
+    pub file_name: Arc<str>,
+    pub release_type: u8,
+    pub file_date: Arc<str>,
+    pub file_length: usize,
+    pub download_url: Option<Arc<str>>,
+    #[serde(default)]
+    pub hashes: Arc<[CurseforgeFileHash]>,
+    #[serde(default)]
+    pub game_versions: Arc<[Arc<str>]>,
+    #[serde(default)]
+    pub dependencies: Arc<[CurseforgeFileDependency]>,
+    #[serde(default)]
+    pub file_fingerprint: u32,
+}
+
+impl CurseforgeFile {
+    pub fn sha1(&self) -> Option<&Arc<str>> {
+        // Algo 1 is sha1, 2 is md5
+        self.hashes.iter().find(|hash| hash.algo == 1).map(|hash| &hash.value)
+    }
+
+    pub fn release_type_name(&self) -> Option<&'static str> {
+        match self.release_type {
+            2 => Some("Beta"),
+            3 => Some("Alpha"),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseforgeFileHash {
+    pub value: Arc<str>,
+    pub algo: u8,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeFileDependency {
+    pub mod_id: u32,
+    pub relation_type: u8,
+}
+
+impl CurseforgeFileDependency {
+    pub fn is_required(&self) -> bool {
+        self.relation_type == 3
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CurseforgeFingerprintsRequest {
+    pub fingerprints: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseforgeFingerprintsResult {
+    pub data: CurseforgeFingerprintMatches,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurseforgeFingerprintMatches {
+    #[serde(default)]
+    pub exact_matches: Vec<CurseforgeFingerprintMatch>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CurseforgeFingerprintMatch {
+    pub id: u32,
+    pub file: CurseforgeFile,
+}
@@ -1,10 +1,14 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
 use serde::Deserialize;
 
+use crate::fabric_mod::Person;
+
 #[derive(Deserialize, Debug)]
 pub struct ModsToml {
-    pub mods: Vec<ModsTomlMod>
+    pub mods: Vec<ModsTomlMod>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub dependencies: HashMap<Arc<str>, Vec<ModsTomlDependency>>,
 }
 
 #[derive(Deserialize, Debug)]
@@ -17,6 +21,17 @@ pub struct ModsTomlMod {
     pub authors: Option<Arc<str>>,
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ModsTomlDependency {
+    pub mod_id: Arc<str>,
+    // Forge uses `mandatory`, NeoForge replaced it with `type`
+    pub mandatory: Option<bool>,
+    pub r#type: Option<Arc<str>>,
+    pub version_range: Option<Arc<str>>,
+    pub side: Option<Arc<str>>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct JarJarMetadata {
     pub jars: Vec<JarJarMetadataJar>
@@ -26,3 +41,61 @@ pub struct JarJarMetadata {
 pub struct JarJarMetadataJar {
     pub path: Arc<str>,
 }
+
+// Used by Forge for 1.12.2 and older, either a plain list of mods or wrapped in an object
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum McmodInfo {
+    List(Vec<McmodInfoMod>),
+    ModList {
+        #[serde(rename = "modList")]
+        mod_list: Vec<McmodInfoMod>,
+    },
+}
+
+impl McmodInfo {
+    pub fn into_mods(self) -> Vec<McmodInfoMod> {
+        match self {
+            McmodInfo::List(mods) => mods,
+            McmodInfo::ModList { mod_list } => mod_list,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct McmodInfoMod {
+    #[serde(alias = "modId")]
+    pub modid: Arc<str>,
+    pub name: Option<Arc<str>>,
+    pub version: Option<Arc<str>>,
+    #[serde(default, alias = "authors", deserialize_with = "crate::try_deserialize")]
+    pub author_list: Vec<Person>,
+    pub logo_file: Option<Arc<str>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mcmod_info_list() {
+        let json = r#"[{"modid": "examplemod", "name": "Example Mod", "version": "1.0", "authorList": ["Someone"]}]"#;
+        let mods = serde_json::from_str::<McmodInfo>(json).unwrap().into_mods();
+        assert_eq!(mods.len(), 1);
+        assert_eq!(&*mods[0].modid, "examplemod");
+        assert_eq!(mods[0].name.as_deref(), Some("Example Mod"));
+        assert_eq!(mods[0].version.as_deref(), Some("1.0"));
+        assert_eq!(mods[0].author_list.len(), 1);
+    }
+
+    #[test]
+    fn test_mcmod_info_mod_list() {
+        let json = r#"{"modListVersion": 2, "modList": [{"modid": "first"}, {"modId": "second", "authors": ["Someone"]}]}"#;
+        let mods = serde_json::from_str::<McmodInfo>(json).unwrap().into_mods();
+        assert_eq!(mods.len(), 2);
+        assert_eq!(&*mods[0].modid, "first");
+        assert_eq!(&*mods[1].modid, "second");
+        assert_eq!(mods[1].author_list.len(), 1);
+    }
+}
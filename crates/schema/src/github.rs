@@ -0,0 +1,20 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+pub const GITHUB_API_URL: &str = "https://api.github.com";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubRelease {
+    pub tag_name: Arc<str>,
+    pub name: Option<Arc<str>>,
+    pub body: Option<Arc<str>>,
+    pub assets: Arc<[GithubReleaseAsset]>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GithubReleaseAsset {
+    pub name: Arc<str>,
+    pub size: usize,
+    pub browser_download_url: Arc<str>,
+}
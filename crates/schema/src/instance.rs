@@ -19,9 +19,56 @@ pub struct InstanceConfiguration {
     pub jvm_binary: Option<InstanceJvmBinaryConfiguration>,
     #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_linux_wrapper_configuration")]
     pub linux_wrapper: Option<InstanceLinuxWrapperConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_env_vars_configuration")]
+    pub env_vars: Option<InstanceEnvVarsConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "is_default_resolution_configuration")]
+    pub resolution: Option<InstanceResolutionConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "Option::is_none")]
+    pub group: Option<Arc<str>>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Arc<str>>,
 }
 
-#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
+impl InstanceConfiguration {
+    // Settings which haven't been overridden by the instance fall back to the global defaults
+    pub fn with_defaults(&self, defaults: &InstanceDefaults) -> Self {
+        fn inherit<T: Clone>(value: &Option<T>, default: &Option<T>, enabled: impl Fn(&T) -> bool) -> Option<T> {
+            match value {
+                Some(value) if enabled(value) => Some(value.clone()),
+                _ => default.clone().filter(|default| enabled(default)).or_else(|| value.clone()),
+            }
+        }
+
+        Self {
+            memory: inherit(&self.memory, &defaults.memory, |v| v.enabled),
+            jvm_flags: inherit(&self.jvm_flags, &defaults.jvm_flags, |v| v.enabled),
+            jvm_binary: inherit(&self.jvm_binary, &defaults.jvm_binary, |v| v.enabled),
+            // Unlike the other settings, the wrapper is applied whenever it's present, so a disabled one is removed
+            linux_wrapper: inherit(&self.linux_wrapper, &defaults.linux_wrapper, |v| v.enabled).filter(|v| v.enabled),
+            env_vars: inherit(&self.env_vars, &defaults.env_vars, |v| v.enabled),
+            resolution: inherit(&self.resolution, &defaults.resolution, |v| v.enabled),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct InstanceDefaults {
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "Option::is_none")]
+    pub memory: Option<InstanceMemoryConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "Option::is_none")]
+    pub jvm_flags: Option<InstanceJvmFlagsConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "Option::is_none")]
+    pub jvm_binary: Option<InstanceJvmBinaryConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "Option::is_none")]
+    pub linux_wrapper: Option<InstanceLinuxWrapperConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "Option::is_none")]
+    pub env_vars: Option<InstanceEnvVarsConfiguration>,
+    #[serde(default, deserialize_with = "crate::try_deserialize", skip_serializing_if = "Option::is_none")]
+    pub resolution: Option<InstanceResolutionConfiguration>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
 pub struct InstanceMemoryConfiguration {
     pub enabled: bool,
     pub min: u32,
@@ -53,7 +100,7 @@ fn is_default_memory_configuration(config: &Option<InstanceMemoryConfiguration>)
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct InstanceJvmFlagsConfiguration {
     pub enabled: bool,
     pub flags: Arc<str>,
@@ -67,7 +114,7 @@ fn is_default_jvm_flags_configuration(config: &Option<InstanceJvmFlagsConfigurat
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct InstanceJvmBinaryConfiguration {
     pub enabled: bool,
     pub path: Option<Arc<Path>>,
@@ -81,8 +128,11 @@ fn is_default_jvm_binary_configuration(config: &Option<InstanceJvmBinaryConfigur
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
 pub struct InstanceLinuxWrapperConfiguration {
+    // Wrapper configurations saved before defaults existed were always explicit
+    #[serde(default = "crate::default_true", deserialize_with = "crate::try_deserialize")]
+    pub enabled: bool,
     #[serde(default, deserialize_with = "crate::try_deserialize")]
     pub use_mangohud: bool,
     #[serde(default, deserialize_with = "crate::try_deserialize")]
@@ -94,6 +144,7 @@ pub struct InstanceLinuxWrapperConfiguration {
 impl Default for InstanceLinuxWrapperConfiguration {
     fn default() -> Self {
         Self {
+            enabled: false,
             use_mangohud: false,
             use_gamemode: false,
             use_discrete_gpu: true,
@@ -103,7 +154,63 @@ impl Default for InstanceLinuxWrapperConfiguration {
 
 fn is_default_linux_wrapper_configuration(config: &Option<InstanceLinuxWrapperConfiguration>) -> bool {
     if let Some(config) = config {
-        !config.use_mangohud && !config.use_gamemode && config.use_discrete_gpu
+        !config.enabled && !config.use_mangohud && !config.use_gamemode && config.use_discrete_gpu
+    } else {
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct InstanceEnvVarsConfiguration {
+    pub enabled: bool,
+    // One KEY=VALUE pair per line
+    pub variables: Arc<str>,
+}
+
+impl InstanceEnvVarsConfiguration {
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.variables.lines().filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            (!key.is_empty()).then_some((key, value.trim()))
+        })
+    }
+}
+
+fn is_default_env_vars_configuration(config: &Option<InstanceEnvVarsConfiguration>) -> bool {
+    if let Some(config) = config {
+        !config.enabled && config.variables.trim_ascii().is_empty()
+    } else {
+        true
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub struct InstanceResolutionConfiguration {
+    pub enabled: bool,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl InstanceResolutionConfiguration {
+    pub const DEFAULT_WIDTH: u32 = 854;
+    pub const DEFAULT_HEIGHT: u32 = 480;
+}
+
+impl Default for InstanceResolutionConfiguration {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            width: Self::DEFAULT_WIDTH,
+            height: Self::DEFAULT_HEIGHT,
+        }
+    }
+}
+
+fn is_default_resolution_configuration(config: &Option<InstanceResolutionConfiguration>) -> bool {
+    if let Some(config) = config {
+        !config.enabled && config.width == InstanceResolutionConfiguration::DEFAULT_WIDTH
+            && config.height == InstanceResolutionConfiguration::DEFAULT_HEIGHT
     } else {
         true
     }
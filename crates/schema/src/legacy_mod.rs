@@ -0,0 +1,22 @@
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use crate::fabric_mod::Person;
+
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteModJson {
+    pub name: Arc<str>,
+    pub display_name: Option<Arc<str>>,
+    pub version: Option<Arc<str>>,
+    pub author: Option<Arc<str>>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct RiftModJson {
+    pub id: Arc<str>,
+    pub name: Option<Arc<str>>,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub authors: Vec<Person>,
+}
@@ -4,14 +4,17 @@ pub mod assets_index;
 pub mod aux;
 pub mod backend_config;
 pub mod content;
+pub mod curseforge;
 pub mod fabric_launch;
 pub mod fabric_loader_manifest;
 pub mod fabric_mod;
 pub mod forge;
 pub mod forge_mod;
+pub mod github;
 pub mod instance;
 pub mod java_runtime_component;
 pub mod java_runtimes;
+pub mod legacy_mod;
 pub mod loader;
 pub mod maven;
 pub mod modification;
@@ -19,6 +22,8 @@ pub mod modrinth;
 pub mod mrpack;
 pub mod pandora_update;
 pub mod resourcepack;
+pub mod snapshot;
+pub mod update_batch;
 pub mod version;
 pub mod version_manifest;
 
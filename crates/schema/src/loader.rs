@@ -1,7 +1,7 @@
 use enumset::EnumSetType;
 use serde::{Deserialize, Serialize};
 
-use crate::modrinth::ModrinthLoader;
+use crate::{curseforge::CurseforgeModLoaderType, modrinth::ModrinthLoader};
 
 #[derive(EnumSetType, Serialize, Deserialize, Debug, Hash)]
 #[serde(rename_all = "lowercase")]
@@ -48,4 +48,14 @@ impl Loader {
             Loader::Unknown => ModrinthLoader::Unknown,
         }
     }
+
+    pub fn as_curseforge_loader(self) -> Option<CurseforgeModLoaderType> {
+        match self {
+            Loader::Vanilla => None,
+            Loader::Fabric => Some(CurseforgeModLoaderType::Fabric),
+            Loader::Forge => Some(CurseforgeModLoaderType::Forge),
+            Loader::NeoForge => Some(CurseforgeModLoaderType::NeoForge),
+            Loader::Unknown => None,
+        }
+    }
 }
@@ -83,6 +83,7 @@ pub enum ModrinthProjectType {
     Modpack,
     Resourcepack,
     Shader,
+    Datapack,
     #[serde(other)]
     #[default]
     Other,
@@ -95,6 +96,7 @@ impl ModrinthProjectType {
             ModrinthProjectType::Modpack => "modpack",
             ModrinthProjectType::Resourcepack => "resourcepack",
             ModrinthProjectType::Shader => "shader",
+            ModrinthProjectType::Datapack => "datapack",
             ModrinthProjectType::Other => "other",
         }
     }
@@ -111,6 +113,7 @@ pub struct ModrinthProjectVersion {
     pub project_id: Arc<str>,
     pub name: Option<Arc<str>>,
     pub version_number: Option<Arc<str>>,
+    pub changelog: Option<Arc<str>>,
     pub dependencies: Option<Vec<ModrinthDependency>>,
     pub version_type: Option<ModrinthVersionType>,
     pub status: Option<ModrinthVersionStatus>,
@@ -147,6 +150,8 @@ pub enum ModrinthLoader {
     Iris,
     Optifine,
     Canvas,
+    // Datapacks
+    Datapack,
     // Other
     #[serde(other)]
     Unknown,
@@ -159,6 +164,8 @@ impl ModrinthLoader {
             ModrinthLoader::Minecraft => Some("resourcepacks"),
             ModrinthLoader::Iris | ModrinthLoader::Optifine => Some("shaderpacks"),
             ModrinthLoader::Canvas => Some("resourcepacks"),
+            // Datapacks are installed per-world, so there is no fixed directory
+            ModrinthLoader::Datapack => None,
             ModrinthLoader::Unknown => None,
         }
     }
@@ -172,6 +179,7 @@ impl ModrinthLoader {
             Self::Iris => "Iris",
             Self::Optifine => "Optifine",
             Self::Canvas => "Canvas",
+            Self::Datapack => "Datapack",
             Self::Unknown => "Unknown",
         }
     }
@@ -185,6 +193,7 @@ impl ModrinthLoader {
             Self::Iris => "iris",
             Self::Optifine => "optifine",
             Self::Canvas => "canvas",
+            Self::Datapack => "datapack",
             Self::Unknown => "unknown",
         }
     }
@@ -198,6 +207,7 @@ impl ModrinthLoader {
             "Iris" | "iris" => Self::Iris,
             "Optifine" | "optifine" => Self::Optifine,
             "Canvas" | "canvas" => Self::Canvas,
+            "Datapack" | "datapack" => Self::Datapack,
             _ => Self::Unknown,
         }
     }
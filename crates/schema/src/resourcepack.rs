@@ -9,4 +9,103 @@ pub struct PackMcmeta {
 #[derive(Deserialize, Debug)]
 pub struct PackMcmetaPack {
     pub description: Arc<str>,
+    pub pack_format: Option<u32>,
+    pub supported_formats: Option<PackSupportedFormats>,
+    // Replaces pack_format and supported_formats since 1.21.9
+    pub min_format: Option<PackFormat>,
+    pub max_format: Option<PackFormat>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum PackSupportedFormats {
+    Single(u32),
+    Range([u32; 2]),
+    Object {
+        min_inclusive: u32,
+        max_inclusive: u32,
+    },
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum PackFormat {
+    Major(u32),
+    MajorMinor(Vec<u32>),
+}
+
+impl PackFormat {
+    pub fn major(&self) -> Option<u32> {
+        match self {
+            PackFormat::Major(major) => Some(*major),
+            PackFormat::MajorMinor(version) => version.first().copied(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackFormatRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl PackFormatRange {
+    pub fn contains(self, format: u32) -> bool {
+        self.min <= format && format <= self.max
+    }
+}
+
+impl PackMcmetaPack {
+    pub fn format_range(&self) -> Option<PackFormatRange> {
+        if let Some(min) = self.min_format.as_ref().and_then(PackFormat::major)
+            && let Some(max) = self.max_format.as_ref().and_then(PackFormat::major)
+        {
+            return Some(PackFormatRange { min, max });
+        }
+
+        match self.supported_formats {
+            Some(PackSupportedFormats::Single(format)) => Some(PackFormatRange { min: format, max: format }),
+            Some(PackSupportedFormats::Range([min, max])) => Some(PackFormatRange { min, max }),
+            Some(PackSupportedFormats::Object { min_inclusive, max_inclusive }) => Some(PackFormatRange {
+                min: min_inclusive,
+                max: max_inclusive,
+            }),
+            None => self.pack_format.map(|format| PackFormatRange { min: format, max: format }),
+        }
+    }
+}
+
+// version.json inside the client jar, present since 1.14
+#[derive(Deserialize, Debug)]
+pub struct ClientVersionJson {
+    pub pack_version: Option<ClientPackVersion>,
+    pub world_version: Option<i32>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+pub enum ClientPackVersion {
+    Single(u32),
+    Split {
+        resource: Option<u32>,
+        resource_major: Option<u32>,
+        data: Option<u32>,
+        data_major: Option<u32>,
+    },
+}
+
+impl ClientPackVersion {
+    pub fn resource(&self) -> Option<u32> {
+        match self {
+            ClientPackVersion::Single(format) => Some(*format),
+            ClientPackVersion::Split { resource, resource_major, .. } => resource_major.or(*resource),
+        }
+    }
+
+    pub fn data(&self) -> Option<u32> {
+        match self {
+            ClientPackVersion::Single(format) => Some(*format),
+            ClientPackVersion::Split { data, data_major, .. } => data_major.or(*data),
+        }
+    }
 }
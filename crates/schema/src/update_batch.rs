@@ -0,0 +1,18 @@
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContentUpdateBatchInfo {
+    pub created: i64,
+    #[serde(default, deserialize_with = "crate::try_deserialize")]
+    pub entries: Vec<ContentUpdateBatchEntry>,
+}
+
+// Paths are relative to the .minecraft folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentUpdateBatchEntry {
+    pub old_path: Arc<str>,
+    pub old_sha1: Arc<str>,
+    pub new_path: Arc<str>,
+}